@@ -36,7 +36,7 @@ use cosmic::{
     surface,
     widget::{
         button, column, container, icon, id_container, nav_bar, row, scrollable, segmented_button,
-        settings, text_input,
+        settings, text_input, toaster,
     },
 };
 #[cfg(feature = "wayland")]
@@ -53,6 +53,9 @@ use page::Entity;
 use std::collections::BTreeSet;
 use std::{borrow::Cow, str::FromStr};
 
+/// Search phrases up to this many characters match whole words first.
+const WHOLE_WORD_SEARCH_LEN: usize = 2;
+
 #[allow(clippy::struct_excessive_bools)]
 #[allow(clippy::module_name_repetitions)]
 pub struct SettingsApp {
@@ -67,8 +70,12 @@ pub struct SettingsApp {
     search_active: bool,
     search_id: cosmic::widget::Id,
     search_input: String,
+    /// The query of the search that was last closed, restored when the search is opened again.
+    last_search: String,
     search_selections: Vec<(page::Entity, section::Entity)>,
+    show_advanced: bool,
     context_title: Option<String>,
+    toasts: toaster::Toasts<Message>,
 }
 
 impl SettingsApp {
@@ -108,6 +115,7 @@ impl SettingsApp {
             PageCommands::Mouse => self.pages.page_id::<input::mouse::Page>(),
             #[cfg(feature = "page-networking")]
             PageCommands::Network => self.pages.page_id::<networking::Page>(),
+            PageCommands::Notifications => self.pages.page_id::<desktop::notifications::Page>(),
             #[cfg(feature = "wayland")]
             PageCommands::Panel => self.pages.page_id::<desktop::panel::Page>(),
             #[cfg(feature = "page-power")]
@@ -122,6 +130,8 @@ impl SettingsApp {
             PageCommands::Touchpad => self.pages.page_id::<input::touchpad::Page>(),
             #[cfg(feature = "page-users")]
             PageCommands::Users => self.pages.page_id::<system::users::Page>(),
+            #[cfg(feature = "page-input")]
+            PageCommands::ValidateShortcuts { .. } => None,
             #[cfg(feature = "page-networking")]
             PageCommands::Vpn => self.pages.page_id::<networking::vpn::Page>(),
             PageCommands::Wallpaper => self.pages.page_id::<desktop::wallpaper::Page>(),
@@ -146,6 +156,7 @@ impl SettingsApp {
 
 #[derive(Clone, Debug)]
 pub enum Message {
+    CloseToast(toaster::ToastId),
     #[cfg(feature = "wayland")]
     DesktopInfo,
     Error(String),
@@ -157,6 +168,7 @@ pub enum Message {
     OutputRemoved(WlOutput),
     Page(page::Entity),
     PageMessage(crate::pages::Message),
+    PageScrolled(iced::widget::scrollable::Viewport),
     #[cfg(feature = "wayland")]
     PanelConfig(CosmicPanelConfig),
     SearchActivate,
@@ -165,6 +177,8 @@ pub enum Message {
     SearchSubmit,
     SetTheme(cosmic::theme::Theme),
     SetWindowTitle,
+    ShowAdvanced(bool),
+    ShowToast(String),
     Surface(surface::Action),
 }
 
@@ -186,6 +200,12 @@ impl cosmic::Application for SettingsApp {
     fn init(core: Core, flags: Self::Flags) -> (Self, Task<Self::Message>) {
         let config = Config::new();
 
+        let last_search = if config.remember_search() {
+            config.last_search()
+        } else {
+            String::new()
+        };
+
         let mut app = SettingsApp {
             active_page: page::Entity::default(),
             active_context_page: None,
@@ -198,10 +218,15 @@ impl cosmic::Application for SettingsApp {
             search_active: false,
             search_id: cosmic::widget::Id::unique(),
             search_input: String::new(),
+            last_search,
             search_selections: Vec::default(),
+            show_advanced: false,
             context_title: None,
+            toasts: toaster::Toasts::new(Message::CloseToast),
         };
 
+        app.pages.resource_register::<crate::pages::PageViewport>();
+
         #[cfg(feature = "page-networking")]
         app.insert_page::<networking::Page>();
         #[cfg(feature = "page-bluetooth")]
@@ -267,8 +292,7 @@ impl cosmic::Application for SettingsApp {
 
     fn on_escape(&mut self) -> Task<Self::Message> {
         if self.search_active {
-            self.search_active = false;
-            self.search_clear();
+            self.search_close();
         }
 
         Task::none()
@@ -283,8 +307,7 @@ impl cosmic::Application for SettingsApp {
     }
 
     fn on_search(&mut self) -> Task<Self::Message> {
-        self.search_active = true;
-        cosmic::widget::text_input::focus(self.search_id.clone())
+        self.search_open()
     }
 
     fn subscription(&self) -> Subscription<Message> {
@@ -307,6 +330,50 @@ impl cosmic::Application for SettingsApp {
                 )) => Some(Message::OutputRemoved(o)),
                 _ => None,
             }),
+            // Move between the key combination inputs of a custom shortcut with the
+            // arrow keys, which the inputs themselves do not use, while the page is shown.
+            #[cfg(feature = "page-input")]
+            self.pages
+                .page_id::<input::keyboard::shortcuts::custom::Page>()
+                .filter(|id| *id == self.active_page)
+                .map_or_else(Subscription::none, |_| {
+                    event::listen_with(|event, status, _id| match (event, status) {
+                        (
+                            iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                                key: iced::keyboard::Key::Named(key),
+                                modifiers,
+                                ..
+                            }),
+                            event::Status::Ignored,
+                        ) if modifiers.is_empty() => {
+                            let message = match key {
+                                iced::keyboard::key::Named::ArrowUp => {
+                                    input::keyboard::shortcuts::custom::Message::FocusPreviousKey
+                                }
+                                iced::keyboard::key::Named::ArrowDown => {
+                                    input::keyboard::shortcuts::custom::Message::FocusNextKey
+                                }
+                                _ => return None,
+                            };
+
+                            Some(Message::PageMessage(pages::Message::CustomShortcuts(
+                                message,
+                            )))
+                        }
+                        _ => None,
+                    })
+                }),
+            #[cfg(feature = "page-input")]
+            self.pages
+                .page::<input::keyboard::shortcuts::custom::Page>()
+                .filter(|_| {
+                    self.pages
+                        .page_id::<input::keyboard::shortcuts::custom::Page>()
+                        .is_some_and(|id| id == self.active_page)
+                })
+                .map_or_else(Subscription::none, |page| {
+                    page.subscription().map(Message::PageMessage)
+                }),
             #[cfg(feature = "wayland")]
             // Watch for changes to installed desktop entries
             desktop_files(0).map(|_| Message::DesktopInfo),
@@ -347,6 +414,17 @@ impl cosmic::Application for SettingsApp {
         match message {
             Message::Page(page) => return self.activate_page(page),
 
+            Message::PageScrolled(viewport) => {
+                if let Some(page_viewport) = self.pages.resource_mut::<crate::pages::PageViewport>()
+                {
+                    *page_viewport = crate::pages::PageViewport {
+                        offset: viewport.absolute_offset().y,
+                        height: Some(viewport.bounds().height),
+                        content_height: viewport.content_bounds().height,
+                    };
+                }
+            }
+
             Message::None => (),
 
             Message::SetWindowTitle => return self.set_title(),
@@ -355,13 +433,12 @@ impl cosmic::Application for SettingsApp {
                 return self.search_changed(phrase);
             }
 
-            Message::SearchActivate => {
-                self.search_active = true;
-                return cosmic::widget::text_input::focus(self.search_id.clone());
-            }
+            Message::SearchActivate => return self.search_open(),
 
+            // Clearing the search forgets it, rather than restoring it when reopened.
             Message::SearchClear => {
                 self.search_clear();
+                self.set_last_search(String::new());
             }
 
             Message::SearchSubmit => {
@@ -478,6 +555,16 @@ impl cosmic::Application for SettingsApp {
                     }
                 }
 
+                #[cfg(feature = "page-input")]
+                crate::pages::Message::ConflictShortcuts(message) => {
+                    if let Some(page) = self
+                        .pages
+                        .page_mut::<input::keyboard::shortcuts::conflicts::Page>()
+                    {
+                        return page.update(message).map(Into::into);
+                    }
+                }
+
                 #[cfg(feature = "page-input")]
                 crate::pages::Message::CustomShortcuts(message) => {
                     if let Some(page) = self
@@ -584,6 +671,12 @@ impl cosmic::Application for SettingsApp {
                     }
                 }
 
+                crate::pages::Message::Notifications(message) => {
+                    if let Some(page) = self.pages.page_mut::<desktop::notifications::Page>() {
+                        return page.update(message);
+                    }
+                }
+
                 #[cfg(feature = "wayland")]
                 crate::pages::Message::Panel(message) => {
                     if let Some(page) = self.pages.page_mut::<panel::Page>() {
@@ -653,6 +746,14 @@ impl cosmic::Application for SettingsApp {
                     );
                 }
 
+                #[cfg(feature = "page-display")]
+                if let Some(page) = self.pages.page_mut::<display::Page>() {
+                    commands.push(page.update(display::Message::OutputAdded(
+                        info.name.clone().unwrap_or_default(),
+                        output.clone(),
+                    )));
+                }
+
                 if let Some(page) = self.pages.page_mut::<dock::Page>() {
                     commands.push(
                         page.update(dock::Message::Inner(_panel::Message::OutputAdded(
@@ -677,6 +778,11 @@ impl cosmic::Application for SettingsApp {
                     );
                 }
 
+                #[cfg(feature = "page-display")]
+                if let Some(page) = self.pages.page_mut::<display::Page>() {
+                    commands.push(page.update(display::Message::OutputRemoved(output.clone())));
+                }
+
                 if let Some(page) = self.pages.page_mut::<dock::Page>() {
                     commands.push(
                         page.update(dock::Message::Inner(_panel::Message::OutputRemoved(output)))
@@ -764,6 +870,21 @@ impl cosmic::Application for SettingsApp {
             Message::Error(error) => {
                 tracing::error!(error, "error occurred");
             }
+
+            Message::ShowAdvanced(show) => {
+                self.show_advanced = show;
+            }
+
+            Message::ShowToast(message) => {
+                return self
+                    .toasts
+                    .push(toaster::Toast::new(message))
+                    .map(Into::into);
+            }
+
+            Message::CloseToast(id) => {
+                self.toasts.remove(id);
+            }
             Message::Surface(a) => {
                 return cosmic::task::message(cosmic::Action::Cosmic(
                     cosmic::app::Action::Surface(a),
@@ -800,11 +921,20 @@ impl cosmic::Application for SettingsApp {
             return self.page_container(row::row());
         };
 
-        container(view).into()
+        toaster(&self.toasts, container(view))
     }
 
     #[allow(clippy::too_many_lines)]
     fn view_window(&self, id: window::Id) -> Element<Message> {
+        #[cfg(all(feature = "page-display", feature = "wayland"))]
+        if let Some(view) = self
+            .pages
+            .page::<display::Page>()
+            .and_then(|page| page.identify_view(id))
+        {
+            return view.map(Message::PageMessage);
+        }
+
         panic!("unknown window ID: {id:?}");
     }
 
@@ -860,6 +990,13 @@ impl cosmic::Application for SettingsApp {
 }
 
 impl SettingsApp {
+    /// Forgets where the previous content was scrolled to, as new content is shown.
+    fn reset_page_viewport(&mut self) {
+        if let Some(page_viewport) = self.pages.resource_mut::<crate::pages::PageViewport>() {
+            *page_viewport = crate::pages::PageViewport::default();
+        }
+    }
+
     /// Activates a page.
     fn activate_page(&mut self, page: page::Entity) -> Task<crate::Message> {
         let current_page = self.active_page;
@@ -881,10 +1018,28 @@ impl SettingsApp {
                 .map(Into::into);
             self.last_active_page = Box::from(&*self.pages.info[page].id);
             self.config.set_active_page(self.last_active_page.clone());
+            self.reset_page_viewport();
         }
 
-        self.search_clear();
-        self.search_active = false;
+        // Sections keep the search that led to their page, for their views to emphasize it.
+        if !self
+            .search_selections
+            .iter()
+            .any(|&(found, _)| found == page)
+        {
+            self.pages.search_highlight(None, false, &[]);
+        }
+
+        // Advanced sections that the search found are expanded to show them.
+        if self
+            .search_selections
+            .iter()
+            .any(|&(found, section)| found == page && self.pages.sections[section].advanced)
+        {
+            self.show_advanced = true;
+        }
+
+        self.search_close();
         self.activate_navbar(page);
         self.loaded_pages.insert(page);
 
@@ -986,6 +1141,8 @@ impl SettingsApp {
             cosmic::widget::text::title3(&page_info.title).into()
         };
 
+        let mut advanced_sections = Vec::new();
+
         for id in content.iter().copied() {
             let section = &self.pages.sections[id];
             let model = &self.pages.page[self.active_page];
@@ -993,18 +1150,63 @@ impl SettingsApp {
             if section
                 .show_while
                 .as_ref()
-                .map_or(true, |func| func(model.as_ref()))
+                .map_or(true, |func| func(&self.pages, model.as_ref()))
             {
-                sections_column.push(
-                    (section.view_fn)(&self.pages, model.as_ref(), section)
-                        .map(Message::PageMessage),
-                );
+                let mut view = (section.view_fn)(&self.pages, model.as_ref(), section)
+                    .map(Message::PageMessage);
+
+                if let Some(reset_fn) = section.reset_fn.as_ref() {
+                    let reset_button = button::standard(fl!("reset-section"))
+                        .on_press_maybe(reset_fn(model.as_ref()).map(Message::PageMessage))
+                        .apply(container)
+                        .width(Length::Fill)
+                        .align_x(iced::Alignment::End);
+
+                    view = column::with_capacity(2)
+                        .spacing(cosmic::theme::active().cosmic().space_xs())
+                        .push(view)
+                        .push(reset_button)
+                        .into();
+                }
+
+                if section.advanced {
+                    advanced_sections.push(view);
+                } else {
+                    sections_column.push(view);
+                }
             }
         }
 
+        // Advanced sections are grouped at the end of the page, collapsed by default.
+        if !advanced_sections.is_empty() {
+            sections_column.push(
+                settings::item::builder(fl!("show-advanced"))
+                    .toggler(self.show_advanced, Message::ShowAdvanced)
+                    .apply(|item| cosmic::widget::list_column().add(item))
+                    .into(),
+            );
+
+            if self.show_advanced {
+                sections_column.extend(advanced_sections);
+            }
+        }
+
+        // Show where the open context drawer sits within the page hierarchy.
+        let breadcrumb = (self.core.window.show_context
+            && self.active_context_page == Some(self.active_page))
+        .then(|| self.pages.breadcrumb(self.active_page))
+        .flatten()
+        .map(|breadcrumb| cosmic::widget::text::caption(breadcrumb.join(" › ")));
+
+        let header = column::with_capacity(2)
+            .spacing(4)
+            .push_maybe(breadcrumb)
+            .push(header);
+
         let view = self
             .page_container(settings::view_column(sections_column))
             .apply(scrollable)
+            .on_scroll(Message::PageScrolled)
             .height(Length::Fill)
             .apply(|w| id_container(w, self.id()));
 
@@ -1022,53 +1224,62 @@ impl SettingsApp {
             return Task::none();
         }
 
+        self.reset_page_viewport();
         let mut tasks = Vec::new();
 
-        // Create a case-insensitive regular expression for the search function.
-        let search_expression = regex::RegexBuilder::new(&phrase)
-            .case_insensitive(true)
-            .unicode(true)
-            .ignore_whitespace(true)
-            .size_limit(16 * 1024)
-            .build();
-
-        if let Ok(expression) = search_expression {
-            // With the new search expression, generate new search results.
-            let results: Vec<_> = self.pages.search(&expression).collect();
-
-            // Use the results if results were found.
-            if !results.is_empty() {
-                self.search_selections = results;
-
-                let mut unload = BTreeSet::new();
-                let mut load = BTreeSet::new();
-
-                'outer: for loaded_page in &self.loaded_pages {
-                    for (page, _) in &self.search_selections {
-                        if loaded_page == page {
-                            continue 'outer;
-                        }
-                    }
+        // Special characters are searched for literally, unless a pattern is entered as
+        // `/pattern/`.
+        let expression = section::build_search_rule(&phrase);
 
-                    unload.insert(*loaded_page);
-                }
+        // With the new search expression, generate new search results. Short
+        // phrases match parts of too many words, so prefer whole words for them.
+        let mut results = Vec::new();
+        if phrase.chars().count() <= WHOLE_WORD_SEARCH_LEN {
+            results.extend(self.pages.search_words(&expression));
+        }
 
+        let whole_words = !results.is_empty();
+        if results.is_empty() {
+            results.extend(self.pages.search(&expression));
+        }
+
+        // Use the results if results were found.
+        if results.is_empty() {
+            // The previous results stay listed, but no longer match what is emphasized.
+            self.pages.search_highlight(None, false, &[]);
+        } else {
+            self.pages
+                .search_highlight(Some(&expression), whole_words, &results);
+            self.search_selections = results;
+
+            let mut unload = BTreeSet::new();
+            let mut load = BTreeSet::new();
+
+            'outer: for loaded_page in &self.loaded_pages {
                 for (page, _) in &self.search_selections {
-                    if !self.loaded_pages.contains(page) {
-                        load.insert(*page);
+                    if loaded_page == page {
+                        continue 'outer;
                     }
                 }
 
-                for page in load {
-                    self.loaded_pages.insert(page);
-                    tasks.push(self.pages.on_enter(page));
-                }
+                unload.insert(*loaded_page);
+            }
 
-                for page in unload {
-                    self.loaded_pages.remove(&page);
-                    self.pages.on_leave(page);
+            for (page, _) in &self.search_selections {
+                if !self.loaded_pages.contains(page) {
+                    load.insert(*page);
                 }
             }
+
+            for page in load {
+                self.loaded_pages.insert(page);
+                tasks.push(self.pages.on_enter(page));
+            }
+
+            for page in unload {
+                self.loaded_pages.remove(&page);
+                self.pages.on_leave(page);
+            }
         }
 
         self.search_input = phrase;
@@ -1082,10 +1293,42 @@ impl SettingsApp {
         }
     }
 
+    /// Opens the search, with the results of the last search if it was closed with a query.
+    fn search_open(&mut self) -> Task<crate::Message> {
+        self.search_active = true;
+        let focus = cosmic::widget::text_input::focus(self.search_id.clone());
+
+        if self.search_input.is_empty() && !self.last_search.is_empty() {
+            let restore = self.search_changed(self.last_search.clone());
+            return Task::batch(vec![restore, focus]);
+        }
+
+        focus
+    }
+
+    /// Closes the search, remembering its query for when the search is opened again.
+    fn search_close(&mut self) {
+        if !self.search_input.is_empty() {
+            self.set_last_search(std::mem::take(&mut self.search_input));
+        }
+
+        self.search_clear();
+        self.search_active = false;
+    }
+
+    fn set_last_search(&mut self, phrase: String) {
+        if self.config.remember_search() {
+            self.config.set_last_search(&phrase);
+        }
+
+        self.last_search = phrase;
+    }
+
     /// Clears the search results so that the search page will not be shown.
     fn search_clear(&mut self) {
         self.search_selections.clear();
         self.search_input.clear();
+        self.reset_page_viewport();
     }
 
     /// Displays the search view.
@@ -1105,7 +1348,7 @@ impl SettingsApp {
             if section
                 .show_while
                 .as_ref()
-                .map_or(true, |func| func(model.as_ref()))
+                .map_or(true, |func| func(&self.pages, model.as_ref()))
             {
                 let section = (section.view_fn)(&self.pages, model.as_ref(), section)
                     .map(Message::PageMessage)
@@ -1118,6 +1361,7 @@ impl SettingsApp {
 
         self.page_container(settings::view_column(sections))
             .apply(scrollable)
+            .on_scroll(Message::PageScrolled)
             .into()
     }
 
@@ -11,6 +11,9 @@ const NAME: &str = "com.system76.CosmicSettings";
 const ACTIVE_PAGE: &str = "active_page";
 const ACCENT_PALETTE_DARK: &str = "accent_palette_dark";
 const ACCENT_PALETTE_LIGHT: &str = "accent_palette_light";
+const LAST_SEARCH: &str = "last_search";
+/// Whether the last search is restored after the app is restarted.
+const REMEMBER_SEARCH: &str = "remember_search";
 
 #[must_use]
 #[derive(Debug, Clone)]
@@ -57,4 +60,17 @@ impl Config {
             tracing::error!(?why, "failed to store active page ID");
         }
     }
+    pub fn remember_search(&self) -> bool {
+        self.config.get::<bool>(REMEMBER_SEARCH).unwrap_or(false)
+    }
+
+    pub fn last_search(&self) -> String {
+        self.state.get::<String>(LAST_SEARCH).unwrap_or_default()
+    }
+
+    pub fn set_last_search(&self, search: &str) {
+        if let Err(why) = self.state.set::<&str>(LAST_SEARCH, search) {
+            tracing::error!(?why, "failed to store last search");
+        }
+    }
 }
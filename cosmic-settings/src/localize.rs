@@ -37,6 +37,15 @@ macro_rules! fl {
     }};
 }
 
+/// Localizes a fluent message by its key, where `message.attribute` selects an attribute.
+#[must_use]
+pub fn message(key: &str) -> String {
+    match key.split_once('.') {
+        Some((message_id, attribute)) => LANGUAGE_LOADER.get_attr(message_id, attribute),
+        None => LANGUAGE_LOADER.get(key),
+    }
+}
+
 // Get the `Localizer` to be used for localizing this library.
 #[must_use]
 #[inline(always)]
@@ -85,6 +85,8 @@ pub enum PageCommands {
     /// Network settings page
     #[cfg(feature = "page-networking")]
     Network,
+    /// Notifications settings page
+    Notifications,
     /// Panel settings page
     #[cfg(feature = "wayland")]
     Panel,
@@ -107,6 +109,15 @@ pub enum PageCommands {
     /// Users settings page
     #[cfg(feature = "page-users")]
     Users,
+    /// Check a shortcuts config file for invalid and duplicate key combinations
+    #[cfg(feature = "page-input")]
+    ValidateShortcuts {
+        /// Path to the shortcuts config file
+        path: std::path::PathBuf,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// VPN settings page
     #[cfg(feature = "page-networking")]
     Vpn,
@@ -169,6 +180,12 @@ pub fn main() -> color_eyre::Result<()> {
 
     let args = Args::parse();
 
+    #[cfg(feature = "page-input")]
+    if let Some(PageCommands::ValidateShortcuts { path, json }) = &args.sub_command {
+        let valid = pages::input::keyboard::shortcuts::validate::run(path, *json);
+        std::process::exit(if valid { 0 } else { 1 });
+    }
+
     let settings = cosmic::app::Settings::default()
         .size_limits(Limits::NONE.min_width(360.0).min_height(300.0));
 
@@ -30,6 +30,8 @@ pub struct Page {
     increment_idx: Option<usize>,
     zoom_in_shortcuts: Vec<Binding>,
     zoom_out_shortcuts: Vec<Binding>,
+    /// Describes the zoom shortcuts as they are bound when the page is entered.
+    controls: String,
 
     wayland_thread: Option<wayland::Sender>,
     magnifier_state: bool,
@@ -88,6 +90,7 @@ impl Default for Page {
             zoom_config,
             increment_values,
             increment_idx,
+            controls: controls_description(&zoom_in_shortcuts, &zoom_out_shortcuts),
             zoom_in_shortcuts,
             zoom_out_shortcuts,
 
@@ -115,13 +118,18 @@ impl page::Page<crate::pages::Message> for Page {
         sections: &mut SlotMap<section::Entity, page::Section<crate::pages::Message>>,
     ) -> Option<page::Content> {
         Some(vec![
-            sections.insert(magnifier(&self.zoom_in_shortcuts, &self.zoom_out_shortcuts)),
+            sections.insert(magnifier(&self.controls)),
+            sections.insert(shortcuts_link()),
             sections.insert(tip()),
             sections.insert(view_movement()),
         ])
     }
 
     fn on_enter(&mut self) -> cosmic::Task<crate::pages::Message> {
+        // The shortcuts may have been changed since the page was last shown.
+        (self.zoom_in_shortcuts, self.zoom_out_shortcuts) = zoom_shortcuts();
+        self.controls = controls_description(&self.zoom_in_shortcuts, &self.zoom_out_shortcuts);
+
         if self.wayland_thread.is_none() {
             match wayland::spawn_wayland_connection() {
                 Ok((tx, mut rx)) => {
@@ -169,36 +177,12 @@ impl page::Page<crate::pages::Message> for Page {
 
 impl page::AutoBind<crate::pages::Message> for Page {}
 
-pub fn magnifier(
-    zoom_in: &[Binding],
-    zoom_out: &[Binding],
-) -> section::Section<crate::pages::Message> {
-    let zoom_in = if zoom_in.is_empty() {
-        String::from("zero")
-    } else {
-        zoom_in.iter().fold(String::new(), |mut str, b| {
-            if !str.is_empty() {
-                str += ", ";
-            }
-            b.to_string_in_place(&mut str);
-            str
-        })
-    };
-    let zoom_out = if zoom_out.is_empty() {
-        String::from("zero")
-    } else {
-        zoom_out.iter().fold(String::new(), |mut str, b| {
-            if !str.is_empty() {
-                str += ", ";
-            }
-            b.to_string_in_place(&mut str);
-            str
-        })
-    };
-
+pub fn magnifier(controls: &str) -> section::Section<crate::pages::Message> {
     crate::slab!(descriptions {
         magnifier = fl!("magnifier");
-        controls = fl!("magnifier", "controls", zoom_in = zoom_in, zoom_out = zoom_out);
+        // Searched as the shortcuts were bound at startup, while the view shows them as
+        // they are bound now.
+        _controls = controls.to_owned();
         scroll_controls = fl!("magnifier", "scroll_controls");
         show_overlay = fl!("magnifier", "show_overlay");
         increment = fl!("magnifier", "increment");
@@ -215,7 +199,7 @@ pub fn magnifier(
                 .title(&section.title)
                 .add(
                     settings::item::builder(&descriptions[magnifier])
-                        .description(&descriptions[controls])
+                        .description(&page.controls)
                         .control(
                             widget::toggler(page.magnifier_state).on_toggle(Message::SetMagnifier),
                         ),
@@ -253,6 +237,32 @@ pub fn magnifier(
         })
 }
 
+/// Leads to the system shortcuts, where the zoom shortcuts are bound.
+pub fn shortcuts_link() -> section::Section<crate::pages::Message> {
+    crate::slab!(descriptions {
+        shortcuts = fl!("magnifier", "shortcuts");
+    });
+
+    Section::default()
+        .descriptions(descriptions)
+        .view::<Page>(move |binder, _page, section| {
+            let descriptions = &section.descriptions;
+
+            let shortcuts_page = binder
+                .info
+                .iter()
+                .find(|(_, info)| info.id == "system-shortcut")
+                .map(|(entity, _)| crate::pages::Message::Page(entity));
+
+            settings::section()
+                .add(crate::widget::go_next_item(
+                    &descriptions[shortcuts],
+                    shortcuts_page,
+                ))
+                .into()
+        })
+}
+
 pub fn tip() -> section::Section<crate::pages::Message> {
     crate::slab!(descriptions {
         applet = fl!("magnifier", "applet");
@@ -424,6 +434,31 @@ impl Page {
     }
 }
 
+/// Lists the zoom shortcuts, or `zero` where an action has none, for the `controls`
+/// description of the magnifier.
+fn controls_description(zoom_in: &[Binding], zoom_out: &[Binding]) -> String {
+    let list = |bindings: &[Binding]| {
+        if bindings.is_empty() {
+            return String::from("zero");
+        }
+
+        bindings.iter().fold(String::new(), |mut str, b| {
+            if !str.is_empty() {
+                str += ", ";
+            }
+            b.to_string_in_place(&mut str);
+            str
+        })
+    };
+
+    fl!(
+        "magnifier",
+        "controls",
+        zoom_in = list(zoom_in),
+        zoom_out = list(zoom_out)
+    )
+}
+
 fn zoom_shortcuts() -> (Vec<Binding>, Vec<Binding>) {
     let Some(config) = shortcuts::context().ok() else {
         return (Vec::new(), Vec::new());
@@ -17,6 +17,7 @@ use num_traits::FromPrimitive;
 use slotmap::SlotMap;
 
 pub mod magnifier;
+mod osk;
 mod wayland;
 pub use wayland::{AccessibilityEvent, AccessibilityRequest, ColorFilter};
 
@@ -33,6 +34,10 @@ pub struct Page {
     wayland_thread: Option<wayland::Sender>,
     theme: Box<cosmic::cosmic_theme::Theme>,
     high_contrast: Option<bool>,
+    /// Whether apps are asked to turn off animations, as read from the GNOME settings.
+    reduce_motion: bool,
+    /// Whether the on-screen keyboard is enabled, as read from the GNOME settings.
+    osk: bool,
     daemon_config: CosmicSettingsDaemonConfig,
     daemon_helper: cosmic_config::Config,
 }
@@ -40,6 +45,7 @@ pub struct Page {
 impl Default for Page {
     fn default() -> Self {
         let daemon_helper = CosmicSettingsDaemonConfig::config().unwrap();
+
         Page {
             entity: page::Entity::default(),
             magnifier_state: false,
@@ -59,6 +65,8 @@ impl Default for Page {
             wayland_thread: None,
             theme: Box::default(),
             high_contrast: None,
+            reduce_motion: false,
+            osk: false,
             daemon_config: CosmicSettingsDaemonConfig::get_entry(&daemon_helper)
                 .unwrap_or_default(),
             daemon_helper,
@@ -72,12 +80,18 @@ pub enum Message {
     ProtocolUnavailable,
     Return,
     HighContrast(bool),
+    ReduceMotion(bool),
+    /// Whether animations are reduced, once read from the GNOME settings.
+    ReducedMotion(bool),
     SystemTheme(Box<cosmic::cosmic_theme::Theme>),
     SetScreenInverted(bool),
     SetScreenFilterActive(bool),
     SetScreenFilterSelection(ColorFilter),
     Surface(surface::Action),
     SetSoundMono(bool),
+    /// Whether the on-screen keyboard is enabled, once read from the GNOME settings.
+    OskEnabled(bool),
+    SetOsk(bool),
 }
 
 impl page::Page<crate::pages::Message> for Page {
@@ -97,17 +111,32 @@ impl page::Page<crate::pages::Message> for Page {
         &self,
         sections: &mut SlotMap<section::Entity, page::Section<crate::pages::Message>>,
     ) -> Option<page::Content> {
-        Some(vec![sections.insert(vision()), sections.insert(hearing())])
+        Some(vec![
+            sections.insert(vision()),
+            sections.insert(hearing()),
+            sections.insert(osk::on_screen_keyboard()),
+        ])
     }
 
     fn on_enter(&mut self) -> cosmic::Task<crate::pages::Message> {
+        let gnome = cosmic::Task::batch([
+            cosmic::task::future(async {
+                let enabled = osk::gnome_osk().await.unwrap_or_default();
+                crate::pages::Message::Accessibility(Message::OskEnabled(enabled))
+            }),
+            cosmic::task::future(async {
+                let animations = gnome_animations().await.unwrap_or(true);
+                crate::pages::Message::Accessibility(Message::ReducedMotion(!animations))
+            }),
+        ]);
+
         if self.wayland_thread.is_none() {
             match wayland::spawn_wayland_connection() {
                 Ok((tx, mut rx)) => {
                     self.wayland_thread = Some(tx);
 
-                    return cosmic::Task::stream(async_fn_stream::fn_stream(
-                        |emitter| async move {
+                    let events =
+                        cosmic::Task::stream(async_fn_stream::fn_stream(|emitter| async move {
                             while let Some(event) = rx.recv().await {
                                 let _ = emitter
                                     .emit(crate::pages::Message::Accessibility(Message::Event(
@@ -121,8 +150,9 @@ impl page::Page<crate::pages::Message> for Page {
                                     Message::ProtocolUnavailable,
                                 ))
                                 .await;
-                        },
-                    ));
+                        }));
+
+                    return cosmic::Task::batch([gnome, events]);
                 }
                 Err(err) => {
                     tracing::warn!(
@@ -134,7 +164,7 @@ impl page::Page<crate::pages::Message> for Page {
             }
         }
 
-        cosmic::Task::none()
+        gnome
     }
 
     fn on_leave(&mut self) -> cosmic::Task<crate::pages::Message> {
@@ -158,6 +188,9 @@ pub fn vision() -> section::Section<crate::pages::Message> {
         off = fl!("accessibility", "off");
         unavailable = fl!("accessibility", "unavailable");
         high_contrast = fl!("accessibility", "high-contrast");
+        high_contrast_desc = fl!("accessibility", "high-contrast-desc");
+        reduce_motion = fl!("accessibility", "reduce-motion");
+        reduce_motion_desc = fl!("accessibility", "reduce-motion-desc");
         invert_colors = fl!("accessibility", "invert-colors");
         color_filters = fl!("accessibility", "color-filters");
         color_filter_type = fl!("color-filter");
@@ -199,10 +232,19 @@ pub fn vision() -> section::Section<crate::pages::Message> {
                 .add(
                     cosmic::Element::from(
                         settings::item::builder(&descriptions[high_contrast])
+                            .description(&descriptions[high_contrast_desc])
                             .toggler(page.theme.is_high_contrast, Message::HighContrast),
                     )
                     .map(crate::pages::Message::Accessibility),
                 )
+                .add(
+                    cosmic::Element::from(
+                        settings::item::builder(&descriptions[reduce_motion])
+                            .description(&descriptions[reduce_motion_desc])
+                            .toggler(page.reduce_motion, Message::ReduceMotion),
+                    )
+                    .map(crate::pages::Message::Accessibility),
+                )
                 .add(
                     cosmic::Element::from(
                         settings::item::builder(&descriptions[invert_colors]).control(
@@ -365,6 +407,14 @@ impl Page {
                     }
                 });
             }
+            Message::ReduceMotion(enabled) => {
+                self.reduce_motion = enabled;
+                // GTK and the settings portal read this hint, which other toolkits follow.
+                tokio::spawn(set_gnome_animations(!enabled));
+            }
+            Message::ReducedMotion(enabled) => {
+                self.reduce_motion = enabled;
+            }
             Message::SetScreenInverted(inverted) => {
                 if let Some(sender) = self.wayland_thread.as_ref() {
                     let _ = sender.send(AccessibilityRequest::ScreenFilter {
@@ -404,7 +454,47 @@ impl Page {
                     tracing::error!("{err:?}");
                 }
             }
+            Message::OskEnabled(enabled) => {
+                self.osk = enabled;
+            }
+            Message::SetOsk(enabled) => {
+                self.osk = enabled;
+                tokio::spawn(osk::set_gnome_osk(enabled));
+            }
         }
         cosmic::iced::Task::none()
     }
 }
+
+/// Reads a boolean key of the GNOME settings, which is unset where `gsettings` is missing.
+async fn gsettings_bool(schema: &str, key: &str) -> Option<bool> {
+    let output = tokio::process::Command::new("gsettings")
+        .args(["get", schema, key])
+        .output()
+        .await
+        .ok()?;
+
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the animations of GNOME/GTK applications are enabled.
+async fn gnome_animations() -> Option<bool> {
+    gsettings_bool("org.gnome.desktop.interface", "enable-animations").await
+}
+
+/// Enable or disable the animations of GNOME/GTK applications.
+async fn set_gnome_animations(enabled: bool) {
+    let _res = tokio::process::Command::new("gsettings")
+        .args([
+            "set",
+            "org.gnome.desktop.interface",
+            "enable-animations",
+            if enabled { "true" } else { "false" },
+        ])
+        .status()
+        .await;
+}
@@ -0,0 +1,51 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The on-screen keyboard, for touchscreens and for those who cannot use a physical keyboard.
+
+use cosmic::{Apply, widget::settings};
+use cosmic_settings_page::Section;
+
+use super::{Message, Page};
+
+/// Schema of the GNOME settings that keyboards such as Squeekboard follow.
+const SCHEMA: &str = "org.gnome.desktop.a11y.applications";
+const KEY: &str = "screen-keyboard-enabled";
+
+/// Whether the on-screen keyboard of GNOME/GTK desktops is enabled.
+pub async fn gnome_osk() -> Option<bool> {
+    super::gsettings_bool(SCHEMA, KEY).await
+}
+
+/// Enable or disable the on-screen keyboard of GNOME/GTK desktops.
+pub async fn set_gnome_osk(enabled: bool) {
+    let _res = tokio::process::Command::new("gsettings")
+        .args(["set", SCHEMA, KEY, if enabled { "true" } else { "false" }])
+        .status()
+        .await;
+}
+
+pub fn on_screen_keyboard() -> Section<crate::pages::Message> {
+    crate::slab!(descriptions {
+        title = fl!("on-screen-keyboard");
+        enable = fl!("on-screen-keyboard", "enable");
+        enable_desc = fl!("on-screen-keyboard", "enable-desc");
+    });
+
+    Section::default()
+        .title(&descriptions[title])
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            settings::section()
+                .title(&section.title)
+                .add(
+                    settings::item::builder(&descriptions[enable])
+                        .description(&descriptions[enable_desc])
+                        .toggler(page.osk, Message::SetOsk),
+                )
+                .apply(cosmic::Element::from)
+                .map(crate::pages::Message::Accessibility)
+        })
+}
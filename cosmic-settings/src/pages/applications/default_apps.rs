@@ -32,6 +32,22 @@ const DROPDOWN_CALENDAR: usize = 6;
 const DROPDOWN_TERMINAL: usize = 7;
 const DROPDOWN_TEXT_EDITOR: usize = 8;
 
+/// Types that the defaults of each dropdown are shown for, in the order of the dropdowns.
+const DROPDOWN_MIMES: [&str; 9] = [
+    "x-scheme-handler/http",
+    "inode/directory",
+    "x-scheme-handler/mailto",
+    "audio/mp3",
+    "video/mp4",
+    "image/png",
+    "text/calendar",
+    "x-scheme-handler/terminal",
+    "text/plain",
+];
+
+/// Other associations shown at once, since each has a dropdown of its own.
+const ASSOCIATIONS_SHOWN: usize = 50;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum Category {
     Audio,
@@ -39,7 +55,8 @@ pub enum Category {
     FileManager,
     Image,
     Mail,
-    Mime(&'static str),
+    /// The type at this index of the other associations.
+    Mime(usize),
     Terminal,
     Video,
     WebBrowser,
@@ -48,6 +65,7 @@ pub enum Category {
 
 #[derive(Clone, Debug)]
 pub enum Message {
+    SearchAssociations(String),
     SetDefault(Category, usize),
     Update(CachedMimeApps),
     Surface(surface::Action),
@@ -72,6 +90,9 @@ pub struct CachedMimeApps {
     pub apps: Vec<AppMeta>,
     pub known_mimes: BTreeSet<mime::Mime>,
     pub config_path: Box<Path>,
+    /// Types that installed applications declare support for, with the applications that
+    /// may open them.
+    pub associations: Vec<(String, AppMeta)>,
 }
 
 #[derive(Clone, Debug)]
@@ -87,6 +108,7 @@ pub struct Page {
     on_enter_handle: Option<cosmic::iced::task::Handle>,
     mime_apps: Option<CachedMimeApps>,
     shortcuts_config: Option<cosmic_config::Config>,
+    association_search: String,
 }
 
 impl page::AutoBind<crate::pages::Message> for Page {}
@@ -96,7 +118,10 @@ impl page::Page<crate::pages::Message> for Page {
         &self,
         sections: &mut SlotMap<section::Entity, Section<crate::pages::Message>>,
     ) -> Option<cosmic_settings_page::Content> {
-        Some(vec![sections.insert(apps())])
+        Some(vec![
+            sections.insert(apps()),
+            sections.insert(associations()),
+        ])
     }
 
     fn info(&self) -> page::Info {
@@ -140,6 +165,13 @@ impl page::Page<crate::pages::Message> for Page {
                 load_defaults(&assocs, &["text/plain"]).await,
             ];
 
+            let defaults = mimeapps_list_paths()
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok())
+                .collect::<Vec<_>>();
+
+            let associations = load_associations(&assocs, &parse_defaults(&defaults));
+
             Message::Update(CachedMimeApps {
                 apps,
                 list,
@@ -149,6 +181,7 @@ impl page::Page<crate::pages::Message> for Page {
                     .expect("config dir not found")
                     .join("mimeapps.list")
                     .into(),
+                associations,
             })
             .into()
         })
@@ -173,6 +206,39 @@ impl page::Page<crate::pages::Message> for Page {
 impl Page {
     pub fn update(&mut self, message: Message) -> Task<crate::Message> {
         match message {
+            Message::SearchAssociations(search) => {
+                self.association_search = search;
+            }
+
+            Message::SetDefault(Category::Mime(idx), id) => {
+                let Some(mime_apps) = self.mime_apps.as_mut() else {
+                    return Task::none();
+                };
+
+                let Some((mime, meta)) = mime_apps.associations.get_mut(idx) else {
+                    return Task::none();
+                };
+
+                if meta.selected != Some(id) {
+                    meta.selected = Some(id);
+                    let appid = meta.app_ids[id].clone();
+
+                    if let Some(category_id) =
+                        DROPDOWN_MIMES.iter().position(|m| *m == mime.as_str())
+                    {
+                        mime_apps.apps[category_id].select(&appid);
+                    }
+
+                    if let Ok(mime) = mime.parse() {
+                        mime_apps
+                            .local_list
+                            .set_default_app(mime, [appid.as_str(), ".desktop"].concat());
+                    }
+
+                    mime_apps.save();
+                }
+            }
+
             Message::SetDefault(category, id) => {
                 let Some(mime_apps) = self.mime_apps.as_mut() else {
                     return Task::none();
@@ -238,7 +304,7 @@ impl Page {
                         ],
                     ),
                     Category::TextEditor => (DROPDOWN_TEXT_EDITOR, &["text/plain"]),
-                    Category::Mime(_mime_type) => return Task::none(),
+                    Category::Mime(_) => return Task::none(),
                 };
 
                 let meta = &mut mime_apps.apps[category_id];
@@ -261,11 +327,15 @@ impl Page {
                         };
                     }
 
-                    let mut buffer = mime_apps.local_list.to_string();
-                    buffer.push('\n');
+                    let appid = appid.clone();
+
+                    for (mime, meta) in &mut mime_apps.associations {
+                        if mime_types.contains(&mime.as_str()) {
+                            meta.select(&appid);
+                        }
+                    }
 
-                    _ = std::fs::write(&mime_apps.config_path, buffer);
-                    _ = std::process::Command::new("update-desktop-database").status();
+                    mime_apps.save();
                 }
             }
             Message::Update(mime_apps) => {
@@ -280,6 +350,26 @@ impl Page {
     }
 }
 
+impl CachedMimeApps {
+    /// Writes the defaults of the user to their mimeapps.list.
+    fn save(&self) {
+        let mut buffer = self.local_list.to_string();
+        buffer.push('\n');
+
+        _ = std::fs::write(&self.config_path, buffer);
+        _ = std::process::Command::new("update-desktop-database").status();
+    }
+}
+
+impl AppMeta {
+    /// Selects the application with this ID, if it may be chosen.
+    fn select(&mut self, appid: &str) {
+        if let Some(id) = self.app_ids.iter().position(|id| id == appid) {
+            self.selected = Some(id);
+        }
+    }
+}
+
 fn apps() -> Section<crate::pages::Message> {
     Section::default().view::<Page>(move |_binder, page, section| {
         let Some(mime_apps) = page.mime_apps.as_ref() else {
@@ -429,6 +519,85 @@ fn apps() -> Section<crate::pages::Message> {
     })
 }
 
+/// Every type that an application declares support for, including URL schemes, and the
+/// applications that may open it.
+fn associations() -> Section<crate::pages::Message> {
+    crate::slab!(descriptions {
+        title = fl!("default-apps", "other-associations");
+        search = fl!("default-apps", "search-associations");
+    });
+
+    Section::default()
+        .title(&descriptions[title])
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let Some(mime_apps) = page.mime_apps.as_ref() else {
+                return widget::row().into();
+            };
+
+            let descriptions = &section.descriptions;
+            let query = page.association_search.trim().to_lowercase();
+
+            let matches = mime_apps
+                .associations
+                .iter()
+                .enumerate()
+                .filter(|(_, (mime, _))| query.is_empty() || mime.contains(&query))
+                .collect::<Vec<_>>();
+
+            let search_input =
+                widget::search_input(&descriptions[search], &page.association_search)
+                    .on_input(Message::SearchAssociations)
+                    .on_clear(Message::SearchAssociations(String::new()));
+
+            let mut list = settings::section().title(&section.title);
+
+            for &(idx, (mime, meta)) in matches.iter().take(ASSOCIATIONS_SHOWN) {
+                list = list.add(
+                    settings::flex_item(
+                        mime.as_str(),
+                        dropdown::popup_dropdown(
+                            &meta.apps,
+                            meta.selected,
+                            move |id| Message::SetDefault(Category::Mime(idx), id),
+                            cosmic::iced::window::Id::RESERVED,
+                            Message::Surface,
+                            |a| {
+                                crate::app::Message::PageMessage(
+                                    crate::pages::Message::DefaultApps(a),
+                                )
+                            },
+                        )
+                        .icons(&meta.icons),
+                    )
+                    .min_item_width(300.0),
+                );
+            }
+
+            if matches.is_empty() {
+                list = list.add(settings::item_row(vec![
+                    widget::text::body(fl!("default-apps", "no-associations")).into(),
+                ]));
+            } else if matches.len() > ASSOCIATIONS_SHOWN {
+                list = list.add(settings::item_row(vec![
+                    widget::text::caption(fl!(
+                        "default-apps",
+                        "more-associations",
+                        count = matches.len() - ASSOCIATIONS_SHOWN
+                    ))
+                    .into(),
+                ]));
+            }
+
+            widget::column::with_capacity(2)
+                .spacing(cosmic::theme::spacing().space_s)
+                .push(search_input)
+                .push(list)
+                .apply(Element::from)
+                .map(crate::pages::Message::DefaultApps)
+        })
+}
+
 fn assign_default_terminal(config: &cosmic_config::Config, appid: &str) {
     let mut actions = config
         .get_local::<SystemActions>("system_actions")
@@ -525,6 +694,140 @@ async fn load_defaults(assocs: &BTreeMap<Arc<str>, Arc<App>>, for_mimes: &[&str]
     }
 }
 
+/// Types declared by installed desktop entries, with the applications that support them.
+fn load_associations(
+    assocs: &BTreeMap<Arc<str>, Arc<App>>,
+    defaults: &BTreeMap<String, String>,
+) -> Vec<(String, AppMeta)> {
+    let mut mimes = BTreeSet::new();
+
+    for path in DesktopEntryIter::new(default_paths()) {
+        if let Ok(bytes) = std::fs::read_to_string(&path) {
+            if let Ok(entry) = DesktopEntry::from_str(&path, &bytes, None::<&[&str]>) {
+                if let Some(types) = entry.mime_type() {
+                    mimes.extend(types.into_iter().map(str::to_lowercase));
+                }
+            }
+        }
+    }
+
+    mimes
+        .into_iter()
+        .filter_map(|for_mime| {
+            let mime = for_mime.parse::<mime::Mime>().ok()?;
+
+            let mut unsorted = mime_apps::apps_for_mime(&mime, assocs)
+                .map(|(app_id, app)| (app_id.clone(), app.clone()))
+                .collect::<Vec<_>>();
+
+            unsorted.sort_unstable_by_key(|(_, app)| app.name.clone());
+            unsorted.dedup_by_key(|(app_id, _)| app_id.clone());
+
+            if unsorted.is_empty() {
+                return None;
+            }
+
+            let mut meta = AppMeta {
+                selected: None,
+                app_ids: Vec::with_capacity(unsorted.len()),
+                apps: Vec::with_capacity(unsorted.len()),
+                icons: Vec::with_capacity(unsorted.len()),
+            };
+
+            for (appid, app) in unsorted {
+                meta.app_ids.push(appid.as_ref().into());
+                meta.apps.push(app.name.as_ref().into());
+                meta.icons.push(if app.icon.starts_with('/') {
+                    icon::from_path(PathBuf::from(app.icon.as_ref()))
+                } else {
+                    icon::from_name(app.icon.as_ref()).size(20).handle()
+                });
+            }
+
+            if let Some(appid) = defaults.get(&for_mime) {
+                meta.select(appid);
+            }
+
+            Some((for_mime, meta))
+        })
+        .collect()
+}
+
+/// The mimeapps.list files that defaults are looked up in, from the first to be consulted.
+fn mimeapps_list_paths() -> Vec<PathBuf> {
+    let split = |var: &str, fallback: &str| {
+        std::env::var(var)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| fallback.to_owned())
+            .split(':')
+            .map(PathBuf::from)
+            .collect::<Vec<_>>()
+    };
+
+    let desktops = std::env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|desktop| !desktop.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+
+    let config_dirs = dirs::config_dir()
+        .into_iter()
+        .chain(split("XDG_CONFIG_DIRS", "/etc/xdg"));
+
+    let data_dirs = dirs::data_dir()
+        .into_iter()
+        .chain(split("XDG_DATA_DIRS", "/usr/local/share:/usr/share"))
+        .map(|dir| dir.join("applications"));
+
+    config_dirs
+        .chain(data_dirs)
+        .flat_map(|dir| {
+            desktops
+                .iter()
+                .map(|desktop| dir.join(format!("{desktop}-mimeapps.list")))
+                .chain(std::iter::once(dir.join("mimeapps.list")))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The default application ID of each type, from the first of the mimeapps.list files that
+/// names one.
+fn parse_defaults(lists: &[String]) -> BTreeMap<String, String> {
+    let mut defaults = BTreeMap::new();
+
+    for list in lists {
+        let mut in_defaults = false;
+
+        for line in list.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_defaults = line == "[Default Applications]";
+                continue;
+            }
+
+            if !in_defaults {
+                continue;
+            }
+
+            let Some((mime, apps)) = line.split_once('=') else {
+                continue;
+            };
+
+            let app = apps.split(';').map(str::trim).find(|app| !app.is_empty());
+
+            if let Some(app) = app {
+                defaults
+                    .entry(mime.trim().to_lowercase())
+                    .or_insert_with(|| app.strip_suffix(".desktop").unwrap_or(app).to_owned());
+            }
+        }
+    }
+
+    defaults
+}
+
 async fn xdg_mime_query_default(mime_type: &str) -> Option<String> {
     let output = tokio::process::Command::new("xdg-mime")
         .args(&["query", "default", mime_type])
@@ -615,3 +918,42 @@ async fn load_terminal_apps(assocs: &BTreeMap<Arc<str>, Arc<App>>) -> AppMeta {
         icons,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_defaults;
+
+    #[test]
+    fn first_list_to_name_a_default_wins() {
+        let user = String::from(
+            "[Added Associations]\n\
+             text/html=org.gnome.Epiphany.desktop;\n\
+             \n\
+             [Default Applications]\n\
+             text/html=firefox.desktop;\n\
+             x-scheme-handler/mailto=;thunderbird.desktop;\n",
+        );
+
+        let system = String::from(
+            "[Default Applications]\n\
+             text/html=org.gnome.Epiphany.desktop\n\
+             inode/directory=com.system76.CosmicFiles.desktop\n",
+        );
+
+        let defaults = parse_defaults(&[user, system]);
+
+        assert_eq!(
+            defaults.get("text/html").map(String::as_str),
+            Some("firefox")
+        );
+        assert_eq!(
+            defaults.get("x-scheme-handler/mailto").map(String::as_str),
+            Some("thunderbird")
+        );
+        assert_eq!(
+            defaults.get("inode/directory").map(String::as_str),
+            Some("com.system76.CosmicFiles")
+        );
+        assert_eq!(defaults.len(), 3);
+    }
+}
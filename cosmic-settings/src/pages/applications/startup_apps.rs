@@ -8,9 +8,15 @@ use freedesktop_desktop_entry::DesktopEntry;
 use itertools::Itertools;
 use slotmap::{Key, SlotMap};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::error;
 
+/// Seconds that an application may be launched after login, offered as delays.
+const DELAYS: [u32; 5] = [0, 5, 10, 30, 60];
+
+/// Key of the delay before launching an autostart entry, in seconds.
+const DELAY_KEY: &str = "X-GNOME-Autostart-Delay";
+
 #[derive(Clone, Debug)]
 pub struct CachedApps {
     apps: HashMap<DirectoryType, Vec<DesktopEntry>>,
@@ -26,6 +32,10 @@ pub struct Page {
     context: Option<Context>,
     app_to_remove: Option<DesktopEntry>,
     target_directory_type: Option<DirectoryType>,
+    /// Name of a command being added to run on login.
+    command_name: String,
+    command: String,
+    delay_labels: Vec<String>,
 }
 
 impl Default for Page {
@@ -38,6 +48,18 @@ impl Default for Page {
             context: None,
             app_to_remove: None,
             target_directory_type: None,
+            command_name: String::new(),
+            command: String::new(),
+            delay_labels: DELAYS
+                .iter()
+                .map(|&seconds| {
+                    if seconds == 0 {
+                        fl!("startup-apps", "no-delay")
+                    } else {
+                        fl!("startup-apps", "delay-seconds", seconds = seconds)
+                    }
+                })
+                .collect(),
         }
     }
 }
@@ -45,9 +67,16 @@ impl Default for Page {
 #[derive(Clone, Debug)]
 pub enum Message {
     AddStartupApplication(DirectoryType, DesktopEntry),
+    AddStartupCommand,
     ApplicationSearch(String),
     CancelRemoveStartupApplication,
+    CommandName(String),
+    CommandInput(String),
     RemoveStartupApplication(DirectoryType, DesktopEntry, bool),
+    /// Launch the entry at this path on login, or not.
+    SetEnabled(PathBuf, bool),
+    /// Delay the launch of the entry at this path by the delay at this index of [`DELAYS`].
+    SetDelay(PathBuf, usize),
     ShowApplicationSidebar(DirectoryType),
     UpdateApplications(CachedApps),
     UpdateStartupApplications(CachedApps),
@@ -56,6 +85,9 @@ pub enum Message {
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum DirectoryType {
     User,
+    /// Installed with the system, which are only turned off by entries of the user that
+    /// hide them.
+    System,
 }
 
 #[derive(Clone, Debug)]
@@ -71,6 +103,13 @@ impl Into<Vec<PathBuf>> for DirectoryType {
                     .expect("config dir not found")
                     .join("autostart"),
             ],
+            DirectoryType::System => std::env::var("XDG_CONFIG_DIRS")
+                .ok()
+                .filter(|dirs| !dirs.is_empty())
+                .unwrap_or_else(|| String::from("/etc/xdg"))
+                .split(':')
+                .map(|dir| Path::new(dir).join("autostart"))
+                .collect(),
         }
     }
 }
@@ -79,6 +118,7 @@ impl std::fmt::Display for DirectoryType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             DirectoryType::User => write!(f, "{}", fl!("startup-apps", "user")),
+            DirectoryType::System => write!(f, "{}", fl!("startup-apps", "system")),
         }
     }
 }
@@ -134,28 +174,7 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
-        let (task, on_enter_handle) = Task::future(async move {
-            let locales = freedesktop_desktop_entry::get_languages_from_env();
-
-            let user_dirs: Vec<PathBuf> = DirectoryType::User.into();
-            let user_entries =
-                freedesktop_desktop_entry::Iter::new(user_dirs.into_iter()).entries(Some(&locales));
-
-            let mut apps_hash = HashMap::with_capacity(1);
-            apps_hash.insert(DirectoryType::User, user_entries.collect_vec());
-
-            Message::UpdateStartupApplications(CachedApps {
-                apps: apps_hash,
-                all_apps: get_all_apps(locales.clone()),
-                locales,
-            })
-            .into()
-        })
-        .abortable();
-
-        self.on_enter_handle = Some(on_enter_handle);
-
-        task
+        self.reload()
     }
 
     fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
@@ -205,8 +224,119 @@ impl page::Page<crate::pages::Message> for Page {
 }
 
 impl Page {
+    /// Reads the autostart entries again, after they were written to.
+    fn reload(&mut self) -> Task<crate::pages::Message> {
+        if let Some(handle) = self.on_enter_handle.take() {
+            handle.abort();
+        }
+
+        let (task, on_enter_handle) = Task::future(async move {
+            let locales = freedesktop_desktop_entry::get_languages_from_env();
+
+            let user_dirs: Vec<PathBuf> = DirectoryType::User.into();
+            let mut user_entries = freedesktop_desktop_entry::Iter::new(user_dirs.into_iter())
+                .entries(Some(&locales))
+                .collect_vec();
+
+            // Entries of the user that share the name of a system entry override it, and are
+            // shown in its place.
+            let system_dirs: Vec<PathBuf> = DirectoryType::System.into();
+            let mut seen = HashSet::new();
+            let system_entries = freedesktop_desktop_entry::Iter::new(system_dirs.into_iter())
+                .entries(Some(&locales))
+                .filter(|entry| seen.insert(file_name(&entry.path)))
+                .filter(runs_in_cosmic)
+                .map(|entry| {
+                    let name = file_name(&entry.path);
+                    match user_entries.iter().position(|e| file_name(&e.path) == name) {
+                        Some(pos) => user_entries.remove(pos),
+                        None => entry,
+                    }
+                })
+                .collect_vec();
+
+            let mut apps_hash = HashMap::with_capacity(2);
+            apps_hash.insert(DirectoryType::User, user_entries);
+            apps_hash.insert(DirectoryType::System, system_entries);
+
+            Message::UpdateStartupApplications(CachedApps {
+                apps: apps_hash,
+                all_apps: get_all_apps(locales.clone()),
+                locales,
+            })
+            .into()
+        })
+        .abortable();
+
+        self.on_enter_handle = Some(on_enter_handle);
+
+        task
+    }
+
     pub fn update(&mut self, message: Message) -> Task<crate::Message> {
         match message {
+            Message::CommandName(name) => {
+                self.command_name = name;
+            }
+
+            Message::CommandInput(command) => {
+                self.command = command;
+            }
+
+            Message::AddStartupCommand => {
+                let name = self.command_name.trim();
+                let command = self.command.trim();
+
+                if name.is_empty() || command.is_empty() {
+                    return Task::none();
+                }
+
+                let directories: Vec<PathBuf> = DirectoryType::User.into();
+                let directory = &directories[0];
+                let path = unique_path(directory, &slug(name));
+
+                let result = std::fs::create_dir_all(directory)
+                    .and_then(|()| std::fs::write(&path, command_entry(name, command)));
+
+                if let Err(why) = result {
+                    error!(?why, "Failed to write autostart entry");
+                    return Task::none();
+                }
+
+                self.command_name.clear();
+                self.command.clear();
+                self.context = None;
+
+                return Task::batch(vec![
+                    cosmic::task::message(crate::pages::Message::CloseContextDrawer),
+                    self.reload().map(Into::into),
+                ]);
+            }
+
+            Message::SetEnabled(path, enabled) => {
+                let hidden = (!enabled).then_some("true");
+                let autostart_enabled = (!enabled).then_some("false");
+
+                override_keys(
+                    &path,
+                    &[
+                        ("Hidden", hidden),
+                        ("X-GNOME-Autostart-enabled", autostart_enabled),
+                    ],
+                );
+
+                return self.reload().map(Into::into);
+            }
+
+            Message::SetDelay(path, idx) => {
+                let delay = DELAYS.get(idx).copied().unwrap_or(0);
+                let delay = (delay != 0).then(|| delay.to_string());
+
+                override_keys(&path, &[(DELAY_KEY, delay.as_deref())]);
+
+                return self.reload().map(Into::into);
+            }
+
             Message::UpdateStartupApplications(cached_apps) => {
                 self.cached_startup_apps = Some(cached_apps);
             }
@@ -225,6 +355,10 @@ impl Page {
 
                 let directory_to_target =
                     directories.get(0).expect("Always at least one directory");
+                if let Err(why) = std::fs::create_dir_all(directory_to_target) {
+                    error!(?why, "Failed to create the autostart directory");
+                }
+
                 if let Ok(exists) = std::fs::exists(directory_to_target.join(file_name.clone())) {
                     if !exists {
                         // when adding an application, we want to symlink to be more user-friendly
@@ -322,6 +456,29 @@ impl Page {
     ) -> Element<'_, crate::pages::Message> {
         let cosmic::cosmic_theme::Spacing { space_xs, .. } = cosmic::theme::spacing();
 
+        let add_command = widget::button::standard(fl!("add")).on_press_maybe(
+            (!self.command_name.trim().is_empty() && !self.command.trim().is_empty())
+                .then_some(Message::AddStartupCommand),
+        );
+
+        let command_form = widget::column::with_capacity(4)
+            .spacing(space_xs)
+            .push(text::heading(fl!("startup-apps", "command")))
+            .push(
+                widget::text_input(fl!("startup-apps", "command-name"), &self.command_name)
+                    .on_input(Message::CommandName),
+            )
+            .push(
+                widget::text_input(fl!("startup-apps", "command-placeholder"), &self.command)
+                    .on_input(Message::CommandInput)
+                    .on_submit(|_| Message::AddStartupCommand),
+            )
+            .push(
+                widget::container(add_command)
+                    .width(Length::Fill)
+                    .align_x(Alignment::End),
+            );
+
         let mut list = widget::list_column();
         let search_input = &self.application_search.trim().to_lowercase();
 
@@ -358,7 +515,12 @@ impl Page {
             }
         }
 
-        list.apply(Element::from)
+        widget::column::with_capacity(3)
+            .spacing(space_xs)
+            .push(command_form)
+            .push(text::heading(fl!("startup-apps", "applications")))
+            .push(list)
+            .apply(Element::from)
             .map(crate::pages::Message::StartupApps)
     }
 }
@@ -376,14 +538,16 @@ fn apps() -> Section<crate::pages::Message> {
             let mut view = widget::column::with_capacity(4).spacing(space_xxs);
 
             if let Some(startup_apps) = &page.cached_startup_apps {
-                let order = vec![DirectoryType::User];
+                let order = vec![DirectoryType::User, DirectoryType::System];
                 for directory_type in order {
                     let mut section = settings::section();
+                    let is_user = directory_type == DirectoryType::User;
 
                     view = view
                         .push(text::heading(directory_type.to_string()))
                         .push(text(match directory_type {
                             DirectoryType::User => fl!("startup-apps", "user-description"),
+                            DirectoryType::System => fl!("startup-apps", "system-description"),
                         }));
 
                     if let Some(apps) = startup_apps.apps.get(&directory_type) {
@@ -403,28 +567,55 @@ fn apps() -> Section<crate::pages::Message> {
                                 row = row.push(text(&app.appid).width(Length::Fill));
                             }
 
-                            row = row.push(
-                                button::icon(icon::from_name("edit-delete-symbolic"))
-                                    .extra_small()
-                                    .on_press(Message::RemoveStartupApplication(
-                                        directory_type.clone(),
-                                        app.clone(),
-                                        false,
-                                    )),
-                            );
+                            let enabled = is_enabled(app);
+
+                            if is_user {
+                                let path = app.path.clone();
+                                row = row.push(
+                                    widget::dropdown(
+                                        &page.delay_labels,
+                                        delay_index(app),
+                                        move |idx| Message::SetDelay(path.clone(), idx),
+                                    )
+                                    .apply(widget::container)
+                                    .max_width(150),
+                                );
+                            }
+
+                            let path = app.path.clone();
+                            row = row.push(widget::toggler(enabled).on_toggle(move |enabled| {
+                                Message::SetEnabled(path.clone(), enabled)
+                            }));
+
+                            if is_user {
+                                row = row.push(
+                                    button::icon(icon::from_name("edit-delete-symbolic"))
+                                        .extra_small()
+                                        .on_press(Message::RemoveStartupApplication(
+                                            directory_type.clone(),
+                                            app.clone(),
+                                            false,
+                                        )),
+                                );
+                            }
 
                             section = section.add(row)
                         }
                     }
 
-                    let add_startup_app = widget::button::standard(fl!("startup-apps", "add"))
-                        .on_press(Message::ShowApplicationSidebar(directory_type.clone()));
+                    view = view.push(section);
+
+                    // Entries of the system are only turned off, by entries of the user.
+                    if is_user {
+                        let add_startup_app = widget::button::standard(fl!("startup-apps", "add"))
+                            .on_press(Message::ShowApplicationSidebar(directory_type.clone()));
 
-                    view = view.push(section).push(widget::container(
-                        widget::container(add_startup_app)
-                            .width(Length::Fill)
-                            .align_x(Alignment::End),
-                    ));
+                        view = view.push(widget::container(
+                            widget::container(add_startup_app)
+                                .width(Length::Fill)
+                                .align_x(Alignment::End),
+                        ));
+                    }
                 }
             }
 
@@ -433,6 +624,168 @@ fn apps() -> Section<crate::pages::Message> {
         })
 }
 
+/// Whether an entry is launched by the COSMIC session, rather than only by other desktops.
+fn runs_in_cosmic(entry: &DesktopEntry) -> bool {
+    entry
+        .only_show_in()
+        .is_none_or(|desktops| desktops.contains(&"COSMIC"))
+        && !entry
+            .not_show_in()
+            .is_some_and(|desktops| desktops.contains(&"COSMIC"))
+}
+
+fn file_name(path: &Path) -> Option<std::ffi::OsString> {
+    path.file_name().map(ToOwned::to_owned)
+}
+
+/// Whether an entry is launched on login, as it is neither hidden nor turned off.
+fn is_enabled(entry: &DesktopEntry) -> bool {
+    entry.desktop_entry("Hidden") != Some("true")
+        && entry.desktop_entry("X-GNOME-Autostart-enabled") != Some("false")
+}
+
+fn delay_index(entry: &DesktopEntry) -> Option<usize> {
+    let delay = entry
+        .desktop_entry(DELAY_KEY)
+        .map_or(Some(0), |delay| delay.trim().parse::<u32>().ok())?;
+
+    DELAYS.iter().position(|&d| d == delay)
+}
+
+/// Writes keys to the user's entry of the same name as the entry at `path`, which is
+/// created from that entry if it is of the system. A linked entry is replaced by a copy so
+/// that the desktop entry of the application is left as it is.
+fn override_keys(path: &Path, keys: &[(&str, Option<&str>)]) {
+    let directories: Vec<PathBuf> = DirectoryType::User.into();
+    let Some(name) = path.file_name() else {
+        return;
+    };
+
+    let target = directories[0].join(name);
+
+    let result = std::fs::read_to_string(path).and_then(|mut content| {
+        for &(key, value) in keys {
+            content = set_key(&content, key, value);
+        }
+
+        std::fs::create_dir_all(&directories[0])?;
+
+        if target.is_symlink() {
+            std::fs::remove_file(&target)?;
+        }
+
+        std::fs::write(&target, content)
+    });
+
+    if let Err(why) = result {
+        error!(?why, ?path, "Failed to write autostart entry");
+    }
+}
+
+/// Sets a key of the `[Desktop Entry]` group of a desktop entry, or removes it.
+fn set_key(content: &str, key: &str, value: Option<&str>) -> String {
+    let mut lines = Vec::new();
+    let mut in_group = false;
+    let mut group_end = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            if in_group {
+                group_end.get_or_insert(lines.len());
+            }
+            in_group = trimmed == "[Desktop Entry]";
+        } else if in_group
+            && trimmed
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key)
+        {
+            continue;
+        }
+
+        lines.push(line.to_owned());
+    }
+
+    if let Some(value) = value {
+        // Set after the last line of the group, ahead of blank lines that separate it.
+        let mut at = group_end.unwrap_or(lines.len());
+        while at > 0 && lines[at - 1].trim().is_empty() {
+            at -= 1;
+        }
+
+        lines.insert(at, format!("{key}={value}"));
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// A file name for an entry, from the name of what it launches.
+fn slug(name: &str) -> String {
+    let slug = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .join("-");
+
+    if slug.is_empty() {
+        String::from("command")
+    } else {
+        slug
+    }
+}
+
+/// A path for a new entry in `directory` that no other entry has.
+fn unique_path(directory: &Path, slug: &str) -> PathBuf {
+    let mut path = directory.join(format!("{slug}.desktop"));
+    let mut n = 2;
+
+    while path.exists() {
+        path = directory.join(format!("{slug}-{n}.desktop"));
+        n += 1;
+    }
+
+    path
+}
+
+/// An autostart entry that runs a command.
+fn command_entry(name: &str, command: &str) -> String {
+    let name = escape_value(name);
+    // A `%` starts a field code such as `%f` in Exec, so a literal one is doubled.
+    let command = escape_value(&command.replace('%', "%%"));
+
+    format!("[Desktop Entry]\nType=Application\nName={name}\nExec={command}\nTerminal=false\n")
+}
+
+/// Escapes the value of a key of a desktop entry, so that it stays on its line and reads
+/// back as it was written. Control characters that have no escape sequence are dropped.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
 fn get_all_apps(locales: Vec<String>) -> Vec<DesktopEntry> {
     let mut dedupe = HashSet::new();
 
@@ -475,3 +828,49 @@ fn get_all_apps(locales: Vec<String>) -> Vec<DesktopEntry> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{command_entry, escape_value, set_key, slug};
+
+    #[test]
+    fn keys_are_set_in_the_entry_group() {
+        let entry =
+            "[Desktop Entry]\nName=Backup\nHidden=false\n\n[Desktop Action Now]\nName=Now\n";
+
+        assert_eq!(
+            set_key(entry, "Hidden", Some("true")),
+            "[Desktop Entry]\nName=Backup\nHidden=true\n\n[Desktop Action Now]\nName=Now\n"
+        );
+
+        assert_eq!(
+            set_key(entry, "Hidden", None),
+            "[Desktop Entry]\nName=Backup\n\n[Desktop Action Now]\nName=Now\n"
+        );
+
+        let command = command_entry("Sync", "rclone sync");
+        assert!(
+            set_key(&command, "X-GNOME-Autostart-Delay", Some("10"))
+                .ends_with("Terminal=false\nX-GNOME-Autostart-Delay=10\n")
+        );
+    }
+
+    #[test]
+    fn values_are_escaped() {
+        assert_eq!(
+            escape_value("Sync\tnow\r\nor\\later\u{7}"),
+            "Sync\\tnow\\r\\nor\\\\later"
+        );
+
+        let entry = command_entry("Backup\nHidden=true", "date +%Y-%m-%d");
+        assert!(entry.contains("\nName=Backup\\nHidden=true\n"));
+        assert!(entry.contains("\nExec=date +%%Y-%%m-%%d\n"));
+        assert_eq!(entry.lines().count(), 5);
+    }
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slug("Sync my Files!"), "sync-my-files");
+        assert_eq!(slug("★"), "command");
+    }
+}
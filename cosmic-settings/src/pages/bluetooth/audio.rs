@@ -0,0 +1,282 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Reads and changes the audio profile and codec of Bluetooth headsets through `pactl`.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+
+use futures::SinkExt;
+use tokio::io::AsyncBufReadExt;
+
+/// Kinds of audio profiles, each of which has a card profile per codec.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// A2DP, which plays audio in high quality without a microphone.
+    HighFidelity,
+    /// HSP/HFP, which enables the microphone at a lower audio quality.
+    Headset,
+}
+
+impl Mode {
+    pub const ALL: [Self; 2] = [Self::HighFidelity, Self::Headset];
+
+    pub fn title(self) -> String {
+        match self {
+            Self::HighFidelity => fl!("bluetooth-audio", "high-fidelity"),
+            Self::Headset => fl!("bluetooth-audio", "headset"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    /// Name of the card profile, such as `a2dp-sink-aac`.
+    pub name: String,
+    pub mode: Mode,
+    /// Name of the codec, such as `aac`.
+    pub codec: String,
+    /// Whether the device supports the profile.
+    pub available: bool,
+    priority: u64,
+}
+
+impl Profile {
+    pub fn codec_title(&self) -> String {
+        match self.codec.as_str() {
+            "sbc" => String::from("SBC"),
+            "sbc_xq" => String::from("SBC-XQ"),
+            "aac" => String::from("AAC"),
+            "aptx" => String::from("aptX"),
+            "aptx_hd" => String::from("aptX HD"),
+            "aptx_ll" => String::from("aptX LL"),
+            "ldac" => String::from("LDAC"),
+            "faststream" => String::from("FastStream"),
+            "msbc" => String::from("mSBC"),
+            "cvsd" => String::from("CVSD"),
+            codec => codec.to_uppercase(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Card {
+    /// Name of the card, such as `bluez_card.00_11_22_33_44_55`.
+    pub name: String,
+    pub active_profile: Option<String>,
+    /// Profiles sorted by mode, then by the priority that the audio server gives them.
+    pub profiles: Vec<Profile>,
+}
+
+impl Card {
+    pub fn active(&self) -> Option<&Profile> {
+        let active = self.active_profile.as_deref()?;
+        self.profiles.iter().find(|profile| profile.name == active)
+    }
+
+    /// The preferred available profile of a mode.
+    pub fn preferred(&self, mode: Mode) -> Option<&Profile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.mode == mode && profile.available)
+    }
+
+    pub fn codecs(&self, mode: Mode) -> impl Iterator<Item = &Profile> {
+        self.profiles
+            .iter()
+            .filter(move |profile| profile.mode == mode)
+    }
+}
+
+/// Sends the audio cards of Bluetooth devices, keyed by device address, every time a card
+/// appears, changes or vanishes, until the receiver is dropped or `pactl subscribe` exits.
+pub async fn watch(mut tx: futures::channel::mpsc::Sender<HashMap<String, Card>>) {
+    let Ok(mut child) = tokio::process::Command::new("pactl")
+        .arg("subscribe")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    else {
+        return;
+    };
+
+    let Some(stdout) = child.stdout.take() else {
+        return;
+    };
+
+    if tx.send(cards().await).await.is_err() {
+        return;
+    }
+
+    let mut lines = tokio::io::BufReader::new(stdout).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        // Events look like `Event 'change' on card #42`.
+        if line.ends_with(|c: char| c.is_ascii_digit())
+            && line.contains(" on card ")
+            && tx.send(cards().await).await.is_err()
+        {
+            break;
+        }
+    }
+}
+
+pub async fn cards() -> HashMap<String, Card> {
+    let Ok(output) = tokio::process::Command::new("pactl")
+        .args(["--format=json", "list", "cards"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    else {
+        return HashMap::new();
+    };
+
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the Bluetooth cards from the output of `pactl --format=json list cards`.
+pub fn parse(json: &str) -> HashMap<String, Card> {
+    let Ok(serde_json::Value::Array(cards)) = serde_json::from_str(json) else {
+        return HashMap::new();
+    };
+
+    cards
+        .iter()
+        .filter_map(|card| {
+            let address = card
+                .get("properties")?
+                .get("api.bluez5.address")?
+                .as_str()?
+                .to_owned();
+
+            let mut profiles = card
+                .get("profiles")?
+                .as_object()?
+                .iter()
+                .filter_map(|(name, profile)| {
+                    let (mode, codec) = mode_and_codec(name)?;
+                    Some(Profile {
+                        name: name.clone(),
+                        mode,
+                        codec: codec.to_owned(),
+                        available: profile
+                            .get("available")
+                            .and_then(serde_json::Value::as_bool)
+                            .unwrap_or(true),
+                        priority: profile
+                            .get("priority")
+                            .and_then(serde_json::Value::as_u64)
+                            .unwrap_or(0),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            profiles.sort_by(|a, b| {
+                let mode = |profile: &Profile| Mode::ALL.iter().position(|m| *m == profile.mode);
+                mode(a).cmp(&mode(b)).then(b.priority.cmp(&a.priority))
+            });
+
+            let card = Card {
+                name: card.get("name")?.as_str()?.to_owned(),
+                active_profile: card
+                    .get("active_profile")
+                    .and_then(serde_json::Value::as_str)
+                    .map(String::from),
+                profiles,
+            };
+
+            Some((address, card))
+        })
+        .collect()
+}
+
+/// Splits a card profile name, such as `a2dp-sink-aac`, into its mode and codec.
+fn mode_and_codec(profile: &str) -> Option<(Mode, &str)> {
+    let (mode, rest, default) = if let Some(rest) = profile.strip_prefix("a2dp-sink") {
+        (Mode::HighFidelity, rest, "sbc")
+    } else if let Some(rest) = profile.strip_prefix("headset-head-unit") {
+        (Mode::Headset, rest, "cvsd")
+    } else {
+        return None;
+    };
+
+    match rest {
+        "" => Some((mode, default)),
+        _ => Some((mode, rest.strip_prefix('-')?)),
+    }
+}
+
+pub fn set_profile(card: String, profile: String) {
+    tokio::task::spawn(async move {
+        _ = tokio::process::Command::new("pactl")
+            .args(["set-card-profile", card.as_str(), profile.as_str()])
+            .status()
+            .await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mode, mode_and_codec, parse};
+
+    #[test]
+    fn profile_names() {
+        assert_eq!(
+            mode_and_codec("a2dp-sink"),
+            Some((Mode::HighFidelity, "sbc"))
+        );
+        assert_eq!(
+            mode_and_codec("a2dp-sink-ldac"),
+            Some((Mode::HighFidelity, "ldac"))
+        );
+        assert_eq!(
+            mode_and_codec("headset-head-unit"),
+            Some((Mode::Headset, "cvsd"))
+        );
+        assert_eq!(
+            mode_and_codec("headset-head-unit-msbc"),
+            Some((Mode::Headset, "msbc"))
+        );
+        assert_eq!(mode_and_codec("a2dp-sinkless"), None);
+        assert_eq!(mode_and_codec("off"), None);
+    }
+
+    #[test]
+    fn bluez_cards() {
+        const EXAMPLE: &str = r#"[
+            {"name": "alsa_card.pci", "properties": {}, "profiles": {}, "active_profile": "off"},
+            {"name": "bluez_card.00_11_22_33_44_55",
+             "properties": {"api.bluez5.address": "00:11:22:33:44:55"},
+             "profiles": {
+                "off": {"available": true, "priority": 0},
+                "a2dp-sink-sbc": {"available": true, "priority": 18},
+                "a2dp-sink-ldac": {"available": false, "priority": 19},
+                "headset-head-unit": {"available": true, "priority": 1}
+             },
+             "active_profile": "a2dp-sink-sbc"}
+        ]"#;
+
+        let cards = parse(EXAMPLE);
+        assert_eq!(cards.len(), 1);
+
+        let card = &cards["00:11:22:33:44:55"];
+        assert_eq!(card.active().map(|p| p.codec.as_str()), Some("sbc"));
+        assert_eq!(
+            card.codecs(Mode::HighFidelity)
+                .map(|p| p.codec.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ldac", "sbc"]
+        );
+        assert_eq!(
+            card.preferred(Mode::HighFidelity).map(|p| p.name.as_str()),
+            Some("a2dp-sink-sbc")
+        );
+        assert_eq!(
+            card.preferred(Mode::Headset).map(|p| p.name.as_str()),
+            Some("headset-head-unit")
+        );
+    }
+}
@@ -16,7 +16,13 @@ use std::sync::Arc;
 use std::time::Duration;
 use zbus::zvariant::OwnedObjectPath;
 
+mod audio;
+
 enum Dialog {
+    ConfirmForget {
+        path: OwnedObjectPath,
+        device: String,
+    },
     // RequestAuthorization {
     //     device: OwnedObjectPath,
     //     response: oneshot::Sender<bool>,
@@ -45,6 +51,8 @@ pub struct Page {
     selected_adapter: Option<OwnedObjectPath>,
     heading: String,
     devices: HashMap<OwnedObjectPath, Device>,
+    /// Whether each paired device may connect without asking.
+    trusted: HashMap<OwnedObjectPath, bool>,
     // Set to true when the org.bluez dbus service is unknown.
     bluez_service_unknown: bool,
     service_is_enabled: bool,
@@ -52,6 +60,9 @@ pub struct Page {
     popup_setting: bool,
     popup_device: Option<OwnedObjectPath>,
     subscription: Option<tokio::sync::oneshot::Sender<()>>,
+    /// Audio cards of Bluetooth devices, keyed by device address.
+    audio_cards: HashMap<String, audio::Card>,
+    audio_subscription: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
 impl page::Page<crate::pages::Message> for Page {
@@ -75,13 +86,36 @@ impl page::Page<crate::pages::Message> for Page {
 
     fn on_enter(&mut self) -> cosmic::Task<crate::pages::Message> {
         // TODO start stream for new device
-        cosmic::task::future(async move {
+        let task = cosmic::task::future(async move {
             match zbus::Connection::system().await {
                 Ok(connection) => Message::DBusConnect(connection),
                 Err(why) => Message::DBusConnectFailed(why),
             }
         })
-        .chain(cosmic::Task::done(Message::SelectAdapter(None).into()))
+        .chain(cosmic::Task::done(Message::SelectAdapter(None).into()));
+
+        if self.audio_subscription.is_some() {
+            return task;
+        }
+
+        let (cancellation, audio_task) = crate::utils::forward_event_loop(|emitter| async move {
+            let (tx, mut rx) = futures::channel::mpsc::channel(1);
+
+            let watcher = std::pin::pin!(audio::watch(tx));
+
+            let forwarder = std::pin::pin!(async move {
+                while let Some(cards) = rx.next().await {
+                    _ = emitter
+                        .emit(crate::pages::Message::Bluetooth(Message::AudioCards(cards)))
+                        .await;
+                }
+            });
+
+            futures::future::join(watcher, forwarder).await;
+        });
+
+        self.audio_subscription = Some(cancellation);
+        cosmic::task::batch(vec![task, audio_task])
     }
 
     fn on_leave(&mut self) -> Task<crate::pages::Message> {
@@ -89,6 +123,10 @@ impl page::Page<crate::pages::Message> for Page {
             _ = cancel.send(());
         }
 
+        if let Some(cancel) = self.audio_subscription.take() {
+            _ = cancel.send(());
+        }
+
         if let Some(connection) = self.connection.take() {
             tokio::spawn(async move {
                 _ = agent::unregister(connection).await;
@@ -98,6 +136,7 @@ impl page::Page<crate::pages::Message> for Page {
         self.adapters.clear();
         self.selected_adapter = None;
         self.devices.clear();
+        self.audio_cards.clear();
         self.popup_device = None;
         self.popup_setting = false;
 
@@ -106,6 +145,25 @@ impl page::Page<crate::pages::Message> for Page {
 
     fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
         match self.dialog.as_ref()? {
+            Dialog::ConfirmForget { path, device } => {
+                let primary_action = widget::button::destructive(fl!("forget"))
+                    .on_press(Message::ForgetDevice(path.clone()));
+
+                let secondary_action =
+                    widget::button::standard(fl!("cancel")).on_press(Message::ForgetDeviceCancel);
+
+                let dialog = widget::dialog()
+                    .title(fl!("bluetooth-forget-dialog", device = device.as_str()))
+                    .icon(widget::icon::from_name("dialog-information").size(64))
+                    .body(fl!("bluetooth-forget-dialog", "description"))
+                    .primary_action(primary_action)
+                    .secondary_action(secondary_action)
+                    .apply(Element::from)
+                    .map(Into::into);
+
+                Some(dialog)
+            }
+
             Dialog::RequestConfirmation {
                 device, passkey, ..
             } => {
@@ -148,12 +206,16 @@ impl page::Page<crate::pages::Message> for Page {
 
 #[derive(Clone, Debug)]
 pub enum Message {
+    AudioCards(HashMap<String, audio::Card>),
     BluetoothEvent(Event),
     ConnectDevice(OwnedObjectPath),
     DBusConnect(zbus::Connection),
     DBusConnectFailed(zbus::Error),
+    DeviceTrusted(OwnedObjectPath, bool),
     DisconnectDevice(OwnedObjectPath),
     ForgetDevice(OwnedObjectPath),
+    ForgetDeviceCancel,
+    ForgetDevicePrompt(OwnedObjectPath),
     PinCancel,
     PinConfirm,
     PopupDevice(Option<OwnedObjectPath>),
@@ -162,6 +224,9 @@ pub enum Message {
     ServiceActivate,
     ServiceEnable,
     SetActive(bool),
+    /// Change the card profile, and so the codec, of the device with the given address.
+    SetAudioProfile(String, String),
+    SetTrusted(OwnedObjectPath, bool),
     UpdateStatus,
 }
 
@@ -201,6 +266,17 @@ impl Page {
         let _span = span.enter();
 
         match message {
+            Message::AudioCards(cards) => {
+                self.audio_cards = cards;
+            }
+
+            Message::SetAudioProfile(address, profile) => {
+                if let Some(card) = self.audio_cards.get_mut(&address) {
+                    card.active_profile = Some(profile.clone());
+                    audio::set_profile(card.name.clone(), profile);
+                }
+            }
+
             Message::BluetoothEvent(event) => match event {
                 Event::DBusError(why) => {
                     tracing::debug!("bluetooth dbus error {why:?}");
@@ -223,6 +299,19 @@ impl Page {
 
                 Event::SetDevices(devices) => {
                     self.devices = devices;
+                    self.trusted
+                        .retain(|path, _| self.devices.contains_key(path));
+
+                    let paired = self
+                        .devices
+                        .iter()
+                        .filter(|(_, device)| device.paired)
+                        .map(|(path, _)| path.clone())
+                        .collect::<Vec<_>>();
+
+                    return cosmic::task::batch(
+                        paired.into_iter().map(|path| self.load_trusted(path)),
+                    );
                 }
 
                 Event::DeviceFailed(path) => {
@@ -276,6 +365,11 @@ impl Page {
                     if let Some(existing) = self.devices.get_mut(&path) {
                         tracing::debug!("Device {} updated", existing.address);
                         existing.update(update);
+
+                        // Load the trust of a device once it has been paired.
+                        if existing.paired && !self.trusted.contains_key(&path) {
+                            return self.load_trusted(path);
+                        }
                     }
                 }
 
@@ -290,11 +384,16 @@ impl Page {
                 Event::RemovedDevice(path) => {
                     tracing::debug!("Device {path} removed");
                     self.devices.remove(&path);
+                    self.trusted.remove(&path);
                 }
 
                 Event::AddedDevice(path, device) => {
                     tracing::debug!("Device {} added", device.address);
-                    self.devices.insert(path, device);
+                    let paired = device.paired;
+                    self.devices.insert(path.clone(), device);
+                    if paired {
+                        return self.load_trusted(path);
+                    }
                 }
 
                 Event::AddedAdapter(path, adapter) => {
@@ -501,9 +600,45 @@ impl Page {
                 }
             }
 
+            Message::DeviceTrusted(path, trusted) => {
+                if self.devices.contains_key(&path) {
+                    self.trusted.insert(path, trusted);
+                }
+            }
+
+            Message::SetTrusted(path, trusted) => {
+                tracing::debug!("Setting trust of device {path} to {trusted}");
+                let Some(connection) = self.connection.clone() else {
+                    tracing::warn!("No DBus connection ready");
+                    return cosmic::Task::none();
+                };
+
+                self.trusted.insert(path.clone(), trusted);
+                return cosmic::task::future(set_trusted(connection, path, trusted));
+            }
+
+            Message::ForgetDevicePrompt(path) => {
+                self.popup_device = None;
+                if let Some(device) = self.devices.get(&path) {
+                    self.dialog = Some(Dialog::ConfirmForget {
+                        device: device.alias_or_addr().to_owned(),
+                        path,
+                    });
+                }
+            }
+
+            Message::ForgetDeviceCancel => {
+                if let Some(Dialog::ConfirmForget { .. }) = self.dialog {
+                    self.dialog = None;
+                }
+            }
+
             Message::ForgetDevice(path) => {
                 tracing::debug!("Forgetting to device {path}");
                 self.popup_device = None;
+                if let Some(Dialog::ConfirmForget { .. }) = self.dialog {
+                    self.dialog = None;
+                }
                 if self.connection.is_none() {
                     return cosmic::Task::none();
                 }
@@ -592,6 +727,30 @@ impl Page {
         cosmic::Task::none()
     }
 
+    /// Reads whether a paired device is trusted to connect without asking.
+    fn load_trusted(&self, path: OwnedObjectPath) -> Task<crate::Message> {
+        let Some(connection) = self.connection.clone() else {
+            return Task::none();
+        };
+
+        cosmic::task::future(async move {
+            let trusted = async {
+                bluez_zbus::device1::Device1Proxy::new(&connection, &path)
+                    .await?
+                    .trusted()
+                    .await
+            };
+
+            match trusted.await {
+                Ok(trusted) => Message::DeviceTrusted(path, trusted),
+                Err(why) => {
+                    tracing::warn!("Failed to read trust of device {path}: {why}");
+                    Message::DeviceTrusted(path, false)
+                }
+            }
+        })
+    }
+
     fn update_status(&mut self) {
         if let Some((_, adapter)) = self.get_selected_adapter() {
             self.heading = fl!(
@@ -731,6 +890,21 @@ fn popup_button(message: Option<Message>, text: &str) -> Element<'_, Message> {
         .into()
 }
 
+fn popup_toggler<'a>(
+    is_checked: bool,
+    on_toggle: impl Fn(bool) -> Message + 'a,
+    text: &'a str,
+) -> Element<'a, Message> {
+    let theme = theme::active();
+    let theme = theme.cosmic();
+    widget::row::with_capacity(2)
+        .push(widget::text::body(text).width(Length::Fill))
+        .push(widget::toggler(is_checked).on_toggle(on_toggle))
+        .align_y(Alignment::Center)
+        .padding([theme.space_xxxs(), theme.space_xs()])
+        .into()
+}
+
 fn connected_devices() -> Section<crate::pages::Message> {
     crate::slab!(descriptions {
         device_connected = fl!("bluetooth", "connected");
@@ -739,6 +913,9 @@ fn connected_devices() -> Section<crate::pages::Message> {
         device_connect = fl!("bluetooth", "connect");
         device_disconnect = fl!("bluetooth", "disconnect");
         device_forget = fl!("bluetooth", "forget");
+        device_trusted = fl!("bluetooth", "trusted");
+        audio_profile = fl!("bluetooth-audio", "profile");
+        audio_codec = fl!("bluetooth-audio", "codec");
     });
 
     Section::default()
@@ -781,8 +958,18 @@ fn connected_devices() -> Section<crate::pages::Message> {
                                             &descriptions[device_disconnect],
                                         )
                                     }))
+                                    .push(popup_toggler(
+                                        page.trusted.get(path).copied().unwrap_or_default(),
+                                        {
+                                            let path = path.clone();
+                                            move |trusted| {
+                                                Message::SetTrusted(path.clone(), trusted)
+                                            }
+                                        },
+                                        &descriptions[device_trusted],
+                                    ))
                                     .push(popup_button(
-                                        Some(Message::ForgetDevice(path.clone())),
+                                        Some(Message::ForgetDevicePrompt(path.clone())),
                                         &descriptions[device_forget],
                                     )),
                             )
@@ -797,7 +984,20 @@ fn connected_devices() -> Section<crate::pages::Message> {
                             .into()
                     };
 
-                    Some(settings::item_row(vec![
+                    let audio = device
+                        .is_connected()
+                        .then(|| page.audio_cards.get(&device.address))
+                        .flatten()
+                        .and_then(|card| {
+                            audio_settings(
+                                &device.address,
+                                card,
+                                &descriptions[audio_profile],
+                                &descriptions[audio_codec],
+                            )
+                        });
+
+                    let row = settings::item_row(vec![
                         widget::icon::from_name(device.icon).size(16).into(),
                         if let Some(battery) = &device.battery {
                             widget::column::with_capacity(2)
@@ -828,7 +1028,9 @@ fn connected_devices() -> Section<crate::pages::Message> {
                                 .into(),
                         },
                         device_menu,
-                    ]))
+                    ]);
+
+                    Some(widget::column::with_capacity(2).push(row).push_maybe(audio))
                 })
                 .fold(section, settings::Section::add)
                 .apply(Element::from)
@@ -836,6 +1038,69 @@ fn connected_devices() -> Section<crate::pages::Message> {
         })
 }
 
+/// Buttons to choose the audio profile and codec of a connected headset, with the
+/// codecs that the headset does not support disabled.
+fn audio_settings<'a>(
+    address: &'a str,
+    card: &'a audio::Card,
+    profile_label: &'a str,
+    codec_label: &'a str,
+) -> Option<Element<'a, Message>> {
+    let space_xxs = theme::active().cosmic().space_xxs();
+    let active = card.active();
+
+    let choice = |label: String, selected: bool, profile: Option<&audio::Profile>| {
+        let button = if selected {
+            widget::button::suggested(label)
+        } else {
+            widget::button::standard(label)
+        };
+
+        button.on_press_maybe(
+            profile
+                .map(|profile| Message::SetAudioProfile(address.to_owned(), profile.name.clone())),
+        )
+    };
+
+    let modes = audio::Mode::ALL
+        .into_iter()
+        .filter(|mode| card.codecs(*mode).next().is_some())
+        .collect::<Vec<_>>();
+
+    if modes.is_empty() {
+        return None;
+    }
+
+    let modes = modes
+        .into_iter()
+        .map(|mode| {
+            choice(
+                mode.title(),
+                active.is_some_and(|active| active.mode == mode),
+                card.preferred(mode),
+            )
+        })
+        .fold(widget::row().spacing(space_xxs), widget::Row::push);
+
+    let codecs = active.map(|active| {
+        card.codecs(active.mode)
+            .map(|profile| {
+                choice(
+                    profile.codec_title(),
+                    profile.name == active.name,
+                    profile.available.then_some(profile),
+                )
+            })
+            .fold(widget::row().spacing(space_xxs), widget::Row::push)
+    });
+
+    let settings = widget::column::with_capacity(2)
+        .push(settings::item(profile_label, modes))
+        .push_maybe(codecs.map(|codecs| settings::item(codec_label, codecs)));
+
+    Some(settings.into())
+}
+
 fn available_devices() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
@@ -936,6 +1201,29 @@ fn multiple_adapter() -> Section<crate::pages::Message> {
 
 impl page::AutoBind<crate::pages::Message> for Page {}
 
+/// Allows or stops a device from connecting without asking, which also decides whether
+/// BlueZ reconnects to it automatically.
+async fn set_trusted(
+    connection: zbus::Connection,
+    path: OwnedObjectPath,
+    trusted: bool,
+) -> Message {
+    let result = async {
+        bluez_zbus::device1::Device1Proxy::new(&connection, &path)
+            .await?
+            .set_trusted(trusted)
+            .await
+    };
+
+    match result.await {
+        Ok(()) => Message::DeviceTrusted(path, trusted),
+        Err(why) => {
+            tracing::error!("Failed to set trust of device {path}: {why}");
+            Message::DeviceTrusted(path, !trusted)
+        }
+    }
+}
+
 mod systemd {
     use futures::FutureExt;
 
@@ -0,0 +1,167 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Custom accent colors, typed as hex or derived from the wallpaper.
+
+use cosmic::cosmic_config::{self, ConfigGet};
+use cosmic::cosmic_theme::palette::{FromColor, Hsv, Srgb};
+use cosmic_settings_wallpaper::{self as wallpaper, Source};
+
+/// Number of hues that the pixels of a wallpaper are sorted into.
+const HUE_BUCKETS: usize = 36;
+
+/// Parses a color written as `#RRGGBB` or `#RGB`, with or without the `#`.
+pub fn parse_hex(input: &str) -> Option<Srgb> {
+    let hex = input.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+
+    let (red, green, blue) = match hex.len() {
+        3 => {
+            let short = |i: usize| channel(&hex[i..=i]).map(|c| c * 17);
+            (short(0)?, short(1)?, short(2)?)
+        }
+        6 => (
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ),
+        _ => return None,
+    };
+
+    Some(Srgb::new(red, green, blue).into_format())
+}
+
+/// Formats a color as `#RRGGBB`.
+pub fn to_hex(color: Srgb) -> String {
+    let color = color.into_format::<u8>();
+    format!("#{:02X}{:02X}{:02X}", color.red, color.green, color.blue)
+}
+
+/// The average color of the most common hue, ignoring the greys, darks and whites that
+/// would make a poor accent.
+pub fn dominant_color(image: &image::RgbaImage) -> Option<Srgb> {
+    let mut buckets = [(0u32, [0.0f32; 3]); HUE_BUCKETS];
+
+    for pixel in image.pixels() {
+        let [red, green, blue, alpha] = pixel.0;
+
+        if alpha < 128 {
+            continue;
+        }
+
+        let color = Srgb::new(red, green, blue).into_format::<f32>();
+        let hsv: Hsv = Hsv::from_color(color);
+
+        if hsv.saturation < 0.25 || hsv.value < 0.2 {
+            continue;
+        }
+
+        let hue = hsv.hue.into_positive_degrees();
+        let (count, sum) = &mut buckets[(hue / 360.0 * HUE_BUCKETS as f32) as usize % HUE_BUCKETS];
+        *count += 1;
+        sum[0] += color.red;
+        sum[1] += color.green;
+        sum[2] += color.blue;
+    }
+
+    let (count, [red, green, blue]) = buckets
+        .into_iter()
+        .max_by_key(|(count, _)| *count)
+        .filter(|(count, _)| *count > 0)?;
+
+    let count = count as f32;
+    Some(Srgb::new(red / count, green / count, blue / count))
+}
+
+/// Derives an accent color from the wallpaper that is currently shown.
+pub fn wallpaper_color() -> Option<Srgb> {
+    match current_wallpaper()? {
+        Source::Path(path) => {
+            let image = if path.extension().is_some_and(|ext| ext == "jxl") {
+                wallpaper::decode_jpegxl(&path).ok()?
+            } else {
+                image::open(&path).ok()?
+            };
+
+            dominant_color(&image.thumbnail(128, 128).to_rgba8())
+        }
+
+        Source::Color(wallpaper::Color::Single([red, green, blue])) => {
+            Some(Srgb::new(red, green, blue))
+        }
+
+        Source::Color(wallpaper::Color::Gradient(gradient)) => gradient
+            .colors
+            .first()
+            .map(|&[red, green, blue]| Srgb::new(red, green, blue)),
+    }
+}
+
+/// The wallpaper that cosmic-bg shows on the first output, which follows slideshows.
+fn current_wallpaper() -> Option<Source> {
+    let shown = cosmic_config::Config::new_state(cosmic_bg_config::NAME, 1)
+        .ok()
+        .and_then(|state| state.get::<Vec<(String, Source)>>("wallpapers").ok())
+        .and_then(|wallpapers| wallpapers.into_iter().next())
+        .map(|(_output, source)| source);
+
+    if shown.is_some() {
+        return shown;
+    }
+
+    let config = wallpaper::Config::load(&cosmic_bg_config::context().ok()?).ok()?;
+
+    Some(
+        config
+            .backgrounds
+            .into_iter()
+            .next()
+            .unwrap_or(config.default_background)
+            .source,
+    )
+    .filter(|source| !matches!(source, Source::Path(path) if path.is_dir()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dominant_color, parse_hex, to_hex};
+    use cosmic::cosmic_theme::palette::Srgb;
+
+    #[test]
+    fn hex() {
+        let orange = Srgb::new(255u8, 136, 0).into_format();
+
+        assert_eq!(parse_hex("#FF8800"), Some(orange));
+        assert_eq!(parse_hex("ff8800"), Some(orange));
+        assert_eq!(parse_hex(" #f80 "), Some(orange));
+        assert_eq!(parse_hex("#FF880"), None);
+        assert_eq!(parse_hex("#GG8800"), None);
+        assert_eq!(parse_hex("#+F8800"), None);
+        assert_eq!(to_hex(orange), "#FF8800");
+    }
+
+    #[test]
+    fn dominant_hue() {
+        let mut image = image::RgbaImage::from_pixel(4, 4, image::Rgba([20, 20, 20, 255]));
+
+        for x in 0..3 {
+            image.put_pixel(x, 0, image::Rgba([200, 40, 40, 255]));
+        }
+
+        image.put_pixel(3, 0, image::Rgba([40, 40, 200, 255]));
+
+        assert_eq!(
+            dominant_color(&image).map(to_hex).as_deref(),
+            Some("#C82828")
+        );
+
+        let grey = image::RgbaImage::from_pixel(2, 2, image::Rgba([128, 128, 128, 255]));
+        assert_eq!(dominant_color(&grey), None);
+    }
+}
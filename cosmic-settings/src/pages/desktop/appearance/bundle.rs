@@ -0,0 +1,160 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! A theme exported with the mode it was made for, so that it may be shared between machines.
+
+use cosmic::cosmic_config::CosmicConfigEntry;
+use cosmic::cosmic_theme::ThemeBuilder;
+use serde::{Deserialize, Serialize};
+
+/// Version of the bundle format, raised when a change would break older readers.
+pub const VERSION: u64 = 1;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThemeBundle {
+    pub version: u64,
+    /// Config version of the theme builder, which its fields are read by.
+    pub builder_version: u64,
+    pub name: String,
+    pub is_dark: bool,
+    pub builder: ThemeBuilder,
+}
+
+impl ThemeBundle {
+    pub fn new(name: String, is_dark: bool, builder: ThemeBuilder) -> Self {
+        Self {
+            version: VERSION,
+            builder_version: ThemeBuilder::VERSION,
+            name,
+            is_dark,
+            builder,
+        }
+    }
+}
+
+/// A theme read from a file, to be previewed before it is applied.
+#[derive(Clone, Debug)]
+pub struct Import {
+    pub name: String,
+    /// The mode of the theme, unless it was exported without one.
+    pub is_dark: Option<bool>,
+    pub builder: ThemeBuilder,
+    pub warnings: Vec<Warning>,
+}
+
+/// Why an imported theme may not look as it did where it was exported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Warning {
+    /// Exported by a version of COSMIC whose theme settings differ from this one.
+    BuilderVersion,
+    /// Exported as a theme alone, without its mode.
+    NoMode,
+}
+
+impl Warning {
+    pub fn localized(self) -> String {
+        match self {
+            Self::BuilderVersion => fl!("theme-import", "builder-version"),
+            Self::NoMode => fl!("theme-import", "no-mode"),
+        }
+    }
+}
+
+/// Why a file cannot be imported as a theme.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ImportError {
+    /// Exported in a bundle format newer than this version understands.
+    Unsupported(u64),
+    Invalid(String),
+}
+
+impl ImportError {
+    pub fn localized(&self) -> String {
+        match self {
+            Self::Unsupported(version) => {
+                fl!("theme-import", "unsupported", version = version.to_string())
+            }
+            Self::Invalid(why) => fl!("theme-import", "invalid", why = why.as_str()),
+        }
+    }
+}
+
+/// Reads a theme bundle, or a theme builder exported before bundles existed.
+pub fn parse(contents: &str, file_name: &str) -> Result<Import, ImportError> {
+    let bundle_error = match ron::from_str::<ThemeBundle>(contents) {
+        Ok(bundle) if bundle.version > VERSION => {
+            return Err(ImportError::Unsupported(bundle.version));
+        }
+
+        Ok(bundle) => {
+            let warnings = if bundle.builder_version == ThemeBuilder::VERSION {
+                Vec::new()
+            } else {
+                vec![Warning::BuilderVersion]
+            };
+
+            return Ok(Import {
+                name: bundle.name,
+                is_dark: Some(bundle.is_dark),
+                builder: bundle.builder,
+                warnings,
+            });
+        }
+
+        Err(why) => why,
+    };
+
+    match ron::from_str::<ThemeBuilder>(contents) {
+        Ok(builder) => Ok(Import {
+            name: file_name.to_owned(),
+            is_dark: None,
+            builder,
+            warnings: vec![Warning::NoMode],
+        }),
+
+        Err(_) => Err(ImportError::Invalid(bundle_error.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportError, ThemeBundle, VERSION, Warning, parse};
+    use cosmic::cosmic_theme::ThemeBuilder;
+    use ron::ser::PrettyConfig;
+
+    #[test]
+    fn round_trip() {
+        let bundle = ThemeBundle::new(String::from("Dusk"), true, ThemeBuilder::dark());
+        let contents = ron::ser::to_string_pretty(&bundle, PrettyConfig::default()).unwrap();
+
+        let import = parse(&contents, "dusk").unwrap();
+        assert_eq!(import.name, "Dusk");
+        assert_eq!(import.is_dark, Some(true));
+        assert_eq!(import.builder, ThemeBuilder::dark());
+        assert!(import.warnings.is_empty());
+
+        let mut bundle = bundle;
+        bundle.version = VERSION + 1;
+        let contents = ron::ser::to_string_pretty(&bundle, PrettyConfig::default()).unwrap();
+        assert_eq!(
+            parse(&contents, "dusk").err(),
+            Some(ImportError::Unsupported(VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn theme_builder_alone() {
+        let contents =
+            ron::ser::to_string_pretty(&ThemeBuilder::light(), PrettyConfig::default()).unwrap();
+
+        let import = parse(&contents, "Light").unwrap();
+        assert_eq!(import.name, "Light");
+        assert_eq!(import.is_dark, None);
+        assert_eq!(import.warnings, vec![Warning::NoMode]);
+
+        assert!(matches!(
+            parse("not a theme", "Light"),
+            Err(ImportError::Invalid(_))
+        ));
+    }
+}
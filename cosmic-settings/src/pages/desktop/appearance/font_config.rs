@@ -1,8 +1,9 @@
 // Copyright 2024 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::BTreeSet;
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Mutex};
 
 use cosmic::{
     Apply, Element, Task,
@@ -65,7 +66,7 @@ pub fn load_font_families() -> (Vec<Arc<str>>, Vec<Arc<str>>) {
 pub fn selection_context<'a>(
     families: &'a [Arc<str>],
     current_font: &str,
-    system: bool,
+    category: Category,
 ) -> Element<'a, super::Message> {
     let svg_accent = Rc::new(|theme: &cosmic::Theme| svg::Style {
         color: Some(theme.cosmic().accent_color().into()),
@@ -93,110 +94,176 @@ pub fn selection_context<'a>(
             .class(cosmic::theme::Container::List)
             .apply(widget::button::custom)
             .class(cosmic::theme::Button::Transparent)
-            .on_press(super::Message::FontSelect(system, family.clone())),
+            .on_press(super::Message::FontSelect(category, family.clone())),
         )
     });
 
     list.into()
 }
 
-/// Set the preferred icon theme for GNOME/GTK applications.
-pub async fn set_gnome_font_name(font_name: &str) {
+/// The font of a text shown in previews, which iced only knows by a static name.
+///
+/// Each family is leaked once, and reused whenever it is previewed again.
+pub fn preview_font(family: &str) -> cosmic::iced::Font {
+    static NAMES: LazyLock<Mutex<BTreeSet<&'static str>>> = LazyLock::new(Mutex::default);
+
+    let mut names = NAMES.lock().unwrap();
+
+    let name = match names.get(family) {
+        Some(name) => *name,
+        None => {
+            let name: &'static str = Box::leak(Box::from(family));
+            names.insert(name);
+            name
+        }
+    };
+
+    cosmic::iced::Font::with_name(name)
+}
+
+/// Set a font family of GNOME/GTK applications.
+pub async fn set_gnome_font(key: &str, font_name: String) {
     let _res = tokio::process::Command::new("gsettings")
-        .args(["set", "org.gnome.desktop.interface", "font-name", font_name])
+        .args(["set", "org.gnome.desktop.interface", key, &font_name])
         .status()
         .await;
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Category {
+    Interface,
+    Monospace,
+}
+
+impl Category {
+    pub const ALL: [Self; 2] = [Self::Interface, Self::Monospace];
+
+    pub fn title(self) -> String {
+        match self {
+            Self::Interface => fl!("interface-font"),
+            Self::Monospace => fl!("monospace-font"),
+        }
+    }
+
+    /// Key of the toolkit config that the family is written to.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Interface => INTERFACE_FONT,
+            Self::Monospace => MONOSPACE_FONT,
+        }
+    }
+
+    const fn gnome_key(self) -> &'static str {
+        match self {
+            Self::Interface => "font-name",
+            Self::Monospace => "monospace-font-name",
+        }
+    }
+}
+
+/// The font family of a category in the toolkit config.
+fn current(category: Category) -> String {
+    match category {
+        Category::Interface => cosmic::config::interface_font().family,
+        Category::Monospace => cosmic::config::monospace_font().family,
+    }
+}
+
+/// The font family that a category is reset to.
+fn initial(category: Category) -> String {
+    let defaults = CosmicTk::default();
+
+    match category {
+        Category::Interface => defaults.interface_font.family,
+        Category::Monospace => defaults.monospace_font.family,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
-    InterfaceFontFamily(usize),
+    FontFamily(Category, Arc<str>),
     LoadedFonts(Vec<Arc<str>>, Vec<Arc<str>>),
-    MonospaceFontFamily(usize),
+    Reset,
 }
 
 #[derive(Debug, Default)]
 pub struct Model {
     pub interface_font_families: Vec<Arc<str>>,
-    pub interface_font_family: Option<usize>,
     pub monospace_font_families: Vec<Arc<str>>,
-    pub monospace_font_family: Option<usize>,
+    /// The font family of each category, by its position in [`Category::ALL`].
+    fonts: [String; 2],
 }
 
 impl Model {
-    pub const fn new() -> Model {
+    pub fn new() -> Model {
         Model {
             interface_font_families: Vec::new(),
-            interface_font_family: None,
             monospace_font_families: Vec::new(),
-            monospace_font_family: None,
+            fonts: Category::ALL.map(current),
         }
     }
 
+    pub fn font(&self, category: Category) -> &str {
+        &self.fonts[category as usize]
+    }
+
+    /// Families that a category may be set to.
+    pub fn families(&self, category: Category) -> &[Arc<str>] {
+        match category {
+            Category::Interface => &self.interface_font_families,
+            Category::Monospace => &self.monospace_font_families,
+        }
+    }
+
+    /// Whether any font differs from its default.
+    pub fn can_reset(&self) -> bool {
+        Category::ALL
+            .into_iter()
+            .any(|category| self.font(category) != initial(category))
+    }
+
     pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
         match message {
-            Message::InterfaceFontFamily(id) => {
-                if let Some(family) = self.interface_font_families.get(id) {
-                    update_config(
-                        INTERFACE_FONT,
-                        FontConfig {
-                            family: family.to_string(),
-                            weight: cosmic::iced::font::Weight::Normal,
-                            style: cosmic::iced::font::Style::Normal,
-                            stretch: cosmic::iced::font::Stretch::Normal,
-                        },
-                    );
-
-                    self.interface_font_family = Some(id);
-
-                    let family = family.clone();
-                    tokio::spawn(async move {
-                        set_gnome_font_name(family.as_ref()).await;
-                    });
-                }
+            Message::FontFamily(category, family) => {
+                self.fonts[category as usize] = family.to_string();
+                self.apply(category);
             }
 
             Message::LoadedFonts(interface, mono) => {
                 self.interface_font_families = interface;
                 self.monospace_font_families = mono;
-
-                let interface_font = cosmic::config::interface_font();
-                let monospace_font = cosmic::config::monospace_font();
-
-                self.interface_font_family =
-                    font_family_to_pos(&self.interface_font_families, &interface_font.family);
-
-                self.monospace_font_family =
-                    font_family_to_pos(&self.monospace_font_families, &monospace_font.family);
+                self.fonts = Category::ALL.map(current);
             }
 
-            Message::MonospaceFontFamily(id) => {
-                if let Some(family) = self.monospace_font_families.get(id) {
-                    update_config(
-                        MONOSPACE_FONT,
-                        FontConfig {
-                            family: family.to_string(),
-                            weight: cosmic::iced::font::Weight::Normal,
-                            style: cosmic::iced::font::Style::Normal,
-                            stretch: cosmic::iced::font::Stretch::Normal,
-                        },
-                    );
-
-                    self.monospace_font_family = Some(id);
+            Message::Reset => {
+                for category in Category::ALL {
+                    self.fonts[category as usize] = initial(category);
+                    self.apply(category);
                 }
             }
         }
 
         Task::none()
     }
-}
 
-fn font_family_to_pos(families: &[Arc<str>], family: &str) -> Option<usize> {
-    families.iter().position(|f| &**f == family)
-}
+    /// Writes the font of a category to the toolkit config, and to GNOME for GTK applications.
+    fn apply(&self, category: Category) {
+        let family = self.font(category).to_owned();
+
+        if let Ok(config) = CosmicTk::config() {
+            _ = config.set(
+                category.key(),
+                FontConfig {
+                    family: family.clone(),
+                    weight: cosmic::iced::font::Weight::Normal,
+                    style: cosmic::iced::font::Style::Normal,
+                    stretch: cosmic::iced::font::Stretch::Normal,
+                },
+            );
+        }
 
-fn update_config(variant: &str, font: FontConfig) {
-    if let Ok(config) = CosmicTk::config() {
-        _ = config.set(variant, font);
+        tokio::spawn(async move {
+            set_gnome_font(category.gnome_key(), family).await;
+        });
     }
 }
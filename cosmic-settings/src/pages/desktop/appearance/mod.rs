@@ -1,8 +1,12 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+mod accent;
+#[cfg(feature = "xdg-portal")]
+mod bundle;
 pub mod font_config;
 pub mod icon_themes;
+mod window_shape;
 
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -35,6 +39,7 @@ use ron::ser::PrettyConfig;
 use serde::Serialize;
 use slab::Slab;
 use slotmap::{Key, SlotMap};
+use window_shape::WindowShape;
 
 use crate::app;
 use crate::widget::color_picker_context_view;
@@ -57,8 +62,7 @@ enum ContextView {
     CustomAccent,
     IconsAndToolkit,
     InterfaceText,
-    MonospaceFont,
-    SystemFont,
+    Font(font_config::Category),
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -70,12 +74,21 @@ pub struct Page {
     no_custom_window_hint: bool,
     context_view: Option<ContextView>,
     custom_accent: ColorPickerModel,
+    /// Hex value of a custom accent color being typed.
+    accent_hex: String,
+    /// Whether an accent color is being derived from the wallpaper.
+    matching_wallpaper: bool,
+    /// A theme being imported, with the theme it builds, until it is applied or cancelled.
+    #[cfg(feature = "xdg-portal")]
+    import_preview: Option<(bundle::Import, Box<Theme>)>,
     accent_window_hint: ColorPickerModel,
     application_background: ColorPickerModel,
     container_background: ColorPickerModel,
     interface_text: ColorPickerModel,
     control_component: ColorPickerModel,
     roundness: Roundness,
+    /// Shown by the window management sliders, and ahead of the config while one is dragged.
+    window_shape: WindowShape,
 
     font_config: font_config::Model,
     font_filter: Vec<Arc<str>>,
@@ -224,12 +237,17 @@ impl
             },
             context_view: None,
             roundness: theme_builder.corner_radii.into(),
+            window_shape: WindowShape::new(&theme_builder),
             custom_accent: ColorPickerModel::new(
                 &*HEX,
                 &*RGB,
                 None,
                 custom_accent.map(Color::from),
             ),
+            accent_hex: String::new(),
+            matching_wallpaper: false,
+            #[cfg(feature = "xdg-portal")]
+            import_preview: None,
             application_background: ColorPickerModel::new(
                 &*HEX,
                 &*RGB,
@@ -367,6 +385,8 @@ impl std::fmt::Debug for OpenResponse {
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    AccentHex(String),
+    AccentHexSubmit,
     AccentWindowHint(ColorPickerUpdate),
     ApplicationBackground(ColorPickerUpdate),
     ApplyThemeGlobal(bool),
@@ -376,8 +396,7 @@ pub enum Message {
     CustomAccent(ColorPickerUpdate),
     DarkMode(bool),
     Density(Density),
-    DisplayMonoFont,
-    DisplaySystemFont,
+    DisplayFont(font_config::Category),
     Entered((IconThemes, IconHandles)),
     IconsAndToolkit,
     #[cfg(feature = "xdg-portal")]
@@ -388,17 +407,23 @@ pub enum Message {
     ExportSuccess,
     FontConfig(font_config::Message),
     FontSearch(String),
-    FontSelect(bool, Arc<str>),
+    FontSelect(font_config::Category, Arc<str>),
+    CornerRadius(u32),
     GapSize(u32),
     IconTheme(usize),
     #[cfg(feature = "xdg-portal")]
+    ImportApply,
+    #[cfg(feature = "xdg-portal")]
+    ImportCancel,
+    #[cfg(feature = "xdg-portal")]
     ImportError,
     #[cfg(feature = "xdg-portal")]
     ImportFile(OpenResponse),
     #[cfg(feature = "xdg-portal")]
-    ImportSuccess(Box<ThemeBuilder>),
+    ImportSuccess(Box<bundle::Import>),
     InterfaceText(ColorPickerUpdate),
     Left,
+    MatchWallpaper,
     NewTheme(Box<Theme>),
     PaletteAccent(cosmic::iced::Color),
     Reset,
@@ -408,7 +433,11 @@ pub enum Message {
     #[cfg(feature = "xdg-portal")]
     StartImport,
     UseDefaultWindowHint(bool),
+    WallpaperAccent(Option<Srgb>),
     WindowHintSize(u32),
+    WindowShapeApply,
+    WindowShapeChange(WindowShape),
+    WindowShapeReset,
     Daytime(bool),
 }
 
@@ -429,6 +458,20 @@ pub enum Roundness {
     Round,
     SlightlyRound,
     Square,
+    /// Corners of windows rounded by this radius.
+    Custom(u32),
+}
+
+impl Roundness {
+    /// The named style whose windows have this radius, if any.
+    pub fn from_radius(radius: u32) -> Self {
+        match radius {
+            16 => Self::Round,
+            8 => Self::SlightlyRound,
+            2 => Self::Square,
+            radius => Self::Custom(radius),
+        }
+    }
 }
 
 impl From<Roundness> for CornerRadii {
@@ -458,19 +501,14 @@ impl From<Roundness> for CornerRadii {
                 radius_l: [2.0; 4],
                 radius_xl: [2.0; 4],
             },
+            Roundness::Custom(radius) => window_shape::corner_radii(radius),
         }
     }
 }
 
 impl From<CornerRadii> for Roundness {
     fn from(value: CornerRadii) -> Self {
-        if (value.radius_m[0] - 16.0).abs() < 0.01 {
-            Self::Round
-        } else if (value.radius_m[0] - 8.0).abs() < 0.01 {
-            Self::SlightlyRound
-        } else {
-            Self::Square
-        }
+        Self::from_radius(value.radius_m[0].round() as u32)
     }
 }
 
@@ -530,15 +568,8 @@ impl Page {
         let mut needs_sync = false;
 
         match message {
-            Message::DisplayMonoFont => {
-                self.context_view = Some(ContextView::MonospaceFont);
-                self.font_search.clear();
-
-                return cosmic::task::message(crate::app::Message::OpenContextDrawer(self.entity));
-            }
-
-            Message::DisplaySystemFont => {
-                self.context_view = Some(ContextView::SystemFont);
+            Message::DisplayFont(category) => {
+                self.context_view = Some(ContextView::Font(category));
                 self.font_search.clear();
 
                 return cosmic::task::message(crate::app::Message::OpenContextDrawer(self.entity));
@@ -552,49 +583,19 @@ impl Page {
                 self.font_search = input.to_lowercase();
                 self.font_filter.clear();
 
-                match self.context_view {
-                    Some(ContextView::SystemFont) => {
-                        self.font_config
-                            .interface_font_families
-                            .iter()
-                            .filter(|f| f.to_lowercase().contains(&self.font_search))
-                            .for_each(|f| self.font_filter.push(f.clone()));
-                    }
-
-                    Some(ContextView::MonospaceFont) => {
-                        self.font_config
-                            .monospace_font_families
-                            .iter()
-                            .filter(|f| f.to_lowercase().contains(&self.font_search))
-                            .for_each(|f| self.font_filter.push(f.clone()));
-                    }
-
-                    _ => (),
+                if let Some(ContextView::Font(category)) = self.context_view {
+                    self.font_config
+                        .families(category)
+                        .iter()
+                        .filter(|f| f.to_lowercase().contains(&self.font_search))
+                        .for_each(|f| self.font_filter.push(f.clone()));
                 }
             }
 
-            Message::FontSelect(is_system, family) => {
-                if is_system {
-                    if let Some(id) = self
-                        .font_config
-                        .interface_font_families
-                        .iter()
-                        .position(|f| f == &family)
-                    {
-                        return self
-                            .font_config
-                            .update(font_config::Message::InterfaceFontFamily(id));
-                    }
-                } else if let Some(id) = self
+            Message::FontSelect(category, family) => {
+                return self
                     .font_config
-                    .monospace_font_families
-                    .iter()
-                    .position(|f| f == &family)
-                {
-                    return self
-                        .font_config
-                        .update(font_config::Message::MonospaceFontFamily(id));
-                }
+                    .update(font_config::Message::FontFamily(category, family));
             }
 
             Message::NewTheme(theme) => {
@@ -667,57 +668,42 @@ impl Page {
 
             Message::WindowHintSize(active_hint) => {
                 needs_sync = true;
+                self.window_shape.active_hint = active_hint;
+                self.apply_window_shape();
+            }
 
-                let Some(config) = self.theme_builder_config.as_ref() else {
-                    return Task::none();
-                };
+            Message::GapSize(gap) => {
+                needs_sync = true;
+                self.window_shape.gap = gap;
+                self.apply_window_shape();
+            }
 
-                if self
-                    .theme_builder
-                    .set_active_hint(config, active_hint)
-                    .unwrap_or_default()
-                {
-                    // Update the gap if it's less than the active hint
-                    if active_hint > self.theme_builder.gaps.1 {
-                        let mut gaps = self.theme_builder.gaps;
-                        gaps.1 = active_hint;
-                        if self
-                            .theme_builder
-                            .set_gaps(config, gaps)
-                            .unwrap_or_default()
-                        {
-                            self.theme_config_write("gaps", gaps);
-                        }
-                    }
+            Message::CornerRadius(radius) => {
+                needs_sync = true;
+                self.window_shape.corner_radius = radius;
+                self.apply_window_shape();
+            }
 
-                    // Update the active_hint in the config
-                    self.theme_config_write("active_hint", active_hint);
-                }
+            // Dragging a slider only previews its value, which is written once it is released.
+            Message::WindowShapeChange(shape) => {
+                self.window_shape = shape.clamped();
             }
 
-            Message::GapSize(gap) => {
+            Message::WindowShapeApply => {
                 needs_sync = true;
+                self.apply_window_shape();
+            }
 
-                let Some(config) = self.theme_builder_config.as_ref() else {
-                    return Task::none();
-                };
-
-                let mut gaps = self.theme_builder.gaps;
+            Message::WindowShapeReset => {
+                needs_sync = true;
 
-                // Ensure that the gap is never less than what the active hint size is.
-                gaps.1 = if gap < self.theme_builder.active_hint {
-                    self.theme_builder.active_hint
+                self.window_shape = WindowShape::new(&if self.theme_mode.is_dark {
+                    ThemeBuilder::dark()
                 } else {
-                    gap
-                };
+                    ThemeBuilder::light()
+                });
 
-                if self
-                    .theme_builder
-                    .set_gaps(config, gaps)
-                    .unwrap_or_default()
-                {
-                    self.theme_config_write("gaps", gaps);
-                }
+                self.apply_window_shape();
             }
 
             Message::ApplicationBackground(u) => {
@@ -834,26 +820,8 @@ impl Page {
 
             Message::Roundness(r) => {
                 needs_sync = true;
-                self.roundness = r;
-
-                let Some(config) = self.theme_builder_config.as_ref() else {
-                    return Task::none();
-                };
-
-                let radii = self.roundness.into();
-
-                if self
-                    .theme_builder
-                    .set_corner_radii(config, radii)
-                    .unwrap_or_default()
-                {
-                    self.theme_config_write("corner_radii", radii);
-                }
-
-                #[cfg(feature = "wayland")]
-                tokio::task::spawn(async move {
-                    Self::update_panel_radii(r);
-                });
+                self.set_roundness(r);
+                self.window_shape = WindowShape::new(&self.theme_builder);
             }
 
             Message::Density(density) => {
@@ -902,6 +870,46 @@ impl Page {
                 )));
             }
 
+            Message::AccentHex(input) => {
+                self.accent_hex = input;
+                return Task::none();
+            }
+
+            Message::AccentHexSubmit => {
+                let Some(color) = accent::parse_hex(&self.accent_hex) else {
+                    return Task::none();
+                };
+
+                self.accent_hex.clear();
+                needs_build = self.set_custom_accent(color);
+            }
+
+            Message::MatchWallpaper => {
+                self.matching_wallpaper = true;
+
+                tasks.push(cosmic::task::future(async {
+                    let color = tokio::task::spawn_blocking(accent::wallpaper_color)
+                        .await
+                        .ok()
+                        .flatten();
+
+                    Message::WallpaperAccent(color)
+                }));
+            }
+
+            Message::WallpaperAccent(color) => {
+                self.matching_wallpaper = false;
+
+                if let Some(color) = color {
+                    needs_build = self.set_custom_accent(color);
+                } else {
+                    tasks.push(cosmic::task::message(app::Message::ShowToast(fl!(
+                        "match-wallpaper",
+                        "failed"
+                    ))));
+                }
+            }
+
             Message::PaletteAccent(c) => {
                 let Some(config) = self.theme_builder_config.as_ref() else {
                     return Task::none();
@@ -1033,14 +1041,20 @@ impl Page {
                     return Task::none();
                 };
 
+                let file_name = file_stem(&path);
+
                 tasks.push(cosmic::task::future(async move {
-                    let res = tokio::fs::read_to_string(path).await;
-                    if let Some(b) = res.ok().and_then(|s| ron::de::from_str(&s).ok()) {
-                        Message::ImportSuccess(Box::new(b))
-                    } else {
-                        // TODO Error toast?
-                        tracing::error!("failed to import a file for a custom theme.");
-                        Message::ImportError
+                    let import = match tokio::fs::read_to_string(path).await {
+                        Ok(contents) => bundle::parse(&contents, &file_name),
+                        Err(why) => Err(bundle::ImportError::Invalid(why.to_string())),
+                    };
+
+                    match import {
+                        Ok(import) => Message::ImportSuccess(Box::new(import)).into(),
+                        Err(why) => {
+                            tracing::error!(?why, "failed to import a file for a custom theme.");
+                            crate::app::Message::ShowToast(why.localized())
+                        }
                     }
                 }));
             }
@@ -1058,11 +1072,14 @@ impl Page {
                     return Task::none();
                 };
 
-                let theme_builder = self.theme_builder.clone();
+                let bundle = bundle::ThemeBundle::new(
+                    file_stem(&path),
+                    self.theme_mode.is_dark,
+                    self.theme_builder.clone(),
+                );
 
                 tasks.push(cosmic::task::future(async move {
-                    let Ok(builder) =
-                        ron::ser::to_string_pretty(&theme_builder, PrettyConfig::default())
+                    let Ok(builder) = ron::ser::to_string_pretty(&bundle, PrettyConfig::default())
                     else {
                         return crate::app::Message::None;
                     };
@@ -1091,9 +1108,35 @@ impl Page {
             }
 
             #[cfg(feature = "xdg-portal")]
-            Message::ImportSuccess(builder) => {
+            Message::ImportSuccess(import) => {
                 tracing::trace!("Import successful");
-                self.theme_builder = *builder;
+                let theme = Box::new(import.builder.clone().build());
+                self.import_preview = Some((*import, theme));
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportCancel => {
+                self.import_preview = None;
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportApply => {
+                let Some((import, _theme)) = self.import_preview.take() else {
+                    return Task::none();
+                };
+
+                // Switch to the mode the theme was made for, so that it replaces that mode's theme.
+                if let Some(is_dark) = import.is_dark.filter(|&d| d != self.theme_mode.is_dark) {
+                    if let Some(config) = self.theme_mode_config.as_ref() {
+                        if let Err(err) = self.theme_mode.set_is_dark(config, is_dark) {
+                            tracing::error!(?err, "Error setting dark mode");
+                        }
+
+                        self.reload_theme_mode();
+                    }
+                }
+
+                self.theme_builder = import.builder;
 
                 if let Some(config) = self.theme_builder_config.as_ref() {
                     _ = self.theme_builder.write_entry(config);
@@ -1318,6 +1361,79 @@ impl Page {
         self.font_config = font_config;
     }
 
+    fn set_roundness(&mut self, roundness: Roundness) {
+        self.roundness = roundness;
+
+        let Some(config) = self.theme_builder_config.as_ref() else {
+            return;
+        };
+
+        let radii = roundness.into();
+
+        if self
+            .theme_builder
+            .set_corner_radii(config, radii)
+            .unwrap_or_default()
+        {
+            self.theme_config_write("corner_radii", radii);
+        }
+
+        #[cfg(feature = "wayland")]
+        tokio::task::spawn(async move {
+            Self::update_panel_radii(roundness);
+        });
+    }
+
+    /// Writes the window shape shown by the sliders to the theme, once it is clamped.
+    fn apply_window_shape(&mut self) {
+        let shape = self.window_shape.clamped();
+        self.window_shape = shape;
+
+        if WindowShape::new(&self.theme_builder).corner_radius != shape.corner_radius {
+            self.set_roundness(Roundness::from_radius(shape.corner_radius));
+        }
+
+        let Some(config) = self.theme_builder_config.as_ref() else {
+            return;
+        };
+
+        if self
+            .theme_builder
+            .set_active_hint(config, shape.active_hint)
+            .unwrap_or_default()
+        {
+            self.theme_config_write("active_hint", shape.active_hint);
+        }
+
+        let gaps = (self.theme_builder.gaps.0, shape.gap);
+
+        if self
+            .theme_builder
+            .set_gaps(config, gaps)
+            .unwrap_or_default()
+        {
+            self.theme_config_write("gaps", gaps);
+        }
+    }
+
+    /// Applies a color from outside the palette as the custom accent color.
+    fn set_custom_accent(&mut self, color: Srgb) -> bool {
+        _ = self
+            .custom_accent
+            .update::<app::Message>(ColorPickerUpdate::ActiveColor(Hsv::from_color(color)));
+        _ = self
+            .custom_accent
+            .update::<app::Message>(ColorPickerUpdate::AppliedColor);
+
+        let Some(config) = self.theme_builder_config.as_ref() else {
+            return false;
+        };
+
+        self.theme_builder
+            .set_accent(config, Some(color))
+            .unwrap_or_default()
+    }
+
     fn update_color_picker(
         &mut self,
         message: &ColorPickerUpdate,
@@ -1551,6 +1667,7 @@ impl page::Page<crate::pages::Message> for Page {
             sections.insert(style()),
             sections.insert(interface_density()),
             sections.insert(window_management()),
+            sections.insert(fonts()),
             sections.insert(experimental()),
             sections.insert(reset_button()),
         ])
@@ -1571,6 +1688,51 @@ impl page::Page<crate::pages::Message> for Page {
         Some(content)
     }
 
+    #[cfg(feature = "xdg-portal")]
+    fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
+        let (import, theme) = self.import_preview.as_ref()?;
+
+        let swatch = |label: String, color: Srgba| {
+            cosmic::iced::widget::column![
+                color_button(None, color.into(), false, 48, 48),
+                text::caption(label),
+            ]
+            .spacing(4)
+            .align_x(Alignment::Center)
+        };
+
+        let swatches = row::with_capacity(3)
+            .push(swatch(fl!("accent-color"), theme.accent.base))
+            .push(swatch(fl!("app-background"), theme.background.base))
+            .push(swatch(fl!("container-background"), theme.primary.base))
+            .spacing(16);
+
+        let control = import.warnings.iter().fold(
+            widget::column::with_capacity(1 + import.warnings.len())
+                .push(swatches)
+                .spacing(8),
+            |column, warning| column.push(text::caption(warning.localized())),
+        );
+
+        let mut dialog = widget::dialog()
+            .title(fl!("theme-import", name = import.name.as_str()))
+            .control(control)
+            .primary_action(
+                button::suggested(fl!("theme-import", "apply")).on_press(Message::ImportApply),
+            )
+            .secondary_action(button::standard(fl!("cancel")).on_press(Message::ImportCancel));
+
+        if let Some(is_dark) = import.is_dark {
+            dialog = dialog.body(if is_dark {
+                fl!("theme-import", "dark")
+            } else {
+                fl!("theme-import", "light")
+            });
+        }
+
+        Some(Element::from(dialog).map(crate::pages::Message::Appearance))
+    }
+
     fn info(&self) -> page::Info {
         page::Info::new("appearance", "preferences-appearance-symbolic")
             .title(fl!("appearance"))
@@ -1678,9 +1840,9 @@ impl page::Page<crate::pages::Message> for Page {
             )
             .title(fl!("text-tint")),
 
-            ContextView::SystemFont => {
+            ContextView::Font(category) => {
                 let filter = if self.font_search.is_empty() {
-                    &self.font_config.interface_font_families
+                    self.font_config.families(category)
                 } else {
                     &self.font_filter
                 };
@@ -1690,37 +1852,14 @@ impl page::Page<crate::pages::Message> for Page {
                     .apply(Element::from)
                     .map(crate::pages::Message::Appearance);
 
-                let current_font = cosmic::config::interface_font();
+                let current_font = self.font_config.font(category);
 
                 context_drawer(
-                    font_config::selection_context(filter, current_font.family.as_str(), true)
+                    font_config::selection_context(filter, current_font, category)
                         .map(crate::pages::Message::Appearance),
                     crate::pages::Message::CloseContextDrawer,
                 )
-                .title(fl!("interface-font"))
-                .header(search_input)
-            }
-
-            ContextView::MonospaceFont => {
-                let filter = if self.font_search.is_empty() {
-                    &self.font_config.monospace_font_families
-                } else {
-                    &self.font_filter
-                };
-                let search_input = widget::search_input(fl!("type-to-search"), &self.font_search)
-                    .on_input(Message::FontSearch)
-                    .on_clear(Message::FontSearch(String::new()))
-                    .apply(Element::from)
-                    .map(crate::pages::Message::Appearance);
-
-                let current_font = cosmic::config::monospace_font();
-
-                context_drawer(
-                    font_config::selection_context(filter, current_font.family.as_str(), false)
-                        .map(crate::pages::Message::Appearance),
-                    crate::pages::Message::CloseContextDrawer,
-                )
-                .title(fl!("monospace-font"))
+                .title(category.title())
                 .header(search_input)
             }
 
@@ -1738,6 +1877,8 @@ pub fn mode_and_colors() -> Section<crate::pages::Message> {
         auto_txt = fl!("auto");
         auto_switch = fl!("auto-switch");
         accent_color = fl!("accent-color");
+        accent_hex_invalid = fl!("accent-color", "invalid-hex");
+        match_wallpaper = fl!("match-wallpaper");
         app_bg = fl!("app-background");
         container_bg = fl!("container-background");
         container_bg_desc = fl!("container-background", "desc");
@@ -1818,6 +1959,42 @@ pub fn mode_and_colors() -> Section<crate::pages::Message> {
             .padding([16, 0, 0, 0])
             .spacing(space_xxs);
 
+            let hex_color = accent::parse_hex(&page.accent_hex);
+            let hex_invalid = !page.accent_hex.trim().is_empty() && hex_color.is_none();
+
+            // Previews the color being typed, or the current accent until it is valid.
+            let accent_hex = row::with_capacity(4)
+                .push(color_button(
+                    None,
+                    hex_color.map_or(cur_accent.into(), Color::from),
+                    false,
+                    32,
+                    32,
+                ))
+                .push(
+                    widget::text_input(accent::to_hex(cur_accent.color), &page.accent_hex)
+                        .on_input(Message::AccentHex)
+                        .on_submit(|_| Message::AccentHexSubmit)
+                        .width(Length::Fixed(120.0)),
+                )
+                .push(horizontal_space())
+                .push(
+                    button::standard(&descriptions[match_wallpaper]).on_press_maybe(
+                        (!page.matching_wallpaper).then_some(Message::MatchWallpaper),
+                    ),
+                )
+                .align_y(Alignment::Center)
+                .spacing(space_xxs);
+
+            let accent_color_palette =
+                accent_color_palette
+                    .push(accent_hex)
+                    .push_maybe(hex_invalid.then(|| {
+                        text::caption(&descriptions[accent_hex_invalid]).class(
+                            cosmic::theme::Text::Color(page.theme.destructive.base.color.into()),
+                        )
+                    }));
+
             let mut section = settings::section()
                 .title(&section.title)
                 .add(
@@ -2134,67 +2311,214 @@ pub fn interface_density() -> Section<crate::pages::Message> {
 pub fn window_management() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
+    let corner_radius = descriptions.insert(fl!("window-management-appearance", "corner-radius"));
     let active_hint = descriptions.insert(fl!("window-management-appearance", "active-hint"));
     let gaps = descriptions.insert(fl!("window-management-appearance", "gaps"));
 
+    /// A slider that previews its value until it is released, with a spin button for entering
+    /// the value directly.
+    fn value_control<'a>(
+        value: u32,
+        max: u32,
+        on_change: impl Fn(u32) -> Message + 'a,
+        on_entry: fn(u32) -> Message,
+    ) -> Element<'a, Message> {
+        widget::row::with_capacity(2)
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .push(
+                widget::slider(0..=max, value, on_change)
+                    .on_release(Message::WindowShapeApply)
+                    .width(Length::Fixed(160.0)),
+            )
+            .push(widget::spin_button(
+                value.to_string(),
+                value,
+                1,
+                0,
+                max,
+                on_entry,
+            ))
+            .into()
+    }
+
+    /// Two tiled windows at half size, the first of them active.
+    fn preview<'a>(shape: WindowShape) -> Element<'a, Message> {
+        let scale = |value: u32| value as f32 / 2.0;
+
+        let window = move |active: bool| {
+            container(horizontal_space())
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .class(cosmic::theme::Container::custom(move |theme| {
+                    let cosmic = theme.cosmic();
+                    container::Style {
+                        background: Some(cosmic::iced::Background::Color(
+                            cosmic.primary.base.into(),
+                        )),
+                        border: cosmic::iced::Border {
+                            radius: scale(shape.corner_radius).into(),
+                            width: if active {
+                                scale(shape.active_hint)
+                            } else {
+                                0.0
+                            },
+                            color: cosmic.accent_color().into(),
+                        },
+                        ..Default::default()
+                    }
+                }))
+        };
+
+        widget::row::with_capacity(2)
+            .spacing(scale(shape.gap))
+            .push(window(true))
+            .push(window(false))
+            .apply(container)
+            .padding(scale(shape.gap))
+            .width(Length::Fixed(320.0))
+            .height(Length::Fixed(128.0))
+            .class(cosmic::theme::Container::custom(|theme| container::Style {
+                background: Some(cosmic::iced::Background::Color(
+                    theme.cosmic().background.base.into(),
+                )),
+                border: cosmic::iced::Border {
+                    radius: theme.cosmic().radius_s().into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))
+            .into()
+    }
+
     Section::default()
         .title(fl!("window-management-appearance"))
         .descriptions(descriptions)
         .view::<Page>(move |_binder, page, section| {
             let descriptions = &section.descriptions;
+            let shape = page.window_shape;
+
+            let default_shape = WindowShape::new(&if page.theme_mode.is_dark {
+                ThemeBuilder::dark()
+            } else {
+                ThemeBuilder::light()
+            });
 
             settings::section()
                 .title(&section.title)
-                .add(settings::item::builder(&descriptions[active_hint]).control(
-                    widget::spin_button(
-                        page.theme_builder.active_hint.to_string(),
-                        page.theme_builder.active_hint,
-                        1,
-                        0,
-                        64,
+                .add(container(preview(shape)).center_x(Length::Fill))
+                .add(
+                    settings::item::builder(&descriptions[corner_radius]).control(value_control(
+                        shape.corner_radius,
+                        window_shape::MAX_CORNER_RADIUS,
+                        move |corner_radius| {
+                            Message::WindowShapeChange(WindowShape {
+                                corner_radius,
+                                ..shape
+                            })
+                        },
+                        Message::CornerRadius,
+                    )),
+                )
+                .add(
+                    settings::item::builder(&descriptions[active_hint]).control(value_control(
+                        shape.active_hint,
+                        window_shape::MAX_ACTIVE_HINT,
+                        move |active_hint| {
+                            Message::WindowShapeChange(WindowShape {
+                                active_hint,
+                                ..shape
+                            })
+                        },
                         Message::WindowHintSize,
-                    ),
-                ))
+                    )),
+                )
                 .add(
-                    settings::item::builder(&descriptions[gaps]).control(widget::spin_button(
-                        page.theme_builder.gaps.1.to_string(),
-                        page.theme_builder.gaps.1,
-                        1,
-                        page.theme_builder.active_hint,
-                        500,
+                    settings::item::builder(&descriptions[gaps]).control(value_control(
+                        shape.gap,
+                        window_shape::MAX_GAP,
+                        move |gap| Message::WindowShapeChange(WindowShape { gap, ..shape }),
                         Message::GapSize,
                     )),
                 )
+                .add(settings::item_row(vec![
+                    horizontal_space().into(),
+                    button::standard(RESET_TO_DEFAULT.as_str())
+                        .on_press_maybe(
+                            (shape != default_shape).then_some(Message::WindowShapeReset),
+                        )
+                        .into(),
+                ]))
                 .apply(Element::from)
                 .map(crate::pages::Message::Appearance)
         })
 }
 
-pub fn experimental() -> Section<crate::pages::Message> {
+pub fn fonts() -> Section<crate::pages::Message> {
     crate::slab!(descriptions {
         interface_font_txt = fl!("interface-font");
         monospace_font_txt = fl!("monospace-font");
+        preview_txt = fl!("font-preview");
+        reset_txt = fl!("reset-fonts");
+    });
+
+    Section::default()
+        .title(fl!("fonts"))
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            let mut fonts = settings::section().title(&*section.title);
+
+            for (category, title) in [
+                (font_config::Category::Interface, interface_font_txt),
+                (font_config::Category::Monospace, monospace_font_txt),
+            ] {
+                let font = page.font_config.font(category);
+
+                let family = crate::widget::go_next_with_item(
+                    &descriptions[title],
+                    text::body(font),
+                    Message::DisplayFont(category),
+                );
+
+                let preview = settings::item_row(vec![
+                    text::body(&descriptions[preview_txt])
+                        .font(font_config::preview_font(font))
+                        .into(),
+                ]);
+
+                fonts = fonts.add(family).add(preview);
+            }
+
+            fonts
+                .add(settings::item_row(vec![
+                    horizontal_space().into(),
+                    button::standard(&descriptions[reset_txt])
+                        .on_press_maybe(
+                            page.font_config
+                                .can_reset()
+                                .then_some(Message::FontConfig(font_config::Message::Reset)),
+                        )
+                        .into(),
+                ]))
+                .apply(Element::from)
+                .map(crate::pages::Message::Appearance)
+        })
+}
+
+pub fn experimental() -> Section<crate::pages::Message> {
+    crate::slab!(descriptions {
         icons_and_toolkit_txt = fl!("icons-and-toolkit");
     });
 
     Section::default()
         .title(fl!("experimental-settings"))
         .descriptions(descriptions)
+        .advanced()
         .view::<Page>(move |_binder, _page, section| {
             let descriptions = &section.descriptions;
 
-            let system_font = crate::widget::go_next_with_item(
-                &descriptions[interface_font_txt],
-                text::body(cosmic::config::interface_font().family),
-                Message::DisplaySystemFont,
-            );
-
-            let mono_font = crate::widget::go_next_with_item(
-                &descriptions[monospace_font_txt],
-                text::body(cosmic::config::monospace_font().family),
-                Message::DisplayMonoFont,
-            );
-
             let icons_and_toolkit = crate::widget::go_next_item(
                 &descriptions[icons_and_toolkit_txt],
                 Message::IconsAndToolkit,
@@ -2202,8 +2526,6 @@ pub fn experimental() -> Section<crate::pages::Message> {
 
             settings::section()
                 .title(&*section.title)
-                .add(system_font)
-                .add(mono_font)
                 .add(icons_and_toolkit)
                 .apply(Element::from)
                 .map(crate::pages::Message::Appearance)
@@ -2233,6 +2555,14 @@ pub fn reset_button() -> Section<crate::pages::Message> {
 impl page::AutoBind<crate::pages::Message> for Page {}
 
 /// A button for selecting a color or gradient.
+/// The name of a file without its extension, which names an exported theme.
+#[cfg(feature = "xdg-portal")]
+fn file_stem(path: &std::path::Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
 pub fn color_button<'a, Message: 'a + Clone>(
     on_press: Option<Message>,
     color: cosmic::iced::Color,
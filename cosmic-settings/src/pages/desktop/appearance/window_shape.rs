@@ -0,0 +1,92 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The roundness of window corners, the gaps between tiled windows, and the border of the
+//! active window.
+
+use cosmic::cosmic_theme::{CornerRadii, ThemeBuilder};
+
+pub const MAX_CORNER_RADIUS: u32 = 32;
+pub const MAX_ACTIVE_HINT: u32 = 16;
+pub const MAX_GAP: u32 = 64;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WindowShape {
+    pub corner_radius: u32,
+    pub gap: u32,
+    pub active_hint: u32,
+}
+
+impl WindowShape {
+    pub fn new(builder: &ThemeBuilder) -> Self {
+        Self {
+            corner_radius: builder.corner_radii.radius_m[0].round() as u32,
+            gap: builder.gaps.1,
+            active_hint: builder.active_hint,
+        }
+    }
+
+    /// Keeps each value within its range, and the gap wide enough for the active hint.
+    pub fn clamped(self) -> Self {
+        let active_hint = self.active_hint.min(MAX_ACTIVE_HINT);
+
+        Self {
+            corner_radius: self.corner_radius.min(MAX_CORNER_RADIUS),
+            gap: self.gap.clamp(active_hint, MAX_GAP),
+            active_hint,
+        }
+    }
+}
+
+/// Radii of a custom roundness, where windows and larger surfaces take the radius, and
+/// smaller controls are kept from rounding into circles.
+pub fn corner_radii(radius: u32) -> CornerRadii {
+    let radius = radius as f32;
+
+    CornerRadii {
+        radius_0: [0.0; 4],
+        radius_xs: [(radius / 2.0).min(4.0); 4],
+        radius_s: [radius.min(8.0); 4],
+        radius_m: [radius; 4],
+        radius_l: [radius; 4],
+        radius_xl: [radius; 4],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_ACTIVE_HINT, MAX_CORNER_RADIUS, MAX_GAP, WindowShape};
+
+    #[test]
+    fn clamped() {
+        let shape = WindowShape {
+            corner_radius: 100,
+            gap: 4,
+            active_hint: 100,
+        };
+
+        assert_eq!(
+            shape.clamped(),
+            WindowShape {
+                corner_radius: MAX_CORNER_RADIUS,
+                gap: MAX_ACTIVE_HINT,
+                active_hint: MAX_ACTIVE_HINT,
+            }
+        );
+
+        let shape = WindowShape {
+            corner_radius: 12,
+            gap: 1000,
+            active_hint: 3,
+        };
+
+        assert_eq!(
+            shape.clamped(),
+            WindowShape {
+                corner_radius: 12,
+                gap: MAX_GAP,
+                active_hint: 3,
+            }
+        );
+    }
+}
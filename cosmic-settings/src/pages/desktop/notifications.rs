@@ -0,0 +1,108 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Do not disturb, which silences notifications until it is turned off.
+
+use cosmic::widget::settings;
+use cosmic::{Apply, Element, Task};
+use cosmic_config::{ConfigGet, ConfigSet};
+use cosmic_settings_page::{self as page, Section, section};
+use slotmap::SlotMap;
+use tracing::error;
+
+/// Config of the notifications daemon, which these settings are read from.
+const CONFIG_ID: &str = "com.system76.CosmicNotifications";
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    DoNotDisturb(bool),
+}
+
+impl From<Message> for crate::pages::Message {
+    fn from(message: Message) -> Self {
+        crate::pages::Message::Notifications(message)
+    }
+}
+
+pub struct Page {
+    config: Option<cosmic_config::Config>,
+    do_not_disturb: bool,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            config: cosmic_config::Config::new(CONFIG_ID, 1)
+                .inspect_err(|err| error!(?err, "Failed to open the notifications config"))
+                .ok(),
+            do_not_disturb: false,
+        }
+    }
+}
+
+impl page::Page<crate::pages::Message> for Page {
+    fn info(&self) -> page::Info {
+        page::Info::new("notifications", "preferences-system-notifications-symbolic")
+            .title(fl!("notifications"))
+            .description(fl!("notifications", "desc"))
+    }
+
+    fn content(
+        &self,
+        sections: &mut SlotMap<section::Entity, Section<crate::pages::Message>>,
+    ) -> Option<page::Content> {
+        Some(vec![sections.insert(do_not_disturb())])
+    }
+
+    fn on_enter(&mut self) -> Task<crate::pages::Message> {
+        if let Some(config) = self.config.as_ref() {
+            self.do_not_disturb = config.get("do_not_disturb").unwrap_or_default();
+        }
+
+        Task::none()
+    }
+}
+
+impl page::AutoBind<crate::pages::Message> for Page {}
+
+impl Page {
+    pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
+        match message {
+            Message::DoNotDisturb(enabled) => {
+                self.do_not_disturb = enabled;
+
+                if let Some(config) = self.config.as_ref() {
+                    if let Err(err) = config.set("do_not_disturb", enabled) {
+                        error!(?err, "Failed to set config 'do_not_disturb'");
+                    }
+                }
+            }
+        }
+
+        Task::none()
+    }
+}
+
+fn do_not_disturb() -> Section<crate::pages::Message> {
+    crate::slab!(descriptions {
+        title = fl!("do-not-disturb");
+        dnd_desc = fl!("do-not-disturb", "desc");
+    });
+
+    Section::default()
+        .title(&descriptions[title])
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            settings::section()
+                .title(&section.title)
+                .add(
+                    settings::item::builder(&descriptions[title])
+                        .description(&descriptions[dnd_desc])
+                        .toggler(page.do_not_disturb, Message::DoNotDisturb),
+                )
+                .apply(Element::from)
+                .map(crate::pages::Message::Notifications)
+        })
+}
@@ -9,7 +9,7 @@ use futures::StreamExt;
 use url::Url;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -30,7 +30,7 @@ use cosmic::{
     iced::{Alignment, Color, Length, window},
     surface,
 };
-use cosmic_bg_config::Source;
+use cosmic_bg_config::{SamplingMethod, Source};
 use cosmic_settings_page::Section;
 use cosmic_settings_page::{self as page, section};
 use cosmic_settings_wallpaper::{self as wallpaper, Entry, ScalingMode};
@@ -41,6 +41,7 @@ use slotmap::{DefaultKey, Key, SecondaryMap, SlotMap};
 
 const ZOOM: usize = 0;
 const FIT: usize = 1;
+const STRETCH: usize = 2;
 
 const SIMULATED_WIDTH: u16 = 300;
 const SIMULATED_HEIGHT: u16 = 169;
@@ -52,11 +53,26 @@ const MINUTES_30: usize = 3;
 const HOUR_1: usize = 4;
 const HOUR_2: usize = 5;
 
+/// Colors that may fill the space around a wallpaper that is fitted to the screen.
+const LETTERBOX_COLORS: [[f32; 3]; 4] = [
+    [0.0, 0.0, 0.0],
+    [0.2, 0.2, 0.2],
+    [0.8, 0.8, 0.8],
+    [1.0, 1.0, 1.0],
+];
+
+/// Number of images of a slideshow folder that are previewed.
+const SLIDESHOW_PREVIEWS: usize = 4;
+
 pub type Image = ImageBuffer<Rgba<u8>, Vec<u8>>;
 
 #[derive(Clone, Debug)]
 struct OutputName(String);
 
+/// The physical size of an output in millimeters, which its preview takes the shape of.
+#[derive(Clone, Copy, Debug)]
+struct OutputSize((u32, u32));
+
 #[derive(Clone, Debug)]
 pub struct InitUpdate {
     service_config: wallpaper::Config,
@@ -89,12 +105,16 @@ pub enum Message {
     ColorSelect(wallpaper::Color),
     /// Sets the wallpaper fit parameter.
     Fit(usize),
+    /// Sets the color around a wallpaper that is fitted to the screen.
+    FitColor([f32; 3]),
     /// Adds a new custom image to the wallpaper view.
     ImageAdd(Option<Arc<(PathBuf, Image, Image)>>),
     /// Creates an image dialog.
     ImageAddDialog,
     /// Removes a custom image from the wallpaper view.
     ImageRemove(DefaultKey),
+    /// Displays that were connected or disconnected while the page was open.
+    Displays(HashMap<String, (String, (u32, u32))>),
     /// Initializes the view.
     Init(Box<InitUpdate>),
     /// Changes the active output display that is to be configured.
@@ -105,6 +125,8 @@ pub enum Message {
     SameWallpaper(bool),
     /// Selects an background option from the list of selections in the view.
     Select(DefaultKey),
+    /// Shows the images of a slideshow in a random order.
+    Shuffle(bool),
     /// Changes the slideshow parameter.
     Slideshow(bool),
     /// State change from cosmic-bg
@@ -174,6 +196,9 @@ pub struct Page {
     /// Model containing available wallpaper fit options.
     fit_options: Vec<String>,
 
+    /// Color around a wallpaper that is fitted to the screen.
+    fit_color: [f32; 3],
+
     /// Model for selecting between display outputs.
     outputs: SingleSelectModel,
 
@@ -189,6 +214,9 @@ pub struct Page {
     /// The ID of the currently-selected slideshow rotation.
     selected_rotation: usize,
 
+    /// Whether the slideshow shows its images in a random order.
+    shuffle: bool,
+
     /// Stores custom colors, custom images, and all image data for every wallpaper.
     selection: Context,
 
@@ -336,7 +364,8 @@ impl Default for Page {
             wallpaper_service_config: wallpaper::Config::default(),
             color_model: ColorPickerModel::new(fl!("hex"), fl!("rgb"), None, Some(Color::WHITE)),
             config: Config::new(),
-            fit_options: vec![fl!("fill"), fl!("fit-to-screen")],
+            fit_options: vec![fl!("fill"), fl!("fit-to-screen"), fl!("stretch")],
+            fit_color: LETTERBOX_COLORS[0],
             outputs: SingleSelectModel::default(),
             rotation_frequency: 300,
             rotation_options: vec![
@@ -362,6 +391,7 @@ impl Default for Page {
             ],
             selected_fit: 0,
             selected_rotation: 0,
+            shuffle: false,
             selection: Context::default(),
             update_config: None,
         };
@@ -420,46 +450,36 @@ impl Page {
             return;
         };
 
-        let temp_image;
-
-        let image = match self.selected_fit {
-            ZOOM => {
-                let (w, h) = (image.width(), image.height());
-
-                let ratio =
-                    (SIMULATED_WIDTH as f64 / w as f64).max(SIMULATED_HEIGHT as f64 / h as f64);
-
-                let (new_width, new_height) = (
-                    (w as f64 * ratio).round() as u32,
-                    (h as f64 * ratio).round() as u32,
-                );
-
-                let mut new_image = image::imageops::resize(image, new_width, new_height, Lanczos3);
-
-                temp_image = image::imageops::crop(
-                    &mut new_image,
-                    (new_width - SIMULATED_WIDTH as u32) / 2,
-                    (new_height - SIMULATED_HEIGHT as u32) / 2,
-                    SIMULATED_WIDTH as u32,
-                    SIMULATED_HEIGHT as u32,
-                )
-                .to_image();
-
-                &temp_image
-            }
-
-            FIT => image,
-
-            _ => return,
-        };
+        let image = fit_preview(
+            image,
+            self.selected_fit,
+            self.fit_color,
+            self.preview_size(),
+        );
 
         self.cached_display_handle = Some(ImageHandle::from_rgba(
             image.width(),
             image.height(),
-            image.to_vec(),
+            image.into_raw(),
         ));
     }
 
+    /// Size of the display preview, in the shape of the output being configured.
+    fn preview_size(&self) -> (u32, u32) {
+        let width = u32::from(SIMULATED_WIDTH);
+
+        let height = self
+            .outputs
+            .active_data::<OutputSize>()
+            .filter(|OutputSize((w, h))| *w > 0 && *h > 0)
+            .map_or(u32::from(SIMULATED_HEIGHT), |OutputSize((w, h))| {
+                (f64::from(width) * f64::from(*h) / f64::from(*w)).round() as u32
+            });
+
+        // Portrait outputs are kept from making the page too tall.
+        (width, height.clamp(u32::from(SIMULATED_HEIGHT) / 2, width))
+    }
+
     fn config_output(&self) -> Option<&str> {
         if self.wallpaper_service_config.same_on_all {
             Some("all")
@@ -476,20 +496,26 @@ impl Page {
             return;
         };
 
-        if self.wallpaper_service_config.same_on_all {
-            self.wallpaper_service_config.backgrounds.clear();
-            self.wallpaper_service_config.outputs.clear();
-        } else if let Some(pos) = self
-            .wallpaper_service_config
-            .backgrounds
-            .iter()
-            .position(|entry| entry.output == output)
-        {
-            let _removed = self.wallpaper_service_config.backgrounds.swap_remove(pos);
+        // The wallpapers of each output are kept while the same wallpaper is shown on all of
+        // them, so that they are restored when that is turned off.
+        if !self.wallpaper_service_config.same_on_all {
+            if let Some(pos) = self
+                .wallpaper_service_config
+                .backgrounds
+                .iter()
+                .position(|entry| entry.output == output)
+            {
+                let _removed = self.wallpaper_service_config.backgrounds.swap_remove(pos);
+            }
         }
 
         let entry = match self.selection.active {
             Choice::Slideshow => {
+                // cosmic-bg would have nothing to show from a folder without images.
+                if self.selection.folder_images().next().is_none() {
+                    return;
+                }
+
                 match self
                     .config_wallpaper_entry(output, self.config.current_folder().to_path_buf())
                 {
@@ -515,6 +541,20 @@ impl Page {
         wallpaper::set(&mut self.wallpaper_service_config, entry);
     }
 
+    /// Name of the folder that the slideshow shows the images of.
+    fn slideshow_folder_name(&self) -> String {
+        let folder = self.config.current_folder();
+
+        if folder == Config::default_folder() {
+            return fl!("wallpaper", "plural");
+        }
+
+        folder.file_name().map_or_else(
+            || folder.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+
     /// Locate the ID of a wallpaper that's already stored in memory
     fn wallpaper_id_from_path(&self, path: &Path) -> Option<DefaultKey> {
         self.selection
@@ -527,56 +567,74 @@ impl Page {
     /// Updates configuration from the wallpaper service.
     fn wallpaper_service_config_update(&mut self, displays: HashMap<String, (String, (u32, u32))>) {
         let mut first = None;
+        let mut previous = None;
         for (name, (_model, physical)) in displays {
             let is_internal = "eDP-1" == name;
+            let was_active = self.active_output.as_deref() == Some(name.as_str());
 
             let entity = self
                 .outputs
                 .insert()
                 .text(crate::utils::display_name(&name, physical))
-                .data(OutputName(name));
+                .data(OutputName(name))
+                .data(OutputSize(physical));
 
             if is_internal || first.is_none() {
                 first = Some(entity.id());
             }
+
+            if was_active {
+                previous = Some(entity.id());
+            }
         }
 
-        if let Some(id) = first {
+        // Keep configuring the output that was selected before, if it is still connected.
+        if let Some(id) = previous.or(first) {
             self.outputs.activate(id);
         }
 
         self.apply_active_selection();
     }
 
-    /// Apply the selection for the active output.
+    /// Apply the selection for the active output, or the default wallpaper if it has none.
     fn apply_active_selection(&mut self) {
-        if self.wallpaper_service_config.same_on_all
-            || self.wallpaper_service_config.backgrounds.is_empty()
-        {
-            let entry = self.wallpaper_service_config.default_background.clone();
-            self.select_wallpaper_entry(&entry);
-        } else if let Some(OutputName(output)) = self.outputs.active_data() {
-            let mut wallpapers = Vec::new();
-
-            std::mem::swap(
-                &mut self.wallpaper_service_config.backgrounds,
-                &mut wallpapers,
-            );
+        if let Some(OutputName(output)) = self.outputs.active_data() {
+            self.active_output = Some(output.clone());
+        }
 
-            for wallpaper in &wallpapers {
-                if wallpaper.output == *output {
-                    self.active_output = Some(output.clone());
-                    self.select_wallpaper_entry(wallpaper);
+        let entry = self
+            .active_output
+            .as_ref()
+            .filter(|_| !self.wallpaper_service_config.same_on_all)
+            .and_then(|output| {
+                self.wallpaper_service_config
+                    .backgrounds
+                    .iter()
+                    .find(|entry| entry.output == *output)
+            })
+            .unwrap_or(&self.wallpaper_service_config.default_background)
+            .clone();
+
+        self.select_wallpaper_entry(&entry);
+    }
 
-                    break;
-                }
-            }
+    /// Whether the connected outputs differ from those that wallpapers are configured for.
+    fn outputs_changed(&self, state: &cosmic_bg_config::state::State) -> bool {
+        let connected = state
+            .wallpapers
+            .iter()
+            .map(|(output, _source)| output.as_str())
+            .collect::<BTreeSet<_>>();
 
-            std::mem::swap(
-                &mut self.wallpaper_service_config.backgrounds,
-                &mut wallpapers,
-            );
-        }
+        let shown = self
+            .outputs
+            .iter()
+            .filter_map(|id| self.outputs.data::<OutputName>(id))
+            .map(|OutputName(output)| output.as_str())
+            .collect::<BTreeSet<_>>();
+
+        // Outputs are not listed until the page has been loaded.
+        !shown.is_empty() && connected != shown
     }
 
     /// Changes the selection category, such as wallpaper select or color select.
@@ -666,7 +724,8 @@ impl Page {
     fn config_wallpaper_entry(&self, output: String, path: PathBuf) -> Option<Entry> {
         let scaling_mode = match self.selected_fit {
             ZOOM => ScalingMode::Zoom,
-            FIT => ScalingMode::Fit([0.0, 0.0, 0.0]),
+            FIT => ScalingMode::Fit(self.fit_color),
+            STRETCH => ScalingMode::Stretch,
             _ => return None,
         };
         let old_entry = if output == "all" {
@@ -678,13 +737,19 @@ impl Page {
                 .find(|entry| entry.output == output)
         };
 
+        let sampling_method = if self.shuffle {
+            SamplingMethod::Random
+        } else {
+            SamplingMethod::Alphanumeric
+        };
+
         let entry = Entry::new(output, wallpaper::Source::Path(path))
             .scaling_mode(scaling_mode)
-            .rotation_frequency(self.rotation_frequency);
+            .rotation_frequency(self.rotation_frequency)
+            .sampling_method(sampling_method);
 
         if let Some(old_entry) = old_entry {
             entry
-                .sampling_method(old_entry.sampling_method)
                 .filter_method(old_entry.filter_method.clone())
                 .filter_by_theme(old_entry.filter_by_theme)
         } else {
@@ -700,14 +765,26 @@ impl Page {
                 .width(Length::Fixed(SIMULATED_WIDTH as f32))
                 .into(),
 
-            None => cosmic::widget::Space::new(SIMULATED_WIDTH, SIMULATED_HEIGHT).into(),
+            None => {
+                let (width, height) = self.preview_size();
+                cosmic::widget::Space::new(width as f32, height as f32).into()
+            }
         }
     }
 
     #[allow(clippy::too_many_lines)]
     pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
         match message {
-            Message::UpdateState(_state) => {
+            Message::UpdateState(state) => {
+                // cosmic-bg shows a wallpaper on each output as it is connected, so the
+                // outputs to choose from are listed again while the page is open.
+                if self.on_enter_handle.is_some() && self.outputs_changed(&state) {
+                    return cosmic::task::future(async {
+                        let (_service_config, displays) = wallpaper::config().await;
+                        Message::Displays(displays)
+                    });
+                }
+
                 if let Choice::Slideshow = self.selection.active {
                     self.cache_display_image();
                 }
@@ -716,6 +793,8 @@ impl Page {
             Message::CacheDisplayImage => self.cache_display_image(),
 
             Message::ChangeFolder(mut context) => {
+                let slideshow = matches!(self.selection.active, Choice::Slideshow);
+
                 // Reassign custom colors and images to the new context.
                 std::mem::swap(&mut context, &mut self.selection);
 
@@ -733,7 +812,13 @@ impl Page {
                     }
                 }
 
-                self.select_first_wallpaper();
+                // A slideshow moves on to the images of the new folder.
+                if slideshow {
+                    self.selection.active = Choice::Slideshow;
+                    self.cache_display_image();
+                } else {
+                    self.select_first_wallpaper();
+                }
             }
 
             Message::ColorAdd(message) => {
@@ -855,6 +940,11 @@ impl Page {
                 self.cache_display_image();
             }
 
+            Message::FitColor(color) => {
+                self.fit_color = color;
+                self.cache_display_image();
+            }
+
             Message::Output(id) => {
                 self.change_output(id);
                 return Task::none();
@@ -864,7 +954,12 @@ impl Page {
 
             Message::SameWallpaper(value) => {
                 self.wallpaper_service_config.same_on_all = value;
-                self.wallpaper_service_config.backgrounds.clear();
+
+                // Each output returns to its own wallpaper when they are no longer the same.
+                if !value {
+                    self.apply_active_selection();
+                    self.cache_display_image();
+                }
             }
 
             Message::Select(id) => {
@@ -872,6 +967,8 @@ impl Page {
                 self.cache_display_image();
             }
 
+            Message::Shuffle(shuffle) => self.shuffle = shuffle,
+
             Message::Slideshow(enable) => {
                 if enable {
                     self.selection.active = Choice::Slideshow;
@@ -953,6 +1050,15 @@ impl Page {
                 }
             }
 
+            Message::Displays(displays) => {
+                // Wallpapers of disconnected outputs are kept for when they return.
+                self.outputs.clear();
+                self.show_tab_bar = displays.len() > 1;
+                self.wallpaper_service_config_update(displays);
+                self.cache_display_image();
+                return Task::none();
+            }
+
             Message::Init(update) => {
                 self.outputs.clear();
                 self.wallpaper_service_config = update.service_config;
@@ -1022,6 +1128,7 @@ impl Page {
             wallpaper::Source::Path(ref path) => {
                 if path.is_dir() {
                     self.selection.active = Choice::Slideshow;
+                    self.read_slideshow_settings(entry);
                     self.cache_display_image();
                 } else if let Some(entity) = self.wallpaper_id_from_path(path) {
                     self.select_wallpaper(entry, entity, path.is_dir());
@@ -1075,10 +1182,20 @@ impl Page {
         };
 
         match entry.scaling_mode {
-            ScalingMode::Zoom | ScalingMode::Stretch => self.selected_fit = ZOOM,
-            ScalingMode::Fit(_) => self.selected_fit = FIT,
+            ScalingMode::Zoom => self.selected_fit = ZOOM,
+            ScalingMode::Stretch => self.selected_fit = STRETCH,
+            ScalingMode::Fit(color) => {
+                self.selected_fit = FIT;
+                self.fit_color = color;
+            }
         }
 
+        self.read_slideshow_settings(entry);
+        self.cache_display_image();
+    }
+
+    /// Reads the rotation and order of a slideshow from its entry.
+    fn read_slideshow_settings(&mut self, entry: &wallpaper::Entry) {
         match entry.rotation_frequency {
             600 => self.selected_rotation = MINUTES_10,
             900 => self.selected_rotation = MINUTES_15,
@@ -1089,8 +1206,7 @@ impl Page {
         }
 
         self.rotation_frequency = entry.rotation_frequency;
-
-        self.cache_display_image();
+        self.shuffle = matches!(entry.sampling_method, SamplingMethod::Random);
     }
 }
 
@@ -1143,6 +1259,13 @@ impl Context {
         self.selection_handles.insert(key, selection);
     }
 
+    /// Images of the current folder, without the custom images added to it.
+    fn folder_images(&self) -> impl Iterator<Item = (DefaultKey, &ImageHandle)> {
+        self.selection_handles
+            .iter()
+            .filter(|(id, _)| !self.is_custom.contains_key(*id))
+    }
+
     fn remove_custom_color(&mut self, color: &wallpaper::Color) {
         if let Some(id) = self.custom_colors.iter().position(|c| c == color) {
             self.custom_colors.remove(id);
@@ -1165,6 +1288,61 @@ impl Context {
     }
 }
 
+/// Shows how a wallpaper is placed on a display of the given size.
+fn fit_preview(
+    image: &Image,
+    fit: usize,
+    letterbox: [f32; 3],
+    (width, height): (u32, u32),
+) -> Image {
+    let (w, h) = (f64::from(image.width()), f64::from(image.height()));
+    let scaled = |ratio: f64| {
+        (
+            ((w * ratio).round() as u32).max(1),
+            ((h * ratio).round() as u32).max(1),
+        )
+    };
+
+    match fit {
+        STRETCH => image::imageops::resize(image, width, height, Lanczos3),
+
+        // Scaled to fit within the display, with the letterbox color around it.
+        FIT => {
+            let (new_width, new_height) = scaled((f64::from(width) / w).min(f64::from(height) / h));
+            let (new_width, new_height) = (new_width.min(width), new_height.min(height));
+            let resized = image::imageops::resize(image, new_width, new_height, Lanczos3);
+
+            let [red, green, blue] =
+                letterbox.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+            let mut canvas = Image::from_pixel(width, height, Rgba([red, green, blue, 255]));
+
+            image::imageops::overlay(
+                &mut canvas,
+                &resized,
+                i64::from((width - new_width) / 2),
+                i64::from((height - new_height) / 2),
+            );
+
+            canvas
+        }
+
+        // Scaled to cover the display, with the edges that overflow it cropped.
+        _ => {
+            let (new_width, new_height) = scaled((f64::from(width) / w).max(f64::from(height) / h));
+            let mut resized = image::imageops::resize(image, new_width, new_height, Lanczos3);
+
+            image::imageops::crop(
+                &mut resized,
+                new_width.saturating_sub(width) / 2,
+                new_height.saturating_sub(height) / 2,
+                width,
+                height,
+            )
+            .to_image()
+        }
+    }
+}
+
 pub async fn change_folder(current_folder: PathBuf) -> Context {
     let mut update = Context::default();
     let mut streams = Vec::with_capacity(2);
@@ -1201,14 +1379,18 @@ pub fn settings() -> Section<crate::pages::Message> {
 
     let same_label = descriptions.insert(fl!("wallpaper", "same"));
     let fit_label = descriptions.insert(fl!("wallpaper", "fit"));
+    let letterbox_label = descriptions.insert(fl!("wallpaper", "letterbox"));
+    let letterbox_desc = descriptions.insert(fl!("wallpaper", "letterbox-desc"));
     let slide_label = descriptions.insert(fl!("wallpaper", "slide"));
     let change_label = descriptions.insert(fl!("wallpaper", "change"));
+    let shuffle_label = descriptions.insert(fl!("wallpaper", "shuffle"));
+    let folder_label = descriptions.insert(fl!("wallpaper", "slideshow-folder"));
 
     Section::default()
         .descriptions(descriptions)
         .view::<Page>(move |_binder, page, section| {
             let descriptions = &section.descriptions;
-            let mut children = Vec::with_capacity(3);
+            let mut children = Vec::with_capacity(5);
 
             let mut show_slideshow_toggle = true;
             // Slideshow is enabled if the background path from cosmic-bg is a directory
@@ -1237,12 +1419,8 @@ pub fn settings() -> Section<crate::pages::Message> {
                     // Displays color options, and hides the slideshow toggle
                     Choice::Color(ref color) => {
                         show_slideshow_toggle = false;
-                        widgets::color_image(
-                            color.clone(),
-                            SIMULATED_WIDTH,
-                            SIMULATED_HEIGHT,
-                            Some(0.0),
-                        )
+                        let (width, height) = page.preview_size();
+                        widgets::color_image(color.clone(), width as u16, height as u16, Some(0.0))
                     }
                 },
             ));
@@ -1284,6 +1462,19 @@ pub fn settings() -> Section<crate::pages::Message> {
                     ))
                     .add(settings::item(&descriptions[fit_label], wallpaper_fit));
 
+                // Colors fill the screen, so only images have space around them.
+                if show_slideshow_toggle && page.selected_fit == FIT {
+                    let colors = LETTERBOX_COLORS.iter().map(|&color| {
+                        widgets::letterbox_color_button(color, color == page.fit_color)
+                    });
+
+                    column = column.add(
+                        settings::item::builder(&descriptions[letterbox_label])
+                            .description(&descriptions[letterbox_desc])
+                            .control(row::with_children(colors.collect()).spacing(8)),
+                    );
+                }
+
                 if show_slideshow_toggle {
                     column = column.add(settings::item(
                         &descriptions[slide_label],
@@ -1314,12 +1505,39 @@ pub fn settings() -> Section<crate::pages::Message> {
                             };
                             dropdown
                         }))
+                        .add(settings::item(
+                            &descriptions[shuffle_label],
+                            toggler(page.shuffle).on_toggle(Message::Shuffle),
+                        ))
+                        .add(settings::item(
+                            &descriptions[folder_label],
+                            row::with_capacity(2)
+                                .align_y(Alignment::Center)
+                                .spacing(8)
+                                .push(text::body(page.slideshow_folder_name()))
+                                .push(
+                                    button::standard(fl!("wallpaper", "choose-folder"))
+                                        .on_press(Message::ChangeCategory(Category::AddFolder)),
+                                ),
+                        ))
                         .into()
                 } else {
                     column.into()
                 }
             });
 
+            // Shows what the slideshow will cycle through, or why it cannot start.
+            if slideshow_enabled {
+                children.push(if page.selection.folder_images().next().is_some() {
+                    widgets::slideshow_preview(&page.selection, SLIDESHOW_PREVIEWS)
+                } else {
+                    text::body(fl!("wallpaper", "empty-folder"))
+                        .apply(cosmic::widget::container)
+                        .center_x(Length::Fill)
+                        .into()
+                });
+            }
+
             let category_selection =
                 dropdown::multi::dropdown(&page.categories, Message::ChangeCategory);
 
@@ -1461,3 +1679,31 @@ fn dialog_response(result: Arc<Result<Url, file_chooser::Error>>) -> DialogRespo
 
     DialogResponse::Path(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FIT, Image, STRETCH, ZOOM, fit_preview};
+    use image::Rgba;
+
+    #[test]
+    fn fit_modes() {
+        // A square image on a display twice as wide as it is tall.
+        let image = Image::from_pixel(40, 40, Rgba([255, 0, 0, 255]));
+        // Resizing may round the channels of the image slightly.
+        let is_red = |pixel: &Rgba<u8>| pixel[0] > 250 && pixel[1] < 5 && pixel[2] < 5;
+
+        for fit in [ZOOM, FIT, STRETCH] {
+            let preview = fit_preview(&image, fit, [0.0, 0.0, 1.0], (100, 50));
+            assert_eq!(preview.dimensions(), (100, 50));
+            assert!(is_red(preview.get_pixel(50, 25)));
+        }
+
+        // Only a fitted image leaves room for the letterbox color at the sides.
+        let fitted = fit_preview(&image, FIT, [0.0, 0.0, 1.0], (100, 50));
+        assert_eq!(*fitted.get_pixel(0, 25), Rgba([0, 0, 255, 255]));
+        assert_eq!(*fitted.get_pixel(99, 25), Rgba([0, 0, 255, 255]));
+
+        let zoomed = fit_preview(&image, ZOOM, [0.0, 0.0, 1.0], (100, 50));
+        assert!(is_red(zoomed.get_pixel(0, 25)));
+    }
+}
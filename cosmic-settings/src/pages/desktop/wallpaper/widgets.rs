@@ -16,6 +16,7 @@ use std::sync::OnceLock;
 const COLOR_WIDTH: u16 = 70;
 const COLUMN_SPACING: u16 = 12;
 const ROW_SPACING: u16 = 16;
+const SLIDESHOW_THUMBNAIL_WIDTH: f32 = 120.0;
 
 /// A button for selecting a color or gradient.
 #[must_use]
@@ -39,6 +40,19 @@ pub fn color_button(
         .into()
 }
 
+/// A button for selecting the color around a wallpaper that is fitted to the screen.
+#[must_use]
+pub fn letterbox_color_button(color: [f32; 3], selected: bool) -> Element<'static, Message> {
+    let content = color_image(wallpaper::Color::Single(color), 32, 32, None);
+
+    button::custom_image_button(content, None)
+        .padding(0)
+        .selected(selected)
+        .class(button::ButtonClass::Image)
+        .on_press(Message::FitColor(color))
+        .into()
+}
+
 /// A sized container that's filled with a color or gradient.
 #[must_use]
 pub fn color_image<'a, M: 'a>(
@@ -168,6 +182,26 @@ pub fn wallpaper_select_options(
     flex_select_row(vec)
 }
 
+/// The first images of a slideshow folder.
+#[must_use]
+pub fn slideshow_preview(context: &super::Context, count: usize) -> Element<'static, Message> {
+    let thumbnails = context
+        .folder_images()
+        .take(count)
+        .map(|(_, handle)| {
+            cosmic::widget::image(handle.clone())
+                .width(Length::Fixed(SLIDESHOW_THUMBNAIL_WIDTH))
+                .into()
+        })
+        .collect::<Vec<_>>();
+
+    cosmic::widget::row::with_children(thumbnails)
+        .spacing(COLUMN_SPACING)
+        .apply(container)
+        .center_x(Length::Fill)
+        .into()
+}
+
 fn flex_select_row(elements: Vec<Element<Message>>) -> Element<Message> {
     cosmic::widget::flex_row(elements)
         .column_spacing(COLUMN_SPACING)
@@ -17,6 +17,8 @@ use randr::Transform;
 const UNIT_PIXELS: f32 = 12.0;
 const VERTICAL_OVERHEAD: f32 = 1.5;
 const VERTICAL_DISPLAY_OVERHEAD: f32 = 4.0;
+/// Distance within which the edges of a dragged display snap to those of its neighbor.
+const SNAP_THRESHOLD: f32 = 8.0;
 
 pub type OnPlacementFunc<Message> = Box<dyn Fn(OutputKey, i32, i32) -> Message>;
 pub type OnSelectFunc<Message> = Box<dyn Fn(segmented_button::Entity) -> Message>;
@@ -359,7 +361,7 @@ impl<'a, Message: Clone> Widget<Message, cosmic::Theme, Renderer> for Arrangemen
             core::text::Renderer::fill_text(
                 renderer,
                 core::Text {
-                    content: itoa::Buffer::new().format(id).to_string(),
+                    content: itoa::Buffer::new().format(id + 1).to_string(),
                     size: core::Pixels(24.0),
                     line_height: core::text::LineHeight::Relative(1.2),
                     font: cosmic::font::bold(),
@@ -544,26 +546,26 @@ fn update_dragged_region(
     }
 
     // Snap-align on x-axis when alignment is near.
-    if (dragged_region.x - nearest_region.x).abs() <= 8.0 {
+    if (dragged_region.x - nearest_region.x).abs() <= SNAP_THRESHOLD {
         dragged_region.x = nearest_region.x;
     }
 
     // Snap-align on x-axis when alignment is near bottom edge.
     if ((dragged_region.x + dragged_region.width) - (nearest_region.x + nearest_region.width)).abs()
-        <= 8.0
+        <= SNAP_THRESHOLD
     {
         dragged_region.x = nearest_region.x + nearest_region.width - dragged_region.width;
     }
 
     // Snap-align on y-axis when alignment is near.
-    if (dragged_region.y - nearest_region.y).abs() <= 8.0 {
+    if (dragged_region.y - nearest_region.y).abs() <= SNAP_THRESHOLD {
         dragged_region.y = nearest_region.y;
     }
 
     // Snap-align on y-axis when alignment is near bottom edge.
     if ((dragged_region.y + dragged_region.height) - (nearest_region.y + nearest_region.height))
         .abs()
-        <= 8.0
+        <= SNAP_THRESHOLD
     {
         dragged_region.y = nearest_region.y + nearest_region.height - dragged_region.height;
     }
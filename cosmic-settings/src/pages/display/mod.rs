@@ -12,6 +12,19 @@ use cosmic::widget::{
     self, column, container, dropdown, list_column, segmented_button, tab_bar, text, toggler,
 };
 use cosmic::{Apply, Element, Task, surface};
+#[cfg(feature = "wayland")]
+use cosmic::{
+    cctk::wayland_client::protocol::wl_output::WlOutput,
+    iced::{
+        platform_specific::{
+            runtime::wayland::layer_surface::{IcedOutput, SctkLayerSurfaceSettings},
+            shell::commands::layer_surface::{
+                Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface,
+            },
+        },
+        window,
+    },
+};
 use cosmic_randr_shell::{
     AdaptiveSyncAvailability, AdaptiveSyncState, List, Output, OutputKey, Transform,
 };
@@ -23,6 +36,9 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::{collections::BTreeMap, process::ExitStatus, sync::Arc};
 use tokio::sync::oneshot;
 
+/// Seconds until a display change that was not kept is reverted.
+const DIALOG_TIMEOUT: usize = 15;
+
 static DPI_SCALES: &[u32] = &[50, 75, 100, 125, 150, 175, 200, 225, 250, 275, 300];
 
 static DPI_SCALE_LABELS: Lazy<Vec<String>> =
@@ -76,16 +92,30 @@ pub enum Message {
     DialogComplete,
     /// How long until the dialog automatically cancelles, in seconds.
     DialogCountdown,
+    /// Shows the number of each display on its screen.
+    #[cfg(feature = "wayland")]
+    IdentifyDisplays,
+    /// Hides the numbers shown by `IdentifyDisplays`.
+    #[cfg(feature = "wayland")]
+    IdentifyDone,
     /// Toggles display on or off.
     DisplayToggle(bool),
     /// Configures mirroring status of a display.
     Mirroring(Mirroring),
+    /// Mirrors the active display to every other display, or stops mirroring.
+    MirrorDisplays(bool),
     /// Handle night light preferences.
     // NightLight(NightLight),
     /// Show the night light mode context drawer.
     // NightLightContext,
     /// Set the orientation of a display.
     Orientation(Transform),
+    /// A wayland output was added.
+    #[cfg(feature = "wayland")]
+    OutputAdded(String, WlOutput),
+    /// A wayland output was removed.
+    #[cfg(feature = "wayland")]
+    OutputRemoved(WlOutput),
     /// Pan the displays view
     Pan(arrangement::Pan),
     /// Status of an applied display change.
@@ -150,6 +180,12 @@ pub struct Page {
     dialog_countdown: usize,
     show_display_options: bool,
     adjusted_scale: u32,
+    /// Wayland outputs by name, for showing surfaces on them.
+    #[cfg(feature = "wayland")]
+    wl_outputs: BTreeMap<String, WlOutput>,
+    /// Surfaces showing display numbers, with the number they show.
+    #[cfg(feature = "wayland")]
+    identify_surfaces: Vec<(window::Id, usize)>,
 }
 
 impl Default for Page {
@@ -172,6 +208,10 @@ impl Default for Page {
             dialog_countdown: 0,
             show_display_options: true,
             adjusted_scale: 0,
+            #[cfg(feature = "wayland")]
+            wl_outputs: BTreeMap::new(),
+            #[cfg(feature = "wayland")]
+            identify_surfaces: Vec::new(),
         }
     }
 }
@@ -444,7 +484,7 @@ impl page::Page<crate::pages::Message> for Page {
 
     /// Opens a dialog to confirm the display settings.
     ///
-    /// This dialog has a 10 (arbitrary) second counter which will
+    /// This dialog has a [`DIALOG_TIMEOUT`] second counter which will
     /// automatically revert to the original display settings when depleted.
     ///
     /// To make a setting activate this dialog. Call the `set_dialog` method with
@@ -511,6 +551,30 @@ impl Page {
 
             Message::Display(display) => self.set_display(display),
 
+            #[cfg(feature = "wayland")]
+            Message::IdentifyDisplays => return self.identify_displays(),
+
+            #[cfg(feature = "wayland")]
+            Message::IdentifyDone => {
+                return Task::batch(
+                    self.identify_surfaces
+                        .drain(..)
+                        .map(|(id, _)| destroy_layer_surface(id)),
+                );
+            }
+
+            #[cfg(feature = "wayland")]
+            Message::OutputAdded(name, output) => {
+                self.wl_outputs.insert(name, output);
+                return Task::none();
+            }
+
+            #[cfg(feature = "wayland")]
+            Message::OutputRemoved(output) => {
+                self.wl_outputs.retain(|_, other| *other != output);
+                return Task::none();
+            }
+
             Message::ColorDepth(color_depth) => return self.set_color_depth(color_depth),
 
             Message::ColorProfile(profile) => return self.set_color_profile(profile),
@@ -537,6 +601,8 @@ impl Page {
                 } // Mirroring::ProjectToAll => (),
             },
 
+            Message::MirrorDisplays(enable) => return self.mirror_displays(enable),
+
             // Message::NightLight(night_light) => {}
             //
             // Message::NightLightContext => {
@@ -674,7 +740,7 @@ impl Page {
             return Task::none();
         }
         self.dialog = Some(revert_request);
-        self.dialog_countdown = 10;
+        self.dialog_countdown = DIALOG_TIMEOUT;
         cosmic::task::future(async {
             tokio::time::sleep(time::Duration::from_secs(1)).await;
             app::Message::from(Message::DialogCountdown)
@@ -846,6 +912,118 @@ impl Page {
         }
     }
 
+    /// The display that the other displays mirror when mirroring all displays.
+    fn mirror_source(&self) -> OutputKey {
+        self.mirror_map
+            .get(self.active_display)
+            .copied()
+            .unwrap_or(self.active_display)
+    }
+
+    /// Whether every display mirrors the same display, which takes at least two displays.
+    fn mirroring_all(&self) -> bool {
+        let source = self.mirror_source();
+
+        self.list.outputs.len() > 1
+            && self
+                .list
+                .outputs
+                .keys()
+                .filter(|&id| id != source)
+                .all(|id| self.mirror_map.get(id) == Some(&source))
+    }
+
+    /// Mirrors every display to the active display, or stops all mirroring.
+    pub fn mirror_displays(&mut self, enable: bool) -> Task<app::Message> {
+        let source = self.mirror_source();
+
+        let tasks = self
+            .list
+            .outputs
+            .iter()
+            .filter(|&(id, _)| id != source)
+            .filter_map(|(id, output)| {
+                if enable {
+                    Some(self.exec_randr(output, Randr::Mirror(source)))
+                } else {
+                    self.mirror_map
+                        .contains_key(id)
+                        .then(|| self.exec_randr(output, Randr::Toggle(true)))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Task::batch(tasks)
+    }
+
+    /// Enabled displays with the number shown for them in the display arrangement.
+    #[cfg(feature = "wayland")]
+    fn display_numbers(&self) -> impl Iterator<Item = (usize, &Output)> {
+        self.display_tabs
+            .iter()
+            .filter_map(|entity| self.display_tabs.data::<OutputKey>(entity))
+            .filter_map(|&key| self.list.outputs.get(key))
+            .filter(|output| output.enabled && output.current.is_some())
+            .enumerate()
+            .map(|(id, output)| (id + 1, output))
+    }
+
+    /// Flashes the number of each display on its screen.
+    #[cfg(feature = "wayland")]
+    fn identify_displays(&mut self) -> Task<app::Message> {
+        if !self.identify_surfaces.is_empty() {
+            return Task::none();
+        }
+
+        let mut tasks = Vec::new();
+        let mut surfaces = Vec::new();
+
+        for (number, output) in self.display_numbers() {
+            let Some(wl_output) = self.wl_outputs.get(&*output.name) else {
+                continue;
+            };
+
+            let id = window::Id::unique();
+            surfaces.push((id, number));
+            tasks.push(get_layer_surface(SctkLayerSurfaceSettings {
+                id,
+                layer: Layer::Overlay,
+                keyboard_interactivity: KeyboardInteractivity::None,
+                anchor: Anchor::empty(),
+                output: IcedOutput::Output(wl_output.clone()),
+                namespace: "display-identifier".into(),
+                size: Some((Some(160), Some(160))),
+                ..Default::default()
+            }));
+        }
+
+        self.identify_surfaces = surfaces;
+
+        tasks.push(cosmic::task::future(async {
+            tokio::time::sleep(time::Duration::from_secs(3)).await;
+            app::Message::from(Message::IdentifyDone)
+        }));
+
+        Task::batch(tasks)
+    }
+
+    /// The view of a surface created by `identify_displays`.
+    #[cfg(feature = "wayland")]
+    pub fn identify_view(&self, id: window::Id) -> Option<Element<'_, pages::Message>> {
+        let &(_, number) = self
+            .identify_surfaces
+            .iter()
+            .find(|(surface_id, _)| *surface_id == id)?;
+
+        text::title1(itoa::Buffer::new().format(number).to_owned())
+            .size(96)
+            .apply(container)
+            .center(Length::Fill)
+            .class(cosmic::theme::Container::Background)
+            .apply(Element::from)
+            .apply(Some)
+    }
+
     /// Change display orientation.
     pub fn set_orientation(&mut self, transform: Transform) -> Task<app::Message> {
         let request = Randr::Transform(transform);
@@ -906,9 +1084,17 @@ impl Page {
         if let Some(ref resolution) = self.config.resolution {
             if let Some(rates) = self.cache.modes.get(resolution) {
                 if let Some(&rate) = rates.get(option) {
+                    let request = Randr::RefreshRate(rate);
+                    let revert_request =
+                        self.config.refresh_rate.map_or(request, Randr::RefreshRate);
+
                     self.cache.refresh_rate_selected = Some(option);
                     self.config.refresh_rate = Some(rate);
-                    return self.exec_randr(output, Randr::RefreshRate(rate));
+
+                    return Task::batch(vec![
+                        self.exec_randr(output, request),
+                        self.set_dialog(revert_request, &request),
+                    ]);
                 }
             }
         }
@@ -937,9 +1123,16 @@ impl Page {
             _ => return Task::none(),
         };
 
+        let request = Randr::VariableRefreshRate(mode);
+        let revert_request = self.config.vrr.map_or(request, Randr::VariableRefreshRate);
+
         self.cache.vrr_selected = Some(option);
         self.config.vrr = Some(mode);
-        self.exec_randr(output, Randr::VariableRefreshRate(mode))
+
+        Task::batch(vec![
+            self.exec_randr(output, request),
+            self.set_dialog(revert_request, &request),
+        ])
     }
 
     /// Change the resolution of the active display.
@@ -1134,6 +1327,10 @@ pub fn display_arrangement() -> Section<crate::pages::Message> {
 
     _ = descriptions.insert(fl!("display", "arrangement"));
     let display_arrangement_desc = descriptions.insert(fl!("display", "arrangement-desc"));
+    let mirror_all = descriptions.insert(fl!("mirroring", "mirror-all"));
+    // Displays can only be identified on Wayland.
+    #[cfg(feature = "wayland")]
+    let identify = descriptions.insert(fl!("display", "identify"));
 
     Section::default()
         .title(fl!("display", "arrangement"))
@@ -1165,6 +1362,26 @@ pub fn display_arrangement() -> Section<crate::pages::Message> {
                         .apply(container)
                         .center_x(Length::Fill)
                 })
+                .push(
+                    list_column()
+                        .add(widget::settings::item(
+                            &descriptions[mirror_all],
+                            toggler(page.mirroring_all()).on_toggle(|enable| {
+                                pages::Message::Displays(Message::MirrorDisplays(enable))
+                            }),
+                        ))
+                        .apply(|options| {
+                            #[cfg(feature = "wayland")]
+                            let options = options.add(widget::settings::item_row(vec![
+                                widget::horizontal_space().into(),
+                                widget::button::standard(&descriptions[identify])
+                                    .on_press(pages::Message::Displays(Message::IdentifyDisplays))
+                                    .into(),
+                            ]));
+
+                            options
+                        }),
+                )
                 .apply(container)
                 .class(cosmic::theme::Container::List)
                 .width(Length::Fill)
@@ -4,6 +4,8 @@
 pub mod shortcuts;
 
 use std::cmp;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use cosmic::{
     Apply, Element, Task,
@@ -15,6 +17,7 @@ use cosmic::{
     widget::{self, ListColumn, button, container, icon, radio, row, settings},
 };
 use cosmic_comp_config::{KeyboardConfig, NumlockState, XkbConfig};
+use cosmic_settings_config::shortcuts::{Action, action::System as SystemAction};
 use cosmic_settings_page::{self as page, Section, section};
 use itertools::Itertools;
 use slab::Slab;
@@ -64,6 +67,10 @@ pub enum Message {
     SourceAdd(DefaultKey),
     SourceContext(SourceContext),
     SpecialCharacterSelect(Option<&'static str>),
+    ApplyRepeatKeys,
+    RepeatKeysTest(String),
+    ResetRepeatKeys,
+    SetComposeKey(usize),
     SetRepeatKeysDelay(u32),
     SetRepeatKeysRate(u32),
     SetShowExtendedInputSources(bool),
@@ -110,6 +117,11 @@ pub struct Page {
     active_layouts: Vec<DefaultKey>,
     expanded_source_popover: Option<DefaultKey>,
     show_extended_input_sources: bool,
+    repeat_keys_debounce: bool,
+    repeat_keys_test: String,
+    input_source_switch: Vec<String>,
+    compose_labels: Vec<String>,
+    compose_file: Option<PathBuf>,
 }
 
 impl Default for Page {
@@ -127,6 +139,14 @@ impl Default for Page {
             keyboard_config: KeyboardConfig::default(),
             input_source_search: String::new(),
             show_extended_input_sources: false,
+            repeat_keys_debounce: false,
+            repeat_keys_test: String::new(),
+            input_source_switch: Vec::new(),
+            compose_labels: std::iter::once("None")
+                .chain(COMPOSE_OPTIONS.iter().map(|(desc, _)| *desc))
+                .map(String::from)
+                .collect(),
+            compose_file: None,
             config,
         }
     }
@@ -251,11 +271,19 @@ fn popover_button(id: DefaultKey, expanded: bool) -> cosmic::Element<'static, Me
 fn input_source(
     id: DefaultKey,
     description: &str,
+    is_default: bool,
     expanded_source_popover: Option<DefaultKey>,
 ) -> cosmic::Element<Message> {
     let expanded = expanded_source_popover.is_some_and(|expanded_id| expanded_id == id);
 
-    settings::item(description, popover_button(id, expanded)).into()
+    let mut item = settings::item::builder(description);
+
+    // The first input source is used by default.
+    if is_default {
+        item = item.description(fl!("keyboard-sources", "default"));
+    }
+
+    item.control(popover_button(id, expanded)).into()
 }
 
 fn special_char_radio_row<'a>(
@@ -285,6 +313,7 @@ impl page::Page<crate::pages::Message> for Page {
         Some(vec![
             sections.insert(input_sources()),
             sections.insert(special_character_entry()),
+            sections.insert(keyboard_compose()),
             sections.insert(keyboard_shortcuts()),
             sections.insert(keyboard_typing_assist()),
             sections.insert(keyboard_num_lock()),
@@ -331,6 +360,12 @@ impl page::Page<crate::pages::Message> for Page {
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
         self.xkb = super::get_config(&self.config, "xkb_config");
         self.keyboard_config = super::get_config(&self.config, "keyboard_config");
+        self.input_source_switch =
+            shortcuts::action_bindings(&Action::System(SystemAction::InputSourceSwitch))
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+        self.compose_file = compose_file();
         match (
             xkb_data::keyboard_layouts(),
             xkb_data::extra_keyboard_layouts(),
@@ -520,29 +555,38 @@ impl Page {
 
             Message::SpecialCharacterSelect(id) => {
                 if let Some(Context::SpecialCharacter(special_key)) = self.context {
-                    let options = self.xkb.options.as_deref().unwrap_or_default();
-                    let prefix = special_key.prefix();
-                    let new_options = options
-                        .split(',')
-                        .filter(|x| !x.starts_with(prefix))
-                        .chain(id)
-                        .join(",");
-
-                    self.xkb.options = Some(new_options).filter(|x| !x.is_empty());
-
-                    if let Err(err) = self.config.set("xkb_config", &self.xkb) {
-                        tracing::error!(?err, "Failed to set config 'xkb_config'");
-                    }
+                    self.set_special_key(special_key, id);
                 }
             }
+            Message::SetComposeKey(position) => {
+                // The first choice disables the compose key.
+                let id = position
+                    .checked_sub(1)
+                    .and_then(|position| COMPOSE_OPTIONS.get(position))
+                    .map(|(_, id)| *id);
+
+                self.set_special_key(SpecialKey::Compose, id);
+            }
             Message::SetRepeatKeysDelay(delay) => {
-                self.xkb.repeat_delay = delay;
-                self.update_xkb_config();
+                self.xkb.repeat_delay = delay.clamp(KB_REPEAT_DELAY_MIN, KB_REPEAT_DELAY_MAX);
+                return self.debounce_repeat_keys();
             }
             Message::SetRepeatKeysRate(rate) => {
-                self.xkb.repeat_rate = rate;
+                self.xkb.repeat_rate = rate.clamp(KB_REPEAT_RATE_MIN, KB_REPEAT_RATE_MAX);
+                return self.debounce_repeat_keys();
+            }
+            Message::ApplyRepeatKeys => {
+                self.repeat_keys_debounce = false;
                 self.update_xkb_config();
             }
+            Message::ResetRepeatKeys => {
+                self.xkb.repeat_delay = KB_REPEAT_DELAY_DEFAULT;
+                self.xkb.repeat_rate = KB_REPEAT_RATE_DEFAULT;
+                self.update_xkb_config();
+            }
+            Message::RepeatKeysTest(text) => {
+                self.repeat_keys_test = text;
+            }
             Message::SetShowExtendedInputSources(value) => {
                 self.show_extended_input_sources = value;
             }
@@ -669,6 +713,56 @@ impl Page {
         cosmic::widget::container(list).padding(24).into()
     }
 
+    /// Replaces the xkb option of a special key, removing it if `id` is `None`.
+    fn set_special_key(&mut self, special_key: SpecialKey, id: Option<&'static str>) {
+        let options = self.xkb.options.as_deref().unwrap_or_default();
+        let prefix = special_key.prefix();
+        let new_options = options
+            .split(',')
+            .filter(|x| !x.starts_with(prefix))
+            .chain(id)
+            .join(",");
+
+        self.xkb.options = Some(new_options).filter(|x| !x.is_empty());
+
+        if let Err(err) = self.config.set("xkb_config", &self.xkb) {
+            tracing::error!(?err, "Failed to set config 'xkb_config'");
+        }
+    }
+
+    /// The position of the active compose key within the compose key choices.
+    fn compose_key_position(&self) -> Option<usize> {
+        let prefix = SpecialKey::Compose.prefix();
+
+        match self
+            .xkb
+            .options
+            .iter()
+            .flat_map(|x| x.split(','))
+            .find(|x| x.starts_with(prefix))
+        {
+            Some(current) => COMPOSE_OPTIONS
+                .iter()
+                .position(|(_, id)| *id == current)
+                .map(|position| position + 1),
+            None => Some(0),
+        }
+    }
+
+    /// Applies the repeat delay and rate once the slider has settled, rather than on every step.
+    fn debounce_repeat_keys(&mut self) -> Task<crate::app::Message> {
+        if self.repeat_keys_debounce {
+            return Task::none();
+        }
+
+        self.repeat_keys_debounce = true;
+
+        cosmic::task::future(async move {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            crate::pages::Message::Keyboard(Message::ApplyRepeatKeys)
+        })
+    }
+
     fn update_xkb_config(&mut self) {
         let result = update_xkb_config(
             &self.config,
@@ -697,23 +791,47 @@ impl page::AutoBind<crate::pages::Message> for Page {
 fn input_sources() -> Section<crate::pages::Message> {
     Section::default()
         .title(fl!("keyboard-sources"))
-        .view::<Page>(move |_binder, page, section| {
+        .view::<Page>(move |binder, page, section| {
             // TODO Need something more custom, with drag and drop
             let mut section = settings::section().title(&section.title);
 
-            for id in &page.active_layouts {
+            for (position, id) in page.active_layouts.iter().enumerate() {
                 if let Some((_locale, _variant, description, _source)) =
                     page.keyboard_layouts.get(*id)
                 {
-                    section =
-                        section.add(input_source(*id, description, page.expanded_source_popover));
+                    section = section.add(input_source(
+                        *id,
+                        description,
+                        position == 0,
+                        page.expanded_source_popover,
+                    ));
                 }
             }
 
+            let switch_shortcut = if page.input_source_switch.is_empty() {
+                fl!("keyboard-sources", "switch-none")
+            } else {
+                page.input_source_switch.join(", ")
+            };
+
+            // Links to the system shortcuts, where the input source switch shortcut is set.
+            let switch_preview = settings::item(
+                fl!("keyboard-sources", "switch"),
+                widget::row::with_capacity(2)
+                    .spacing(cosmic::theme::spacing().space_xxs)
+                    .align_y(Alignment::Center)
+                    .push(widget::text::body(switch_shortcut))
+                    .push_maybe(binder.page_id::<shortcuts::system::Page>().map(|entity| {
+                        button::icon(icon::from_name("go-next-symbolic"))
+                            .extra_small()
+                            .on_press(crate::pages::Message::Page(entity))
+                    })),
+            );
+
             let add_input_source = widget::button::standard(fl!("keyboard-sources", "add"))
                 .on_press(Message::ShowInputSourcesContext);
 
-            widget::column::with_capacity(2)
+            let sources = widget::column::with_capacity(2)
                 .spacing(cosmic::theme::spacing().space_xxs)
                 .push(section)
                 .push(
@@ -722,7 +840,13 @@ fn input_sources() -> Section<crate::pages::Message> {
                         .align_x(Alignment::End),
                 )
                 .apply(Element::from)
-                .map(crate::pages::Message::Keyboard)
+                .map(crate::pages::Message::Keyboard);
+
+            widget::column::with_capacity(2)
+                .spacing(cosmic::theme::spacing().space_xxs)
+                .push(sources)
+                .push(settings::section().add(switch_preview))
+                .into()
         })
 }
 
@@ -730,7 +854,6 @@ fn special_character_entry() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
     let alternate = descriptions.insert(fl!("keyboard-special-char", "alternate"));
-    let compose = descriptions.insert(fl!("keyboard-special-char", "compose"));
     let caps = descriptions.insert(fl!("keyboard-special-char", "caps"));
 
     Section::default()
@@ -745,10 +868,6 @@ fn special_character_entry() -> Section<crate::pages::Message> {
                     &descriptions[alternate],
                     Message::OpenSpecialCharacterContext(SpecialKey::AlternateCharacters),
                 ))
-                .add(crate::widget::go_next_item(
-                    &descriptions[compose],
-                    Message::OpenSpecialCharacterContext(SpecialKey::Compose),
-                ))
                 .add(crate::widget::go_next_item(
                     &descriptions[caps],
                     Message::OpenSpecialCharacterContext(SpecialKey::CapsLock),
@@ -758,6 +877,50 @@ fn special_character_entry() -> Section<crate::pages::Message> {
         })
 }
 
+fn keyboard_compose() -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
+
+    let compose = descriptions.insert(fl!("keyboard-special-char", "compose"));
+
+    Section::default()
+        .title(fl!("keyboard-compose"))
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+
+            let compose_file = match page.compose_file.as_ref() {
+                Some(path) => fl!(
+                    "keyboard-compose",
+                    "file",
+                    path = path.display().to_string()
+                ),
+                None => fl!("keyboard-compose", "default-file"),
+            };
+
+            settings::section()
+                .title(&section.title)
+                .add(
+                    settings::item::builder(&descriptions[compose])
+                        .description(compose_file)
+                        .control(widget::dropdown(
+                            &page.compose_labels,
+                            page.compose_key_position(),
+                            Message::SetComposeKey,
+                        )),
+                )
+                .apply(cosmic::Element::from)
+                .map(crate::pages::Message::Keyboard)
+        })
+}
+
+/// The compose sequence file that applications load, if it is not the locale's default.
+fn compose_file() -> Option<PathBuf> {
+    std::env::var_os("XCOMPOSEFILE")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".XCompose")))
+        .filter(|path| path.is_file())
+}
+
 fn keyboard_shortcuts() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
@@ -793,6 +956,8 @@ fn keyboard_typing_assist() -> Section<crate::pages::Message> {
     let long = descriptions.insert(fl!("long"));
     let slow = descriptions.insert(fl!("slow"));
     let fast = descriptions.insert(fl!("fast"));
+    let test = descriptions.insert(fl!("keyboard-typing-assist", "test"));
+    let reset = descriptions.insert(fl!("reset-to-default"));
 
     Section::default()
         .title(fl!("keyboard-typing-assist"))
@@ -842,6 +1007,26 @@ fn keyboard_typing_assist() -> Section<crate::pages::Message> {
                         .push(rate_slider)
                         .push(widget::text::body(&descriptions[fast]))
                 }))
+                .add(settings::flex_item(
+                    &descriptions[test],
+                    widget::text_input(
+                        fl!("keyboard-typing-assist", "test-placeholder"),
+                        &page.repeat_keys_test,
+                    )
+                    .on_input(Message::RepeatKeysTest)
+                    .apply(widget::container)
+                    .max_width(250),
+                ))
+                .add(settings::item_row(vec![
+                    widget::horizontal_space().into(),
+                    button::standard(&descriptions[reset])
+                        .on_press_maybe(
+                            (page.xkb.repeat_delay != KB_REPEAT_DELAY_DEFAULT
+                                || page.xkb.repeat_rate != KB_REPEAT_RATE_DEFAULT)
+                                .then_some(Message::ResetRepeatKeys),
+                        )
+                        .into(),
+                ]))
                 .apply(cosmic::Element::from)
                 .map(crate::pages::Message::Keyboard)
         })
@@ -0,0 +1,74 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Lists installed applications that a custom shortcut may launch.
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Application {
+    pub name: String,
+    pub icon: Option<String>,
+    /// The command to spawn, without the field codes of the desktop entry.
+    pub command: String,
+}
+
+/// Reads the desktop entries of every installed application, sorted by name.
+pub fn installed() -> Vec<Application> {
+    let locales = freedesktop_desktop_entry::get_languages_from_env();
+
+    let mut applications =
+        freedesktop_desktop_entry::Iter::new(freedesktop_desktop_entry::default_paths())
+            .entries(Some(&locales))
+            .filter(|entry| !entry.no_display() && entry.desktop_entry("X-CosmicApplet").is_none())
+            .filter_map(|entry| {
+                Some(Application {
+                    name: entry.name(&locales)?.into_owned(),
+                    icon: entry.icon().map(String::from),
+                    command: command(entry.exec()?)?,
+                })
+            })
+            .collect::<Vec<_>>();
+
+    applications.sort_by(|a, b| a.name.cmp(&b.name));
+    // The same entry may be installed in several data directories.
+    applications.dedup();
+    applications
+}
+
+/// Strips the field codes, such as `%U`, from the `Exec` key of a desktop entry.
+pub fn command(exec: &str) -> Option<String> {
+    let mut command = String::with_capacity(exec.len());
+    let mut chars = exec.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            command.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('%') => command.push('%'),
+            // Field codes expand to files, URLs, or details of the entry that a
+            // shortcut does not have.
+            Some(_) => (),
+            None => command.push('%'),
+        }
+    }
+
+    let command = command.trim();
+    (!command.is_empty()).then(|| command.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::command;
+
+    #[test]
+    fn field_codes() {
+        assert_eq!(command("firefox %u").as_deref(), Some("firefox"),);
+        assert_eq!(
+            command("sh -c 'echo 100%%' %F").as_deref(),
+            Some("sh -c 'echo 100%'")
+        );
+        assert_eq!(command("%U"), None);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The command of a custom shortcut, which is run with `sh -c`.
+//!
+//! A command is either written for the shell, or given as a list of arguments that are each
+//! single-quoted so that the shell passes them on unchanged. Commands are stored as the string
+//! given to the shell, and a string made only of single-quoted words is read back as a list.
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    /// Parsed by the shell, with its pipes, variables and globs.
+    Shell(String),
+    /// Passed to the program exactly as they are.
+    Arguments(Vec<String>),
+}
+
+impl Command {
+    /// Reads a command as it was stored by [`Command::to_shell`].
+    pub fn parse(stored: &str) -> Self {
+        arguments(stored).map_or_else(|| Self::Shell(stored.to_owned()), Self::Arguments)
+    }
+
+    /// The command as it is given to the shell.
+    pub fn to_shell(&self) -> String {
+        match self {
+            Self::Shell(command) => command.clone(),
+            Self::Arguments(arguments) => arguments
+                .iter()
+                .map(|argument| quote(argument))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// The command as it is shown in the list of shortcuts.
+    pub fn display(&self) -> String {
+        match self {
+            Self::Shell(command) => command.clone(),
+            Self::Arguments(arguments) => arguments.join(" "),
+        }
+    }
+}
+
+/// Single-quotes an argument, closing and reopening the quotes around any quote inside it.
+pub fn quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', r"'\''"))
+}
+
+/// Splits a command made only of words quoted by [`quote`].
+fn arguments(command: &str) -> Option<Vec<String>> {
+    let mut arguments = Vec::new();
+    let mut chars = command.trim().chars().peekable();
+
+    while chars.peek().is_some() {
+        let mut argument = String::new();
+
+        // A word is a run of quoted strings and escaped quotes, ending at a space.
+        loop {
+            match chars.next()? {
+                '\'' => loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        char => argument.push(char),
+                    }
+                },
+
+                '\\' if chars.next()? == '\'' => argument.push('\''),
+
+                _ => return None,
+            }
+
+            match chars.peek() {
+                None => break,
+                Some(' ') => {
+                    while chars.next_if_eq(&' ').is_some() {}
+                    break;
+                }
+                Some(_) => (),
+            }
+        }
+
+        arguments.push(argument);
+    }
+
+    (!arguments.is_empty()).then_some(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+
+    #[test]
+    fn quoted_path_with_spaces() {
+        let arguments = vec![
+            String::from("/opt/My App/bin/app"),
+            String::from("--open"),
+            String::from("it's here.txt"),
+            String::new(),
+        ];
+
+        let command = Command::Arguments(arguments.clone());
+        let stored = command.to_shell();
+        assert_eq!(
+            stored,
+            r"'/opt/My App/bin/app' '--open' 'it'\''s here.txt' ''"
+        );
+        assert_eq!(Command::parse(&stored), command);
+
+        // The shell must pass each argument on unchanged.
+        let printf = [
+            vec![String::from("printf"), String::from("%s\n")],
+            arguments,
+        ]
+        .concat();
+        let output = std::process::Command::new("sh")
+            .args(["-c", &Command::Arguments(printf).to_shell()])
+            .output()
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "/opt/My App/bin/app\n--open\nit's here.txt\n\n"
+        );
+    }
+
+    #[test]
+    fn shell_commands() {
+        for command in [
+            "firefox --new-window",
+            "'/opt/My App/bin/app' --open",
+            "notify-send 'a' | cat",
+            r"echo \'",
+        ] {
+            assert_eq!(Command::parse(command), Command::Shell(command.to_owned()));
+        }
+    }
+}
@@ -2,17 +2,21 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use cosmic::app::ContextDrawer;
+use cosmic::cosmic_theme::{Density, Spacing};
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{self, button, icon, settings, text};
 use cosmic::{Apply, Element, Task, theme};
 use cosmic_config::{ConfigGet, ConfigSet};
 use cosmic_settings_config::shortcuts::{self, Action, Binding, Shortcuts};
 use cosmic_settings_page as page;
+use serde::{Deserialize, Serialize};
 use slab::Slab;
 use slotmap::Key;
 use std::borrow::Cow;
-use std::{io, mem};
+use std::collections::BTreeMap;
+use std::ops::Range;
 use std::str::FromStr;
+use std::{io, mem};
 
 #[derive(Clone, Debug)]
 pub enum ShortcutMessage {
@@ -22,10 +26,56 @@ pub enum ShortcutMessage {
     DeleteBinding(usize),
     DeleteShortcut(usize),
     EditBinding(usize, bool),
+    EditNote(widget::text_editor::Action),
     InputBinding(usize, String),
+    ResetBinding(usize),
     ResetBindings,
+    ResetCategory,
+    ResetCategoryCancel,
+    ResetCategoryPrompt,
+    ResetToDefault(Action),
+    RunShortcut(usize),
+    SaveNote,
+    SetModifiedFilter(Filter),
     ShowShortcut(usize, String),
     SubmitBinding(usize),
+    ToggleCategory(bool),
+}
+
+/// Which shortcuts of a category are listed, by whether their bindings were changed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Filter {
+    #[default]
+    All,
+    Modified,
+    Default,
+}
+
+impl Filter {
+    pub const ALL: [Self; 3] = [Self::All, Self::Modified, Self::Default];
+
+    pub fn title(self) -> String {
+        match self {
+            Self::All => fl!("shortcut-filter", "all"),
+            Self::Modified => fl!("shortcut-filter", "modified"),
+            Self::Default => fl!("shortcut-filter", "default"),
+        }
+    }
+
+    pub fn matches(self, shortcut: &ShortcutModel) -> bool {
+        match self {
+            Self::All => true,
+            Self::Modified => shortcut.modified != 0,
+            Self::Default => shortcut.modified == 0,
+        }
+    }
+}
+
+/// Outcome of the most recent write to the shortcuts config.
+#[derive(Clone, Debug)]
+pub enum ApplyStatus {
+    Applied,
+    Failed(String),
 }
 
 #[derive(Debug)]
@@ -35,10 +85,15 @@ pub struct ShortcutBinding {
     pub input: String,
     pub is_default: bool,
     pub is_saved: bool,
+    /// The default binding that this binding replaced, which resetting it restores.
+    pub default: Option<Binding>,
+    /// Whether the input was submitted as a key combination that cannot be bound.
+    pub invalid: bool,
 }
 
 impl ShortcutBinding {
     pub fn reset(&mut self) {
+        self.invalid = false;
         self.input = if self.is_saved {
             self.binding.to_string()
         } else {
@@ -54,9 +109,49 @@ pub struct ShortcutModel {
     pub bindings: Slab<ShortcutBinding>,
     pub description: String,
     pub modified: u16,
+    pub note: Option<String>,
+    /// Whether the command of a custom shortcut is run in a terminal.
+    pub terminal: bool,
 }
 
+/// What is saved of a custom shortcut besides its bindings.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ShortcutDetails {
+    /// Free text that the user keeps with the shortcut.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Whether the command is run in the desktop's terminal.
+    #[serde(default)]
+    pub terminal: bool,
+}
+
+impl ShortcutDetails {
+    /// Whether nothing besides the bindings needs to be saved of the shortcut.
+    pub fn is_empty(&self) -> bool {
+        self.note.is_none() && !self.terminal
+    }
+}
+
+/// The name and command that the details of a custom shortcut are saved under, as shortcuts
+/// that spawn the same command under other names are listed separately.
+pub type DetailsKey = (String, String);
+
 impl ShortcutModel {
+    /// The key that the details of a custom shortcut are saved under.
+    pub fn details_key(&self) -> Option<DetailsKey> {
+        match &self.action {
+            Action::Spawn(command) => Some((self.description.clone(), command.clone())),
+            _ => None,
+        }
+    }
+
+    /// Whether any key combination is bound to this shortcut.
+    pub fn is_enabled(&self) -> bool {
+        self.bindings
+            .iter()
+            .any(|(_, shortcut)| shortcut.binding.is_set())
+    }
+
     pub fn new(defaults: &Shortcuts, shortcuts: &Shortcuts, action: Action) -> Self {
         let (bindings, modified) =
             shortcuts
@@ -70,6 +165,8 @@ impl ShortcutModel {
                         input: String::new(),
                         is_default,
                         is_saved: true,
+                        default: None,
+                        invalid: false,
                     });
 
                     (slab, if is_default { modified } else { modified + 1 })
@@ -98,6 +195,8 @@ impl ShortcutModel {
             ),
             action,
             bindings,
+            note: None,
+            terminal: false,
         }
     }
 }
@@ -106,15 +205,117 @@ impl ShortcutModel {
 pub struct Model {
     pub entity: page::Entity,
     pub add_keybindings_button_id: cosmic::widget::Id,
+    pub apply_status: Option<ApplyStatus>,
     pub defaults: Shortcuts,
     pub editing: Option<usize>,
+    /// The note of the custom shortcut shown in the drawer, as it is being edited.
+    pub note: widget::text_editor::Content,
     pub replace_dialog: Option<(usize, Binding, Action, String)>,
     pub shortcut_models: Slab<ShortcutModel>,
     pub shortcut_context: Option<usize>,
     pub shortcut_title: String,
     pub config: cosmic_config::Config,
     pub custom: bool,
+    pub glyphs: bool,
     pub actions: fn(&Shortcuts, &Shortcuts) -> Slab<ShortcutModel>,
+    /// Which shortcuts are listed, by whether they were modified.
+    pub filter: Filter,
+    /// The changed shortcuts of the category and the custom shortcuts that resetting it
+    /// removes, while the reset awaits confirmation.
+    reset_prompt: Option<(Vec<super::ChangedShortcut>, Vec<(Binding, String)>)>,
+}
+
+/// Lists of more shortcuts than this only build the rows that are in view of the page.
+const VIRTUALIZE_AFTER: usize = 50;
+/// Height assumed for the view of a page until it is first scrolled, which is taller than
+/// most displays.
+const FALLBACK_VIEW_HEIGHT: f32 = 2160.0;
+/// Rows built beyond each edge of the view, so that they are ready as it scrolls.
+const OVERSCAN: usize = 4;
+/// Most key combinations that a row of a list of shortcuts shows.
+const MAX_ROW_BINDINGS: usize = 3;
+
+/// Paddings of the shortcut lists and drawers, which are packed tighter at the compact
+/// interface density so that more shortcuts fit in view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    /// Padding of the text inputs of the drawers.
+    pub input: [u16; 2],
+    /// Horizontal padding of the text of a key combination being edited.
+    pub key_input: u16,
+    /// Padding of the rows of key combinations in the drawer.
+    pub key_row: [u16; 2],
+    /// Padding of the captions below a row of key combinations.
+    pub key_caption: [u16; 4],
+    /// Padding of the fields above the key combinations.
+    pub form: [u16; 2],
+    /// Spacing between the fields above the key combinations.
+    pub form_spacing: u16,
+    /// Spacing between the description of a shortcut and its key combinations.
+    pub item_spacing: u16,
+    /// Padding of the rows of a list of shortcuts, if it differs from that of other lists.
+    pub list_item: Option<[u16; 2]>,
+    /// Height of a row of a virtualized list that shows one key combination, including the
+    /// divider below it.
+    pub row_height: f32,
+    /// Height that each further key combination adds to a row of a virtualized list.
+    pub binding_height: f32,
+}
+
+impl Layout {
+    /// The layout of the standard and spacious interface densities.
+    pub fn comfortable(spacing: Spacing) -> Self {
+        Self {
+            input: [spacing.space_xxxs, spacing.space_xs],
+            key_input: spacing.space_xs,
+            key_row: [spacing.space_xxs, spacing.space_m],
+            key_caption: [0, spacing.space_m, spacing.space_xxs, spacing.space_m],
+            form: [spacing.space_s, spacing.space_m],
+            form_spacing: spacing.space_xs,
+            item_spacing: spacing.space_s,
+            list_item: None,
+            row_height: 56.0,
+            binding_height: 24.0,
+        }
+    }
+
+    /// The layout of the compact interface density.
+    pub fn compact(spacing: Spacing) -> Self {
+        Self {
+            input: [spacing.space_xxxs / 2, spacing.space_xxs],
+            key_input: spacing.space_xxs,
+            key_row: [spacing.space_xxxs, spacing.space_s],
+            key_caption: [0, spacing.space_s, spacing.space_xxxs, spacing.space_s],
+            form: [spacing.space_xxs, spacing.space_s],
+            form_spacing: spacing.space_xxs,
+            item_spacing: spacing.space_xxs,
+            list_item: Some([spacing.space_xxxs, spacing.space_s]),
+            row_height: 44.0,
+            binding_height: 20.0,
+        }
+    }
+
+    /// Height of a row of a virtualized list that shows the key combinations of a shortcut.
+    pub fn row_height_of(&self, shortcut: &ShortcutModel) -> f32 {
+        let bindings = shortcut
+            .bindings
+            .iter()
+            .filter(|(_, binding)| binding.binding.is_set())
+            .count()
+            .clamp(1, MAX_ROW_BINDINGS);
+
+        self.row_height + (bindings - 1) as f32 * self.binding_height
+    }
+
+    /// The layout of the interface density chosen in the appearance settings.
+    pub fn current() -> Self {
+        let spacing = theme::spacing();
+
+        match cosmic::config::interface_density() {
+            Density::Compact => Self::compact(spacing),
+            Density::Standard | Density::Spacious => Self::comfortable(spacing),
+        }
+    }
 }
 
 impl Default for Model {
@@ -122,15 +323,20 @@ impl Default for Model {
         Self {
             entity: page::Entity::null(),
             add_keybindings_button_id: widget::Id::unique(),
+            apply_status: None,
             defaults: Shortcuts::default(),
             editing: None,
+            note: widget::text_editor::Content::new(),
             replace_dialog: None,
             shortcut_models: Slab::new(),
             shortcut_context: None,
             shortcut_title: String::new(),
             config: shortcuts::context().unwrap(),
             custom: false,
+            glyphs: false,
             actions: |_, _| Slab::new(),
+            filter: Filter::All,
+            reset_prompt: None,
         }
     }
 }
@@ -146,27 +352,69 @@ impl Model {
         self
     }
 
+    /// Records the outcome of a config write, and notifies the user if it failed.
+    pub(super) fn apply_result(
+        &mut self,
+        result: Result<(), cosmic_config::Error>,
+    ) -> Task<crate::app::Message> {
+        match result {
+            Ok(()) => {
+                self.apply_status = Some(ApplyStatus::Applied);
+                Task::none()
+            }
+
+            Err(why) => {
+                tracing::error!(?why, "failed to write shortcuts config");
+                let why = why.to_string();
+                self.apply_status = Some(ApplyStatus::Failed(why.clone()));
+                cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                    "shortcut-apply-status",
+                    "failed-desc",
+                    why = why
+                )))
+            }
+        }
+    }
+
     /// Adds a new binding to the shortcuts config
-    pub(super) fn config_add(&self, action: Action, binding: Binding) {
+    pub(super) fn config_add(
+        &self,
+        action: Action,
+        binding: Binding,
+    ) -> Result<(), cosmic_config::Error> {
+        self.config_edit(|shortcuts| {
+            shortcuts.0.insert(binding, action);
+        })
+    }
+
+    /// Applies a set of changes to the shortcuts config in a single write, so that none of
+    /// them are applied if the write fails.
+    pub(super) fn config_edit(
+        &self,
+        edit: impl FnOnce(&mut Shortcuts),
+    ) -> Result<(), cosmic_config::Error> {
         let mut shortcuts = self.shortcuts_config();
-        shortcuts.0.insert(binding, action);
-        self.shortcuts_config_set(shortcuts);
+        edit(&mut shortcuts);
+        self.shortcuts_config_set(shortcuts)
     }
 
     /// Check if a binding is already set
     pub(super) fn config_contains(&self, binding: &Binding) -> Option<Action> {
+        let binding = super::canonical_binding(binding);
+
+        // Bindings of older configs may have been written in another form.
         self.shortcuts_system_config()
             .0
-            .get(binding)
-            .cloned()
-            .filter(|action| *action != Action::Disable)
+            .into_iter()
+            .find(|(existing, action)| {
+                *action != Action::Disable && super::canonical_binding(existing) == binding
+            })
+            .map(|(_, action)| action)
     }
 
     /// Removes a binding from the shortcuts config
-    pub(super) fn config_remove(&self, binding: &Binding) {
-        let mut shortcuts = self.shortcuts_config();
-        shortcuts.0.retain(|b, _| b != binding);
-        self.shortcuts_config_set(shortcuts);
+    pub(super) fn config_remove(&self, binding: &Binding) -> Result<(), cosmic_config::Error> {
+        self.config_edit(|shortcuts| shortcuts.0.retain(|b, _| b != binding))
     }
 
     pub(super) fn context_drawer(
@@ -178,7 +426,9 @@ impl Model {
                 context_drawer(
                     &self.shortcut_title,
                     &self.shortcut_models,
+                    self.apply_status.as_ref(),
                     self.editing,
+                    self.custom.then_some(&self.note),
                     self.add_keybindings_button_id.clone(),
                     *id,
                     self.custom,
@@ -190,7 +440,19 @@ impl Model {
     }
 
     pub(super) fn dialog(&self) -> Option<Element<'_, ShortcutMessage>> {
-        if let Some(&(id, _, _, ref action)) = self.replace_dialog.as_ref() {
+        if let Some((changed, displaced)) = self.reset_prompt.as_ref() {
+            return Some(super::reset_dialog(
+                fl!("reset-section-dialog"),
+                fl!("reset-section-dialog", "desc", count = changed.len()),
+                fl!("reset-section-dialog", "reset"),
+                changed,
+                displaced,
+                ShortcutMessage::ResetCategory,
+                ShortcutMessage::ResetCategoryCancel,
+            ));
+        }
+
+        if let Some(&(id, ref new_binding, _, ref action)) = self.replace_dialog.as_ref() {
             if let Some(short_id) = self.shortcut_context {
                 if let Some(model) = self.shortcut_models.get(short_id) {
                     if let Some(shortcut) = model.bindings.get(id) {
@@ -214,6 +476,7 @@ impl Model {
                                     .unwrap_or(action)
                                     .to_owned()
                             ))
+                            .control(super::binding_emphasis(new_binding))
                             .primary_action(primary_action)
                             .secondary_action(secondary_action);
 
@@ -238,6 +501,22 @@ impl Model {
         }
 
         self.shortcut_models = (self.actions)(&self.defaults, &shortcuts);
+        restore_replaced_defaults(
+            &mut self.shortcut_models,
+            &self.defaults,
+            &self.replaced_config(),
+        );
+
+        if self.custom {
+            let details = self.details_config();
+            for (_, model) in &mut self.shortcut_models {
+                let saved = model.details_key().and_then(|key| details.get(&key));
+                model.note = saved.and_then(|saved| saved.note.clone());
+                // Commands only look like they run in a terminal unless they were saved so.
+                model.terminal &= saved.is_some_and(|saved| saved.terminal);
+            }
+        }
+
         self.shortcut_context = None;
         self.editing = None;
     }
@@ -260,10 +539,23 @@ impl Model {
     }
 
     pub(super) fn on_clear(&mut self) {
+        self.apply_status = None;
         self.shortcut_models.clear();
         self.shortcut_models.shrink_to_fit();
     }
 
+    /// The message that asks to reset every shortcut of the category to its defaults, while any
+    /// of them is modified.
+    pub(super) fn reset_category(
+        &self,
+        apply: fn(ShortcutMessage) -> crate::pages::Message,
+    ) -> Option<crate::pages::Message> {
+        self.shortcut_models
+            .iter()
+            .any(|(_, model)| model.modified != 0)
+            .then(|| apply(ShortcutMessage::ResetCategoryPrompt))
+    }
+
     /// Gets the custom configuration for keyboard shortcuts.
     pub(super) fn shortcuts_config(&self) -> Shortcuts {
         match self.config.get::<Shortcuts>("custom") {
@@ -290,10 +582,98 @@ impl Model {
     }
 
     /// Writes a new configuration to the keyboard shortcuts config file.
-    pub(super) fn shortcuts_config_set(&self, shortcuts: Shortcuts) {
-        if let Err(why) = self.config.set("custom", shortcuts) {
-            tracing::error!(?why, "failed to write shortcuts config");
+    pub(super) fn shortcuts_config_set(
+        &self,
+        shortcuts: Shortcuts,
+    ) -> Result<(), cosmic_config::Error> {
+        self.config.set("custom", shortcuts)
+    }
+
+    /// Gets the bindings that were disabled with their category, and the actions that they
+    /// were bound to.
+    pub(super) fn disabled_config(&self) -> Shortcuts {
+        self.config
+            .get::<Shortcuts>("category_disabled")
+            .unwrap_or_default()
+    }
+
+    /// Writes the bindings that were disabled with their category to the config.
+    pub(super) fn disabled_config_set(
+        &self,
+        disabled: Shortcuts,
+    ) -> Result<(), cosmic_config::Error> {
+        self.config.set("category_disabled", disabled)
+    }
+
+    /// Gets the bindings that replaced defaults, paired with the defaults that they replaced.
+    pub(super) fn replaced_config(&self) -> Vec<(Binding, Binding)> {
+        self.config
+            .get::<Vec<(Binding, Binding)>>("replaced_defaults")
+            .unwrap_or_default()
+    }
+
+    /// Writes the bindings that replaced defaults to the config.
+    pub(super) fn replaced_config_set(
+        &self,
+        replaced: Vec<(Binding, Binding)>,
+    ) -> Result<(), cosmic_config::Error> {
+        self.config.set("replaced_defaults", replaced)
+    }
+
+    /// Records the default that a binding replaced, in place of the record of the binding that
+    /// it was edited from. A binding that replaced no default only has its record removed.
+    fn record_replaced(
+        &self,
+        prev: &Binding,
+        replacement: Option<(Binding, Binding)>,
+    ) -> Result<(), cosmic_config::Error> {
+        let mut replaced = self.replaced_config();
+        let len = replaced.len();
+        replaced.retain(|(binding, _)| binding != prev);
+
+        match replacement {
+            Some(replacement) => replaced.push(replacement),
+            None if replaced.len() == len => return Ok(()),
+            None => (),
         }
+
+        self.replaced_config_set(replaced)
+    }
+
+    /// Gets the details of custom shortcuts, keyed by their name and command.
+    pub(super) fn details_config(&self) -> BTreeMap<DetailsKey, ShortcutDetails> {
+        self.config
+            .get::<BTreeMap<DetailsKey, ShortcutDetails>>("custom_details")
+            .unwrap_or_default()
+    }
+
+    /// Writes the details of custom shortcuts to the config.
+    pub(super) fn details_config_set(
+        &self,
+        details: BTreeMap<DetailsKey, ShortcutDetails>,
+    ) -> Result<(), cosmic_config::Error> {
+        self.config.set("custom_details", details)
+    }
+
+    /// Saves the details of a custom shortcut, removing them once nothing is left to save.
+    pub(super) fn details_save(
+        &self,
+        key: DetailsKey,
+        new_details: ShortcutDetails,
+    ) -> Result<(), cosmic_config::Error> {
+        let mut details = self.details_config();
+
+        if new_details.is_empty() {
+            if details.remove(&key).is_none() {
+                return Ok(());
+            }
+        } else if details.get(&key) == Some(&new_details) {
+            return Ok(());
+        } else {
+            details.insert(key, new_details);
+        }
+
+        self.details_config_set(details)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -324,6 +704,8 @@ impl Model {
                             input: String::new(),
                             is_default: false,
                             is_saved: false,
+                            default: None,
+                            invalid: false,
                         }));
 
                         return widget::text_input::focus(id);
@@ -334,8 +716,7 @@ impl Model {
             ShortcutMessage::ApplyReplace => {
                 if let Some((id, new_binding, ..)) = self.replace_dialog.take() {
                     if let Some(short_id) = self.shortcut_context {
-                        // Remove conflicting bindings that are saved on disk.
-                        self.config_remove(&new_binding);
+                        let mut edited = None;
 
                         // Clear any binding that matches this in the current model
                         for (_, model) in &mut self.shortcut_models {
@@ -362,13 +743,33 @@ impl Model {
                                     self.editing = None;
                                 }
 
-                                let action = model.action.clone();
-                                self.config_remove(&prev_binding);
-                                self.config_add(action, new_binding);
+                                let replacement = shortcut
+                                    .default
+                                    .clone()
+                                    .map(|default| (new_binding.clone(), default));
+
+                                edited = Some((prev_binding, model.action.clone(), replacement));
                             }
                         }
 
+                        // Remove conflicting bindings that are saved on disk, and save the
+                        // edited binding in the same write.
+                        let mut result = self.config_edit(|shortcuts| {
+                            shortcuts.0.retain(|binding, _| *binding != new_binding);
+
+                            if let Some((prev_binding, action, _)) = &edited {
+                                shortcuts.0.retain(|binding, _| binding != prev_binding);
+                                shortcuts.0.insert(new_binding.clone(), action.clone());
+                            }
+                        });
+
+                        if let Some((prev_binding, _, replacement)) = edited {
+                            result = result
+                                .and_then(|()| self.record_replaced(&prev_binding, replacement));
+                        }
+
                         self.on_enter();
+                        return self.apply_result(result);
                     }
                 }
             }
@@ -390,20 +791,38 @@ impl Model {
                 if let Some(short_id) = self.shortcut_context {
                     if let Some(model) = self.shortcut_models.get_mut(short_id) {
                         let shortcut = model.bindings.remove(id);
-                        if shortcut.is_default {
-                            self.config_add(Action::Disable, shortcut.binding.clone());
+                        let result = if shortcut.is_default {
+                            self.config_add(Action::Disable, shortcut.binding.clone())
                         } else {
-                            self.config_remove(&shortcut.binding);
-                        }
+                            self.config_remove(&shortcut.binding)
+                                .and_then(|()| self.record_replaced(&shortcut.binding, None))
+                        };
+
+                        return self.apply_result(result);
                     }
                 }
             }
 
             ShortcutMessage::DeleteShortcut(id) => {
                 let model = self.shortcut_models.remove(id);
-                for (_, shortcut) in model.bindings {
-                    self.config_remove(&shortcut.binding);
+                let mut result = self.config_edit(|shortcuts| {
+                    shortcuts.0.retain(|binding, _| {
+                        !model
+                            .bindings
+                            .iter()
+                            .any(|(_, shortcut)| shortcut.binding == *binding)
+                    });
+                });
+
+                // The note and terminal flag of a custom shortcut go with it.
+                if let Some(key) = model.details_key() {
+                    let mut details = self.details_config();
+                    if details.remove(&key).is_some() {
+                        result = result.and_then(|()| self.details_config_set(details));
+                    }
                 }
+
+                return self.apply_result(result);
             }
 
             ShortcutMessage::EditBinding(id, enable) => {
@@ -411,9 +830,17 @@ impl Model {
                     if let Some(model) = self.shortcut_models.get_mut(short_id) {
                         if let Some(shortcut) = model.bindings.get_mut(id) {
                             if enable {
+                                // Refocusing an input being edited must not reset its caret.
+                                if self.editing == Some(id) {
+                                    return Task::none();
+                                }
+
                                 self.editing = Some(id);
                                 shortcut.input = shortcut.binding.to_string();
-                                return widget::text_input::select_all(shortcut.id.clone());
+                                return super::focus_key_input(
+                                    shortcut.id.clone(),
+                                    super::FocusBy::Pointer,
+                                );
                             } else if self.editing == Some(id) {
                                 self.editing = None;
                             }
@@ -422,11 +849,47 @@ impl Model {
                 }
             }
 
+            ShortcutMessage::EditNote(action) => {
+                self.note.perform(action);
+            }
+
             ShortcutMessage::InputBinding(id, text) => {
                 if let Some(short_id) = self.shortcut_context {
                     if let Some(model) = self.shortcut_models.get_mut(short_id) {
                         if let Some(shortcut) = model.bindings.get_mut(id) {
                             shortcut.input = text;
+                            shortcut.invalid = false;
+                            self.editing = Some(id);
+                        }
+                    }
+                }
+            }
+
+            // Removes a binding that replaced a default, restoring that default.
+            ShortcutMessage::ResetBinding(id) => {
+                if let Some(short_id) = self.shortcut_context {
+                    let shortcut = self
+                        .shortcut_models
+                        .get(short_id)
+                        .and_then(|model| model.bindings.get(id));
+
+                    if let Some(shortcut) = shortcut {
+                        if let Some(default) = shortcut.default.as_ref() {
+                            // Defaults are restored by removing what overrides them.
+                            let result = self
+                                .config_edit(|shortcuts| {
+                                    shortcuts.0.retain(|binding, _| {
+                                        binding != &shortcut.binding && binding != default
+                                    });
+                                })
+                                .and_then(|()| self.record_replaced(&shortcut.binding, None));
+
+                            if self.editing == Some(id) {
+                                self.editing = None;
+                            }
+
+                            self.on_enter();
+                            return self.apply_result(result);
                         }
                     }
                 }
@@ -435,38 +898,166 @@ impl Model {
             // Removes all bindings from the active shortcut context, and reloads the shortcuts model.
             ShortcutMessage::ResetBindings => {
                 if let Some(short_id) = self.shortcut_context {
+                    let mut result = Ok(());
+
                     if let Some(model) = self.shortcut_models.get(short_id) {
-                        for (_, shortcut) in &model.bindings {
-                            self.config_remove(&shortcut.binding);
-                        }
+                        let defaults = self.config.get::<Shortcuts>("defaults").unwrap_or_default();
+
+                        result = self.config_edit(|shortcuts| {
+                            shortcuts.0.retain(|binding, _| {
+                                let bound = model
+                                    .bindings
+                                    .iter()
+                                    .any(|(_, shortcut)| shortcut.binding == *binding);
+
+                                !bound && defaults.0.get(binding) != Some(&model.action)
+                            });
+                        });
+                    }
 
-                        if let Ok(defaults) = self.config.get::<Shortcuts>("defaults") {
-                            for (binding, action) in defaults.0 {
-                                if action == model.action {
-                                    self.config_remove(&binding);
-                                }
-                            }
+                    self.on_enter();
+                    return self.apply_result(result);
+                }
+            }
+
+            ShortcutMessage::ResetCategory => {
+                self.reset_prompt = None;
+                let mut shortcuts = self.shortcuts_config();
+                for (_, model) in &self.shortcut_models {
+                    reset_to_default(&mut shortcuts, &self.defaults, &model.action);
+                }
+                let result = self.shortcuts_config_set(shortcuts);
+
+                self.on_enter();
+                return self.apply_result(result);
+            }
+
+            ShortcutMessage::ResetCategoryCancel => {
+                self.reset_prompt = None;
+            }
+
+            // Lists what resetting the category changes and removes before it is confirmed.
+            ShortcutMessage::ResetCategoryPrompt => {
+                let custom = self.shortcuts_config();
+
+                let changed = self
+                    .shortcut_models
+                    .iter()
+                    .filter(|(_, model)| model.modified != 0)
+                    .map(|(_, model)| super::ChangedShortcut {
+                        action: model.action.clone(),
+                        description: model.description.clone(),
+                        current: model
+                            .bindings
+                            .iter()
+                            .map(|(_, shortcut)| shortcut.binding.clone())
+                            .collect(),
+                        default: self.defaults.shortcuts(&model.action).cloned().collect(),
+                    })
+                    .collect::<Vec<_>>();
+
+                let displaced = changed
+                    .iter()
+                    .flat_map(|changed| {
+                        displaced_by_reset(&custom, &self.defaults, &changed.action)
+                    })
+                    .map(|(binding, action)| {
+                        let name = super::localize_custom_action(&action, &binding);
+                        (binding, name)
+                    })
+                    .collect();
+
+                if !changed.is_empty() {
+                    self.reset_prompt = Some((changed, displaced));
+                }
+            }
+
+            ShortcutMessage::ResetToDefault(action) => {
+                let mut shortcuts = self.shortcuts_config();
+                reset_to_default(&mut shortcuts, &self.defaults, &action);
+                let result = self.shortcuts_config_set(shortcuts);
+
+                self.on_enter();
+                return self.apply_result(result);
+            }
+
+            // Runs the command of a custom shortcut from its run button.
+            ShortcutMessage::RunShortcut(id) => {
+                let Some(Action::Spawn(command)) = self
+                    .shortcut_models
+                    .get(id)
+                    .map(|model| model.action.clone())
+                else {
+                    return Task::none();
+                };
+
+                return cosmic::task::future(async move {
+                    match super::spawn(command).await {
+                        Ok(()) => crate::app::Message::None,
+                        Err(why) => {
+                            tracing::error!(why, "failed to run custom shortcut command");
+                            crate::app::Message::ShowToast(fl!(
+                                "custom-shortcuts",
+                                "run-failed",
+                                why = why
+                            ))
                         }
                     }
+                });
+            }
 
-                    self.on_enter();
+            ShortcutMessage::SaveNote => {
+                let Some(key) = self
+                    .shortcut_context
+                    .and_then(|id| self.shortcut_models.get(id))
+                    .and_then(ShortcutModel::details_key)
+                else {
+                    return Task::none();
+                };
+
+                let note = self.note.text();
+                let note = Some(note.trim()).filter(|note| !note.is_empty());
+
+                let mut details = self.details_config().remove(&key).unwrap_or_default();
+                details.note = note.map(str::to_owned);
+                let result = self.details_save(key, details);
+
+                if result.is_ok() {
+                    if let Some(model) = self
+                        .shortcut_context
+                        .and_then(|id| self.shortcut_models.get_mut(id))
+                    {
+                        model.note = note.map(str::to_owned);
+                    }
                 }
+
+                return self.apply_result(result);
+            }
+
+            ShortcutMessage::SetModifiedFilter(filter) => {
+                self.filter = filter;
             }
 
             ShortcutMessage::ShowShortcut(id, description) => {
                 self.shortcut_context = Some(id);
                 self.shortcut_title = description;
+                self.note = widget::text_editor::Content::with_text(
+                    self.shortcut_models
+                        .get(id)
+                        .and_then(|model| model.note.as_deref())
+                        .unwrap_or_default(),
+                );
                 self.replace_dialog = None;
+                self.apply_status = None;
 
                 let mut tasks = vec![cosmic::task::message(
                     crate::app::Message::OpenContextDrawer(self.entity),
                 )];
 
-                if let Some(model) = self.shortcut_models.get(0) {
-                    if let Some(shortcut) = model.bindings.get(0) {
-                        self.editing = Some(0);
-                        tasks.push(widget::text_input::focus(shortcut.id.clone()));
-                        tasks.push(widget::text_input::select_all(shortcut.id.clone()));
+                if let Some(model) = self.shortcut_models.get(id) {
+                    if let Some((bind_id, shortcut)) = model.bindings.iter().next() {
+                        self.editing = Some(bind_id);
+                        tasks.push(super::select_key_input(shortcut.id.clone()));
                     }
                 }
 
@@ -474,19 +1065,219 @@ impl Model {
             }
 
             ShortcutMessage::SubmitBinding(id) => return self.submit_binding(id),
+
+            // Disables every binding in the category, or restores what they were bound to.
+            ShortcutMessage::ToggleCategory(enable) => {
+                let mut shortcuts = self.shortcuts_config();
+                let mut disabled = self.disabled_config();
+
+                if enable {
+                    let actions = self
+                        .shortcut_models
+                        .iter()
+                        .map(|(_, model)| model.action.clone())
+                        .collect::<Vec<_>>();
+
+                    enable_bindings(&mut shortcuts, &mut disabled, &self.defaults, &actions);
+                } else {
+                    let bound = self.shortcut_models.iter().flat_map(|(_, model)| {
+                        model
+                            .bindings
+                            .iter()
+                            .filter(|(_, shortcut)| shortcut.binding.is_set())
+                            .map(|(_, shortcut)| (shortcut.binding.clone(), model.action.clone()))
+                    });
+
+                    disable_bindings(&mut shortcuts, &mut disabled, &self.defaults, bound);
+                }
+
+                let result = self
+                    .shortcuts_config_set(shortcuts)
+                    .and_then(|()| self.disabled_config_set(disabled));
+
+                self.on_enter();
+                return self.apply_result(result);
+            }
         }
 
         Task::none()
     }
 
-    pub(super) fn view(&self) -> Element<ShortcutMessage> {
-        self.shortcut_models
+    /// Displays the shortcuts of a category beneath a toggle for enabling all of them.
+    pub(super) fn category_view(
+        &self,
+        binder: &page::Binder<crate::pages::Message>,
+        section: &page::Section<crate::pages::Message>,
+    ) -> Element<ShortcutMessage> {
+        let enabled = self
+            .shortcut_models
             .iter()
-            .map(|(id, shortcut)| shortcut_item(self.custom, id, shortcut))
-            .fold(widget::list_column(), widget::ListColumn::add)
+            .filter(|(_, model)| model.is_enabled())
+            .count();
+
+        let mut toggle = settings::item::builder(fl!("shortcut-category", "enable"));
+
+        // Some of the shortcuts in the category are enabled, and others disabled.
+        if enabled != 0 && enabled != self.shortcut_models.len() {
+            toggle = toggle.description(fl!("shortcut-category", "mixed"));
+        }
+
+        let toggle = toggle.toggler(enabled != 0, ShortcutMessage::ToggleCategory);
+
+        let filters = Filter::ALL.into_iter().fold(
+            widget::row::with_capacity(3).spacing(8),
+            |row, filter| {
+                let chip = if filter == self.filter {
+                    button::suggested(filter.title())
+                } else {
+                    button::standard(filter.title())
+                };
+
+                row.push(chip.on_press(ShortcutMessage::SetModifiedFilter(filter)))
+            },
+        );
+
+        widget::column::with_capacity(3)
+            .spacing(theme::spacing().space_m)
+            .push(widget::list_column().add(toggle))
+            .push(filters)
+            .push(self.view(binder, section))
             .into()
     }
 
+    /// Lists the shortcuts that pass the filter, emphasizing those matched by the search that
+    /// found the section.
+    pub(super) fn view(
+        &self,
+        binder: &page::Binder<crate::pages::Message>,
+        section: &page::Section<crate::pages::Message>,
+    ) -> Element<ShortcutMessage> {
+        let layout = Layout::current();
+        let emphasized = |shortcut: &ShortcutModel| match &shortcut.action {
+            Action::Spawn(command) if section.search_emphasizes(command) => true,
+            action => {
+                section.search_emphasizes(&shortcut.description)
+                    || super::action_keywords(action)
+                        .is_some_and(|keywords| section.search_emphasizes(&keywords))
+            }
+        };
+
+        let shortcuts = self
+            .shortcut_models
+            .iter()
+            .filter(|(_, shortcut)| self.filter.matches(shortcut))
+            .collect::<Vec<_>>();
+
+        let len = shortcuts.len();
+
+        if len == 0 && !self.shortcut_models.is_empty() {
+            return widget::list_column()
+                .add(settings::item_row(vec![
+                    text::body(fl!("shortcut-filter", "none")).into(),
+                ]))
+                .into();
+        }
+
+        if len <= VIRTUALIZE_AFTER {
+            return shortcuts
+                .into_iter()
+                .map(|(id, shortcut)| {
+                    let emphasized = emphasized(shortcut);
+                    shortcut_item(
+                        &layout,
+                        self.custom,
+                        self.glyphs,
+                        id,
+                        shortcut,
+                        MAX_ROW_BINDINGS,
+                        emphasized,
+                    )
+                })
+                .fold(
+                    match layout.list_item {
+                        Some(padding) => widget::list_column().list_item_padding(padding),
+                        None => widget::list_column(),
+                    },
+                    widget::ListColumn::add,
+                )
+                .into();
+        }
+
+        // Rows are given fixed heights, so that the rows in view are found from the scroll
+        // offset of the page, and the rows out of view are stood in for by spaces of their
+        // height.
+        let heights = shortcuts
+            .iter()
+            .map(|(_, shortcut)| layout.row_height_of(shortcut))
+            .collect::<Vec<_>>();
+        let view = list_view(crate::pages::PageViewport::of(binder), heights.iter().sum());
+        let range = visible_range(&heights, view);
+        let above = heights[..range.start].iter().sum::<f32>();
+        let below = heights[range.end..].iter().sum::<f32>();
+
+        let rows = shortcuts
+            .into_iter()
+            .zip(heights)
+            .skip(range.start)
+            .take(range.len())
+            .map(|((id, shortcut), height)| {
+                widget::column::with_capacity(2)
+                    .push(
+                        shortcut_item(
+                            &layout,
+                            self.custom,
+                            self.glyphs,
+                            id,
+                            shortcut,
+                            MAX_ROW_BINDINGS,
+                            emphasized(shortcut),
+                        )
+                        .apply(widget::container)
+                        .height(Length::Fixed(height - 1.0))
+                        .clip(true),
+                    )
+                    .push(widget::divider::horizontal::default())
+                    .into()
+            });
+
+        widget::column::with_capacity(3)
+            .push(widget::vertical_space().height(Length::Fixed(above)))
+            .push(widget::column::with_children(rows.collect::<Vec<_>>()))
+            .push(widget::vertical_space().height(Length::Fixed(below)))
+            .apply(widget::container)
+            .class(theme::Container::List)
+            .into()
+    }
+
+    /// Moves the focus from the binding being edited in the context drawer to the one
+    /// above or below it.
+    pub fn focus_adjacent_binding(&mut self, next: bool) -> Task<crate::app::Message> {
+        let (Some(current), Some(model)) = (
+            self.editing,
+            self.shortcut_context
+                .and_then(|id| self.shortcut_models.get_mut(id)),
+        ) else {
+            return Task::none();
+        };
+
+        let ids = model.bindings.iter().map(|(id, _)| id);
+
+        let adjacent = if next {
+            ids.skip_while(|id| *id != current).nth(1)
+        } else {
+            ids.take_while(|id| *id != current).last()
+        };
+
+        let Some((id, shortcut)) = adjacent.and_then(|id| Some((id, model.bindings.get_mut(id)?)))
+        else {
+            return Task::none();
+        };
+
+        self.editing = Some(id);
+        shortcut.input = shortcut.binding.to_string();
+        super::focus_key_input(shortcut.id.clone(), super::FocusBy::Keyboard)
+    }
+
     fn submit_binding(&mut self, id: usize) -> Task<crate::app::Message> {
         if let Some(short_id) = self.shortcut_context {
             let mut apply_binding = None;
@@ -494,7 +1285,9 @@ impl Model {
             // Check for conflicts with the new binding.
             if let Some(model) = self.shortcut_models.get_mut(short_id) {
                 if let Some(shortcut) = model.bindings.get_mut(id) {
-                    if shortcut.input.is_empty() {
+                    // Keep an input that cannot be bound, so that it is marked until corrected.
+                    shortcut.invalid = super::is_invalid_input(&shortcut.input);
+                    if shortcut.input.is_empty() || shortcut.invalid {
                         return Task::none();
                     }
 
@@ -535,7 +1328,13 @@ impl Model {
                 if let Some(model) = self.shortcut_models.get_mut(short_id) {
                     if let Some(shortcut) = model.bindings.get_mut(id) {
                         let prev_binding = mem::replace(&mut shortcut.binding, new_binding.clone());
-                        
+                        let replaces_default = mem::take(&mut shortcut.is_default);
+
+                        // The default that the binding replaced is kept for resetting it.
+                        if replaces_default {
+                            shortcut.default = Some(prev_binding.clone());
+                        }
+
                         shortcut.is_saved = true;
                         shortcut.input.clear();
 
@@ -544,16 +1343,30 @@ impl Model {
                         }
 
                         let action = model.action.clone();
-                        
-                        if shortcut.is_default {
-                            self.config_add(Action::Disable, prev_binding);
-                        } else {
-                            self.config_remove(&prev_binding);
-                        }
-                        self.config_add(action, new_binding);
-                        return cosmic::widget::text_input::focus(
-                            self.add_keybindings_button_id.clone(),
-                        );
+                        let replacement = shortcut
+                            .default
+                            .clone()
+                            .map(|default| (new_binding.clone(), default));
+
+                        // The default that the binding replaced is recorded for the next load.
+                        let result = self
+                            .config_edit(|shortcuts| {
+                                if replaces_default {
+                                    shortcuts.0.insert(prev_binding.clone(), Action::Disable);
+                                } else {
+                                    shortcuts.0.remove(&prev_binding);
+                                }
+
+                                shortcuts.0.insert(new_binding, action);
+                            })
+                            .and_then(|()| self.record_replaced(&prev_binding, replacement));
+
+                        return Task::batch(vec![
+                            self.apply_result(result),
+                            cosmic::widget::text_input::focus(
+                                self.add_keybindings_button_id.clone(),
+                            ),
+                        ]);
                     }
                 }
             }
@@ -566,7 +1379,9 @@ impl Model {
 fn context_drawer<'a>(
     title: &'a str,
     shortcuts: &'a Slab<ShortcutModel>,
+    apply_status: Option<&'a ApplyStatus>,
     editing: Option<usize>,
+    note: Option<&'a widget::text_editor::Content>,
     add_keybindings_id: widget::Id,
     id: usize,
     show_action: bool,
@@ -579,19 +1394,57 @@ fn context_drawer<'a>(
     } = theme::spacing();
 
     let model = &shortcuts[id];
+    let layout = Layout::current();
 
     let action = show_action.then(|| {
-        let description = if let Action::Spawn(task) = &model.action {
-            Cow::Borrowed(task.as_str())
-        } else {
-            Cow::Owned(super::localize_action(&model.action))
+        let description = match &model.action {
+            Action::Spawn(task) if model.terminal => {
+                Cow::Owned(super::terminal::unwrap(task).unwrap_or_else(|| task.clone()))
+            }
+            Action::Spawn(task) => Cow::Borrowed(task.as_str()),
+            action => Cow::Owned(super::localize_action(action)),
         };
 
-        text::body(description)
+        widget::column::with_capacity(2)
+            .spacing(space_xxs)
+            .push(text::body(description))
+            .push_maybe(
+                model
+                    .terminal
+                    .then(|| text::caption(fl!("custom-shortcuts", "runs-in-terminal"))),
+            )
+    });
+
+    // The note of a custom shortcut is edited here, and saved once its button is pressed.
+    let note = note.map(|note| {
+        let unchanged = note.text().trim() == model.note.as_deref().unwrap_or_default();
+
+        widget::column::with_capacity(3)
+            .spacing(space_xxs)
+            .push(text::body(fl!("custom-shortcuts", "note")))
+            .push(
+                widget::text_editor(note)
+                    .placeholder(fl!("custom-shortcuts", "note-placeholder"))
+                    .height(96)
+                    .padding(layout.input)
+                    .on_action(ShortcutMessage::EditNote),
+            )
+            .push(
+                widget::button::standard(fl!("custom-shortcuts", "save-note"))
+                    .on_press_maybe((!unchanged).then_some(ShortcutMessage::SaveNote))
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Alignment::End),
+            )
     });
 
+    let list = match layout.list_item {
+        Some(padding) => widget::list_column().list_item_padding(padding),
+        None => widget::list_column(),
+    };
+
     let bindings = model.bindings.iter().enumerate().fold(
-        widget::list_column().spacing(space_xxs),
+        list.spacing(space_xxs),
         |section, (_, (bind_id, shortcut))| {
             let editing = editing == Some(bind_id);
             let text: Cow<'_, str> = if !editing && shortcut.binding.is_set() {
@@ -607,13 +1460,28 @@ fn context_drawer<'a>(
             .on_input(move |text| ShortcutMessage::InputBinding(bind_id, text))
             .on_unfocus(ShortcutMessage::SubmitBinding(bind_id))
             .on_submit(move |_| ShortcutMessage::SubmitBinding(bind_id))
-            .padding([0, space_xs])
-            .id(shortcut.id.clone())
-            .into();
+            .padding([0, layout.key_input])
+            .id(shortcut.id.clone());
+
+            let input = super::validated_input(input, shortcut.invalid);
 
-            let mut children = Vec::with_capacity(2);
+            let mut children = Vec::with_capacity(3);
             children.push(input);
 
+            if let Some(default) = shortcut.default.as_ref().filter(|_| shortcut.is_saved) {
+                let reset_button = widget::button::icon(icon::from_name("edit-undo-symbolic"))
+                    .on_press(ShortcutMessage::ResetBinding(bind_id));
+
+                children.push(
+                    widget::tooltip(
+                        reset_button,
+                        text::body(fl!("reset-binding", binding = default.to_string())),
+                        widget::tooltip::Position::Top,
+                    )
+                    .into(),
+                );
+            }
+
             if shortcut.is_saved {
                 let delete_button = widget::button::icon(icon::from_name("edit-delete-symbolic"))
                     .on_press(ShortcutMessage::DeleteBinding(bind_id))
@@ -621,7 +1489,14 @@ fn context_drawer<'a>(
                 children.push(delete_button);
             }
 
-            section.add(settings::item_row(children).align_y(Alignment::Center))
+            let reserved = super::reserved_warning(&shortcut.input)
+                .or_else(|| super::modifiers_only_warning(&shortcut.input));
+
+            section.add(
+                widget::column::with_capacity(2)
+                    .push(settings::item_row(children).align_y(Alignment::Center))
+                    .push_maybe(reserved),
+            )
         },
     );
 
@@ -649,29 +1524,234 @@ fn context_drawer<'a>(
         .width(Length::Fill)
         .align_x(Alignment::End);
 
-    widget::column::with_capacity(if show_action { 4 } else { 3 })
+    widget::column::with_capacity(if show_action { 6 } else { 4 })
         .push(widget::text::heading(title))
         .spacing(space_l)
         .push_maybe(action)
+        .push_maybe(note)
         .push(bindings)
         .push(button_container)
+        .push_maybe(apply_status.map(apply_status_view))
+        .into()
+}
+
+/// A subtle caption reporting whether the last change was written to the config.
+pub(super) fn apply_status_view<'a, Message: 'static>(
+    status: &ApplyStatus,
+) -> Element<'a, Message> {
+    let label = match status {
+        ApplyStatus::Applied => fl!("shortcut-apply-status", "applied"),
+        ApplyStatus::Failed(why) => {
+            fl!("shortcut-apply-status", "failed-desc", why = why.as_str())
+        }
+    };
+
+    text::caption(label)
+        .apply(widget::container)
+        .width(Length::Fill)
+        .align_x(Alignment::End)
         .into()
 }
 
+/// The part of a list, from its top, that may be in view of the page that scrolls it.
+///
+/// Where the list starts within the page is not known, but it is no further down than the
+/// height of the rest of the page, so the view is widened up by that much.
+fn list_view(viewport: crate::pages::PageViewport, list_height: f32) -> Range<f32> {
+    let height = viewport.height.unwrap_or(FALLBACK_VIEW_HEIGHT);
+    let list_top = (viewport.content_height - list_height).max(0.0);
+
+    viewport.offset - list_top..viewport.offset + height
+}
+
+/// The rows of a virtualized list, given their heights, that are in a part of the list,
+/// and those overscanned around them.
+fn visible_range(heights: &[f32], mut view: Range<f32>) -> Range<usize> {
+    let len = heights.len();
+    let list_height = heights.iter().sum::<f32>();
+
+    // The offset may be past the end of a list that rows were removed from.
+    if view.start >= list_height {
+        view = (list_height - (view.end - view.start)).max(0.0)..list_height;
+    }
+
+    let mut first = len;
+    let mut end = len;
+    let mut top = 0.0;
+
+    for (row, height) in heights.iter().enumerate() {
+        if top >= view.end {
+            end = row;
+            break;
+        }
+
+        top += height;
+
+        if first == len && top > view.start {
+            first = row;
+        }
+    }
+
+    first.min(end).saturating_sub(OVERSCAN)..(end + OVERSCAN).min(len)
+}
+
+/// Removes the custom bindings that override the defaults of an action, whether they bind the
+/// action to other keys or disable or rebind the keys that it is bound to by default.
+/// Disables the given bindings, recording the actions that they were bound to so that enabling
+/// them again restores them. Default bindings are overridden, while others are removed.
+pub(super) fn disable_bindings(
+    custom: &mut Shortcuts,
+    disabled: &mut Shortcuts,
+    defaults: &Shortcuts,
+    bound: impl IntoIterator<Item = (Binding, Action)>,
+) {
+    for (binding, action) in bound {
+        if defaults.0.get(&binding) == Some(&action) {
+            custom.0.insert(binding.clone(), Action::Disable);
+        } else {
+            custom.0.remove(&binding);
+        }
+
+        disabled.0.insert(binding, action);
+    }
+}
+
+/// Restores the bindings of the given actions that were disabled with their category. Actions
+/// without any recorded bindings get their disabled defaults back.
+pub(super) fn enable_bindings(
+    custom: &mut Shortcuts,
+    disabled: &mut Shortcuts,
+    defaults: &Shortcuts,
+    actions: &[Action],
+) {
+    for action in actions {
+        let recorded = disabled
+            .0
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(binding, _)| binding.clone())
+            .collect::<Vec<_>>();
+
+        if recorded.is_empty() {
+            for (binding, a) in &defaults.0 {
+                if a == action && custom.0.get(binding) == Some(&Action::Disable) {
+                    custom.0.remove(binding);
+                }
+            }
+
+            continue;
+        }
+
+        for binding in recorded {
+            disabled.0.remove(&binding);
+
+            if defaults.0.get(&binding) == Some(action) {
+                if custom.0.get(&binding) == Some(&Action::Disable) {
+                    custom.0.remove(&binding);
+                }
+
+                continue;
+            }
+
+            // Keys that were bound to another shortcut since keep it.
+            let taken = custom
+                .0
+                .get(&binding)
+                .or_else(|| defaults.0.get(&binding))
+                .is_some_and(|a| *a != Action::Disable);
+
+            if !taken {
+                custom.0.insert(binding, action.clone());
+            }
+        }
+    }
+}
+
+/// Gives the bindings that replaced defaults the defaults that they replaced, as they were
+/// recorded when the bindings were edited.
+fn restore_replaced_defaults(
+    models: &mut Slab<ShortcutModel>,
+    defaults: &Shortcuts,
+    replaced: &[(Binding, Binding)],
+) {
+    for (_, model) in models.iter_mut() {
+        // Records of defaults that are bound again are stale.
+        let bound = model
+            .bindings
+            .iter()
+            .map(|(_, shortcut)| shortcut.binding.clone())
+            .collect::<Vec<_>>();
+
+        for (_, shortcut) in model.bindings.iter_mut() {
+            if shortcut.is_default {
+                continue;
+            }
+
+            shortcut.default = replaced
+                .iter()
+                .find(|(binding, default)| {
+                    *binding == shortcut.binding
+                        && defaults.0.get(default) == Some(&model.action)
+                        && !bound.contains(default)
+                })
+                .map(|(_, default)| default.clone());
+        }
+    }
+}
+
+/// The custom shortcuts that resetting an action to its defaults removes, as they are bound to
+/// the key combinations of its defaults.
+pub(super) fn displaced_by_reset(
+    custom: &Shortcuts,
+    defaults: &Shortcuts,
+    action: &Action,
+) -> Vec<(Binding, Action)> {
+    custom
+        .0
+        .iter()
+        .filter(|(binding, custom_action)| {
+            matches!(custom_action, Action::Spawn(_)) && defaults.0.get(*binding) == Some(action)
+        })
+        .map(|(binding, custom_action)| (binding.clone(), custom_action.clone()))
+        .collect()
+}
+
+pub(super) fn reset_to_default(custom: &mut Shortcuts, defaults: &Shortcuts, action: &Action) {
+    custom.0.retain(|binding, custom_action| {
+        custom_action != action && defaults.0.get(binding) != Some(action)
+    });
+}
+
 /// Display a shortcut as a list item
-fn shortcut_item(custom: bool, id: usize, data: &ShortcutModel) -> Element<ShortcutMessage> {
+fn shortcut_item(
+    layout: &Layout,
+    custom: bool,
+    glyphs: bool,
+    id: usize,
+    data: &ShortcutModel,
+    max_bindings: usize,
+    emphasized: bool,
+) -> Element<ShortcutMessage> {
     #[derive(Copy, Clone, Debug)]
     enum LocalMessage {
         Remove,
+        Reset,
+        Run,
         Show,
     }
 
     let bindings = data
         .bindings
         .iter()
-        .take(3)
         .filter(|(_, shortcut)| shortcut.binding.is_set())
-        .map(|(_, shortcut)| text::body(shortcut.binding.to_string()).into())
+        .take(max_bindings)
+        .map(|(_, shortcut)| {
+            if glyphs {
+                super::binding_glyphs_view(&shortcut.binding)
+            } else {
+                text::body(shortcut.binding.to_string()).into()
+            }
+        })
         .collect::<Vec<_>>();
 
     let shortcuts: Element<LocalMessage> = if bindings.is_empty() {
@@ -688,10 +1768,47 @@ fn shortcut_item(custom: bool, id: usize, data: &ShortcutModel) -> Element<Short
         Some(text::body(fl!("modified", count = data.modified)))
     };
 
-    let control = widget::row::with_capacity(4)
+    // Indicate how many key combinations are bound when there is more than one.
+    let binding_count = data
+        .bindings
+        .iter()
+        .filter(|(_, shortcut)| shortcut.binding.is_set())
+        .count();
+
+    let binding_count = (binding_count > 1).then(|| {
+        text::caption(fl!("binding-count", count = binding_count))
+            .apply(widget::container)
+            .padding([2, 8])
+            .class(theme::Container::Card)
+    });
+
+    let reset_button = (!custom && data.modified != 0).then(|| {
+        widget::tooltip(
+            widget::button::icon(icon::from_name("edit-undo-symbolic"))
+                .on_press(LocalMessage::Reset),
+            text::body(fl!("reset-to-default")),
+            widget::tooltip::Position::Top,
+        )
+    });
+
+    // Running a command takes a button of its own, so that clicking the row cannot run it.
+    let run_button = (custom && matches!(data.action, Action::Spawn(_))).then(|| {
+        widget::button::icon(icon::from_name("media-playback-start-symbolic"))
+            .on_press(LocalMessage::Run)
+    });
+
+    let terminal = data
+        .terminal
+        .then(|| icon::from_name("utilities-terminal-symbolic").size(16));
+
+    let control = widget::row::with_capacity(7)
+        .push_maybe(terminal)
         .push_maybe(modified)
+        .push_maybe(binding_count)
         .push(shortcuts)
         .push(icon::from_name("go-next-symbolic").size(16))
+        .push_maybe(reset_button)
+        .push_maybe(run_button)
         .push_maybe(custom.then(|| {
             widget::button::icon(icon::from_name("edit-delete-symbolic"))
                 .on_press(LocalMessage::Remove)
@@ -699,11 +1816,20 @@ fn shortcut_item(custom: bool, id: usize, data: &ShortcutModel) -> Element<Short
         .align_y(Alignment::Center)
         .spacing(8);
 
-    settings::item::builder(&data.description)
-        .flex_control(control)
-        .spacing(16)
+    let mut item = settings::item::builder(&data.description);
+
+    if let Some(note) = data.note.as_deref() {
+        item = item.description(note);
+    }
+
+    item.flex_control(control)
+        .spacing(layout.item_spacing)
         .apply(widget::container)
-        .class(theme::Container::List)
+        .class(if emphasized {
+            crate::theme::search_match()
+        } else {
+            theme::Container::List
+        })
         .apply(widget::button::custom)
         .class(theme::Button::Transparent)
         .on_press(LocalMessage::Show)
@@ -711,5 +1837,334 @@ fn shortcut_item(custom: bool, id: usize, data: &ShortcutModel) -> Element<Short
         .map(move |message| match message {
             LocalMessage::Show => ShortcutMessage::ShowShortcut(id, data.description.clone()),
             LocalMessage::Remove => ShortcutMessage::DeleteShortcut(id),
+            LocalMessage::Reset => ShortcutMessage::ResetToDefault(data.action.clone()),
+            LocalMessage::Run => ShortcutMessage::RunShortcut(id),
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FALLBACK_VIEW_HEIGHT, Filter, Layout, Model, OVERSCAN, ShortcutMessage, ShortcutModel,
+        disable_bindings, displaced_by_reset, enable_bindings, list_view, reset_to_default,
+        restore_replaced_defaults, visible_range,
+    };
+    use crate::pages::PageViewport;
+    use cosmic::cosmic_theme::Spacing;
+    use cosmic_settings_config::Binding;
+    use cosmic_settings_config::shortcuts::{Action, Shortcuts};
+    use slab::Slab;
+    use std::str::FromStr;
+
+    fn binding(keys: &str) -> Binding {
+        Binding::from_str(keys).unwrap()
+    }
+
+    #[test]
+    fn rows_in_view() {
+        let heights = [50.0; 500];
+
+        assert_eq!(visible_range(&heights, 0.0..500.0), 0..10 + OVERSCAN);
+        assert_eq!(
+            visible_range(&heights, 5025.0..5525.0),
+            100 - OVERSCAN..111 + OVERSCAN
+        );
+
+        // Scrolling past the end, as when rows are removed, shows the last rows.
+        assert_eq!(
+            visible_range(&heights, 30000.0..30500.0),
+            490 - OVERSCAN..500
+        );
+        assert_eq!(visible_range(&heights[..3], -10.0..500.0), 0..3);
+
+        // Rows that show more key combinations are taller.
+        let mut heights = [50.0; 20];
+        heights[0] = 150.0;
+        assert_eq!(visible_range(&heights, 0.0..200.0), 0..2 + OVERSCAN);
+    }
+
+    #[test]
+    fn list_in_view_of_page() {
+        let viewport = PageViewport {
+            offset: 1000.0,
+            height: Some(500.0),
+            content_height: 25300.0,
+        };
+
+        // The list may start anywhere in the 300 pixels of the page beside it.
+        assert_eq!(list_view(viewport, 25000.0), 700.0..1500.0);
+        assert_eq!(
+            list_view(PageViewport::default(), 25000.0),
+            0.0..FALLBACK_VIEW_HEIGHT
+        );
+    }
+
+    #[test]
+    fn rows_show_every_binding() {
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.0.insert(binding("Super+q"), Action::Close);
+        shortcuts.0.insert(binding("Alt+F4"), Action::Close);
+
+        let close = ShortcutModel::new(&Shortcuts::default(), &shortcuts, Action::Close);
+        let maximize = ShortcutModel::new(&Shortcuts::default(), &shortcuts, Action::Maximize);
+
+        let layout = Layout::comfortable(Spacing::default());
+        assert_eq!(layout.row_height_of(&maximize), layout.row_height);
+        assert_eq!(
+            layout.row_height_of(&close),
+            layout.row_height + layout.binding_height
+        );
+    }
+
+    /// Measures how long the view of a list of 500 shortcuts takes to build, with only the
+    /// rows in view and with every row. Run with
+    /// `cargo test view_build_time -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn view_build_time() {
+        use cosmic_settings_page::{Binder, Section};
+        use std::time::{Duration, Instant};
+
+        let modifiers = ["Super", "Alt", "Ctrl", "Shift"];
+        let mut shortcuts = Shortcuts::default();
+        let keys = (1..16u8)
+            .map(|set| {
+                (0..4)
+                    .filter(|bit| set & (1 << bit) != 0)
+                    .map(|bit| modifiers[bit])
+                    .collect::<Vec<_>>()
+                    .join("+")
+            })
+            .flat_map(|modifiers| {
+                ('a'..='z')
+                    .chain('0'..='9')
+                    .map(move |key| format!("{modifiers}+{key}"))
+            })
+            .take(500);
+
+        for (n, keys) in keys.enumerate() {
+            shortcuts
+                .0
+                .insert(binding(&keys), Action::Spawn(format!("command-{n}")));
+        }
+
+        let mut model = Model::default();
+        for (_, action) in shortcuts.iter() {
+            model.shortcut_models.insert(ShortcutModel::new(
+                &Shortcuts::default(),
+                &shortcuts,
+                action.clone(),
+            ));
+        }
+        assert_eq!(model.shortcut_models.len(), 500);
+
+        let section = Section::<crate::pages::Message>::default();
+        let time = |binder: &Binder<crate::pages::Message>| {
+            let start = Instant::now();
+            for _ in 0..100 {
+                drop(model.view(binder, &section));
+            }
+            start.elapsed() / 100
+        };
+
+        let mut binder = Binder::default();
+        binder.resource_register::<PageViewport>();
+        let in_view: Duration = time(&binder);
+
+        *binder.resource_mut::<PageViewport>().unwrap() = PageViewport {
+            offset: 0.0,
+            height: Some(f32::INFINITY),
+            content_height: 0.0,
+        };
+        let every_row = time(&binder);
+
+        println!("rows in view: {in_view:?}, every row: {every_row:?}");
+        assert!(in_view < every_row);
+    }
+
+    #[test]
+    fn replaced_defaults_are_captured() {
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+q"), Action::Close);
+        defaults.0.insert(binding("Alt+F4"), Action::Close);
+
+        // Super+q was rebound to Super+w.
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.0.insert(binding("Alt+F4"), Action::Close);
+        shortcuts.0.insert(binding("Super+q"), Action::Disable);
+        shortcuts.0.insert(binding("Super+w"), Action::Close);
+
+        let mut models = Slab::new();
+        models.insert(ShortcutModel::new(&defaults, &shortcuts, Action::Close));
+
+        let default_of = |models: &Slab<ShortcutModel>, keys| {
+            models[0]
+                .bindings
+                .iter()
+                .find(|(_, shortcut)| shortcut.binding == binding(keys))
+                .map(|(_, shortcut)| shortcut.default.clone())
+        };
+
+        // Without a record, the default that a binding replaced is not guessed.
+        assert_eq!(default_of(&models, "Super+w"), Some(None));
+
+        let replaced = [(binding("Super+w"), binding("Super+q"))];
+        restore_replaced_defaults(&mut models, &defaults, &replaced);
+
+        assert_eq!(default_of(&models, "Alt+F4"), Some(None));
+        assert_eq!(
+            default_of(&models, "Super+w"),
+            Some(Some(binding("Super+q")))
+        );
+    }
+
+    #[test]
+    fn drawer_edits_the_shown_shortcut() {
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.0.insert(binding("Super+q"), Action::Close);
+        shortcuts.0.insert(binding("Super+m"), Action::Maximize);
+        shortcuts
+            .0
+            .insert(binding("Super+Shift+m"), Action::Maximize);
+
+        let mut model = Model::default();
+        model.shortcut_models.insert(ShortcutModel::new(
+            &Shortcuts::default(),
+            &shortcuts,
+            Action::Close,
+        ));
+        let maximize = model.shortcut_models.insert(ShortcutModel::new(
+            &Shortcuts::default(),
+            &shortcuts,
+            Action::Maximize,
+        ));
+
+        let _task = model.update(ShortcutMessage::ShowShortcut(maximize, String::new()));
+        assert_eq!(model.shortcut_context, Some(maximize));
+        assert_eq!(model.editing, Some(0));
+
+        // The arrow keys move between the bindings of the shown shortcut.
+        let _task = model.focus_adjacent_binding(true);
+        assert_eq!(model.editing, Some(1));
+        let _task = model.focus_adjacent_binding(true);
+        assert_eq!(model.editing, Some(1));
+        let _task = model.focus_adjacent_binding(false);
+        assert_eq!(model.editing, Some(0));
+    }
+
+    #[test]
+    fn invalid_input_is_marked_once_submitted() {
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.0.insert(binding("Super+q"), Action::Close);
+
+        let mut model = Model::default();
+        let close = model.shortcut_models.insert(ShortcutModel::new(
+            &Shortcuts::default(),
+            &shortcuts,
+            Action::Close,
+        ));
+
+        let _task = model.update(ShortcutMessage::ShowShortcut(close, String::new()));
+        let _task = model.update(ShortcutMessage::InputBinding(0, String::from("Hyper+q")));
+        assert!(!model.shortcut_models[close].bindings[0].invalid);
+
+        // The input is kept to be corrected.
+        let _task = model.update(ShortcutMessage::SubmitBinding(0));
+        let shortcut = &model.shortcut_models[close].bindings[0];
+        assert!(shortcut.invalid);
+        assert_eq!(shortcut.input, "Hyper+q");
+
+        let _task = model.update(ShortcutMessage::InputBinding(
+            0,
+            String::from("Super+Shift"),
+        ));
+        assert!(!model.shortcut_models[close].bindings[0].invalid);
+    }
+
+    #[test]
+    fn filter_by_modified() {
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+q"), Action::Close);
+        defaults.0.insert(binding("Super+m"), Action::Maximize);
+
+        let mut shortcuts = defaults.clone();
+        shortcuts.0.insert(binding("Super+w"), Action::Close);
+
+        let close = ShortcutModel::new(&defaults, &shortcuts, Action::Close);
+        let maximize = ShortcutModel::new(&defaults, &shortcuts, Action::Maximize);
+
+        assert!(Filter::All.matches(&close) && Filter::All.matches(&maximize));
+        assert!(Filter::Modified.matches(&close) && !Filter::Modified.matches(&maximize));
+        assert!(!Filter::Default.matches(&close) && Filter::Default.matches(&maximize));
+    }
+
+    #[test]
+    fn reset_one_action_to_default() {
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+q"), Action::Close);
+        defaults.0.insert(binding("Super+m"), Action::Maximize);
+
+        let mut custom = Shortcuts::default();
+        custom.0.insert(binding("Super+w"), Action::Close);
+        custom.0.insert(binding("Super+q"), Action::Disable);
+        custom.0.insert(binding("Super+e"), Action::Maximize);
+
+        reset_to_default(&mut custom, &defaults, &Action::Close);
+
+        // The overrides of other actions are kept.
+        assert_eq!(custom.0.len(), 1);
+        assert_eq!(custom.0.get(&binding("Super+e")), Some(&Action::Maximize));
+    }
+
+    #[test]
+    fn reset_lists_displaced_custom_shortcuts() {
+        let spawn = Action::Spawn(String::from("cosmic-term"));
+
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+q"), Action::Close);
+
+        let mut custom = Shortcuts::default();
+        custom.0.insert(binding("Super+q"), spawn.clone());
+        custom.0.insert(binding("Super+t"), spawn.clone());
+
+        assert_eq!(
+            displaced_by_reset(&custom, &defaults, &Action::Close),
+            [(binding("Super+q"), spawn)]
+        );
+        assert!(displaced_by_reset(&custom, &defaults, &Action::Maximize).is_empty());
+    }
+
+    #[test]
+    fn toggling_a_category_keeps_rebinds() {
+        let action = Action::Workspace(1);
+
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+1"), action.clone());
+        defaults.0.insert(binding("Super+Shift+1"), action.clone());
+
+        // One default is bound, and the other was replaced by a key of the user's own.
+        let mut custom = Shortcuts::default();
+        custom.0.insert(binding("Super+Shift+1"), Action::Disable);
+        custom.0.insert(binding("Super+Alt+1"), action.clone());
+        let before = custom.0.clone();
+
+        let mut disabled = Shortcuts::default();
+        disable_bindings(
+            &mut custom,
+            &mut disabled,
+            &defaults,
+            [
+                (binding("Super+1"), action.clone()),
+                (binding("Super+Alt+1"), action.clone()),
+            ],
+        );
+
+        assert_eq!(custom.0.get(&binding("Super+1")), Some(&Action::Disable));
+        assert!(!custom.0.contains_key(&binding("Super+Alt+1")));
+
+        enable_bindings(&mut custom, &mut disabled, &defaults, &[action]);
+
+        assert_eq!(custom.0, before);
+        assert!(disabled.0.is_empty());
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Lists every key combination that more than one shortcut is bound to, across all categories.
+
+use std::collections::BTreeMap;
+
+use super::{ShortcutMessage, ShortcutModel};
+use cosmic::app::ContextDrawer;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, button, settings, text};
+use cosmic::{Apply, Element, Task};
+use cosmic_settings_config::Binding;
+use cosmic_settings_config::shortcuts::{Action, Shortcuts};
+use cosmic_settings_page::{self as page, Section, section};
+use slab::Slab;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// Opens a binding of a shortcut for editing.
+    Resolve(usize, usize),
+    Shortcut(ShortcutMessage),
+}
+
+pub struct Page {
+    model: super::Model,
+    /// Each conflicting key combination, with the shortcuts and bindings that it is bound by.
+    conflicts: Vec<(String, Vec<(usize, usize)>)>,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self {
+            model: super::Model::default().actions(|defaults, shortcuts| {
+                let mut actions = Vec::new();
+
+                for (_, action) in conflicts(shortcuts).into_values().flatten() {
+                    if !actions.contains(&action) {
+                        actions.push(action);
+                    }
+                }
+
+                actions.into_iter().fold(Slab::new(), |mut slab, action| {
+                    slab.insert(ShortcutModel::new(defaults, shortcuts, action));
+                    slab
+                })
+            }),
+            conflicts: Vec::new(),
+        }
+    }
+}
+
+impl Page {
+    pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
+        match message {
+            Message::Resolve(id, binding_id) => {
+                let Some(description) = self
+                    .model
+                    .shortcut_models
+                    .get(id)
+                    .map(|model| model.description.clone())
+                else {
+                    return Task::none();
+                };
+
+                let mut tasks = vec![
+                    self.model
+                        .update(ShortcutMessage::ShowShortcut(id, description)),
+                ];
+
+                // Edit the conflicting binding, rather than the first binding of the shortcut.
+                if let Some(shortcut) = self
+                    .model
+                    .shortcut_models
+                    .get_mut(id)
+                    .and_then(|model| model.bindings.get_mut(binding_id))
+                {
+                    self.model.editing = Some(binding_id);
+                    shortcut.input = shortcut.binding.to_string();
+                    tasks.push(super::select_key_input(shortcut.id.clone()));
+                }
+
+                Task::batch(tasks)
+            }
+
+            Message::Shortcut(message) => {
+                let task = self.model.update(message);
+                self.conflicts = self.group();
+                task
+            }
+        }
+    }
+
+    /// Finds the shortcut and binding of each conflicting binding in the config.
+    fn group(&self) -> Vec<(String, Vec<(usize, usize)>)> {
+        let models = &self.model.shortcut_models;
+
+        conflicts(&self.model.shortcuts_system_config())
+            .into_iter()
+            .map(|(keys, bound)| {
+                let ids = bound
+                    .iter()
+                    .filter_map(|(binding, action)| {
+                        models
+                            .iter()
+                            .find(|(_, model)| model.action == *action)
+                            .and_then(|(id, model)| {
+                                model
+                                    .bindings
+                                    .iter()
+                                    .find(|(_, shortcut)| shortcut.binding == *binding)
+                                    .map(|(binding_id, _)| (id, binding_id))
+                            })
+                    })
+                    .collect();
+
+                (keys, ids)
+            })
+            .collect()
+    }
+}
+
+impl page::Page<crate::pages::Message> for Page {
+    fn set_id(&mut self, entity: page::Entity) {
+        self.model.entity = entity;
+    }
+
+    fn info(&self) -> page::Info {
+        page::Info::new("shortcut-conflicts", "input-keyboard-symbolic")
+            .title(fl!("shortcut-conflicts"))
+            .description(fl!("shortcut-conflicts", "desc"))
+    }
+
+    fn content(
+        &self,
+        sections: &mut slotmap::SlotMap<section::Entity, Section<crate::pages::Message>>,
+    ) -> Option<page::Content> {
+        Some(vec![sections.insert(conflicts_view())])
+    }
+
+    fn context_drawer(&self) -> Option<ContextDrawer<'_, crate::pages::Message>> {
+        self.model
+            .context_drawer(|msg| crate::pages::Message::ConflictShortcuts(Message::Shortcut(msg)))
+    }
+
+    fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
+        self.model.dialog().map(|el| {
+            el.map(|msg| crate::pages::Message::ConflictShortcuts(Message::Shortcut(msg)))
+        })
+    }
+
+    fn on_context_drawer_close(&mut self) -> Task<crate::pages::Message> {
+        self.model.on_context_drawer_close();
+        // Shortcuts whose conflicts were resolved are no longer listed.
+        self.model.on_enter();
+        self.conflicts = self.group();
+        Task::none()
+    }
+
+    fn on_enter(&mut self) -> Task<crate::pages::Message> {
+        self.model.on_enter();
+        self.conflicts = self.group();
+        Task::none()
+    }
+
+    fn on_leave(&mut self) -> Task<crate::pages::Message> {
+        self.model.on_clear();
+        self.conflicts.clear();
+        Task::none()
+    }
+}
+
+impl page::AutoBind<crate::pages::Message> for Page {}
+
+/// Groups the bindings of the merged shortcuts by the keys they are written as, keeping the
+/// keys that are bound to more than one action.
+///
+/// Bindings that are written differently, or that are told apart by their names, are distinct
+/// entries of the config, though the compositor can run only one of them.
+pub fn conflicts(shortcuts: &Shortcuts) -> BTreeMap<String, Vec<(Binding, Action)>> {
+    let mut bound_by_keys = BTreeMap::<String, Vec<(Binding, Action)>>::new();
+
+    for (binding, action) in &shortcuts.0 {
+        if *action == Action::Disable || !binding.is_set() {
+            continue;
+        }
+
+        bound_by_keys
+            .entry(super::canonical_binding(binding).to_string())
+            .or_default()
+            .push((binding.clone(), action.clone()));
+    }
+
+    bound_by_keys.retain(|_, bound| bound.iter().any(|(_, action)| *action != bound[0].1));
+    bound_by_keys
+}
+
+fn conflicts_view() -> Section<crate::pages::Message> {
+    crate::slab!(descriptions {
+        none = fl!("shortcut-conflicts", "none");
+        resolve = fl!("shortcut-conflicts", "resolve");
+    });
+
+    Section::default()
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+            let models = &page.model.shortcut_models;
+
+            if page.conflicts.is_empty() {
+                return widget::list_column()
+                    .add(settings::item_row(vec![
+                        text::body(&descriptions[none]).into(),
+                    ]))
+                    .apply(Element::from)
+                    .map(crate::pages::Message::ConflictShortcuts);
+            }
+
+            page.conflicts
+                .iter()
+                .fold(
+                    widget::column::with_capacity(page.conflicts.len()).spacing(24),
+                    |column, (keys, ids)| {
+                        let group = ids.iter().fold(
+                            settings::section().title(keys),
+                            |group, &(id, binding_id)| {
+                                let model = &models[id];
+                                let binding = &model.bindings[binding_id].binding;
+
+                                group.add(settings::item_row(vec![
+                                    text::body(&model.description).width(Length::Fill).into(),
+                                    text::body(binding.to_string())
+                                        .font(cosmic::font::mono())
+                                        .into(),
+                                    button::standard(&descriptions[resolve])
+                                        .on_press(Message::Resolve(id, binding_id))
+                                        .into(),
+                                ]))
+                            },
+                        );
+
+                        column.push(group)
+                    },
+                )
+                .align_x(Alignment::Start)
+                .apply(Element::from)
+                .map(crate::pages::Message::ConflictShortcuts)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::conflicts;
+    use cosmic_settings_config::Binding;
+    use cosmic_settings_config::shortcuts::{Action, Shortcuts};
+    use std::str::FromStr;
+
+    #[test]
+    fn keys_bound_to_several_actions() {
+        let binding = |keys: &str, name: Option<&str>| {
+            let mut binding = Binding::from_str(keys).unwrap();
+            binding.description = name.map(String::from);
+            binding
+        };
+
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.0.insert(
+            binding("Super+t", None),
+            Action::Spawn(String::from("cosmic-term")),
+        );
+        shortcuts.0.insert(
+            binding("Super+t", Some("Files")),
+            Action::Spawn(String::from("cosmic-files")),
+        );
+        shortcuts.0.insert(
+            binding("Super+e", None),
+            Action::Spawn(String::from("cosmic-edit")),
+        );
+        // The same action under two names does not conflict with itself.
+        shortcuts.0.insert(
+            binding("Super+b", None),
+            Action::Spawn(String::from("firefox")),
+        );
+        shortcuts.0.insert(
+            binding("Super+b", Some("Browser")),
+            Action::Spawn(String::from("firefox")),
+        );
+        // Disabled bindings are bound to nothing.
+        shortcuts
+            .0
+            .insert(binding("Super+e", Some("Disabled")), Action::Disable);
+
+        let conflicts = conflicts(&shortcuts);
+        assert_eq!(conflicts.len(), 1);
+
+        let bound = conflicts.values().next().unwrap();
+        assert_eq!(bound.len(), 2);
+        assert!(
+            bound
+                .iter()
+                .any(|(_, action)| *action == Action::Spawn(String::from("cosmic-files")))
+        );
+    }
+}
@@ -1,12 +1,24 @@
 // Copyright 2024 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 //
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::str::FromStr;
 
-use super::{ShortcutBinding, ShortcutMessage, ShortcutModel};
+use super::applications::{self, Application};
+use super::command::Command;
+#[cfg(feature = "xdg-portal")]
+use super::export;
+use super::terminal;
+use super::{
+    DetailsKey, FocusBy, InvalidBinding, Layout, ShortcutBinding, ShortcutDetails, ShortcutMessage,
+    ShortcutModel, SpawnOutput,
+};
+use crate::pages::SectionDescriptions;
 
 use cosmic::app::ContextDrawer;
-use cosmic::iced::{Alignment, Length};
+#[cfg(feature = "xdg-portal")]
+use cosmic::dialog::file_chooser;
+use cosmic::iced::{self, Alignment, Length, Subscription, event};
 use cosmic::widget::{self, button, icon};
 use cosmic::{Apply, Element, Task};
 use cosmic_settings_config::Binding;
@@ -19,18 +31,44 @@ pub struct Page {
     entity: page::Entity,
     model: super::Model,
     add_shortcut: AddShortcut,
-    replace_dialog: Vec<(Binding, Action, String)>,
+    replace_dialog: Vec<(Binding, Action, String, Action)>,
+    replace_total: usize,
+    /// Details of the shortcuts whose bindings wait on the replace dialog, saved with the
+    /// first of their bindings that is.
+    pending_details: BTreeMap<DetailsKey, ShortcutDetails>,
+    selection: Option<BTreeSet<usize>>,
+    confirm_delete: bool,
+    confirm_reset: bool,
+    /// Outcome of the last GNOME import, shown once its conflicts are resolved.
+    import_summary: Option<ImportSummary>,
+    /// Commands of the shortcuts added in this session, the most recent first.
+    recent_commands: VecDeque<String>,
+    /// Names and commands of the shortcuts for the settings search while the page is not
+    /// shown, and so has not loaded them.
+    search_items: Option<Vec<String>>,
     task_id: widget::Id,
     name_id: widget::Id,
 }
 
 impl Default for Page {
     fn default() -> Self {
+        let model = super::Model::default().custom().actions(bindings);
+        let search_items =
+            search_items(&bindings(&Shortcuts::default(), &model.shortcuts_config()));
+
         Self {
             entity: page::Entity::null(),
-            model: super::Model::default().custom().actions(bindings),
+            model,
             add_shortcut: AddShortcut::default(),
             replace_dialog: Vec::new(),
+            replace_total: 0,
+            pending_details: BTreeMap::new(),
+            selection: None,
+            confirm_delete: false,
+            confirm_reset: false,
+            import_summary: None,
+            recent_commands: VecDeque::with_capacity(RECENT_COMMANDS_MAX),
+            search_items: Some(search_items),
             task_id: widget::Id::unique(),
             name_id: widget::Id::unique(),
         }
@@ -41,28 +79,198 @@ impl Default for Page {
 pub enum Message {
     /// Adds a new key binding input
     AddKeybinding,
+    /// Installed applications, for choosing one to launch
+    Applications(Vec<Application>),
+    /// Update the search of the application picker
+    ApplicationSearch(String),
     /// Add a new custom shortcut to the config
     AddShortcut,
+    /// Request confirmation to delete the selected shortcuts
+    DeleteSelected,
+    /// Delete the selected shortcuts
+    DeleteSelectedApply,
+    /// Cancel deleting the selected shortcuts
+    DeleteSelectedCancel,
+    /// Choose a GNOME keybindings export to import
+    #[cfg(feature = "xdg-portal")]
+    ImportGnome,
+    /// Contents of the chosen GNOME keybindings export, if a file was chosen
+    #[cfg(feature = "xdg-portal")]
+    ImportGnomeFile(Option<Result<String, String>>),
+    /// Close the summary of a GNOME import
+    ImportSummaryClose,
+    /// Move the focus to the next key combination input
+    FocusNextKey,
+    /// Move the focus to the previous key combination input
+    FocusPreviousKey,
+    /// Choose a file to save the custom shortcuts to
+    #[cfg(feature = "xdg-portal")]
+    ExportCustom,
+    /// Result of saving the custom shortcuts, if a file was chosen
+    #[cfg(feature = "xdg-portal")]
+    ExportCustomFile(Option<Result<(), String>>),
+    /// Choose an export of custom shortcuts to import
+    #[cfg(feature = "xdg-portal")]
+    ImportCustom,
+    /// Contents of the chosen export of custom shortcuts, if a file was chosen
+    #[cfg(feature = "xdg-portal")]
+    ImportCustomFile(Option<Result<String, String>>),
     /// Update the Task text input
     TaskInput(String),
+    /// Fill the Task text input with a recently used command
+    RecentCommand(String),
+    /// Enter the command as a list of arguments rather than as a shell command
+    ArgumentList(bool),
+    /// Update an argument of the command
+    ArgumentInput(usize, String),
+    /// Add an argument to the command
+    AddArgument,
+    /// Remove an argument from the command
+    RemoveArgument(usize),
+    /// Run the command of the shortcut being added in the desktop's terminal
+    RunInTerminal(bool),
+    /// Spawn the command of the shortcut being added
+    TestCommand,
+    /// Result of spawning the command of the shortcut being added
+    TestCommandResult(Result<(), String>),
+    /// Capture what the command writes when it is tested
+    CaptureOutput(bool),
+    /// What the tested command wrote, and how it exited
+    TestOutput(Result<SpawnOutput, String>),
+    /// Copy the output of the tested command to the clipboard
+    CopyTestOutput,
     /// Toggle editing of the key text input
-    EditCombination,
+    EditCombination(FocusBy),
     /// Toggle editability of the key text input
     KeyEditing(usize, bool),
     /// Update the key text input
     KeyInput(usize, String),
     /// Update the name text input
     NameInput(String),
+    /// Choose an installed application to launch instead of typing a command
+    PickApplication,
+    /// Return to the shortcut without choosing an application
+    PickApplicationCancel,
     /// Enter key pressed in the name text input
     NameSubmit,
+    /// Remove a key binding input
+    RemoveKeybinding(usize),
     /// Apply a requested shortcut replace operation
     ReplaceApply,
     /// Cancel a requested shortcut replace operation
     ReplaceCancel,
+    /// Remove every custom shortcut
+    ResetAllCustom,
+    /// Cancel removing every custom shortcut
+    ResetAllCustomCancel,
+    /// Request confirmation to remove every custom shortcut
+    ResetAllCustomPrompt,
+    /// Fill in the name and command of the chosen application
+    SelectApplication(usize),
+    /// Keep the add shortcut context drawer open after adding a shortcut
+    KeepAdding(bool),
+    /// Show or hide the help of a field of the add shortcut context drawer
+    ToggleHelp(Field),
     /// Emit a generic shortcut message
     Shortcut(ShortcutMessage),
+    /// Edit the note of the shortcut
+    NoteAction(widget::text_editor::Action),
+    /// Display key combinations as symbols instead of text
+    ShowGlyphs(bool),
     /// Open the add shortcut context drawer
     ShortcutContext,
+    /// Toggle the selection of a shortcut
+    ToggleSelect(usize),
+    /// Enter or exit the shortcut selection mode
+    ToggleSelectionMode,
+}
+
+impl From<Message> for crate::app::Message {
+    fn from(message: Message) -> Self {
+        crate::pages::Message::CustomShortcuts(message).into()
+    }
+}
+
+/// How many keybindings of a GNOME export were imported, and why the rest were not.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct ImportSummary {
+    imported: usize,
+    /// Already present with the same command.
+    unchanged: usize,
+    /// Bound to another action, and queued for replacement.
+    conflicts: usize,
+    /// Using an accelerator that has no equivalent binding.
+    failed: usize,
+    /// Not imported because the config could not be written.
+    unsaved: usize,
+}
+
+/// A field of the add shortcut context drawer that has help.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    Name,
+    Command,
+    Keys,
+}
+
+impl Field {
+    fn help(self) -> String {
+        match self {
+            Self::Name => fl!("custom-shortcuts", "name-help"),
+            Self::Command => fl!("custom-shortcuts", "command-help"),
+            Self::Keys => fl!("custom-shortcuts", "keys-help"),
+        }
+    }
+}
+
+/// Characters that the name of a shortcut may have, beyond which the list of shortcuts
+/// cannot show it.
+const NAME_MAX: usize = 64;
+
+/// Characters left before the name limit at which a counter is shown.
+const NAME_COUNTER_WITHIN: usize = 10;
+
+/// Recently used commands that are remembered for suggesting.
+const RECENT_COMMANDS_MAX: usize = 8;
+
+/// Fits an entered name to a single line of at most [`NAME_MAX`] characters. Line breaks
+/// and tabs become spaces, and other control characters are removed.
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .take(NAME_MAX)
+        .collect()
+}
+
+/// Moves a command to the front of the recently used commands, dropping the oldest command
+/// once there are more than [`RECENT_COMMANDS_MAX`].
+fn remember_command(recent: &mut VecDeque<String>, command: &str) {
+    let command = command.trim();
+
+    if command.is_empty() {
+        return;
+    }
+
+    recent.retain(|recent| recent != command);
+    recent.push_front(command.to_owned());
+    recent.truncate(RECENT_COMMANDS_MAX);
+}
+
+/// Recently used commands that contain the entered text, other than the entered command.
+fn suggested_commands<'a>(
+    recent: &'a VecDeque<String>,
+    input: &str,
+) -> impl Iterator<Item = &'a String> {
+    let entered = input.trim().to_owned();
+    let input = entered.to_lowercase();
+
+    recent
+        .iter()
+        .filter(move |command| **command != entered && command.to_lowercase().contains(&input))
 }
 
 #[derive(Default)]
@@ -71,7 +279,25 @@ struct AddShortcut {
     pub editing: Option<usize>,
     pub name: String,
     pub task: String,
-    pub keys: Slab<(String, widget::Id)>,
+    /// Arguments of the command, while it is entered as a list of them
+    pub arguments: Option<Vec<String>>,
+    /// Whether the command is run in the desktop's terminal
+    pub terminal: bool,
+    pub note: widget::text_editor::Content,
+    pub keys: Slab<(String, widget::Id, Option<InvalidBinding>)>,
+    pub test_result: Option<Result<(), String>>,
+    /// Whether test runs capture what the command writes
+    pub capture_output: bool,
+    /// What the command wrote in its last test run, if it was captured
+    pub test_output: Option<SpawnOutput>,
+    /// Search of the application picker, while it is shown
+    pub application_search: Option<String>,
+    pub applications: Vec<Application>,
+    /// Whether the drawer is cleared for the next shortcut, rather than closed, once a
+    /// shortcut is added
+    pub keep_adding: bool,
+    /// The field whose help is shown below its label, after its help button was pressed
+    pub help: Option<Field>,
 }
 
 impl AddShortcut {
@@ -79,45 +305,312 @@ impl AddShortcut {
         self.active = true;
         self.name.clear();
         self.task.clear();
+        self.arguments = None;
+        self.terminal = false;
+        self.note = widget::text_editor::Content::new();
+        self.test_result = None;
+        self.test_output = None;
+        self.application_search = None;
+
+        // Rows may be removed from anywhere, so keep whichever row comes first.
+        if let Some(first) = self.keys.iter().next().map(|(id, _)| id) {
+            self.keys.retain(|id, _| id == first);
+            self.keys[first].0.clear();
+            self.keys[first].2 = None;
+        } else {
+            self.keys
+                .insert((String::new(), widget::Id::unique(), None));
+        }
+    }
 
-        if self.keys.is_empty() {
-            self.keys.insert((String::new(), widget::Id::unique()));
+    /// Stores the arguments being entered as the command given to the shell.
+    fn set_task_from_arguments(&mut self) {
+        let Some(arguments) = self.arguments.as_ref() else {
+            return;
+        };
+
+        // Rows left empty are skipped, rather than passed as empty arguments.
+        let arguments = arguments
+            .iter()
+            .filter(|argument| !argument.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.task = if arguments.is_empty() {
+            String::new()
         } else {
-            while self.keys.len() > 1 {
-                self.keys.remove(self.keys.len() - 1);
-            }
+            Command::Arguments(arguments).to_shell()
+        };
 
-            self.keys[0].0.clear();
-        }
+        self.test_result = None;
     }
 }
 
 impl Page {
+    /// Resolves the replace dialog with Enter and Escape while it is shown. A focused text
+    /// input captures these keys, so typing in the add shortcut drawer is left alone.
+    pub fn subscription(&self) -> Subscription<crate::pages::Message> {
+        if self.replace_dialog.is_empty() {
+            return Subscription::none();
+        }
+
+        event::listen_with(|event, status, _id| match (event, status) {
+            (
+                iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                    key: iced::keyboard::Key::Named(key),
+                    modifiers,
+                    ..
+                }),
+                event::Status::Ignored,
+            ) if modifiers.is_empty() => {
+                let message = match key {
+                    iced::keyboard::key::Named::Enter => Message::ReplaceApply,
+                    iced::keyboard::key::Named::Escape => Message::ReplaceCancel,
+                    _ => return None,
+                };
+
+                Some(crate::pages::Message::CustomShortcuts(message))
+            }
+            _ => None,
+        })
+    }
+
     pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
         match message {
             Message::TaskInput(text) => {
                 self.add_shortcut.task = text;
+                self.add_shortcut.test_result = None;
+            }
+
+            Message::RecentCommand(command) => {
+                self.add_shortcut.task = command;
+                self.add_shortcut.test_result = None;
+                return widget::text_input::focus(self.task_id.clone());
+            }
+
+            Message::ArgumentList(enable) => {
+                self.add_shortcut.arguments = enable.then(|| {
+                    let arguments = match Command::parse(&self.add_shortcut.task) {
+                        Command::Arguments(arguments) => arguments,
+                        // Start from the words of the command, for quoted ones to be corrected.
+                        Command::Shell(command) => {
+                            command.split_whitespace().map(String::from).collect()
+                        }
+                    };
+
+                    if arguments.is_empty() {
+                        vec![String::new()]
+                    } else {
+                        arguments
+                    }
+                });
+
+                self.add_shortcut.set_task_from_arguments();
+            }
+
+            Message::ArgumentInput(id, text) => {
+                if let Some(argument) = self
+                    .add_shortcut
+                    .arguments
+                    .as_mut()
+                    .and_then(|arguments| arguments.get_mut(id))
+                {
+                    *argument = text;
+                    self.add_shortcut.set_task_from_arguments();
+                }
+            }
+
+            Message::AddArgument => {
+                if let Some(arguments) = self.add_shortcut.arguments.as_mut() {
+                    arguments.push(String::new());
+                }
+            }
+
+            Message::RemoveArgument(id) => {
+                if let Some(arguments) = self.add_shortcut.arguments.as_mut() {
+                    if id < arguments.len() {
+                        arguments.remove(id);
+                        self.add_shortcut.set_task_from_arguments();
+                    }
+                }
+            }
+
+            Message::RunInTerminal(enable) => {
+                self.add_shortcut.terminal = enable;
+                self.add_shortcut.test_result = None;
+            }
+
+            Message::TestCommand => {
+                if self.add_shortcut.task.trim().is_empty() {
+                    return Task::none();
+                }
+
+                let command = self.drawer_command();
+                self.add_shortcut.test_output = None;
+
+                if self.add_shortcut.capture_output {
+                    return cosmic::task::future(async move {
+                        Message::TestOutput(super::spawn_captured(command).await)
+                    });
+                }
+
+                return cosmic::task::future(async move {
+                    Message::TestCommandResult(super::spawn(command).await)
+                });
+            }
+
+            Message::CaptureOutput(capture) => {
+                self.add_shortcut.capture_output = capture;
+
+                if !capture {
+                    self.add_shortcut.test_output = None;
+                }
+            }
+
+            Message::TestOutput(result) => match result {
+                Ok(output) => {
+                    self.add_shortcut.test_result = Some(Ok(()));
+                    self.add_shortcut.test_output = Some(output);
+                }
+                Err(why) => return self.update(Message::TestCommandResult(Err(why))),
+            },
+
+            Message::CopyTestOutput => {
+                if let Some(output) = self.add_shortcut.test_output.as_ref() {
+                    return Task::batch(vec![
+                        cosmic::iced::clipboard::write(output.to_report()),
+                        cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                            "copied-to-clipboard"
+                        ))),
+                    ]);
+                }
+            }
+
+            Message::TestCommandResult(result) => {
+                if let Err(why) = &result {
+                    tracing::error!(why, "failed to spawn custom shortcut command");
+                }
+
+                self.add_shortcut.test_result = Some(result);
             }
 
             Message::KeyInput(id, text) => {
+                let cleared = text.is_empty();
+                // Errors are described once the row is submitted or unfocused, not while typing.
+                self.add_shortcut.keys[id].2 = None;
                 self.add_shortcut.keys[id].0 = text;
+                // Keep the row in its editing state so that its caret position is retained.
+                self.add_shortcut.editing = Some(id);
+
+                // Rows are cleared rather than removed, so that at least one remains.
+                if cleared {
+                    return widget::text_input::focus(self.add_shortcut.keys[id].1.clone());
+                }
             }
 
             Message::KeyEditing(id, enable) => {
                 if enable {
                     self.add_shortcut.editing = Some(id)
                 } else if self.add_shortcut.editing == Some(id) {
+                    if !self.validate_key(id) {
+                        self.add_shortcut.editing = None;
+                        return Task::none();
+                    }
+
                     let task = self.add_keybinding();
                     self.add_shortcut.editing = None;
                     return task;
                 }
             }
 
+            Message::FocusNextKey => return self.focus_adjacent_key(true),
+
+            Message::FocusPreviousKey => return self.focus_adjacent_key(false),
+
             Message::NameInput(text) => {
-                self.add_shortcut.name = text;
+                self.add_shortcut.name = sanitize_name(&text);
+            }
+
+            Message::NoteAction(action) => {
+                self.add_shortcut.note.perform(action);
+            }
+
+            Message::AddKeybinding => {
+                // An invalid row keeps the focus, with the reason beneath it.
+                let valid = self
+                    .add_shortcut
+                    .editing
+                    .is_none_or(|id| self.validate_key(id));
+
+                if valid {
+                    return self.add_keybinding();
+                }
+            }
+
+            Message::Applications(applications) => {
+                self.add_shortcut.applications = applications;
+            }
+
+            Message::ApplicationSearch(search) => {
+                self.add_shortcut.application_search = Some(search);
+            }
+
+            Message::PickApplication => {
+                self.add_shortcut.application_search = Some(String::new());
+
+                if self.add_shortcut.applications.is_empty() {
+                    return cosmic::task::future(async {
+                        let applications = tokio::task::spawn_blocking(applications::installed)
+                            .await
+                            .unwrap_or_default();
+
+                        Message::Applications(applications)
+                    });
+                }
+            }
+
+            Message::PickApplicationCancel => {
+                self.add_shortcut.application_search = None;
+            }
+
+            Message::SelectApplication(id) => {
+                let Some(application) = self.add_shortcut.applications.get(id) else {
+                    return Task::none();
+                };
+
+                self.add_shortcut.name = sanitize_name(&application.name);
+                self.add_shortcut.task = application.command.clone();
+                self.add_shortcut.arguments = None;
+                self.add_shortcut.test_result = None;
+                self.add_shortcut.application_search = None;
+
+                if let Some((slab_index, (_, id, _))) = self.add_shortcut.keys.iter().next() {
+                    self.add_shortcut.editing = Some(slab_index);
+                    return super::select_key_input(id.clone());
+                }
             }
 
-            Message::AddKeybinding => return self.add_keybinding(),
+            Message::RemoveKeybinding(id) => {
+                // At least one key row must remain.
+                if self.add_shortcut.keys.len() < 2 || !self.add_shortcut.keys.contains(id) {
+                    return Task::none();
+                }
+
+                self.add_shortcut.keys.remove(id);
+
+                let neighbor = self
+                    .add_shortcut
+                    .keys
+                    .iter()
+                    .take_while(|(key_id, _)| *key_id < id)
+                    .last()
+                    .or_else(|| self.add_shortcut.keys.iter().next());
+
+                if let Some((key_id, (_, widget_id, _))) = neighbor {
+                    self.add_shortcut.editing = Some(key_id);
+                    return widget::text_input::focus(widget_id.clone());
+                }
+            }
 
             Message::AddShortcut => {
                 let name = self.add_shortcut.name.trim();
@@ -127,44 +620,316 @@ impl Page {
                     return Task::none();
                 }
 
+                remember_command(&mut self.recent_commands, task);
+
+                // A shortcut with the same name and command gains the new bindings
+                // instead of being listed again.
+                let merged = model_for_shortcut(
+                    &self.model.shortcut_models,
+                    &Action::Spawn(self.drawer_command()),
+                    name,
+                )
+                .is_some();
+
+                // Nothing is saved while any of the key combinations cannot be bound.
+                let ids = self.add_shortcut.keys.iter().map(|(id, _)| id);
+                let mut valid = true;
+                for id in ids.collect::<Vec<_>>() {
+                    valid &= self.validate_key(id);
+                }
+
+                if !valid {
+                    return Task::none();
+                }
+
                 let mut addable_bindings = Vec::new();
+                let mut entered = Vec::new();
 
                 for (_, (keys, ..)) in &self.add_shortcut.keys {
                     if keys.is_empty() {
                         continue;
                     }
 
-                    let Ok(binding) = Binding::from_str(keys) else {
+                    let Ok(binding) = super::parse_custom_binding(keys) else {
                         return Task::none();
                     };
 
-                    if !binding.is_set() {
-                        return Task::none();
+                    // The same key combination may be entered twice in different forms.
+                    if entered.contains(&binding) {
+                        continue;
                     }
 
+                    entered.push(binding.clone());
+
+                    let (binding, new_action) = self.drawer_shortcut(binding);
+
                     if let Some(action) = self.model.config_contains(&binding) {
                         let action_str = super::localize_action(&action);
-                        self.replace_dialog.push((binding, action, action_str));
+                        self.replace_dialog
+                            .push((binding, action, action_str, new_action));
                         continue;
                     }
 
-                    addable_bindings.push(binding);
+                    addable_bindings.push((binding, new_action));
                 }
 
-                for binding in addable_bindings {
-                    self.add_shortcut(binding);
+                self.replace_total = self.replace_dialog.len();
+
+                let added = !addable_bindings.is_empty();
+                let merged = merged && added;
+                let name = self.add_shortcut.name.clone();
+
+                let (key, details) = self.drawer_details();
+
+                // Nothing is saved of a shortcut until one of its bindings is, so that
+                // cancelling every replacement leaves no details behind.
+                let result = if added {
+                    self.model
+                        .details_save(key, details)
+                        .and_then(|()| self.add_shortcuts(addable_bindings))
+                } else {
+                    if !self.replace_dialog.is_empty() {
+                        self.pending_details.insert(key, details);
+                    }
+
+                    Ok(())
+                };
+
+                self.model.on_enter();
+
+                let next = if added && result.is_ok() {
+                    self.continue_adding()
+                } else {
+                    Task::none()
+                };
+
+                if merged && result.is_ok() {
+                    return Task::batch(vec![
+                        self.model.apply_result(result),
+                        cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                            "custom-shortcuts",
+                            "added-binding-to-existing",
+                            name = name
+                        ))),
+                        next,
+                    ]);
                 }
 
+                return Task::batch(vec![self.model.apply_result(result), next]);
+            }
+
+            Message::DeleteSelected => {
+                self.confirm_delete = self
+                    .selection
+                    .as_ref()
+                    .is_some_and(|selection| !selection.is_empty());
+            }
+
+            Message::DeleteSelectedApply => {
+                self.confirm_delete = false;
+
+                let Some(selection) = self.selection.take() else {
+                    return Task::none();
+                };
+
+                // Gather the bindings of every selected shortcut before touching the config.
+                let selected = selection
+                    .into_iter()
+                    .filter_map(|id| self.model.shortcut_models.get(id))
+                    .collect::<Vec<_>>();
+
+                let mut shortcuts = self.model.shortcuts_config();
+                shortcuts.0.retain(|binding, _| {
+                    !selected.iter().any(|model| {
+                        model
+                            .bindings
+                            .iter()
+                            .any(|(_, shortcut)| shortcut.binding == *binding)
+                    })
+                });
+
+                let removed = selected
+                    .into_iter()
+                    .filter_map(ShortcutModel::details_key)
+                    .collect::<Vec<_>>();
+
+                let mut details = self.model.details_config();
+                details.retain(|key, _| !removed.contains(key));
+
+                let result = self
+                    .model
+                    .shortcuts_config_set(shortcuts)
+                    .and_then(|()| self.model.details_config_set(details));
+
                 self.model.on_enter();
+                return self.model.apply_result(result);
+            }
+
+            Message::DeleteSelectedCancel => {
+                self.confirm_delete = false;
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ExportCustom => {
+                let mut shortcuts = self.model.shortcuts_config();
+                shortcuts
+                    .0
+                    .retain(|_, action| matches!(action, Action::Spawn(_)));
+
+                let notes = self
+                    .model
+                    .shortcut_models
+                    .iter()
+                    .filter_map(|(_, model)| Some((model.details_key()?, model.note.clone()?)))
+                    .collect();
+
+                let export = export::Export { shortcuts, notes };
+
+                return cosmic::task::future(async move {
+                    let response = file_chooser::save::Dialog::new()
+                        .title(fl!("custom-shortcuts", "export"))
+                        .modal(true)
+                        .file_name("custom-shortcuts.ron")
+                        .save_file()
+                        .await;
+
+                    let path = match response {
+                        Ok(response) => response.url().and_then(|url| url.to_file_path().ok()),
+                        Err(why) => {
+                            tracing::error!(?why, "failed to choose a file to export shortcuts to");
+                            None
+                        }
+                    };
+
+                    let Some(path) = path else {
+                        return Message::ExportCustomFile(None);
+                    };
+
+                    let result = match export.to_ron() {
+                        Ok(export) => tokio::fs::write(path, export)
+                            .await
+                            .map_err(|why| why.to_string()),
+                        Err(why) => Err(why),
+                    };
+
+                    Message::ExportCustomFile(Some(result))
+                });
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ExportCustomFile(result) => {
+                let toast = match result {
+                    Some(Ok(())) => fl!("custom-shortcuts", "export-success"),
+                    Some(Err(why)) => {
+                        tracing::error!(why, "failed to export custom shortcuts");
+                        fl!("custom-shortcuts", "export-failed", why = why)
+                    }
+                    None => return Task::none(),
+                };
+
+                return cosmic::task::message(crate::app::Message::ShowToast(toast));
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportGnome => {
+                return cosmic::task::future(async {
+                    let response = file_chooser::open::Dialog::new()
+                        .title(fl!("custom-shortcuts", "import-gnome"))
+                        .modal(true)
+                        .open_file()
+                        .await;
+
+                    let path = match response {
+                        Ok(response) => response.url().to_file_path().ok(),
+                        Err(why) => {
+                            tracing::error!(?why, "failed to choose a GNOME keybindings export");
+                            None
+                        }
+                    };
+
+                    let Some(path) = path else {
+                        return Message::ImportGnomeFile(None);
+                    };
+
+                    let result = tokio::fs::read_to_string(path)
+                        .await
+                        .map_err(|why| why.to_string());
+
+                    Message::ImportGnomeFile(Some(result))
+                });
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportGnomeFile(result) => match result {
+                Some(Ok(dump)) => return self.import_gnome(&dump),
+                Some(Err(why)) => {
+                    tracing::error!(why, "failed to read GNOME keybindings export");
+                    return cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                        "custom-shortcuts",
+                        "import-failed",
+                        why = why
+                    )));
+                }
+                None => (),
+            },
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportCustom => {
+                return cosmic::task::future(async {
+                    let response = file_chooser::open::Dialog::new()
+                        .title(fl!("custom-shortcuts", "import"))
+                        .modal(true)
+                        .open_file()
+                        .await;
+
+                    let path = match response {
+                        Ok(response) => response.url().to_file_path().ok(),
+                        Err(why) => {
+                            tracing::error!(?why, "failed to choose a custom shortcuts export");
+                            None
+                        }
+                    };
+
+                    let Some(path) = path else {
+                        return Message::ImportCustomFile(None);
+                    };
+
+                    let result = tokio::fs::read_to_string(path)
+                        .await
+                        .map_err(|why| why.to_string());
+
+                    Message::ImportCustomFile(Some(result))
+                });
+            }
+
+            #[cfg(feature = "xdg-portal")]
+            Message::ImportCustomFile(result) => {
+                let result = match result {
+                    Some(result) => result.and_then(|export| export::Export::from_ron(&export)),
+                    None => return Task::none(),
+                };
+
+                match result {
+                    Ok(export) => return self.import_custom(export),
+                    Err(why) => {
+                        tracing::error!(why, "failed to import custom shortcuts");
+                        return cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                            "custom-shortcuts",
+                            "import-custom-failed",
+                            why = why
+                        )));
+                    }
+                }
             }
 
-            Message::EditCombination => {
-                if let Some((slab_index, (_, id))) = self.add_shortcut.keys.iter().next() {
+            Message::ImportSummaryClose => {
+                self.import_summary = None;
+            }
+
+            Message::EditCombination(by) => {
+                if let Some((slab_index, (_, id, _))) = self.add_shortcut.keys.iter().next() {
                     self.add_shortcut.editing = Some(slab_index);
-                    return Task::batch(vec![
-                        widget::text_input::focus(id.clone()),
-                        widget::text_input::select_all(id.clone()),
-                    ]);
+                    return super::focus_key_input(id.clone(), by);
                 }
             }
 
@@ -175,19 +940,63 @@ impl Page {
             }
 
             Message::ReplaceApply => {
-                if let Some((binding, ..)) = self.replace_dialog.pop() {
-                    self.model.config_remove(&binding);
-                    self.add_shortcut(binding);
+                if let Some((binding, _, _, action)) = self.replace_dialog.pop() {
+                    let details = details_key(&binding, &action)
+                        .and_then(|key| self.pending_details.remove_entry(&key));
+
+                    // Adding the binding replaces the action that it was bound to.
+                    let result = match details {
+                        Some((key, details)) => self.model.details_save(key, details),
+                        None => Ok(()),
+                    }
+                    .and_then(|()| self.add_shortcuts([(binding, action)]));
 
                     if self.replace_dialog.is_empty() {
+                        self.pending_details.clear();
                         self.model.on_enter();
                     }
+
+                    let next = if result.is_ok() {
+                        self.continue_adding()
+                    } else {
+                        Task::none()
+                    };
+
+                    return Task::batch(vec![self.model.apply_result(result), next]);
                 }
             }
 
+            Message::ResetAllCustom => {
+                self.confirm_reset = false;
+
+                // Only spawned commands are custom shortcuts; other actions in the custom
+                // config are modified system shortcuts.
+                let mut shortcuts = self.model.shortcuts_config();
+                shortcuts
+                    .0
+                    .retain(|_, action| !matches!(action, Action::Spawn(_)));
+
+                let result = self
+                    .model
+                    .shortcuts_config_set(shortcuts)
+                    .and_then(|()| self.model.details_config_set(Default::default()));
+
+                self.model.on_enter();
+                return self.model.apply_result(result);
+            }
+
+            Message::ResetAllCustomCancel => {
+                self.confirm_reset = false;
+            }
+
+            Message::ResetAllCustomPrompt => {
+                self.confirm_reset = !self.model.shortcut_models.is_empty();
+            }
+
             Message::ReplaceCancel => {
                 _ = self.replace_dialog.pop();
                 if self.replace_dialog.is_empty() {
+                    self.pending_details.clear();
                     self.model.on_enter();
                 }
             }
@@ -200,7 +1009,40 @@ impl Page {
                 return self.model.update(message);
             }
 
+            Message::KeepAdding(keep_adding) => {
+                self.add_shortcut.keep_adding = keep_adding;
+            }
+
+            Message::ToggleHelp(field) => {
+                self.add_shortcut.help = if self.add_shortcut.help == Some(field) {
+                    None
+                } else {
+                    Some(field)
+                };
+            }
+
+            Message::ShowGlyphs(glyphs) => {
+                self.model.glyphs = glyphs;
+            }
+
+            Message::ToggleSelect(id) => {
+                if let Some(selection) = self.selection.as_mut() {
+                    if !selection.remove(&id) {
+                        selection.insert(id);
+                    }
+                }
+            }
+
+            Message::ToggleSelectionMode => {
+                self.selection = match self.selection {
+                    Some(_) => None,
+                    None => Some(BTreeSet::new()),
+                };
+            }
+
             Message::ShortcutContext => {
+                // Shortcut IDs are reassigned once the new shortcut is added.
+                self.selection = None;
                 self.add_shortcut.enable();
                 return Task::batch(vec![
                     cosmic::task::message(crate::app::Message::OpenContextDrawer(self.entity)),
@@ -212,63 +1054,238 @@ impl Page {
         Task::none()
     }
 
+    /// Describes why the key combination of a row cannot be bound, if it cannot be. Returns
+    /// whether it can be.
+    fn validate_key(&mut self, id: usize) -> bool {
+        let Some((text, _, error)) = self.add_shortcut.keys.get_mut(id) else {
+            return true;
+        };
+
+        // Empty rows are skipped when adding the shortcut, so they are not errors.
+        *error = super::parse_custom_binding(text)
+            .err()
+            .filter(|why| *why != InvalidBinding::Empty);
+
+        error.is_none()
+    }
+
     fn add_keybinding(&mut self) -> Task<crate::app::Message> {
         // If an empty entry exists, focus it instead of creating a new input.
-        for (_, (binding, id)) in &mut self.add_shortcut.keys {
+        for (key_id, (binding, id, error)) in &mut self.add_shortcut.keys {
             if Binding::from_str(binding).is_ok() {
                 continue;
             }
 
             binding.clear();
+            *error = None;
+            self.add_shortcut.editing = Some(key_id);
 
             return widget::text_input::focus(id.clone());
         }
 
         let new_id = widget::Id::unique();
-        self.add_shortcut.editing = Some(
-            self.add_shortcut
-                .keys
-                .insert((String::new(), new_id.clone())),
-        );
+        self.add_shortcut.editing = Some(self.add_shortcut.keys.insert((
+            String::new(),
+            new_id.clone(),
+            None,
+        )));
 
-        Task::batch(vec![
-            widget::text_input::focus(new_id.clone()),
-            widget::text_input::select_all(new_id),
-        ])
+        super::select_key_input(new_id)
+    }
+
+    /// Moves the focus from the key combination input being edited to the one above or
+    /// below it, in the order that the inputs are listed.
+    fn focus_adjacent_key(&mut self, next: bool) -> Task<crate::app::Message> {
+        if !self.add_shortcut.active {
+            return self.model.focus_adjacent_binding(next);
+        }
+
+        let Some(current) = self.add_shortcut.editing else {
+            return Task::none();
+        };
+
+        let keys = self
+            .add_shortcut
+            .keys
+            .iter()
+            .map(|(id, (_, widget_id, _))| (id, widget_id));
+
+        let adjacent = if next {
+            keys.skip_while(|(id, _)| *id != current).nth(1)
+        } else {
+            keys.take_while(|(id, _)| *id != current).last()
+        };
+
+        let Some((id, widget_id)) = adjacent else {
+            return Task::none();
+        };
+
+        let widget_id = widget_id.clone();
+        self.add_shortcut.editing = Some(id);
+        super::focus_key_input(widget_id, FocusBy::Keyboard)
     }
 
     fn add_keybinding_context(&self) -> Element<'_, Message> {
+        let layout = Layout::current();
+
         let name_input = widget::text_input("", &self.add_shortcut.name)
-            .padding([6, 12])
+            .padding(layout.input)
             .on_input(Message::NameInput)
             .on_submit(|_| Message::NameSubmit)
             .id(self.name_id.clone());
 
         let task_input = widget::text_input("", &self.add_shortcut.task)
-            .padding([6, 12])
+            .padding(layout.input)
             .on_input(Message::TaskInput)
-            .on_submit(|_| Message::EditCombination)
+            .on_submit(|_| Message::EditCombination(FocusBy::Keyboard))
             .id(self.task_id.clone());
 
+        let name_len = self.add_shortcut.name.chars().count();
+
+        let name_counter = (name_len + NAME_COUNTER_WITHIN >= NAME_MAX).then(|| {
+            widget::text::caption(fl!(
+                "custom-shortcuts",
+                "name-length",
+                count = name_len,
+                max = NAME_MAX
+            ))
+            .width(Length::Fill)
+            .align_x(Alignment::End)
+        });
+
         let name_control = widget::column()
             .spacing(4)
-            .push(widget::text::body(fl!("shortcut-name")))
-            .push(name_input);
+            .push(self.field_label(fl!("shortcut-name"), Field::Name))
+            .push(name_input)
+            .push_maybe(name_counter);
 
-        let command_control = widget::column()
-            .spacing(4)
-            .push(widget::text::body(fl!("command")))
-            .push(task_input);
+        let test_button = widget::button::standard(fl!("custom-shortcuts", "test")).on_press_maybe(
+            (!self.add_shortcut.task.trim().is_empty()).then_some(Message::TestCommand),
+        );
 
-        let input_fields = widget::column()
-            .spacing(12)
+        let test_result = self.add_shortcut.test_result.as_ref().map(|result| {
+            widget::text::caption(match result {
+                Ok(()) => fl!("custom-shortcuts", "test-success"),
+                Err(why) => fl!("custom-shortcuts", "test-failed", why = why.as_str()),
+            })
+        });
+
+        let pick_button = widget::button::standard(fl!("custom-shortcuts", "pick-application"))
+            .on_press(Message::PickApplication);
+
+        let argument_list_toggle = widget::checkbox(
+            fl!("custom-shortcuts", "argument-list"),
+            self.add_shortcut.arguments.is_some(),
+        )
+        .on_toggle(Message::ArgumentList);
+
+        let terminal_toggle = widget::checkbox(
+            fl!("custom-shortcuts", "run-in-terminal"),
+            self.add_shortcut.terminal,
+        )
+        .on_toggle(Message::RunInTerminal);
+
+        let command_input: Element<'_, Message> = match self.add_shortcut.arguments.as_ref() {
+            // Each argument is passed to the program as it is, without shell parsing.
+            Some(arguments) => arguments
+                .iter()
+                .enumerate()
+                .fold(
+                    widget::column::with_capacity(arguments.len() + 1).spacing(8),
+                    |column, (id, argument)| {
+                        let placeholder = if id == 0 {
+                            fl!("custom-shortcuts", "program")
+                        } else {
+                            fl!("custom-shortcuts", "argument")
+                        };
+
+                        let remove_button = (arguments.len() > 1).then(|| {
+                            widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::RemoveArgument(id))
+                        });
+
+                        column.push(
+                            widget::row::with_capacity(2)
+                                .spacing(8)
+                                .align_y(Alignment::Center)
+                                .push(
+                                    widget::text_input(placeholder, argument)
+                                        .padding(layout.input)
+                                        .on_input(move |input| Message::ArgumentInput(id, input)),
+                                )
+                                .push_maybe(remove_button),
+                        )
+                    },
+                )
+                .push(
+                    widget::row::with_capacity(2)
+                        .spacing(8)
+                        .push(
+                            widget::button::standard(fl!("custom-shortcuts", "add-argument"))
+                                .on_press(Message::AddArgument),
+                        )
+                        .push(test_button),
+                )
+                .into(),
+
+            None => widget::column::with_capacity(2)
+                .spacing(4)
+                .push(
+                    widget::row::with_capacity(3)
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .push(task_input)
+                        .push(pick_button)
+                        .push(test_button),
+                )
+                .push_maybe(self.recent_commands_view())
+                .into(),
+        };
+
+        let capture_toggle = widget::checkbox(
+            fl!("custom-shortcuts", "capture-output"),
+            self.add_shortcut.capture_output,
+        )
+        .on_toggle(Message::CaptureOutput);
+
+        let test_output = self
+            .add_shortcut
+            .test_output
+            .as_ref()
+            .map(super::spawn_output_view)
+            .map(|view| view.map(|()| Message::CopyTestOutput));
+
+        let command_control = widget::column()
+            .spacing(4)
+            .push(self.field_label(fl!("command"), Field::Command))
+            .push(command_input)
+            .push(argument_list_toggle)
+            .push(terminal_toggle)
+            .push(capture_toggle)
+            .push_maybe(test_result)
+            .push_maybe(test_output);
+
+        let note_control = widget::column()
+            .spacing(4)
+            .push(widget::text::body(fl!("custom-shortcuts", "note")))
+            .push(
+                widget::text_editor(&self.add_shortcut.note)
+                    .placeholder(fl!("custom-shortcuts", "note-placeholder"))
+                    .height(96)
+                    .padding(layout.input)
+                    .on_action(Message::NoteAction),
+            );
+
+        let input_fields = widget::column()
+            .spacing(layout.form_spacing)
             .push(name_control)
             .push(command_control)
-            .padding([16, 24]);
+            .push(note_control)
+            .padding(layout.form);
 
         let keys = self.add_shortcut.keys.iter().fold(
             widget::list_column().spacing(0),
-            |column, (id, (text, widget_id))| {
+            |column, (id, (text, widget_id, error))| {
                 let key_combination = widget::editable_input(
                     fl!("type-key-combination"),
                     text,
@@ -276,24 +1293,94 @@ impl Page {
                     move |enable| Message::KeyEditing(id, enable),
                 )
                 .select_on_focus(true)
-                .padding([0, 12])
+                .padding([0, layout.key_input])
                 .on_input(move |input| Message::KeyInput(id, input))
                 .on_submit(|_| Message::AddKeybinding)
-                .id(widget_id.clone())
-                .apply(widget::container)
-                .padding([8, 24]);
+                .id(widget_id.clone());
+
+                let clear_button = (!text.is_empty()).then(|| {
+                    widget::button::icon(icon::from_name("edit-clear-symbolic"))
+                        .on_press(Message::KeyInput(id, String::new()))
+                });
+
+                let remove_button = (self.add_shortcut.keys.len() > 1).then(|| {
+                    widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                        .on_press(Message::RemoveKeybinding(id))
+                });
+
+                let key_combination = widget::row::with_capacity(3)
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(super::validated_input(key_combination, error.is_some()))
+                    .push_maybe(clear_button)
+                    .push_maybe(remove_button)
+                    .apply(widget::container)
+                    .padding(layout.key_row);
+
+                let error = error.as_ref().map(|why| {
+                    widget::text::caption(why.localized(text))
+                        .apply(widget::container)
+                        .padding(layout.key_caption)
+                });
+
+                // Parsing may reorder or rename the keys typed, so show what will be saved.
+                let saved_as = super::parse_custom_binding(text).ok().map(|binding| {
+                    widget::text::caption(fl!(
+                        "custom-shortcuts",
+                        "saved-as",
+                        binding = binding.to_string()
+                    ))
+                    .apply(widget::container)
+                    .padding(layout.key_caption)
+                });
+
+                let glyphs = self
+                    .model
+                    .glyphs
+                    .then(|| Binding::from_str(text).ok())
+                    .flatten()
+                    .filter(Binding::is_set)
+                    .map(|binding| {
+                        widget::container(super::binding_glyphs_view(&binding))
+                            .padding(layout.key_caption)
+                    });
 
-                column.add(key_combination)
+                column.add(
+                    widget::column::with_capacity(4)
+                        .push(key_combination)
+                        .push_maybe(saved_as)
+                        .push_maybe(glyphs)
+                        .push_maybe(error),
+                )
             },
         );
 
-        let controls = widget::list_column().add(input_fields).add(keys).spacing(0);
-
-        let add_keybinding_button = widget::button::standard(fl!("add-another-keybinding"))
-            .on_press(Message::AddShortcut)
+        let keys_label = self
+            .field_label(fl!("custom-shortcuts", "keys"), Field::Keys)
             .apply(widget::container)
-            .width(Length::Fill)
-            .align_x(Alignment::End);
+            .padding(layout.form);
+
+        let controls = widget::list_column()
+            .add(input_fields)
+            .add(keys_label)
+            .add(keys)
+            .spacing(0);
+
+        let keep_adding_toggle = widget::checkbox(
+            fl!("custom-shortcuts", "keep-adding"),
+            self.add_shortcut.keep_adding,
+        )
+        .on_toggle(Message::KeepAdding)
+        .width(Length::Fill);
+
+        let add_keybinding_button = widget::row::with_capacity(2)
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(keep_adding_toggle)
+            .push(
+                widget::button::standard(fl!("add-another-keybinding"))
+                    .on_press(Message::AddShortcut),
+            );
 
         widget::column()
             .spacing(32)
@@ -302,11 +1389,357 @@ impl Page {
             .into()
     }
 
-    fn add_shortcut(&mut self, mut binding: Binding) {
-        self.add_shortcut.active = !self.replace_dialog.is_empty();
+    /// Labels a field of the drawer, with a button that shows its help.
+    ///
+    /// The help is a tooltip while the button is hovered, and is shown below the label
+    /// once the button is pressed, so that it may be read without a pointer.
+    fn field_label(&self, label: String, field: Field) -> Element<'_, Message> {
+        let help_button = widget::button::icon(icon::from_name("help-info-symbolic").size(16))
+            .on_press(Message::ToggleHelp(field));
+
+        let help_button = widget::tooltip(
+            help_button,
+            widget::text::body(field.help()),
+            widget::tooltip::Position::Top,
+        );
+
+        let help =
+            (self.add_shortcut.help == Some(field)).then(|| widget::text::caption(field.help()));
+
+        widget::column::with_capacity(2)
+            .spacing(4)
+            .push(
+                widget::row::with_capacity(2)
+                    .spacing(4)
+                    .align_y(Alignment::Center)
+                    .push(widget::text::body(label))
+                    .push(help_button),
+            )
+            .push_maybe(help)
+            .into()
+    }
+
+    /// Lists the installed applications that match the search of the application picker.
+    fn application_picker(&self, search: &str) -> Element<'_, Message> {
+        let search = search.trim().to_lowercase();
+
+        self.add_shortcut
+            .applications
+            .iter()
+            .enumerate()
+            .filter(|(_, application)| {
+                search.is_empty()
+                    || application.name.to_lowercase().contains(&search)
+                    || application.command.to_lowercase().contains(&search)
+            })
+            .map(|(id, application)| {
+                let icon =
+                    icon::from_name(application.icon.as_deref().unwrap_or("application-default"))
+                        .size(32);
+
+                widget::row::with_capacity(3)
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(icon)
+                    .push(widget::text::body(&application.name).width(Length::Fill))
+                    .push(
+                        widget::button::text(fl!("custom-shortcuts", "select"))
+                            .on_press(Message::SelectApplication(id)),
+                    )
+            })
+            .fold(widget::list_column(), widget::ListColumn::add)
+            .into()
+    }
+
+    /// Displays the shortcuts with a checkbox for selecting them.
+    fn selection_view<'a>(&'a self, selection: &'a BTreeSet<usize>) -> Element<'a, Message> {
+        self.model
+            .shortcut_models
+            .iter()
+            .map(|(id, shortcut)| {
+                widget::checkbox(shortcut.description.as_str(), selection.contains(&id))
+                    .on_toggle(move |_| Message::ToggleSelect(id))
+                    .apply(|checkbox| widget::settings::item_row(vec![checkbox.into()]))
+            })
+            .fold(widget::list_column(), widget::ListColumn::add)
+            .into()
+    }
+
+    /// Warns of shortcuts that share a key combination, with links to each of them.
+    fn conflicts_view(&self) -> Option<Element<'_, Message>> {
+        let models = &self.model.shortcut_models;
+
+        let keys = models.iter().flat_map(|(id, model)| {
+            model
+                .bindings
+                .iter()
+                .filter(|(_, shortcut)| shortcut.binding.is_set())
+                .map(move |(_, shortcut)| {
+                    (id, super::canonical_binding(&shortcut.binding).to_string())
+                })
+        });
+
+        let conflicts = conflicts(keys);
+
+        if conflicts.is_empty() {
+            return None;
+        }
+
+        let link = |id: usize| {
+            let model = &models[id];
+            widget::button::link(super::localize_action(&model.action)).on_press(Message::Shortcut(
+                ShortcutMessage::ShowShortcut(id, model.description.clone()),
+            ))
+        };
+
+        let header = widget::row::with_capacity(2)
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .push(icon::from_name("dialog-warning-symbolic").size(16))
+            .push(widget::text::heading(fl!("custom-shortcuts", "conflicts")));
+
+        let rows = conflicts.into_iter().map(|(key, first, second)| {
+            widget::row::with_capacity(3)
+                .align_y(Alignment::Center)
+                .spacing(8)
+                .push(widget::text::body(key).font(cosmic::font::mono()))
+                .push(link(first))
+                .push(link(second))
+                .into()
+        });
+
+        let banner = widget::column::with_children(
+            std::iter::once(header.into())
+                .chain(rows)
+                .collect::<Vec<_>>(),
+        )
+        .spacing(8)
+        .apply(widget::container)
+        .padding(16)
+        .width(Length::Fill)
+        .class(cosmic::theme::Container::Card);
+
+        Some(banner.into())
+    }
+
+    /// Recently used commands that match the entered command, for filling it in.
+    fn recent_commands_view(&self) -> Option<Element<'_, Message>> {
+        let theme = cosmic::theme::active();
+        let theme = theme.cosmic();
+
+        let suggestions = suggested_commands(&self.recent_commands, &self.add_shortcut.task)
+            .map(|command| {
+                widget::text::body(command)
+                    .font(cosmic::font::mono())
+                    .apply(widget::button::custom)
+                    .padding([theme.space_xxxs(), theme.space_xs()])
+                    .width(Length::Fill)
+                    .class(cosmic::theme::Button::MenuItem)
+                    .on_press(Message::RecentCommand(command.clone()))
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+
+        if suggestions.is_empty() {
+            return None;
+        }
+
+        widget::column::with_capacity(2)
+            .push(
+                widget::text::caption(fl!("custom-shortcuts", "recent-commands"))
+                    .apply(widget::container)
+                    .padding([theme.space_xxxs(), theme.space_xs()]),
+            )
+            .push(widget::column::with_children(suggestions))
+            .apply(widget::container)
+            .padding(1)
+            .width(Length::Fill)
+            .class(cosmic::style::Container::Dropdown)
+            .apply(Element::from)
+            .apply(Some)
+    }
+
+    /// The command of the shortcut being added, as it is spawned.
+    fn drawer_command(&self) -> String {
+        if self.add_shortcut.terminal {
+            let terminal = terminal::default_terminal(&self.model.config);
+            terminal::wrap(&terminal, self.add_shortcut.task.trim())
+        } else {
+            self.add_shortcut.task.clone()
+        }
+    }
+
+    /// The note of the shortcut being added, and whether its command is run in a terminal,
+    /// with the name and command that they are saved under. A shortcut that gains bindings
+    /// keeps its note unless another is entered.
+    fn drawer_details(&self) -> (DetailsKey, ShortcutDetails) {
+        let key = (self.add_shortcut.name.clone(), self.drawer_command());
+        let note = self.add_shortcut.note.text();
+
+        let note = Some(note.trim())
+            .filter(|note| !note.is_empty())
+            .map(str::to_owned)
+            .or_else(|| {
+                self.model
+                    .details_config()
+                    .remove(&key)
+                    .and_then(|details| details.note)
+            });
+
+        let details = ShortcutDetails {
+            note,
+            terminal: self.add_shortcut.terminal,
+        };
+
+        (key, details)
+    }
+
+    /// Names a binding and pairs it with the command of the shortcut being added.
+    fn drawer_shortcut(&self, mut binding: Binding) -> (Binding, Action) {
         binding.description = Some(self.add_shortcut.name.clone());
-        let new_action = Action::Spawn(self.add_shortcut.task.clone());
-        self.model.config_add(new_action, binding);
+        (binding, Action::Spawn(self.drawer_command()))
+    }
+
+    /// Saves new bindings to the config in a single write.
+    fn add_shortcuts(
+        &mut self,
+        bindings: impl IntoIterator<Item = (Binding, Action)>,
+    ) -> Result<(), cosmic_config::Error> {
+        self.add_shortcut.active &=
+            !self.replace_dialog.is_empty() || self.add_shortcut.keep_adding;
+        self.model
+            .config_edit(|shortcuts| shortcuts.0.extend(bindings))
+    }
+
+    /// Clears the drawer for the next shortcut once every binding of the one being added was
+    /// saved, if it is kept open for adding more.
+    fn continue_adding(&mut self) -> Task<crate::app::Message> {
+        if !self.add_shortcut.keep_adding
+            || !self.add_shortcut.active
+            || !self.replace_dialog.is_empty()
+        {
+            return Task::none();
+        }
+
+        self.add_shortcut.enable();
+        widget::text_input::focus(self.name_id.clone())
+    }
+
+    /// Adds the custom keybindings of a GNOME export, queueing conflicts for replacement.
+    #[cfg(feature = "xdg-portal")]
+    fn import_gnome(&mut self, dump: &str) -> Task<crate::app::Message> {
+        let mut addable_bindings = Vec::new();
+        let mut summary = ImportSummary::default();
+
+        for keybinding in super::gnome::custom_keybindings(dump) {
+            let Some(mut binding) = super::gnome::accelerator_to_binding(&keybinding.binding)
+                .and_then(|binding| super::parse_custom_binding(&binding).ok())
+            else {
+                tracing::warn!(
+                    accelerator = keybinding.binding,
+                    "skipping unsupported GNOME keybinding"
+                );
+                summary.failed += 1;
+                continue;
+            };
+
+            binding.description = Some(keybinding.name);
+            let new_action = Action::Spawn(keybinding.command);
+
+            match self.model.config_contains(&binding) {
+                // Already imported.
+                Some(action) if action == new_action => summary.unchanged += 1,
+
+                Some(action) => {
+                    let action_str = super::localize_action(&action);
+                    self.replace_dialog
+                        .push((binding, action, action_str, new_action));
+                    summary.conflicts += 1;
+                }
+
+                None => addable_bindings.push((binding, new_action)),
+            }
+        }
+
+        self.replace_total = self.replace_dialog.len();
+
+        // The bindings are written together, so either all of them are imported or none.
+        let addable = addable_bindings.len();
+        let result = self.add_shortcuts(addable_bindings);
+
+        if result.is_ok() {
+            summary.imported = addable;
+        } else {
+            summary.unsaved = addable;
+        }
+
+        self.import_summary = Some(summary);
+
+        self.model.on_enter();
+        self.model.apply_result(result)
+    }
+
+    /// Adds the shortcuts of an export, queueing conflicts for replacement.
+    #[cfg(feature = "xdg-portal")]
+    fn import_custom(&mut self, export: export::Export) -> Task<crate::app::Message> {
+        let mut addable_bindings = Vec::new();
+        let mut bound = Vec::new();
+
+        for (binding, new_action) in export.shortcuts.0 {
+            if !matches!(new_action, Action::Spawn(_)) {
+                continue;
+            }
+
+            match self.model.config_contains(&binding) {
+                Some(action) if action == new_action => {
+                    bound.extend(details_key(&binding, &new_action));
+                }
+
+                Some(action) => {
+                    let action_str = super::localize_action(&action);
+                    self.replace_dialog
+                        .push((binding, action, action_str, new_action));
+                }
+
+                None => addable_bindings.push((binding, new_action)),
+            }
+        }
+
+        self.replace_total = self.replace_dialog.len();
+
+        bound.extend(
+            addable_bindings
+                .iter()
+                .filter_map(|(binding, action)| details_key(binding, action)),
+        );
+
+        // Notes are saved with the shortcuts that they belong to, or wait on their
+        // replacements.
+        let saved = self.model.details_config();
+        let mut noted = Vec::new();
+
+        for (key, note) in export.notes {
+            let mut details = saved.get(&key).cloned().unwrap_or_default();
+            details.note = Some(note);
+
+            if bound.contains(&key) {
+                noted.push((key, details));
+            } else if self
+                .replace_dialog
+                .iter()
+                .any(|(binding, _, _, action)| details_key(binding, action).as_ref() == Some(&key))
+            {
+                self.pending_details.insert(key, details);
+            }
+        }
+
+        let result = noted
+            .into_iter()
+            .try_for_each(|(key, details)| self.model.details_save(key, details))
+            .and_then(|()| self.add_shortcuts(addable_bindings));
+
+        self.model.on_enter();
+        self.model.apply_result(result)
     }
 }
 
@@ -329,14 +1762,75 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
+        if self.confirm_reset {
+            let count = self.model.shortcut_models.len();
+
+            let primary_action = button::destructive(fl!("reset-custom-dialog", "remove"))
+                .on_press(Message::ResetAllCustom);
+
+            let secondary_action =
+                button::standard(fl!("cancel")).on_press(Message::ResetAllCustomCancel);
+
+            let dialog = widget::dialog()
+                .title(fl!("reset-custom-dialog"))
+                .icon(icon::from_name("dialog-warning").size(64))
+                .body(fl!("reset-custom-dialog", "desc", count = count))
+                .primary_action(primary_action)
+                .secondary_action(secondary_action);
+
+            #[cfg(feature = "xdg-portal")]
+            let dialog = dialog.tertiary_action(
+                button::text(fl!("reset-custom-dialog", "export")).on_press(Message::ExportCustom),
+            );
+
+            return Some(
+                dialog
+                    .apply(Element::from)
+                    .map(crate::pages::Message::CustomShortcuts),
+            );
+        }
+
+        if self.confirm_delete {
+            let count = self.selection.as_ref().map_or(0, BTreeSet::len);
+
+            let primary_action =
+                button::destructive(fl!("delete")).on_press(Message::DeleteSelectedApply);
+
+            let secondary_action =
+                button::standard(fl!("cancel")).on_press(Message::DeleteSelectedCancel);
+
+            let dialog = widget::dialog()
+                .title(fl!("delete-shortcuts-dialog"))
+                .icon(icon::from_name("dialog-warning").size(64))
+                .body(fl!("delete-shortcuts-dialog", "desc", count = count))
+                .primary_action(primary_action)
+                .secondary_action(secondary_action)
+                .apply(Element::from)
+                .map(crate::pages::Message::CustomShortcuts);
+
+            return Some(dialog);
+        }
+
         // Check if a new shortcut is being added that requires a replace dialog.
-        if let Some((binding, _action, action_str)) = self.replace_dialog.last() {
+        if let Some((binding, _action, action_str, _)) = self.replace_dialog.last() {
             let primary_action = button::suggested(fl!("replace")).on_press(Message::ReplaceApply);
 
             let secondary_action = button::standard(fl!("cancel")).on_press(Message::ReplaceCancel);
 
+            // Conflicts are resolved from the end of the queue.
+            let title = if self.replace_total > 1 {
+                fl!(
+                    "replace-shortcut-dialog",
+                    "progress",
+                    current = self.replace_total - self.replace_dialog.len() + 1,
+                    total = self.replace_total
+                )
+            } else {
+                fl!("replace-shortcut-dialog")
+            };
+
             let dialog = widget::dialog()
-                .title(fl!("replace-shortcut-dialog"))
+                .title(title)
                 .icon(icon::from_name("dialog-warning").size(64))
                 .body(fl!(
                     "replace-shortcut-dialog",
@@ -344,6 +1838,7 @@ impl page::Page<crate::pages::Message> for Page {
                     shortcut = binding.to_string(),
                     name = action_str.clone()
                 ))
+                .control(super::binding_emphasis(binding))
                 .primary_action(primary_action)
                 .secondary_action(secondary_action)
                 .apply(Element::from)
@@ -352,14 +1847,113 @@ impl page::Page<crate::pages::Message> for Page {
             return Some(dialog);
         }
 
+        if let Some(summary) = self.import_summary {
+            let mut lines = Vec::with_capacity(5);
+
+            if summary.imported > 0 {
+                lines.push(fl!(
+                    "import-gnome-dialog",
+                    "imported",
+                    count = summary.imported
+                ));
+            }
+
+            if summary.unchanged > 0 {
+                lines.push(fl!(
+                    "import-gnome-dialog",
+                    "unchanged",
+                    count = summary.unchanged
+                ));
+            }
+
+            if summary.conflicts > 0 {
+                lines.push(fl!(
+                    "import-gnome-dialog",
+                    "conflicts",
+                    count = summary.conflicts
+                ));
+            }
+
+            if summary.failed > 0 {
+                lines.push(fl!("import-gnome-dialog", "failed", count = summary.failed));
+            }
+
+            if summary.unsaved > 0 {
+                lines.push(fl!(
+                    "import-gnome-dialog",
+                    "unsaved",
+                    count = summary.unsaved
+                ));
+            }
+
+            if lines.is_empty() {
+                lines.push(fl!("import-gnome-dialog", "none"));
+            }
+
+            let control = lines
+                .into_iter()
+                .fold(widget::column().spacing(4), |column, line| {
+                    column.push(widget::text::body(line))
+                });
+
+            let dialog = widget::dialog()
+                .title(fl!("import-gnome-dialog"))
+                .icon(icon::from_name("dialog-information").size(64))
+                .control(control)
+                .primary_action(button::suggested(fl!("ok")).on_press(Message::ImportSummaryClose))
+                .apply(Element::from)
+                .map(crate::pages::Message::CustomShortcuts);
+
+            return Some(dialog);
+        }
+
         // Check if a keybinding is being added that requires a replace dialog.
         self.model
             .dialog()
             .map(|el| el.map(|m| crate::pages::Message::CustomShortcuts(Message::Shortcut(m))))
     }
 
+    fn context_title(&self) -> Option<String> {
+        if self.add_shortcut.active && self.add_shortcut.application_search.is_some() {
+            Some(fl!("custom-shortcuts", "pick-application"))
+        } else if self.add_shortcut.active {
+            Some(fl!("custom-shortcuts", "context"))
+        } else {
+            self.model
+                .shortcut_context
+                .map(|_| self.model.shortcut_title.clone())
+        }
+    }
+
     fn context_drawer(&self) -> Option<ContextDrawer<'_, crate::pages::Message>> {
-        if self.add_shortcut.active {
+        if let Some(search) = self
+            .add_shortcut
+            .application_search
+            .as_deref()
+            .filter(|_| self.add_shortcut.active)
+        {
+            let search_input = widget::search_input(fl!("type-to-search"), search)
+                .on_input(Message::ApplicationSearch)
+                .on_clear(Message::ApplicationSearch(String::new()))
+                .apply(Element::from)
+                .map(crate::pages::Message::CustomShortcuts);
+
+            let cancel_button = widget::button::standard(fl!("cancel"))
+                .on_press(Message::PickApplicationCancel)
+                .apply(Element::from)
+                .map(crate::pages::Message::CustomShortcuts);
+
+            Some(
+                cosmic::app::context_drawer(
+                    self.application_picker(search)
+                        .map(crate::pages::Message::CustomShortcuts),
+                    crate::pages::Message::CloseContextDrawer,
+                )
+                .title(fl!("custom-shortcuts", "pick-application"))
+                .header(search_input)
+                .footer(cancel_button),
+            )
+        } else if self.add_shortcut.active {
             Some(
                 cosmic::app::context_drawer(
                     self.add_keybinding_context()
@@ -382,10 +1976,15 @@ impl page::Page<crate::pages::Message> for Page {
 
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
         self.model.on_enter();
+        self.search_items = None;
         Task::none()
     }
 
     fn on_leave(&mut self) -> Task<crate::pages::Message> {
+        self.selection = None;
+        self.confirm_delete = false;
+        self.confirm_reset = false;
+        self.search_items = Some(search_items(&self.model.shortcut_models));
         self.model.on_clear();
         Task::none()
     }
@@ -398,10 +1997,9 @@ fn bindings(_defaults: &Shortcuts, keybindings: &Shortcuts) -> Slab<ShortcutMode
         .iter()
         .fold(Slab::new(), |mut slab, (binding, action)| {
             if let Action::Spawn(task) = action {
-                let description = binding
-                    .description
-                    .clone()
-                    .unwrap_or_else(|| task.to_owned());
+                // Checked against the commands saved to run in a terminal once they are loaded.
+                let terminal_command = terminal::unwrap(task);
+                let description = shortcut_name(binding, task);
 
                 let new_binding = ShortcutBinding {
                     id: widget::Id::unique(),
@@ -409,13 +2007,12 @@ fn bindings(_defaults: &Shortcuts, keybindings: &Shortcuts) -> Slab<ShortcutMode
                     input: String::new(),
                     is_default: false,
                     is_saved: true,
+                    default: None,
+                    invalid: false,
                 };
 
-                if let Some((_, existing_model)) =
-                    slab.iter_mut().find(|(_, m)| &m.action == action)
-                {
-                    existing_model.description = description;
-                    existing_model.bindings.insert(new_binding);
+                if let Some(id) = model_for_shortcut(&slab, action, &description) {
+                    slab[id].bindings.insert(new_binding);
                 } else {
                     slab.insert(ShortcutModel {
                         action: action.clone(),
@@ -426,6 +2023,8 @@ fn bindings(_defaults: &Shortcuts, keybindings: &Shortcuts) -> Slab<ShortcutMode
                         },
                         description,
                         modified: 0,
+                        note: None,
+                        terminal: terminal_command.is_some(),
                     });
                 }
             }
@@ -434,35 +2033,789 @@ fn bindings(_defaults: &Shortcuts, keybindings: &Shortcuts) -> Slab<ShortcutMode
         })
 }
 
-fn shortcuts() -> Section<crate::pages::Message> {
-    let descriptions = Slab::new();
+/// The name that a custom shortcut is listed under, given one of its bindings.
+fn shortcut_name(binding: &Binding, task: &str) -> String {
+    binding.description.clone().unwrap_or_else(|| {
+        let command = terminal::unwrap(task);
+        Command::parse(command.as_deref().unwrap_or(task)).display()
+    })
+}
 
-    // TODO: Add shortcuts to descriptions
+/// The names and commands of custom shortcuts, which the settings search matches.
+fn search_items(models: &Slab<ShortcutModel>) -> Vec<String> {
+    models
+        .iter()
+        .filter_map(|(_, model)| match &model.action {
+            Action::Spawn(command) => Some([model.description.clone(), command.clone()]),
+            _ => None,
+        })
+        .flatten()
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+/// The key that the details of the custom shortcut of a binding are saved under.
+fn details_key(binding: &Binding, action: &Action) -> Option<DetailsKey> {
+    match action {
+        Action::Spawn(task) => Some((shortcut_name(binding, task), task.clone())),
+        _ => None,
+    }
+}
+
+/// Pairs the shortcuts that are bound to the same key combination, with the combination they
+/// share, given the key combinations of each shortcut.
+///
+/// Bindings are told apart by their names too, so that two shortcuts may be saved with the same
+/// keys, and which of them the compositor runs is left to chance.
+fn conflicts(keys: impl IntoIterator<Item = (usize, String)>) -> Vec<(String, usize, usize)> {
+    let mut shortcuts_by_key = BTreeMap::<String, Vec<usize>>::new();
+
+    for (id, key) in keys {
+        let ids = shortcuts_by_key.entry(key).or_default();
+
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    let mut pairs = Vec::new();
+
+    for (key, ids) in shortcuts_by_key {
+        for (nth, &first) in ids.iter().enumerate() {
+            for &second in &ids[nth + 1..] {
+                pairs.push((key.clone(), first, second));
+            }
+        }
+    }
 
+    pairs
+}
+
+/// Finds the shortcut that a binding of an action is merged into. Shortcuts that spawn the
+/// same command are told apart by their names, so that each name is listed on its own.
+fn model_for_shortcut(
+    models: &Slab<ShortcutModel>,
+    action: &Action,
+    description: &str,
+) -> Option<usize> {
+    models
+        .iter()
+        .find(|(_, model)| {
+            &model.action == action
+                && (!matches!(action, Action::Spawn(_)) || model.description == description)
+        })
+        .map(|(id, _)| id)
+}
+
+fn shortcuts() -> Section<crate::pages::Message> {
     Section::default()
-        .descriptions(descriptions)
-        .view::<Page>(move |_binder, page, _section| {
+        .descriptions_from(&[
+            "custom-shortcuts.add",
+            "custom-shortcuts.none",
+            "custom-shortcuts.glyphs",
+            "custom-shortcuts.import-gnome",
+            "custom-shortcuts.select",
+            "shortcut-name",
+            "command",
+        ])
+        .search_items::<Page>(|page| match page.search_items.as_ref() {
+            Some(items) => items.clone(),
+            None => search_items(&page.model.shortcut_models),
+        })
+        .view::<Page>(move |binder, page, section| {
             let content = if page.model.shortcut_models.is_empty() {
                 widget::settings::section()
                     .add(widget::settings::item_row(vec![
                         widget::text::body(fl!("custom-shortcuts", "none")).into(),
                     ]))
                     .into()
+            } else if let Some(selection) = page.selection.as_ref() {
+                page.selection_view(selection)
             } else {
-                page.model.view().map(Message::Shortcut)
+                page.model.view(binder, section).map(Message::Shortcut)
             };
 
-            let add_shortcut = widget::button::standard(fl!("custom-shortcuts", "add"))
-                .on_press(Message::ShortcutContext)
+            let selection_controls = (!page.model.shortcut_models.is_empty()).then(|| {
+                let toggle_label = if page.selection.is_some() {
+                    fl!("cancel")
+                } else {
+                    fl!("custom-shortcuts", "select")
+                };
+
+                let delete_button = match page.selection.as_ref() {
+                    Some(selection) => {
+                        widget::button::destructive(fl!("custom-shortcuts", "delete-selected"))
+                            .on_press_maybe(
+                                (!selection.is_empty()).then_some(Message::DeleteSelected),
+                            )
+                    }
+                    None => widget::button::destructive(fl!("custom-shortcuts", "reset-all"))
+                        .on_press(Message::ResetAllCustomPrompt),
+                };
+
+                widget::row::with_capacity(2)
+                    .spacing(8)
+                    .push(
+                        widget::button::standard(toggle_label)
+                            .on_press(Message::ToggleSelectionMode),
+                    )
+                    .push(delete_button)
+            });
+
+            let add_shortcut = widget::row::with_capacity(4)
+                .spacing(8)
+                .push_maybe(selection_controls);
+
+            #[cfg(feature = "xdg-portal")]
+            let add_shortcut = add_shortcut
+                .push(
+                    widget::button::standard(fl!("custom-shortcuts", "import"))
+                        .on_press(Message::ImportCustom),
+                )
+                .push(
+                    widget::button::standard(fl!("custom-shortcuts", "import-gnome"))
+                        .on_press(Message::ImportGnome),
+                );
+
+            let add_shortcut = add_shortcut
+                .push(
+                    widget::button::standard(fl!("custom-shortcuts", "add"))
+                        .on_press(Message::ShortcutContext),
+                )
                 .apply(widget::container)
                 .width(Length::Fill)
                 .align_x(Alignment::End);
 
+            let apply_status = page
+                .model
+                .apply_status
+                .as_ref()
+                .map(super::common::apply_status_view);
+
+            // Derived from the shortcuts rather than stored as the section title, which search
+            // matches against.
+            let count = page.model.shortcut_models.len();
+            let header = (count != 0)
+                .then(|| widget::text::heading(fl!("custom-shortcuts", "count", count = count)));
+
+            let glyphs_toggle = widget::settings::item::builder(fl!("custom-shortcuts", "glyphs"))
+                .toggler(page.model.glyphs, Message::ShowGlyphs)
+                .apply(|item| widget::list_column().add(item));
+
             widget::column()
-                .push(content)
+                .push(glyphs_toggle)
+                .push_maybe(page.conflicts_view())
+                .push(
+                    widget::column::with_capacity(2)
+                        .spacing(8)
+                        .push_maybe(header)
+                        .push(content),
+                )
                 .push(add_shortcut)
+                .push_maybe(apply_status)
                 .spacing(24)
                 .apply(Element::from)
                 .map(crate::pages::Message::CustomShortcuts)
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AddShortcut, ImportSummary, InvalidBinding, Message, NAME_MAX, Page, RECENT_COMMANDS_MAX,
+        ShortcutMessage, conflicts, remember_command, sanitize_name, suggested_commands,
+    };
+    use cosmic_settings_config::Binding;
+    use cosmic_settings_config::shortcuts::{Action, Shortcuts};
+    use std::collections::VecDeque;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    /// Drives a custom shortcuts page with messages, discarding the tasks that they return.
+    ///
+    /// The page reads and writes a shortcuts config in a temporary directory.
+    struct Harness {
+        page: Page,
+        path: PathBuf,
+    }
+
+    impl Harness {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("cosmic-settings-{name}-{}", std::process::id()));
+
+            let mut page = Page::default();
+            page.model.config = cosmic_config::Config::with_custom_path(
+                "com.system76.CosmicSettings.Shortcuts",
+                1,
+                path.clone(),
+            )
+            .unwrap();
+
+            Self { page, path }
+        }
+
+        fn send(&mut self, messages: impl IntoIterator<Item = Message>) -> &mut Self {
+            for message in messages {
+                let _task = self.page.update(message);
+            }
+
+            self
+        }
+
+        /// Opens the add shortcut drawer and fills in every field.
+        fn add(&mut self, name: &str, command: &str, keys: &str) -> &mut Self {
+            self.send([
+                Message::ShortcutContext,
+                Message::NameInput(name.to_owned()),
+                Message::TaskInput(command.to_owned()),
+                Message::KeyInput(0, keys.to_owned()),
+                Message::AddShortcut,
+            ])
+        }
+
+        fn add_shortcut(&self) -> &AddShortcut {
+            &self.page.add_shortcut
+        }
+
+        fn replace_dialog(&self) -> &[(Binding, Action, String, Action)] {
+            &self.page.replace_dialog
+        }
+
+        fn custom_shortcuts(&self) -> Shortcuts {
+            self.page.model.shortcuts_config()
+        }
+    }
+
+    impl Drop for Harness {
+        fn drop(&mut self) {
+            _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn binding(keys: &str) -> Binding {
+        Binding::from_str(keys).unwrap()
+    }
+
+    #[test]
+    fn empty_name_is_not_added() {
+        let mut harness = Harness::new("empty-name");
+        harness.add("", "cosmic-term", "Super+t");
+
+        assert!(harness.add_shortcut().active);
+        assert!(harness.replace_dialog().is_empty());
+        assert!(harness.custom_shortcuts().0.is_empty());
+    }
+
+    #[test]
+    fn invalid_keys_are_described_once_submitted() {
+        let mut harness = Harness::new("invalid-keys");
+        harness.send([
+            Message::ShortcutContext,
+            Message::NameInput(String::from("Terminal")),
+            Message::TaskInput(String::from("cosmic-term")),
+            Message::KeyInput(0, String::from("Hyper+t")),
+        ]);
+
+        assert_eq!(harness.add_shortcut().keys[0].2, None);
+
+        harness.send([Message::AddKeybinding]);
+        assert_eq!(
+            harness.add_shortcut().keys[0].2,
+            Some(InvalidBinding::UnknownModifier(String::from("Hyper")))
+        );
+        assert_eq!(harness.add_shortcut().keys[0].0, "Hyper+t");
+
+        harness.send([Message::KeyInput(0, String::from("Super+Shift"))]);
+        assert_eq!(harness.add_shortcut().keys[0].2, None);
+
+        harness.send([Message::AddShortcut]);
+        assert_eq!(
+            harness.add_shortcut().keys[0].2,
+            Some(InvalidBinding::ModifiersOnly)
+        );
+        assert!(harness.custom_shortcuts().0.is_empty());
+    }
+
+    #[test]
+    fn conflict_requests_replacement() {
+        let mut harness = Harness::new("conflict");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.add("Files", "cosmic-files", "Super+t");
+
+        let [(conflict, existing, _, new)] = harness.replace_dialog() else {
+            panic!("expected a single replace request");
+        };
+
+        assert_eq!(*conflict, binding("Super+t"));
+        assert_eq!(*existing, Action::Spawn(String::from("cosmic-term")));
+        assert_eq!(*new, Action::Spawn(String::from("cosmic-files")));
+
+        harness.send([Message::ReplaceApply]);
+
+        assert!(harness.replace_dialog().is_empty());
+        assert_eq!(
+            harness.custom_shortcuts().0.get(&binding("Super+t")),
+            Some(&Action::Spawn(String::from("cosmic-files")))
+        );
+    }
+
+    #[test]
+    fn keep_adding_clears_drawer() {
+        let mut harness = Harness::new("keep-adding");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        assert!(!harness.add_shortcut().active);
+
+        harness.send([Message::KeepAdding(true)]);
+        harness.add("Files", "cosmic-files", "Super+f");
+
+        assert!(harness.add_shortcut().active);
+        assert!(harness.add_shortcut().name.is_empty());
+        assert!(harness.add_shortcut().task.is_empty());
+
+        // The drawer is only cleared once the conflicting binding is replaced.
+        harness.send([
+            Message::NameInput(String::from("Editor")),
+            Message::TaskInput(String::from("cosmic-edit")),
+            Message::KeyInput(0, String::from("Super+t")),
+            Message::AddShortcut,
+        ]);
+        assert_eq!(harness.add_shortcut().name, "Editor");
+
+        harness.send([Message::ReplaceApply]);
+
+        assert!(harness.add_shortcut().active);
+        assert!(harness.add_shortcut().name.is_empty());
+        assert_eq!(harness.custom_shortcuts().0.len(), 2);
+    }
+
+    #[test]
+    fn help_toggles_per_field() {
+        use super::Field;
+
+        let mut harness = Harness::new("help");
+        harness.send([
+            Message::ShortcutContext,
+            Message::ToggleHelp(Field::Command),
+        ]);
+        assert_eq!(harness.add_shortcut().help, Some(Field::Command));
+
+        harness.send([Message::ToggleHelp(Field::Keys)]);
+        assert_eq!(harness.add_shortcut().help, Some(Field::Keys));
+
+        harness.send([Message::ToggleHelp(Field::Keys)]);
+        assert_eq!(harness.add_shortcut().help, None);
+    }
+
+    #[test]
+    fn recent_commands() {
+        let mut recent = VecDeque::new();
+
+        for command in ["cosmic-term", "cosmic-files", " cosmic-term ", ""] {
+            remember_command(&mut recent, command);
+        }
+
+        assert_eq!(recent, ["cosmic-term", "cosmic-files"]);
+
+        for n in 0..RECENT_COMMANDS_MAX {
+            remember_command(&mut recent, &format!("command-{n}"));
+        }
+
+        assert_eq!(recent.len(), RECENT_COMMANDS_MAX);
+        assert!(!recent.iter().any(|command| command.starts_with("cosmic")));
+
+        let mut recent = VecDeque::new();
+        remember_command(&mut recent, "cosmic-files");
+        remember_command(&mut recent, "cosmic-term");
+        remember_command(&mut recent, "firefox");
+
+        let suggested = |input: &str| suggested_commands(&recent, input).collect::<Vec<_>>();
+        assert_eq!(suggested("Cosmic"), ["cosmic-term", "cosmic-files"]);
+        assert_eq!(suggested("firefox"), Vec::<&String>::new());
+        assert_eq!(suggested("").len(), 3);
+    }
+
+    #[test]
+    fn same_command_under_other_names_stays_separate() {
+        let mut harness = Harness::new("separate");
+        harness.add("Editor", "code", "Super+e");
+        harness.add("Notes", "code", "Super+n");
+
+        assert!(harness.replace_dialog().is_empty());
+        assert_eq!(harness.custom_shortcuts().0.len(), 2);
+
+        let models = &harness.page.model.shortcut_models;
+        assert_eq!(models.len(), 2);
+
+        let mut names = models
+            .iter()
+            .map(|(_, model)| model.description.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, ["Editor", "Notes"]);
+
+        // Deleting one of them leaves the other bound.
+        let editor = models
+            .iter()
+            .find(|(_, model)| model.description == "Editor")
+            .map(|(id, _)| id)
+            .unwrap();
+
+        harness.send([
+            Message::ToggleSelectionMode,
+            Message::ToggleSelect(editor),
+            Message::DeleteSelectedApply,
+        ]);
+
+        let custom = harness.custom_shortcuts();
+        assert_eq!(custom.0.len(), 1);
+        assert!(custom.0.contains_key(&binding("Super+n")));
+    }
+
+    #[test]
+    fn details_are_kept_per_shortcut() {
+        let mut harness = Harness::new("details");
+        harness.send([
+            Message::ShortcutContext,
+            Message::NameInput(String::from("Editor")),
+            Message::TaskInput(String::from("code")),
+            Message::KeyInput(0, String::from("Super+e")),
+        ]);
+        harness.page.add_shortcut.note = cosmic::widget::text_editor::Content::with_text("Work");
+        harness.send([Message::AddShortcut]);
+        harness.add("Notes", "code", "Super+n");
+
+        let note_of = |harness: &Harness, name: &str| {
+            harness
+                .page
+                .model
+                .shortcut_models
+                .iter()
+                .find(|(_, model)| model.description == name)
+                .map(|(id, model)| (id, model.note.clone()))
+        };
+
+        let (editor, note) = note_of(&harness, "Editor").unwrap();
+        assert_eq!(note.as_deref(), Some("Work"));
+        assert_eq!(note_of(&harness, "Notes").unwrap().1, None);
+
+        // The details of a deleted shortcut are deleted with it.
+        harness.send([
+            Message::ToggleSelectionMode,
+            Message::ToggleSelect(editor),
+            Message::DeleteSelectedApply,
+        ]);
+
+        assert!(harness.page.model.details_config().is_empty());
+    }
+
+    #[test]
+    fn deleting_a_shortcut_deletes_its_details() {
+        let mut harness = Harness::new("delete-details");
+        harness.send([
+            Message::ShortcutContext,
+            Message::NameInput(String::from("Top")),
+            Message::TaskInput(String::from("htop")),
+            Message::KeyInput(0, String::from("Super+h")),
+            Message::RunInTerminal(true),
+            Message::AddShortcut,
+        ]);
+
+        assert_eq!(harness.page.model.details_config().len(), 1);
+
+        let (id, _) = harness.page.model.shortcut_models.iter().next().unwrap();
+        harness.send([Message::Shortcut(ShortcutMessage::DeleteShortcut(id))]);
+
+        assert!(harness.custom_shortcuts().0.is_empty());
+        assert!(harness.page.model.details_config().is_empty());
+    }
+
+    #[test]
+    fn note_waits_on_replacement() {
+        let mut harness = Harness::new("pending-note");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.send([
+            Message::ShortcutContext,
+            Message::NameInput(String::from("Files")),
+            Message::TaskInput(String::from("cosmic-files")),
+            Message::KeyInput(0, String::from("Super+t")),
+        ]);
+        harness.page.add_shortcut.note = cosmic::widget::text_editor::Content::with_text("Home");
+        harness.send([Message::AddShortcut]);
+
+        // Nothing is saved of a shortcut whose only binding is not replaced.
+        assert_eq!(harness.replace_dialog().len(), 1);
+        assert!(harness.page.model.details_config().is_empty());
+
+        harness.send([Message::ReplaceApply]);
+
+        let details = harness.page.model.details_config();
+        let key = (String::from("Files"), String::from("cosmic-files"));
+        assert_eq!(details[&key].note.as_deref(), Some("Home"));
+    }
+
+    #[test]
+    fn note_is_edited_in_drawer() {
+        let mut harness = Harness::new("edit-note");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+
+        let (id, _) = harness.page.model.shortcut_models.iter().next().unwrap();
+        harness.send([Message::Shortcut(ShortcutMessage::ShowShortcut(
+            id,
+            String::from("Terminal"),
+        ))]);
+        harness.page.model.note = cosmic::widget::text_editor::Content::with_text("Work");
+        harness.send([Message::Shortcut(ShortcutMessage::SaveNote)]);
+
+        assert_eq!(
+            harness.page.model.shortcut_models[id].note.as_deref(),
+            Some("Work")
+        );
+
+        harness.page.model.on_enter();
+        let (_, model) = harness.page.model.shortcut_models.iter().next().unwrap();
+        assert_eq!(model.note.as_deref(), Some("Work"));
+    }
+
+    #[test]
+    fn search_items_follow_the_shortcuts() {
+        use cosmic_settings_page::Page as _;
+
+        let mut harness = Harness::new("search-items");
+        let _task = harness.page.on_enter();
+        harness.add("Terminal", "cosmic-term", "Super+t");
+
+        // While the page is shown, searches match the shortcuts that it loaded.
+        assert_eq!(harness.page.search_items, None);
+
+        let _task = harness.page.on_leave();
+        assert_eq!(
+            harness.page.search_items.as_deref(),
+            Some(&[String::from("Terminal"), String::from("cosmic-term")][..])
+        );
+    }
+
+    #[test]
+    fn names_are_sanitized() {
+        assert_eq!(sanitize_name("Open\nterminal\r\n"), "Open terminal  ");
+        assert_eq!(sanitize_name("Tab\tand\u{7}bell"), "Tab andbell");
+
+        let long = "é".repeat(NAME_MAX + 10);
+        assert_eq!(sanitize_name(&long).chars().count(), NAME_MAX);
+
+        let mut harness = Harness::new("name");
+        harness.send([
+            Message::ShortcutContext,
+            Message::NameInput(format!("Editor\n{long}")),
+        ]);
+
+        let name = &harness.add_shortcut().name;
+        assert!(name.starts_with("Editor "));
+        assert_eq!(name.chars().count(), NAME_MAX);
+    }
+
+    #[test]
+    fn reset_all_keeps_system_shortcuts() {
+        let mut harness = Harness::new("reset-all");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.add("Files", "cosmic-files", "Super+f");
+
+        // A system shortcut that was rebound by the user is stored with the custom shortcuts.
+        let mut shortcuts = harness.custom_shortcuts();
+        shortcuts.0.insert(binding("Super+q"), Action::Close);
+        harness.page.model.shortcuts_config_set(shortcuts).unwrap();
+        harness.page.model.on_enter();
+
+        harness.send([Message::ResetAllCustomPrompt]);
+        assert!(harness.page.confirm_reset);
+
+        harness.send([Message::ResetAllCustom]);
+
+        assert!(!harness.page.confirm_reset);
+        assert_eq!(
+            harness.custom_shortcuts().0.into_iter().collect::<Vec<_>>(),
+            vec![(binding("Super+q"), Action::Close)]
+        );
+    }
+
+    #[cfg(feature = "xdg-portal")]
+    #[test]
+    fn gnome_import_summary() {
+        const EXPORT: &str = "[custom-keybindings/custom0]
+binding='<Super>t'
+command='cosmic-term'
+name='Terminal'
+
+[custom-keybindings/custom1]
+binding='<Super><Alt>y'
+command='cosmic-files'
+name='Files'
+
+[custom-keybindings/custom2]
+binding='<Hyper>x'
+command='xterm'
+name='XTerm'
+";
+
+        let mut harness = Harness::new("gnome-import");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        let _task = harness.page.import_gnome(EXPORT);
+
+        assert_eq!(
+            harness.page.import_summary,
+            Some(ImportSummary {
+                imported: 1,
+                unchanged: 1,
+                conflicts: 0,
+                failed: 1,
+                unsaved: 0,
+            })
+        );
+
+        harness.send([Message::ImportSummaryClose]);
+        assert_eq!(harness.page.import_summary, None);
+    }
+
+    #[cfg(feature = "xdg-portal")]
+    #[test]
+    fn gnome_import_counts_unsaved_shortcuts() {
+        const EXPORT: &str = "[custom-keybindings/custom0]
+binding='<Super>t'
+command='cosmic-term'
+name='Terminal'
+";
+
+        let mut harness = Harness::new("gnome-import-unsaved");
+
+        // Writes fail while a file stands in place of the config directory.
+        _ = std::fs::remove_dir_all(&harness.path);
+        std::fs::write(&harness.path, "").unwrap();
+
+        let _task = harness.page.import_gnome(EXPORT);
+        _ = std::fs::remove_file(&harness.path);
+
+        assert_eq!(
+            harness.page.import_summary,
+            Some(ImportSummary {
+                imported: 0,
+                unchanged: 0,
+                conflicts: 0,
+                failed: 0,
+                unsaved: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_requests_replacement() {
+        let mut harness = Harness::new("duplicate");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+
+        assert_eq!(harness.replace_dialog().len(), 1);
+
+        harness.send([Message::ReplaceCancel]);
+
+        assert!(harness.replace_dialog().is_empty());
+        assert_eq!(harness.custom_shortcuts().0.len(), 1);
+    }
+
+    #[test]
+    fn sections_are_searchable() {
+        let mut binder = cosmic_settings_page::Binder::<crate::pages::Message>::default();
+        let id = binder.register::<Page>().id();
+
+        let sections = binder.page_sections(id).collect::<Vec<_>>();
+        let [(_, section)] = sections[..] else {
+            panic!("expected a single section");
+        };
+
+        for description in [fl!("custom-shortcuts", "add"), fl!("command")] {
+            assert!(section.descriptions.iter().any(|(_, d)| *d == description));
+        }
+    }
+
+    #[test]
+    fn aliased_keys_conflict() {
+        let mut harness = Harness::new("aliased-keys");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.add("Files", "cosmic-files", "T+Meta");
+
+        let [(conflict, ..)] = harness.replace_dialog() else {
+            panic!("expected a single replace request");
+        };
+
+        assert_eq!(*conflict, binding("Super+t"));
+    }
+
+    #[test]
+    fn arrow_keys_move_between_key_rows() {
+        let mut harness = Harness::new("arrow-keys");
+        harness.send([
+            Message::ShortcutContext,
+            Message::KeyInput(0, String::from("Super+t")),
+            Message::AddKeybinding,
+        ]);
+
+        let rows = harness
+            .add_shortcut()
+            .keys
+            .iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        let [first, second] = rows[..] else {
+            panic!("expected two key rows");
+        };
+
+        assert_eq!(harness.add_shortcut().editing, Some(second));
+
+        harness.send([Message::FocusPreviousKey]);
+        assert_eq!(harness.add_shortcut().editing, Some(first));
+
+        // There is no row above the first one.
+        harness.send([Message::FocusPreviousKey]);
+        assert_eq!(harness.add_shortcut().editing, Some(first));
+
+        harness.send([Message::FocusNextKey]);
+        assert_eq!(harness.add_shortcut().editing, Some(second));
+    }
+
+    #[test]
+    fn same_command_joins_existing_shortcut() {
+        let mut harness = Harness::new("merge");
+        harness.add("Terminal", "cosmic-term", "Super+t");
+        harness.add("Terminal", "cosmic-term", "Super+Shift+t");
+
+        assert!(harness.replace_dialog().is_empty());
+        assert_eq!(harness.custom_shortcuts().0.len(), 2);
+
+        let models = &harness.page.model.shortcut_models;
+        assert_eq!(models.len(), 1);
+
+        let (_, model) = models.iter().next().unwrap();
+        assert_eq!(model.description, "Terminal");
+        assert_eq!(model.bindings.len(), 2);
+    }
+
+    #[test]
+    fn conflicting_keys() {
+        let keys = [
+            (0, String::from("Super+T")),
+            (1, String::from("Super+T")),
+            (1, String::from("Super+E")),
+            (2, String::from("Super+T")),
+            // A shortcut bound twice to the same keys does not conflict with itself.
+            (3, String::from("Super+B")),
+            (3, String::from("Super+B")),
+        ];
+
+        assert_eq!(
+            conflicts(keys),
+            vec![
+                (String::from("Super+T"), 0, 1),
+                (String::from("Super+T"), 0, 2),
+                (String::from("Super+T"), 1, 2),
+            ]
+        );
+    }
+}
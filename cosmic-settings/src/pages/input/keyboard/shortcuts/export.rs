@@ -0,0 +1,71 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The file that custom shortcuts are exported to, and imported back from.
+
+use std::collections::BTreeMap;
+
+use cosmic_settings_config::shortcuts::Shortcuts;
+use serde::{Deserialize, Serialize};
+
+use super::DetailsKey;
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct Export {
+    pub shortcuts: Shortcuts,
+    /// Notes of the shortcuts, keyed by their name and command.
+    #[serde(default)]
+    pub notes: BTreeMap<DetailsKey, String>,
+}
+
+impl Export {
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|why| why.to_string())
+    }
+
+    /// Reads an export, or the shortcuts alone as they were once exported.
+    pub fn from_ron(export: &str) -> Result<Self, String> {
+        ron::from_str::<Self>(export).or_else(|why| {
+            ron::from_str::<Shortcuts>(export)
+                .map(|shortcuts| Self {
+                    shortcuts,
+                    notes: BTreeMap::new(),
+                })
+                .map_err(|_| why.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Export;
+    use cosmic_settings_config::Binding;
+    use cosmic_settings_config::shortcuts::Action;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trip() {
+        let mut export = Export::default();
+        export.shortcuts.0.insert(
+            Binding::from_str("Super+t").unwrap(),
+            Action::Spawn(String::from("cosmic-term")),
+        );
+        export.notes.insert(
+            (String::from("Terminal"), String::from("cosmic-term")),
+            String::from("Opens a new window"),
+        );
+
+        let imported = Export::from_ron(&export.to_ron().unwrap()).unwrap();
+        assert_eq!(imported.shortcuts.0, export.shortcuts.0);
+        assert_eq!(imported.notes, export.notes);
+
+        // Older exports hold the shortcuts alone.
+        let legacy = ron::ser::to_string(&export.shortcuts).unwrap();
+        let imported = Export::from_ron(&legacy).unwrap();
+        assert_eq!(imported.shortcuts.0, export.shortcuts.0);
+        assert!(imported.notes.is_empty());
+
+        assert!(Export::from_ron("not an export").is_err());
+    }
+}
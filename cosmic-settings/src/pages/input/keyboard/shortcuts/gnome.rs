@@ -0,0 +1,132 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Imports custom keybindings from a `dconf dump` of GNOME's media-keys settings.
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Keybinding {
+    pub name: String,
+    pub command: String,
+    pub binding: String,
+}
+
+/// Parses every custom keybinding found in the output of
+/// `dconf dump /org/gnome/settings-daemon/plugins/media-keys/`.
+pub fn custom_keybindings(dump: &str) -> Vec<Keybinding> {
+    let mut keybindings = Vec::new();
+    let mut current: Option<Keybinding> = None;
+
+    for line in dump.lines().map(str::trim) {
+        if line.starts_with('[') {
+            keybindings.extend(current.take().filter(is_complete));
+            current = Some(Keybinding::default());
+            continue;
+        }
+
+        let (Some(keybinding), Some((key, value))) = (current.as_mut(), line.split_once('='))
+        else {
+            continue;
+        };
+
+        let Some(value) = string_value(value.trim()) else {
+            continue;
+        };
+
+        match key.trim() {
+            "name" => keybinding.name = value,
+            "command" => keybinding.command = value,
+            "binding" => keybinding.binding = value,
+            _ => (),
+        }
+    }
+
+    keybindings.extend(current.filter(is_complete));
+    keybindings
+}
+
+/// Translates a GNOME accelerator such as `<Super><Shift>Return` into a binding
+/// string such as `Super+Shift+Return`.
+pub fn accelerator_to_binding(accelerator: &str) -> Option<String> {
+    let mut binding = String::new();
+    let mut rest = accelerator.trim();
+
+    while let Some(modifier) = rest.strip_prefix('<') {
+        let (modifier, remaining) = modifier.split_once('>')?;
+
+        binding.push_str(match modifier.to_ascii_lowercase().as_str() {
+            "super" => "Super",
+            "primary" | "control" | "ctrl" => "Ctrl",
+            "alt" | "mod1" => "Alt",
+            "shift" => "Shift",
+            _ => return None,
+        });
+
+        binding.push('+');
+        rest = remaining;
+    }
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    binding.push_str(rest);
+    Some(binding)
+}
+
+fn is_complete(keybinding: &Keybinding) -> bool {
+    !keybinding.command.is_empty() && !keybinding.binding.is_empty()
+}
+
+/// Reads a single-quoted GVariant string.
+fn string_value(value: &str) -> Option<String> {
+    let value = value.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        if char == '\\' {
+            unescaped.extend(chars.next());
+        } else {
+            unescaped.push(char);
+        }
+    }
+
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Keybinding, accelerator_to_binding, custom_keybindings};
+
+    #[test]
+    fn dconf_dump() {
+        const EXAMPLE: &str = "[/]
+custom-keybindings=['/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/custom0/']
+home=['<Super>e']
+
+[custom-keybindings/custom0]
+binding='<Super><Shift>Return'
+command='sh -c \\'echo hi\\''
+name='Terminal'
+";
+
+        assert_eq!(
+            custom_keybindings(EXAMPLE),
+            vec![Keybinding {
+                name: String::from("Terminal"),
+                command: String::from("sh -c 'echo hi'"),
+                binding: String::from("<Super><Shift>Return"),
+            }]
+        );
+    }
+
+    #[test]
+    fn accelerator() {
+        assert_eq!(
+            accelerator_to_binding("<Primary><Alt>t").as_deref(),
+            Some("Ctrl+Alt+t")
+        );
+        assert_eq!(accelerator_to_binding("<Super>"), None);
+        assert_eq!(accelerator_to_binding("<Hyper>a"), None);
+    }
+}
@@ -1,19 +1,30 @@
 // Copyright 2024 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+mod applications;
+mod command;
 mod common;
 
-pub use common::{Model, ShortcutBinding, ShortcutMessage, ShortcutModel};
+pub use common::{
+    DetailsKey, Layout, Model, ShortcutBinding, ShortcutDetails, ShortcutMessage, ShortcutModel,
+};
 
+pub mod conflicts;
 pub mod custom;
+#[cfg(feature = "xdg-portal")]
+mod export;
+#[cfg(feature = "xdg-portal")]
+mod gnome;
 pub mod manage_windows;
 pub mod move_window;
 pub mod nav;
 pub mod system;
+mod terminal;
 pub mod tiling;
+pub mod validate;
 
 use cosmic::app::ContextDrawer;
-use cosmic::iced::Length;
+use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{self, icon, settings, text};
 use cosmic::{Apply, Element, Task, theme};
 use cosmic_config::ConfigGet;
@@ -29,10 +40,24 @@ use shortcuts::action::System as SystemAction;
 use slab::Slab;
 use slotmap::{DefaultKey, Key, SecondaryMap, SlotMap};
 use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::Stdio;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 pub struct Page {
     entity: page::Entity,
     modified: Modified,
+    /// Number of key combinations that more than one shortcut is bound to.
+    conflicts: usize,
+    /// System shortcuts whose bindings differ from their defaults.
+    changed: Vec<ChangedShortcut>,
+    /// Custom shortcuts that resetting the changed shortcuts removes, with their names.
+    displaced: Vec<(Binding, String)>,
+    /// Whether resetting every changed shortcut awaits confirmation.
+    confirm_reset: bool,
     search: Search,
     search_model: Model,
     shortcuts_context: Option<cosmic_config::Config>,
@@ -50,6 +75,7 @@ struct Modified {
 }
 
 struct SubPages {
+    conflicts: page::Entity,
     custom: page::Entity,
     manage_window: page::Entity,
     move_window: page::Entity,
@@ -67,9 +93,21 @@ struct Search {
     defaults: Shortcuts,
 }
 
+/// A system shortcut whose bindings differ from its defaults.
+struct ChangedShortcut {
+    action: Action,
+    description: String,
+    current: Vec<Binding>,
+    default: Vec<Binding>,
+}
+
 #[derive(Clone, Debug)]
 pub enum Message {
     Category(Category),
+    Conflicts,
+    ResetAll,
+    ResetAllCancel,
+    ResetAllPrompt,
     Search(String),
     SearchShortcut(ShortcutMessage),
 }
@@ -89,10 +127,15 @@ impl Default for Page {
         Self {
             entity: page::Entity::default(),
             modified: Modified::default(),
+            conflicts: 0,
+            changed: Vec::new(),
+            displaced: Vec::new(),
+            confirm_reset: false,
             search: Search::default(),
             search_model: Model::default(),
             shortcuts_context: None,
             sub_pages: SubPages {
+                conflicts: page::Entity::null(),
                 custom: page::Entity::null(),
                 manage_window: page::Entity::null(),
                 move_window: page::Entity::null(),
@@ -134,6 +177,21 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn dialog(&self) -> Option<Element<'_, crate::pages::Message>> {
+        if self.confirm_reset {
+            return Some(
+                reset_dialog(
+                    fl!("reset-shortcuts-dialog"),
+                    fl!("reset-shortcuts-dialog", "desc", count = self.changed.len()),
+                    fl!("reset-shortcuts-dialog", "reset"),
+                    &self.changed,
+                    &self.displaced,
+                    Message::ResetAll,
+                    Message::ResetAllCancel,
+                )
+                .map(crate::pages::Message::KeyboardShortcuts),
+            );
+        }
+
         if self.search_model.shortcut_models.is_empty() {
             None
         } else {
@@ -144,6 +202,93 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
+        self.load();
+        Task::none()
+    }
+
+    fn on_leave(&mut self) -> Task<crate::pages::Message> {
+        self.search.actions = SlotMap::new();
+        self.search.localized = SecondaryMap::new();
+        self.search.input = String::new();
+        self.search_model.on_clear();
+        self.modified.custom = 0;
+        self.modified.manage_windows = 0;
+        self.modified.move_windows = 0;
+        self.modified.nav = 0;
+        self.modified.system = 0;
+        Task::none()
+    }
+}
+
+impl Page {
+    pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
+        match message {
+            Message::Category(category) => match category {
+                Category::Custom => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.custom))
+                }
+
+                Category::ManageWindow => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.manage_window))
+                }
+
+                Category::MoveWindow => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.move_window))
+                }
+
+                Category::Nav => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.nav))
+                }
+
+                Category::System => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.system))
+                }
+
+                Category::WindowTiling => {
+                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.window_tiling))
+                }
+            },
+
+            Message::Conflicts => {
+                cosmic::task::message(crate::app::Message::Page(self.sub_pages.conflicts))
+            }
+
+            Message::ResetAll => {
+                self.confirm_reset = false;
+
+                let mut custom = self.search_model.shortcuts_config();
+                for changed in &self.changed {
+                    common::reset_to_default(&mut custom, &self.search.defaults, &changed.action);
+                }
+
+                let result = self.search_model.shortcuts_config_set(custom);
+                self.load();
+                self.search_model.apply_result(result)
+            }
+
+            Message::ResetAllCancel => {
+                self.confirm_reset = false;
+                Task::none()
+            }
+
+            Message::ResetAllPrompt => {
+                self.confirm_reset = !self.changed.is_empty();
+                Task::none()
+            }
+
+            Message::Search(input) => {
+                self.search(input);
+                Task::none()
+            }
+
+            Message::SearchShortcut(message) => self.search_model.update(message),
+        }
+    }
+
+    /// Compares the shortcuts in the config with their defaults.
+    fn load(&mut self) {
+        self.modified = Modified::default();
+
         if self.shortcuts_context.is_none() {
             self.shortcuts_context = cosmic_settings_config::shortcuts::context().ok();
         }
@@ -196,63 +341,22 @@ impl page::Page<crate::pages::Message> for Page {
             }
 
             self.search.defaults = defaults.clone();
-            defaults.0.extend(custom.0);
+            defaults.0.extend(custom.0.clone());
+            self.conflicts = conflicts::conflicts(&defaults).len();
+            self.changed = changed_shortcuts(&self.search.defaults, &defaults);
+            self.displaced = self
+                .changed
+                .iter()
+                .flat_map(|changed| {
+                    common::displaced_by_reset(&custom, &self.search.defaults, &changed.action)
+                })
+                .map(|(binding, action)| {
+                    let name = localize_custom_action(&action, &binding);
+                    (binding, name)
+                })
+                .collect();
             self.search.shortcuts = defaults;
         }
-
-        Task::none()
-    }
-
-    fn on_leave(&mut self) -> Task<crate::pages::Message> {
-        self.search.actions = SlotMap::new();
-        self.search.localized = SecondaryMap::new();
-        self.search.input = String::new();
-        self.search_model.on_clear();
-        self.modified.custom = 0;
-        self.modified.manage_windows = 0;
-        self.modified.move_windows = 0;
-        self.modified.nav = 0;
-        self.modified.system = 0;
-        Task::none()
-    }
-}
-
-impl Page {
-    pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
-        match message {
-            Message::Category(category) => match category {
-                Category::Custom => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.custom))
-                }
-
-                Category::ManageWindow => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.manage_window))
-                }
-
-                Category::MoveWindow => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.move_window))
-                }
-
-                Category::Nav => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.nav))
-                }
-
-                Category::System => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.system))
-                }
-
-                Category::WindowTiling => {
-                    cosmic::task::message(crate::app::Message::Page(self.sub_pages.window_tiling))
-                }
-            },
-
-            Message::Search(input) => {
-                self.search(input);
-                Task::none()
-            }
-
-            Message::SearchShortcut(message) => self.search_model.update(message),
-        }
     }
 
     fn search(&mut self, input: String) {
@@ -273,6 +377,7 @@ impl page::AutoBind<crate::pages::Message> for Page {
     fn sub_pages(
         mut page: cosmic_settings_page::Insert<crate::pages::Message>,
     ) -> cosmic_settings_page::Insert<crate::pages::Message> {
+        let conflicts = page.sub_page_with_id::<conflicts::Page>();
         let custom = page.sub_page_with_id::<custom::Page>();
         let manage_window = page.sub_page_with_id::<manage_windows::Page>();
         let move_window = page.sub_page_with_id::<move_window::Page>();
@@ -281,6 +386,7 @@ impl page::AutoBind<crate::pages::Message> for Page {
         let window_tiling = page.sub_page_with_id::<tiling::Page>();
 
         let model = page.model.page_mut::<Page>().unwrap();
+        model.sub_pages.conflicts = conflicts;
         model.sub_pages.custom = custom;
         model.sub_pages.manage_window = manage_window;
         model.sub_pages.move_window = move_window;
@@ -311,7 +417,11 @@ impl Search {
         self.localized.clear();
         let custom_actions = self.retrieve_custom_actions();
         for action in all_system_actions() {
-            let localized = localize_action(action);
+            let mut localized = localize_action(action);
+            if let Some(keywords) = action_keywords(action) {
+                localized.push('\n');
+                localized.push_str(&keywords);
+            }
             let id = self.actions.insert(action.clone());
             self.localized.insert(id, localized);
         }
@@ -360,6 +470,8 @@ impl Search {
 fn shortcuts() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
+    let conflicts_label = descriptions.insert(fl!("shortcut-conflicts"));
+    let reset_label = descriptions.insert(fl!("reset-shortcuts"));
     let custom_label = descriptions.insert(fl!("custom"));
     let manage_window_label = descriptions.insert(fl!("manage-windows"));
     let move_window_label = descriptions.insert(fl!("move-windows"));
@@ -369,7 +481,7 @@ fn shortcuts() -> Section<crate::pages::Message> {
 
     Section::default()
         .descriptions(descriptions)
-        .view::<Page>(move |_binder, page, section| {
+        .view::<Page>(move |binder, page, section| {
             let descriptions = &section.descriptions;
 
             let search = widget::search_input(fl!("type-to-search"), &page.search.input)
@@ -413,15 +525,30 @@ fn shortcuts() -> Section<crate::pages::Message> {
                         &descriptions[custom_label],
                         page.modified.custom,
                     ))
+                    .add(conflicts_item(
+                        &descriptions[conflicts_label],
+                        page.conflicts,
+                    ))
                     .apply(Element::from)
             } else {
-                page.search_model.view().map(Message::SearchShortcut)
+                page.search_model
+                    .view(binder, section)
+                    .map(Message::SearchShortcut)
             };
 
-            widget::column::with_capacity(2)
+            let reset_button = page.search.input.is_empty().then(|| {
+                widget::button::standard(&descriptions[reset_label])
+                    .on_press_maybe((!page.changed.is_empty()).then_some(Message::ResetAllPrompt))
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Alignment::End)
+            });
+
+            widget::column::with_capacity(3)
                 .spacing(32)
                 .push(search)
                 .push(content)
+                .push_maybe(reset_button)
                 .apply(Element::from)
                 .map(crate::pages::Message::KeyboardShortcuts)
         })
@@ -451,6 +578,137 @@ fn category_item(category: Category, name: &str, modified: u16) -> Element<Messa
         .into()
 }
 
+/// Links to the conflicting shortcuts, with how many key combinations conflict.
+fn conflicts_item(name: &str, conflicts: usize) -> Element<Message> {
+    let icon = icon::from_name("go-next-symbolic").size(16);
+
+    let control = if conflicts == 0 {
+        Element::from(icon)
+    } else {
+        widget::row()
+            .push(text::body(fl!(
+                "shortcut-conflicts",
+                "count",
+                count = conflicts
+            )))
+            .push(icon)
+            .into()
+    };
+
+    settings::item::builder(name)
+        .control(control)
+        .spacing(16)
+        .apply(widget::container)
+        .class(theme::Container::List)
+        .apply(widget::button::custom)
+        .class(theme::Button::Transparent)
+        .on_press(Message::Conflicts)
+        .into()
+}
+
+/// Lists each changed shortcut with its current and default bindings, and the custom shortcuts
+/// that are removed, for confirming that all of them are reset.
+fn reset_dialog<'a, Message: Clone + 'static>(
+    title: String,
+    description: String,
+    reset: String,
+    changed: &'a [ChangedShortcut],
+    displaced: &'a [(Binding, String)],
+    confirm: Message,
+    cancel: Message,
+) -> Element<'a, Message> {
+    let bindings = |bindings: &[Binding]| {
+        if bindings.is_empty() {
+            fl!("disabled")
+        } else {
+            bindings.iter().map(ToString::to_string).join(", ")
+        }
+    };
+
+    let row = |name: Element<'static, Message>, current: String, default: String| {
+        widget::row::with_capacity(3)
+            .spacing(8)
+            .push(widget::container(name).width(Length::Fill))
+            .push(text::body(current).width(Length::Fixed(140.0)))
+            .push(text::body(default).width(Length::Fixed(140.0)))
+    };
+
+    let header = row(
+        text::heading(fl!("reset-shortcuts-dialog", "shortcut")).into(),
+        fl!("reset-shortcuts-dialog", "current"),
+        fl!("reset-shortcuts-dialog", "default"),
+    );
+
+    let diff = changed
+        .iter()
+        .fold(
+            widget::column::with_capacity(changed.len()).spacing(8),
+            |column, changed| {
+                column.push(row(
+                    text::body(changed.description.clone()).into(),
+                    bindings(&changed.current),
+                    bindings(&changed.default),
+                ))
+            },
+        )
+        .apply(widget::scrollable)
+        .apply(widget::container)
+        .max_height(320);
+
+    // Custom shortcuts on the keys of defaults are removed for the defaults to be restored.
+    let displaced = (!displaced.is_empty()).then(|| {
+        displaced.iter().fold(
+            widget::column::with_capacity(displaced.len() + 1)
+                .spacing(8)
+                .push(text::heading(fl!("reset-shortcuts-dialog", "removed"))),
+            |column, (binding, name)| {
+                column.push(row(
+                    text::body(name.clone()).into(),
+                    binding.to_string(),
+                    fl!("reset-shortcuts-dialog", "removed-binding"),
+                ))
+            },
+        )
+    });
+
+    widget::dialog()
+        .title(title)
+        .icon(icon::from_name("dialog-warning").size(64))
+        .body(description)
+        .control(
+            widget::column::with_capacity(3)
+                .spacing(8)
+                .push(header)
+                .push(diff)
+                .push_maybe(displaced),
+        )
+        .primary_action(widget::button::destructive(reset).on_press(confirm))
+        .secondary_action(widget::button::standard(fl!("cancel")).on_press(cancel))
+        .into()
+}
+
+/// The system shortcuts whose bindings differ from their defaults, as the shortcut model of
+/// each finds them.
+fn changed_shortcuts(defaults: &Shortcuts, shortcuts: &Shortcuts) -> Vec<ChangedShortcut> {
+    all_system_actions()
+        .iter()
+        .filter_map(|action| {
+            let model = ShortcutModel::new(defaults, shortcuts, action.clone());
+
+            (model.modified != 0).then(|| ChangedShortcut {
+                current: model
+                    .bindings
+                    .iter()
+                    .map(|(_, shortcut)| shortcut.binding.clone())
+                    .collect(),
+                default: defaults.shortcuts(action).cloned().collect(),
+                description: model.description,
+                action: model.action,
+            })
+        })
+        .collect()
+}
+
 fn action_category(action: &Action) -> Option<Category> {
     Some(if manage_windows::actions().contains(action) {
         Category::ManageWindow
@@ -550,6 +808,8 @@ fn all_system_actions() -> &'static [Action] {
         Action::Workspace(7),
         Action::Workspace(8),
         Action::Workspace(9),
+        Action::ZoomIn,
+        Action::ZoomOut,
     ]
 }
 
@@ -678,6 +938,605 @@ fn localize_action(action: &Action) -> String {
     }
 }
 
+/// Other words for an action, which searches for shortcuts also match.
+fn action_keywords(action: &Action) -> Option<String> {
+    let keywords = match action {
+        Action::System(system) => match system {
+            SystemAction::AppLibrary => fl!("shortcut-keywords", "app-library"),
+            SystemAction::BrightnessDown => fl!("shortcut-keywords", "brightness-down"),
+            SystemAction::BrightnessUp => fl!("shortcut-keywords", "brightness-up"),
+            SystemAction::InputSourceSwitch => fl!("shortcut-keywords", "input-source-switch"),
+            SystemAction::HomeFolder => fl!("shortcut-keywords", "home-folder"),
+            SystemAction::KeyboardBrightnessDown => {
+                fl!("shortcut-keywords", "keyboard-brightness-down")
+            }
+            SystemAction::KeyboardBrightnessUp => {
+                fl!("shortcut-keywords", "keyboard-brightness-up")
+            }
+            SystemAction::Launcher => fl!("shortcut-keywords", "launcher"),
+            SystemAction::LogOut => fl!("shortcut-keywords", "log-out"),
+            SystemAction::LockScreen => fl!("shortcut-keywords", "lock-screen"),
+            SystemAction::Mute => fl!("shortcut-keywords", "mute"),
+            SystemAction::MuteMic => fl!("shortcut-keywords", "mute-mic"),
+            SystemAction::PlayPause => fl!("shortcut-keywords", "play-pause"),
+            SystemAction::PlayNext => fl!("shortcut-keywords", "play-next"),
+            SystemAction::PlayPrev => fl!("shortcut-keywords", "play-prev"),
+            SystemAction::Screenshot => fl!("shortcut-keywords", "screenshot"),
+            SystemAction::Terminal => fl!("shortcut-keywords", "terminal"),
+            SystemAction::VolumeLower => fl!("shortcut-keywords", "volume-lower"),
+            SystemAction::VolumeRaise => fl!("shortcut-keywords", "volume-raise"),
+            SystemAction::WebBrowser => fl!("shortcut-keywords", "web-browser"),
+            SystemAction::WindowSwitcher => fl!("shortcut-keywords", "window-switcher"),
+            SystemAction::WindowSwitcherPrevious => {
+                fl!("shortcut-keywords", "window-switcher-previous")
+            }
+            SystemAction::WorkspaceOverview => fl!("shortcut-keywords", "workspace-overview"),
+        },
+        Action::ZoomIn => fl!("shortcut-keywords", "zoom-in"),
+        Action::ZoomOut => fl!("shortcut-keywords", "zoom-out"),
+        _ => return None,
+    };
+
+    Some(keywords)
+}
+
+/// Gets the key combinations bound to an action, with custom bindings overriding the defaults.
+pub fn action_bindings(action: &Action) -> Vec<Binding> {
+    let Ok(config) = shortcuts::context() else {
+        return Vec::new();
+    };
+
+    let mut shortcuts = config.get::<Shortcuts>("defaults").unwrap_or_default();
+
+    if let Ok(custom) = config.get::<Shortcuts>("custom") {
+        shortcuts.0.extend(custom.0);
+    }
+
+    shortcuts
+        .shortcuts(action)
+        .filter(|binding| binding.is_set())
+        .cloned()
+        .collect()
+}
+
+/// Checks if a key combination is handled by the compositor itself, and thus can never trigger a shortcut.
+pub fn is_reserved(binding: &Binding) -> bool {
+    let binding = binding.to_string();
+
+    // Ctrl+Alt+F1 through Ctrl+Alt+F12 are used for switching virtual terminals.
+    (1..=12).any(|n| {
+        Binding::from_str(&format!("Ctrl+Alt+F{n}"))
+            .is_ok_and(|reserved| reserved.to_string() == binding)
+    })
+}
+
+/// Why a key combination cannot be bound to a custom shortcut.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InvalidBinding {
+    /// The key combination could not be parsed.
+    Unparsable,
+    /// No key combination was given.
+    Empty,
+    /// A modifier of the key combination is not known.
+    UnknownModifier(String),
+    /// The key of the key combination is not known.
+    UnknownKey(String),
+    /// The key combination has no key.
+    Unset,
+    /// The key combination has several modifiers but no other key, so it would never fire.
+    ModifiersOnly,
+    /// The key combination is handled by the compositor itself.
+    Reserved,
+}
+
+impl InvalidBinding {
+    /// A localized description of the error for the key combination `input`.
+    pub fn localized(&self, input: &str) -> String {
+        match self {
+            Self::Unparsable => fl!("binding-error", "unparsable"),
+            Self::Empty => fl!("binding-error", "empty"),
+            Self::UnknownModifier(modifier) => {
+                fl!(
+                    "binding-error",
+                    "unknown-modifier",
+                    modifier = modifier.as_str()
+                )
+            }
+            Self::UnknownKey(key) => fl!("binding-error", "unknown-key", key = key.as_str()),
+            Self::Unset => fl!("binding-error", "unset"),
+            Self::ModifiersOnly => fl!("binding-error", "modifiers-only", shortcut = input),
+            Self::Reserved => fl!("reserved-shortcut", shortcut = input),
+        }
+    }
+}
+
+impl std::fmt::Display for InvalidBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unparsable => f.write_str("not a valid key combination"),
+            Self::Empty => f.write_str("no key combination was given"),
+            Self::UnknownModifier(modifier) => write!(f, "unknown modifier: {modifier}"),
+            Self::UnknownKey(key) => write!(f, "unknown key: {key}"),
+            Self::Unset => f.write_str("no key is set"),
+            Self::ModifiersOnly => f.write_str("only modifiers were given"),
+            Self::Reserved => f.write_str("reserved by the compositor"),
+        }
+    }
+}
+
+/// Modifiers by the name they are written with in a canonical key combination, in the order
+/// that they are written, along with the other names they are known by.
+const MODIFIERS: [(&str, &[&str]); 4] = [
+    (
+        "Super",
+        &["super", "meta", "logo", "win", "windows", "mod4"],
+    ),
+    ("Ctrl", &["ctrl", "control"]),
+    ("Alt", &["alt", "mod1"]),
+    ("Shift", &["shift"]),
+];
+
+/// Writes a key combination in the one form that each binding has, so that combinations
+/// which differ only in their modifier names, modifier order, or the case of a letter key
+/// are seen as the same binding: `Meta+T` and `T+Super` are both written as `Super+t`.
+pub fn canonical_keys(input: &str) -> String {
+    let mut modifiers = [false; MODIFIERS.len()];
+    let mut keys = Vec::new();
+
+    for part in input.split('+').map(str::trim) {
+        let lowercase = part.to_lowercase();
+
+        match MODIFIERS
+            .iter()
+            .position(|(_, names)| names.contains(&lowercase.as_str()))
+        {
+            Some(modifier) => modifiers[modifier] = true,
+            // Letters are the same key whether or not shift is written with them.
+            None if part.len() == 1 && part.chars().all(|c| c.is_ascii_alphabetic()) => {
+                keys.push(lowercase);
+            }
+            None => keys.push(part.to_owned()),
+        }
+    }
+
+    MODIFIERS
+        .iter()
+        .zip(modifiers)
+        .filter_map(|((name, _), set)| set.then(|| (*name).to_owned()))
+        .chain(keys)
+        .join("+")
+}
+
+/// Whether a key combination is made of more than one modifier and nothing else, such as
+/// `Super+Shift`.
+///
+/// A lone modifier is bound on purpose, as `Super` opens the launcher when it is tapped, but
+/// the compositor never fires a combination of modifiers that lacks a key.
+fn is_modifiers_only(input: &str) -> bool {
+    let mut modifiers = [false; MODIFIERS.len()];
+
+    for part in input.split('+').map(str::trim) {
+        let lowercase = part.to_lowercase();
+
+        match MODIFIERS
+            .iter()
+            .position(|(_, names)| names.contains(&lowercase.as_str()))
+        {
+            Some(modifier) => modifiers[modifier] = true,
+            None => return false,
+        }
+    }
+
+    modifiers.iter().filter(|&&set| set).count() > 1
+}
+
+/// A binding in its canonical form, for comparing bindings that were parsed from differently
+/// written key combinations.
+pub fn canonical_binding(binding: &Binding) -> Binding {
+    let mut canonical = parse_binding(&binding.to_string()).unwrap_or_else(|_| binding.clone());
+    canonical.description.clone_from(&binding.description);
+    canonical
+}
+
+/// Parses a key combination, describing which part of it is invalid on failure.
+pub fn parse_binding(input: &str) -> Result<Binding, InvalidBinding> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(InvalidBinding::Empty);
+    }
+
+    // Checked before parsing, which may read the last modifier as the key.
+    if is_modifiers_only(input) {
+        return Err(InvalidBinding::ModifiersOnly);
+    }
+
+    if let Ok(binding) = Binding::from_str(&canonical_keys(input)) {
+        return Ok(binding);
+    }
+
+    let mut keys = input.split('+').map(str::trim);
+    let key = keys.next_back().unwrap_or_default();
+
+    // Test each modifier alone against a key that is known to be valid.
+    if let Some(modifier) =
+        keys.find(|modifier| Binding::from_str(&format!("{modifier}+a")).is_err())
+    {
+        return Err(InvalidBinding::UnknownModifier(modifier.to_owned()));
+    }
+
+    Err(InvalidBinding::UnknownKey(key.to_owned()))
+}
+
+/// Parses a key combination for a custom shortcut.
+pub fn parse_custom_binding(input: &str) -> Result<Binding, InvalidBinding> {
+    let binding = parse_binding(input)?;
+
+    if !binding.is_set() {
+        return Err(InvalidBinding::Unset);
+    }
+
+    if is_reserved(&binding) {
+        return Err(InvalidBinding::Reserved);
+    }
+
+    Ok(binding)
+}
+
+/// A warning to display beneath a key row if its input is a reserved key combination.
+fn reserved_warning<'a, Message: 'static>(input: &str) -> Option<Element<'a, Message>> {
+    Binding::from_str(input)
+        .ok()
+        .filter(is_reserved)
+        .map(|binding| {
+            text::caption(fl!("reserved-shortcut", shortcut = binding.to_string())).into()
+        })
+}
+
+/// A warning to display beneath a key row if its input has modifiers but no key.
+fn modifiers_only_warning<'a, Message: 'static>(input: &str) -> Option<Element<'a, Message>> {
+    is_modifiers_only(input)
+        .then(|| text::caption(InvalidBinding::ModifiersOnly.localized(input.trim())).into())
+}
+
+/// How a key row's input was reached, which decides where its caret goes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FocusBy {
+    /// Clicked, which selects the whole key combination so that typing replaces it.
+    Pointer,
+    /// Reached from the keyboard, which places the caret at the end so that keys can be appended.
+    Keyboard,
+}
+
+/// Focuses a key row's input, placing its caret by how the row was reached.
+fn focus_key_input<Message: 'static>(id: widget::Id, by: FocusBy) -> Task<Message> {
+    match by {
+        FocusBy::Pointer => select_key_input(id),
+        FocusBy::Keyboard => Task::batch(vec![
+            widget::text_input::focus(id.clone()),
+            widget::text_input::move_cursor_to_end(id),
+        ]),
+    }
+}
+
+/// Focuses a key row's input with its key combination selected, as it is when the page
+/// itself moves the focus there.
+fn select_key_input<Message: 'static>(id: widget::Id) -> Task<Message> {
+    Task::batch(vec![
+        widget::text_input::focus(id.clone()),
+        widget::text_input::select_all(id),
+    ])
+}
+
+/// Wraps a key row's input in a red border with an error icon while its key combination
+/// is invalid.
+///
+/// The wrapper is kept when the input is valid so that the input keeps its focus and
+/// caret as its validity changes.
+fn validated_input<'a, Message: 'static>(
+    input: impl Into<Element<'a, Message>>,
+    invalid: bool,
+) -> Element<'a, Message> {
+    let error_icon = invalid.then(|| icon::from_name("dialog-error-symbolic").size(16).icon());
+
+    widget::row::with_capacity(2)
+        .align_y(cosmic::iced::Alignment::Center)
+        .spacing(theme::spacing().space_xxs)
+        .push(input)
+        .push_maybe(error_icon)
+        .apply(widget::container)
+        .padding([0, theme::spacing().space_xxs, 0, 0])
+        .class(if invalid {
+            theme::Container::custom(|theme| {
+                let cosmic = theme.cosmic();
+                widget::container::Style {
+                    icon_color: Some(cosmic.destructive_color().into()),
+                    border: cosmic::iced::Border {
+                        color: cosmic.destructive_color().into(),
+                        radius: cosmic.corner_radii.radius_s.into(),
+                        width: 1.0,
+                    },
+                    ..Default::default()
+                }
+            })
+        } else {
+            theme::Container::Transparent
+        })
+        .into()
+}
+
+/// Checks if the input of a key row is a key combination that cannot be bound.
+fn is_invalid_input(input: &str) -> bool {
+    match parse_binding(input) {
+        Ok(binding) => is_reserved(&binding),
+        // Empty rows are cleared rather than bound.
+        Err(why) => why != InvalidBinding::Empty,
+    }
+}
+
+/// Converts a key combination into a symbol for each of its keys, using text for keys without one.
+pub fn binding_to_glyphs(binding: &Binding) -> Vec<String> {
+    binding
+        .to_string()
+        .split('+')
+        .map(|key| key_glyph(key).map_or_else(|| key.to_owned(), String::from))
+        .collect()
+}
+
+fn key_glyph(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "Super" => "❖",
+        "Ctrl" => "⌃",
+        "Alt" => "⌥",
+        "Shift" => "⇧",
+        "Return" => "↵",
+        "BackSpace" => "⌫",
+        "Delete" => "⌦",
+        "Escape" => "⎋",
+        "Tab" => "⇥",
+        "space" => "␣",
+        "Up" => "↑",
+        "Down" => "↓",
+        "Left" => "←",
+        "Right" => "→",
+        "Home" => "⇱",
+        "End" => "⇲",
+        "Page_Up" => "⇞",
+        "Page_Down" => "⇟",
+        _ => return None,
+    })
+}
+
+/// Displays a key combination as a row of symbol badges.
+fn binding_glyphs_view<'a, Message: 'static>(binding: &Binding) -> Element<'a, Message> {
+    binding_to_glyphs(binding)
+        .into_iter()
+        .fold(widget::row().spacing(4), |row, glyph| {
+            row.push(
+                text::body(glyph)
+                    .apply(widget::container)
+                    .padding([2, 6])
+                    .class(theme::Container::Card),
+            )
+        })
+        .into()
+}
+
+/// Displays a key combination prominently in a monospaced font.
+fn binding_emphasis<'a, Message: 'static>(binding: &Binding) -> Element<'a, Message> {
+    text::title4(binding.to_string())
+        .font(cosmic::font::mono())
+        .apply(widget::container)
+        .center_x(Length::Fill)
+        .into()
+}
+
+/// Spawns a command the same way that the compositor spawns an `Action::Spawn`.
+async fn spawn(command: String) -> Result<(), String> {
+    let mut child = tokio::process::Command::new("sh")
+        .args(["-c", &command])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|why| why.to_string())?;
+
+    // Reap the process in the background so that it does not linger as a zombie.
+    tokio::spawn(async move {
+        _ = child.wait().await;
+    });
+
+    Ok(())
+}
+
+/// Most bytes of each output stream that a test run keeps.
+const OUTPUT_LIMIT: usize = 16 * 1024;
+
+/// How long a test run is waited on before it is reported as still running.
+const OUTPUT_WAIT: Duration = Duration::from_secs(5);
+
+/// How a test run of a command ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpawnExit {
+    Code(i32),
+    Signal(i32),
+    /// Still running once the wait was over, as applications keep running until they are
+    /// closed.
+    Running,
+}
+
+impl SpawnExit {
+    pub fn localized(self) -> String {
+        match self {
+            Self::Code(code) => fl!("spawn-output", "exit-code", code = code),
+            Self::Signal(signal) => fl!("spawn-output", "signal", signal = signal),
+            Self::Running => fl!("spawn-output", "running"),
+        }
+    }
+}
+
+/// What a test run of a command wrote, and how it ended.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpawnOutput {
+    pub exit: SpawnExit,
+    pub stdout: String,
+    pub stderr: String,
+    /// Whether either stream wrote more than is kept.
+    pub truncated: bool,
+}
+
+impl SpawnOutput {
+    /// The output as it is copied, with the stream that each part came from.
+    pub fn to_report(&self) -> String {
+        let mut report = self.exit.localized();
+
+        for (name, output) in [("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if !output.is_empty() {
+                report.push_str("\n\n");
+                report.push_str(name);
+                report.push_str(":\n");
+                report.push_str(output.trim_end());
+            }
+        }
+
+        if self.truncated {
+            report.push_str("\n\n");
+            report.push_str(&fl!(
+                "spawn-output",
+                "truncated",
+                limit = OUTPUT_LIMIT / 1024
+            ));
+        }
+
+        report
+    }
+}
+
+/// Spawns a command as [`spawn`] does, but captures what it writes and how it exits, for
+/// diagnosing a command that fails.
+///
+/// Commands that keep running are left running, and only the output written while they
+/// were waited on is kept.
+async fn spawn_captured(command: String) -> Result<SpawnOutput, String> {
+    let mut child = tokio::process::Command::new("sh")
+        .args(["-c", &command])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|why| why.to_string())?;
+
+    let stdout = Arc::new(Mutex::new((Vec::new(), false)));
+    let stderr = Arc::new(Mutex::new((Vec::new(), false)));
+
+    // Streams are drained until they close, even past the limit, so that a command that
+    // keeps writing never blocks on a full pipe.
+    let mut readers = Vec::with_capacity(2);
+    if let Some(pipe) = child.stdout.take() {
+        readers.push(tokio::spawn(drain(pipe, stdout.clone())));
+    }
+    if let Some(pipe) = child.stderr.take() {
+        readers.push(tokio::spawn(drain(pipe, stderr.clone())));
+    }
+
+    let exit = match tokio::time::timeout(OUTPUT_WAIT, child.wait()).await {
+        Ok(Ok(status)) => {
+            // Background processes of the command may hold the streams open.
+            _ = tokio::time::timeout(Duration::from_millis(100), async {
+                for reader in readers {
+                    _ = reader.await;
+                }
+            })
+            .await;
+
+            match (status.code(), status.signal()) {
+                (Some(code), _) => SpawnExit::Code(code),
+                (None, Some(signal)) => SpawnExit::Signal(signal),
+                (None, None) => SpawnExit::Code(-1),
+            }
+        }
+        Ok(Err(why)) => return Err(why.to_string()),
+        Err(_) => {
+            // Reap the process in the background so that it does not linger as a zombie.
+            tokio::spawn(async move {
+                _ = child.wait().await;
+            });
+
+            SpawnExit::Running
+        }
+    };
+
+    let (stdout, stdout_truncated) = stdout.lock().unwrap().clone();
+    let (stderr, stderr_truncated) = stderr.lock().unwrap().clone();
+
+    Ok(SpawnOutput {
+        exit,
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        truncated: stdout_truncated || stderr_truncated,
+    })
+}
+
+/// Shows what a test run wrote, with a button that copies it.
+fn spawn_output_view(output: &SpawnOutput) -> Element<'_, ()> {
+    let header = widget::row::with_capacity(2)
+        .spacing(8)
+        .align_y(cosmic::iced::Alignment::Center)
+        .push(text::heading(output.exit.localized()).width(Length::Fill))
+        .push(widget::button::standard(fl!("copy-to-clipboard")).on_press(()));
+
+    let stream = |label: String, output: &str| {
+        (!output.is_empty()).then(|| {
+            widget::column::with_capacity(2)
+                .spacing(4)
+                .push(text::caption(label))
+                .push(text::body(output.trim_end()).font(cosmic::font::mono()))
+        })
+    };
+
+    let empty = (output.stdout.is_empty() && output.stderr.is_empty())
+        .then(|| text::caption(fl!("spawn-output", "empty")));
+
+    let truncated = output.truncated.then(|| {
+        text::caption(fl!(
+            "spawn-output",
+            "truncated",
+            limit = OUTPUT_LIMIT / 1024
+        ))
+    });
+
+    widget::column::with_capacity(5)
+        .spacing(8)
+        .push(header)
+        .push_maybe(stream(fl!("spawn-output", "stdout"), &output.stdout))
+        .push_maybe(stream(fl!("spawn-output", "stderr"), &output.stderr))
+        .push_maybe(empty)
+        .push_maybe(truncated)
+        .apply(widget::container)
+        .padding(12)
+        .width(Length::Fill)
+        .class(cosmic::theme::Container::Card)
+        .into()
+}
+
+/// Reads a stream until it closes, keeping what fits in the output limit.
+async fn drain(mut pipe: impl AsyncRead + Unpin, output: Arc<Mutex<(Vec<u8>, bool)>>) {
+    let mut chunk = [0; 4096];
+
+    while let Ok(read @ 1..) = pipe.read(&mut chunk).await {
+        let (kept, truncated) = &mut *output.lock().unwrap();
+        *truncated |= append_limited(kept, &chunk[..read], OUTPUT_LIMIT);
+    }
+}
+
+/// Appends as much of a chunk as fits in the limit, and returns whether any of it did not.
+fn append_limited(output: &mut Vec<u8>, chunk: &[u8], limit: usize) -> bool {
+    let fits = limit.saturating_sub(output.len()).min(chunk.len());
+    output.extend_from_slice(&chunk[..fits]);
+    fits < chunk.len()
+}
+
 fn localize_custom_action(action: &Action, binding: &Binding) -> String {
     if let Some(description) = &binding.description {
         description.to_string()
@@ -685,3 +1544,105 @@ fn localize_custom_action(action: &Action, binding: &Binding) -> String {
         localize_action(action)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InvalidBinding, append_limited, canonical_keys, changed_shortcuts, parse_binding,
+        parse_custom_binding,
+    };
+    use cosmic_settings_config::shortcuts::{Action, Shortcuts};
+
+    #[test]
+    fn binding_errors() {
+        assert_eq!(parse_binding("  ").err(), Some(InvalidBinding::Empty));
+
+        assert_eq!(
+            parse_binding("Hyper+a").err(),
+            Some(InvalidBinding::UnknownModifier(String::from("Hyper")))
+        );
+
+        assert_eq!(
+            parse_binding("Super+NotAKey").err(),
+            Some(InvalidBinding::UnknownKey(String::from("NotAKey")))
+        );
+
+        assert_eq!(
+            parse_custom_binding("Ctrl+Alt+F1").err(),
+            Some(InvalidBinding::Reserved)
+        );
+
+        assert!(parse_custom_binding("Super+Return").is_ok());
+    }
+
+    #[test]
+    fn modifiers_only() {
+        for input in ["Super+Shift", "ctrl + alt", "Shift+Meta+Control"] {
+            assert_eq!(
+                parse_custom_binding(input).err(),
+                Some(InvalidBinding::ModifiersOnly)
+            );
+        }
+
+        // A lone modifier is set, and bound when the modifier is tapped.
+        let tap = parse_custom_binding("Super").unwrap();
+        assert!(tap.is_set());
+        assert_eq!(tap.to_string(), "Super");
+
+        // Repeating one modifier is still a lone modifier.
+        assert!(parse_binding("Super+Meta").is_ok());
+        assert!(parse_custom_binding("Super+Shift+T").unwrap().is_set());
+    }
+
+    #[test]
+    fn equivalent_bindings() {
+        let super_t = parse_binding("Super+T").unwrap();
+
+        assert_eq!(parse_binding("Meta+T").unwrap(), super_t);
+        assert_eq!(parse_binding("T+Super").unwrap(), super_t);
+        assert_eq!(parse_binding("super + t").unwrap(), super_t);
+        assert_ne!(parse_binding("Super+Shift+T").unwrap(), super_t);
+
+        assert_eq!(canonical_keys("T+Super"), "Super+t");
+        assert_eq!(
+            canonical_keys("shift+Control+Meta+Return"),
+            "Super+Ctrl+Shift+Return"
+        );
+        assert_eq!(canonical_keys("Hyper+a"), "Hyper+a");
+    }
+
+    #[test]
+    fn output_is_limited() {
+        let mut output = Vec::new();
+
+        assert!(!append_limited(&mut output, b"hello ", 8));
+        assert!(append_limited(&mut output, b"world", 8));
+        assert_eq!(output, b"hello wo");
+
+        // Nothing more is kept once the limit is reached.
+        assert!(append_limited(&mut output, b"!", 8));
+        assert!(!append_limited(&mut output, b"", 8));
+        assert_eq!(output, b"hello wo");
+    }
+
+    #[test]
+    fn changed_shortcuts_are_diffed() {
+        let binding = |keys| parse_binding(keys).unwrap();
+
+        let mut defaults = Shortcuts::default();
+        defaults.0.insert(binding("Super+q"), Action::Close);
+        defaults.0.insert(binding("Super+m"), Action::Maximize);
+
+        let mut shortcuts = defaults.clone();
+        shortcuts.0.insert(binding("Super+q"), Action::Disable);
+        shortcuts.0.insert(binding("Super+w"), Action::Close);
+
+        let [changed] = &changed_shortcuts(&defaults, &shortcuts)[..] else {
+            panic!("expected only the close shortcut to differ");
+        };
+
+        assert_eq!(changed.action, Action::Close);
+        assert_eq!(changed.current, [binding("Super+w")]);
+        assert_eq!(changed.default, [binding("Super+q")]);
+    }
+}
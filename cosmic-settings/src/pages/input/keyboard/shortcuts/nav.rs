@@ -115,7 +115,13 @@ fn shortcuts() -> Section<crate::pages::Message> {
 
     Section::default()
         .descriptions(descriptions)
-        .view::<Page>(move |_binder, page, _section| {
-            page.model.view().map(crate::pages::Message::NavShortcuts)
+        .on_reset::<Page>(|page| {
+            page.model
+                .reset_category(crate::pages::Message::NavShortcuts)
+        })
+        .view::<Page>(move |binder, page, section| {
+            page.model
+                .category_view(binder, section)
+                .map(crate::pages::Message::NavShortcuts)
         })
 }
@@ -102,6 +102,9 @@ pub const fn actions() -> &'static [Action] {
         Action::System(SystemAction::Terminal),
         Action::System(SystemAction::HomeFolder),
         Action::System(SystemAction::WebBrowser),
+        // Zooming in starts the magnifier, and zooming all the way out stops it.
+        Action::ZoomIn,
+        Action::ZoomOut,
     ]
 }
 
@@ -111,13 +114,20 @@ fn shortcuts() -> Section<crate::pages::Message> {
     // Make these searchable in the global settings search.
     for action in actions() {
         descriptions.insert(super::localize_action(action));
+        if let Some(keywords) = super::action_keywords(action) {
+            descriptions.insert(keywords);
+        }
     }
 
     Section::default()
         .descriptions(descriptions)
-        .view::<Page>(move |_binder, page, _section| {
+        .on_reset::<Page>(|page| {
+            page.model
+                .reset_category(crate::pages::Message::SystemShortcuts)
+        })
+        .view::<Page>(move |binder, page, section| {
             page.model
-                .view()
+                .category_view(binder, section)
                 .map(crate::pages::Message::SystemShortcuts)
         })
 }
@@ -0,0 +1,80 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Commands of custom shortcuts that are run in a terminal, so that their output can be seen.
+//!
+//! Such a command is stored as the terminal running `sh -c` with the command, and the stored
+//! string is remembered in the config so that it is not mistaken for a command typed that way.
+
+use cosmic_config::ConfigGet;
+use cosmic_settings_config::shortcuts::SystemActions;
+use cosmic_settings_config::shortcuts::action::System;
+
+use super::command::{Command, quote};
+
+/// Run when the desktop has no terminal set.
+const FALLBACK: &str = "cosmic-term";
+
+/// The terminal that the desktop opens, without the field codes of its desktop entry.
+pub fn default_terminal(config: &cosmic_config::Config) -> String {
+    let exec = config
+        .get::<SystemActions>("system_actions")
+        .ok()
+        .and_then(|actions| actions.get(&System::Terminal).cloned())
+        .unwrap_or_default();
+
+    let terminal = exec
+        .split_whitespace()
+        .filter(|word| !word.starts_with('%'))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if terminal.is_empty() {
+        FALLBACK.to_owned()
+    } else {
+        terminal
+    }
+}
+
+/// The command that runs a command in a terminal.
+pub fn wrap(terminal: &str, command: &str) -> String {
+    format!(
+        "{terminal} -e {} {} {}",
+        quote("sh"),
+        quote("-c"),
+        quote(command)
+    )
+}
+
+/// The command that a terminal was given by [`wrap`].
+pub fn unwrap(spawn: &str) -> Option<String> {
+    let (_terminal, arguments) = spawn.split_once(" -e ")?;
+
+    match Command::parse(arguments) {
+        Command::Arguments(arguments) => match <[String; 3]>::try_from(arguments) {
+            Ok([shell, flag, command]) if shell == "sh" && flag == "-c" => Some(command),
+            _ => None,
+        },
+        Command::Shell(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unwrap, wrap};
+
+    #[test]
+    fn round_trip() {
+        let command = "journalctl -f | grep 'kernel: '";
+        let spawn = wrap("cosmic-term", command);
+
+        assert_eq!(
+            spawn,
+            r"cosmic-term -e 'sh' '-c' 'journalctl -f | grep '\''kernel: '\'''"
+        );
+        assert_eq!(unwrap(&spawn).as_deref(), Some(command));
+
+        assert_eq!(unwrap("xterm -e htop"), None);
+        assert_eq!(unwrap("cosmic-term"), None);
+    }
+}
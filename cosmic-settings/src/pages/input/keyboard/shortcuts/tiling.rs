@@ -99,9 +99,13 @@ fn shortcuts() -> Section<crate::pages::Message> {
 
     Section::default()
         .descriptions(descriptions)
-        .view::<Page>(move |_binder, page, _section| {
+        .on_reset::<Page>(|page| {
             page.model
-                .view()
+                .reset_category(crate::pages::Message::TilingShortcuts)
+        })
+        .view::<Page>(move |binder, page, section| {
+            page.model
+                .category_view(binder, section)
                 .map(crate::pages::Message::TilingShortcuts)
         })
 }
@@ -0,0 +1,229 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Lints a shortcuts config file without applying it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use cosmic_settings_config::Binding;
+use serde::Serialize;
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+
+use super::InvalidBinding;
+
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// Number of shortcuts in the config.
+    pub entries: usize,
+    /// Shortcuts whose key combination cannot be used.
+    pub invalid: Vec<InvalidEntry>,
+    /// Key combinations bound by more than one shortcut.
+    pub duplicates: Vec<String>,
+    /// Why the config could not be read, if it could not be.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.invalid.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvalidEntry {
+    pub binding: String,
+    pub reason: InvalidBinding,
+}
+
+/// Validates every key combination of a shortcuts config, such as the `custom` key of
+/// `com.system76.CosmicSettings.Shortcuts`.
+pub fn validate(config: &str) -> Report {
+    let mut report = Report::default();
+
+    let keys = match ron::from_str::<Keys>(config) {
+        Ok(Keys(keys)) => keys,
+        Err(why) => {
+            report.error = Some(why.to_string());
+            return report;
+        }
+    };
+
+    let mut seen = BTreeMap::<String, usize>::new();
+
+    for key in keys {
+        report.entries += 1;
+
+        let binding = match key.clone().into_rust::<Binding>() {
+            Ok(binding) => binding.to_string(),
+            Err(_) => {
+                report.invalid.push(InvalidEntry {
+                    binding: format!("{key:?}"),
+                    reason: InvalidBinding::Unparsable,
+                });
+                continue;
+            }
+        };
+
+        // Reparse the binding just as a shortcut added in the UI would be.
+        match super::parse_custom_binding(&binding) {
+            Ok(binding) => *seen.entry(binding.to_string()).or_default() += 1,
+            Err(reason) => report.invalid.push(InvalidEntry { binding, reason }),
+        }
+    }
+
+    report.duplicates = seen
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(binding, _)| binding)
+        .collect();
+
+    report
+}
+
+/// The keys of a map of bindings to actions, in the order that they were written.
+///
+/// A key written twice is kept twice, where a map would keep only the last of them.
+struct Keys(Vec<ron::Value>);
+
+impl<'de> Deserialize<'de> for Keys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = Keys;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of bindings to actions")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Keys, A::Error> {
+                let mut keys = Vec::new();
+
+                while let Some((key, IgnoredAny)) = map.next_entry::<ron::Value, IgnoredAny>()? {
+                    keys.push(key);
+                }
+
+                Ok(Keys(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+    }
+}
+
+/// Validates the shortcuts config at `path`, printing a report to stdout.
+///
+/// Returns `true` if the config is valid.
+pub fn run(path: &Path, json: bool) -> bool {
+    let report = match std::fs::read_to_string(path) {
+        Ok(config) => validate(&config),
+        Err(why) => Report {
+            error: Some(why.to_string()),
+            ..Report::default()
+        },
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(output) => println!("{output}"),
+            Err(why) => eprintln!("failed to serialize report: {why}"),
+        }
+    } else {
+        if let Some(why) = &report.error {
+            println!("error: {why}");
+        }
+
+        for entry in &report.invalid {
+            println!("invalid: {}: {}", entry.binding, entry.reason);
+        }
+
+        for binding in &report.duplicates {
+            println!("duplicate: {binding}");
+        }
+
+        if report.is_valid() {
+            println!("{} shortcuts are valid", report.entries);
+        }
+    }
+
+    report.is_valid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidBinding, validate};
+    use cosmic_settings_config::Binding;
+    use cosmic_settings_config::shortcuts::Action;
+    use std::str::FromStr;
+
+    fn entry(binding: &str, action: &Action) -> String {
+        let binding = Binding::from_str(binding).unwrap();
+        format!(
+            "{}: {},",
+            ron::ser::to_string(&binding).unwrap(),
+            ron::ser::to_string(action).unwrap()
+        )
+    }
+
+    #[test]
+    fn valid() {
+        let terminal = Action::Spawn(String::from("cosmic-term"));
+        let config = format!(
+            "{{{}{}}}",
+            entry("Super+t", &terminal),
+            entry("Super+Shift+t", &Action::Disable)
+        );
+
+        let report = validate(&config);
+        assert!(report.is_valid(), "{report:?}");
+        assert_eq!(report.entries, 2);
+    }
+
+    #[test]
+    fn invalid() {
+        let config = format!("{{{}42: Disable,}}", entry("Ctrl+Alt+F1", &Action::Disable));
+
+        let report = validate(&config);
+        assert!(!report.is_valid());
+        assert_eq!(report.entries, 2);
+
+        let reasons = report
+            .invalid
+            .iter()
+            .map(|entry| &entry.reason)
+            .collect::<Vec<_>>();
+        assert!(reasons.contains(&&InvalidBinding::Reserved));
+        assert!(reasons.contains(&&InvalidBinding::Unparsable));
+
+        // A config that is not a map cannot be validated at all.
+        let report = validate("[Disable]");
+        assert!(report.error.is_some());
+        assert!(!report.is_valid());
+
+        let report = validate("{");
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn duplicates() {
+        let terminal = Action::Spawn(String::from("cosmic-term"));
+
+        // The same key written twice would be merged by a map.
+        let config = format!(
+            "{{{}{}}}",
+            entry("Super+t", &terminal),
+            entry("Super+t", &Action::Disable)
+        );
+
+        let report = validate(&config);
+        assert!(!report.is_valid());
+        assert_eq!(report.entries, 2);
+        assert_eq!(
+            report.duplicates,
+            vec![Binding::from_str("Super+t").unwrap().to_string()]
+        );
+    }
+}
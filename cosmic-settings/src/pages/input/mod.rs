@@ -1,16 +1,21 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::HashMap;
+
 use crate::app;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, row, settings, text};
 use cosmic::{
-    Task,
+    Apply, Element, Task,
     cosmic_config::{self, ConfigGet, ConfigSet},
 };
 use cosmic_comp_config::input::{
     AccelConfig, AccelProfile, ClickMethod, InputConfig, ScrollConfig, ScrollMethod, TapButtonMap,
     TapConfig,
 };
-use cosmic_settings_page as page;
+use cosmic_settings_page::{self as page, Section};
+use slab::Slab;
 use tracing::error;
 
 pub mod keyboard;
@@ -22,7 +27,11 @@ pub enum Message {
     // seperate close message, to make sure another isn't closed?
     DisableWhileTyping(bool, bool),
     PrimaryButtonSelected(cosmic::widget::segmented_button::Entity, bool),
+    RefreshDevices,
+    ResetDevice(String),
     SetAcceleration(bool, bool),
+    SetDeviceAccelProfile(String, usize),
+    SetDeviceSpeed(String, f64),
     SetMouseSpeed(f64, bool),
     SetNaturalScroll(bool, bool),
     SetSecondaryClickBehavior(Option<ClickMethod>, bool),
@@ -36,6 +45,10 @@ pub struct Page {
     input_default: InputConfig,
     #[allow(dead_code)]
     input_touchpad: InputConfig,
+    /// Per-device overrides, keyed by device name. Entries of unplugged devices are kept.
+    input_devices: HashMap<String, InputConfig>,
+    devices: Vec<Device>,
+    accel_profiles: Vec<String>,
 
     // Mouse
     primary_button: cosmic::widget::segmented_button::SingleSelectModel,
@@ -44,6 +57,17 @@ pub struct Page {
     touchpad_primary_button: cosmic::widget::segmented_button::SingleSelectModel,
 }
 
+/// A connected mouse or touchpad.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Device {
+    /// The name by which the compositor identifies the device.
+    pub name: String,
+    pub touchpad: bool,
+}
+
+/// Acceleration profiles in the order of the profile dropdown.
+const ACCEL_PROFILES: [AccelProfile; 2] = [AccelProfile::Adaptive, AccelProfile::Flat];
+
 fn get_config<T: Default + serde::de::DeserializeOwned>(
     config: &cosmic_config::Config,
     key: &str,
@@ -62,6 +86,7 @@ impl Default for Page {
         let config = cosmic_config::Config::new("com.system76.CosmicComp", 1).unwrap();
         let input_default: InputConfig = get_config(&config, "input_default");
         let input_touchpad: InputConfig = get_config(&config, "input_touchpad");
+        let input_devices: HashMap<String, InputConfig> = get_config(&config, "input_devices");
 
         let mut primary_button = mouse::default_primary_button();
         let idx = input_default.left_handed.unwrap_or(false) as u16;
@@ -75,6 +100,9 @@ impl Default for Page {
             config,
             input_default,
             input_touchpad,
+            input_devices,
+            devices: pointer_devices(),
+            accel_profiles: vec![fl!("input-device", "adaptive"), fl!("input-device", "flat")],
 
             // Mouse
             primary_button,
@@ -98,6 +126,41 @@ impl Page {
         }
     }
 
+    fn update_device<F: FnOnce(&mut InputConfig)>(&mut self, name: String, f: F) {
+        f(self.input_devices.entry(name).or_default());
+        self.write_devices();
+    }
+
+    fn write_devices(&self) {
+        if let Err(err) = self.config.set("input_devices", &self.input_devices) {
+            error!(?err, "Failed to set config 'input_devices'");
+        }
+    }
+
+    /// The acceleration of a device, which falls back to that of all mice or touchpads.
+    fn device_acceleration(&self, device: &Device) -> AccelConfig {
+        self.input_devices
+            .get(&device.name)
+            .and_then(|config| config.acceleration.clone())
+            .unwrap_or_else(|| self.inherited_acceleration(device.touchpad))
+    }
+
+    fn inherited_acceleration(&self, touchpad: bool) -> AccelConfig {
+        let config = if touchpad {
+            &self.input_touchpad
+        } else {
+            &self.input_default
+        };
+
+        config.acceleration.clone().unwrap_or_default()
+    }
+
+    fn is_touchpad(&self, name: &str) -> bool {
+        self.devices
+            .iter()
+            .any(|device| device.touchpad && device.name == name)
+    }
+
     #[allow(clippy::too_many_lines)]
     pub fn update(&mut self, message: Message) -> Task<app::Message> {
         match message {
@@ -113,6 +176,32 @@ impl Page {
                 });
             }
 
+            Message::RefreshDevices => self.devices = pointer_devices(),
+
+            Message::ResetDevice(name) => {
+                if self.input_devices.remove(&name).is_some() {
+                    self.write_devices();
+                }
+            }
+
+            Message::SetDeviceAccelProfile(name, position) => {
+                let Some(profile) = ACCEL_PROFILES.get(position).copied() else {
+                    return Task::none();
+                };
+
+                let inherited = self.inherited_acceleration(self.is_touchpad(&name));
+                self.update_device(name, |x| {
+                    x.acceleration.get_or_insert(inherited).profile = Some(profile);
+                });
+            }
+
+            Message::SetDeviceSpeed(name, value) => {
+                let inherited = self.inherited_acceleration(self.is_touchpad(&name));
+                self.update_device(name, |x| {
+                    x.acceleration.get_or_insert(inherited).speed = value;
+                });
+            }
+
             Message::SetMouseSpeed(value, touchpad) => self.update_input(touchpad, |x| {
                 x.acceleration.get_or_insert(AccelConfig::default()).speed = value;
             }),
@@ -204,6 +293,136 @@ impl page::AutoBind<crate::pages::Message> for Page {
     }
 }
 
+/// Acceleration settings for each connected mouse, or each connected touchpad.
+fn devices<P: page::Page<crate::pages::Message>>(touchpad: bool) -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
+
+    let profile = descriptions.insert(fl!("input-device", "profile"));
+    let speed = descriptions.insert(fl!("input-device", "speed"));
+    let reset = descriptions.insert(fl!("input-device", "reset"));
+
+    Section::default()
+        .title(fl!("input-device"))
+        .descriptions(descriptions)
+        .show_while_binder::<P>(move |binder, _page| {
+            binder.page::<Page>().map_or(false, |input| {
+                input
+                    .devices
+                    .iter()
+                    .any(|device| device.touchpad == touchpad)
+            })
+        })
+        .view::<P>(move |binder, _page, section| {
+            let descriptions = &section.descriptions;
+            let input = binder.page::<Page>().expect("input page not found");
+
+            input
+                .devices
+                .iter()
+                .filter(|device| device.touchpad == touchpad)
+                .fold(
+                    widget::column().spacing(cosmic::theme::spacing().space_m),
+                    |column, device| {
+                        let acceleration = input.device_acceleration(device);
+
+                        let profile_position = ACCEL_PROFILES.iter().position(|profile| {
+                            *profile == acceleration.profile.unwrap_or(AccelProfile::Adaptive)
+                        });
+
+                        let name = device.name.clone();
+                        let profile_dropdown =
+                            widget::dropdown(&input.accel_profiles, profile_position, move |x| {
+                                Message::SetDeviceAccelProfile(name.clone(), x)
+                            });
+
+                        let value = (acceleration.speed + 0.81) * 70.71;
+                        let name = device.name.clone();
+                        let slider = widget::slider(0.0..=100.0, value, move |value| {
+                            Message::SetDeviceSpeed(name.clone(), (value / 70.71) - 0.81)
+                        })
+                        .width(Length::Fill)
+                        .breakpoints(&[50.0])
+                        .apply(widget::container)
+                        .max_width(250);
+
+                        let speed_control = row::with_capacity(2)
+                            .align_y(Alignment::Center)
+                            .spacing(8)
+                            .push(
+                                text::body(format!("{:.0}", value.round()))
+                                    .width(Length::Fixed(22.0))
+                                    .align_x(Alignment::Center),
+                            )
+                            .push(slider);
+
+                        let reset_button = widget::button::standard(&descriptions[reset])
+                            .on_press_maybe(
+                                input
+                                    .input_devices
+                                    .contains_key(&device.name)
+                                    .then(|| Message::ResetDevice(device.name.clone())),
+                            );
+
+                        column.push(
+                            settings::section()
+                                .title(&device.name)
+                                .add(
+                                    settings::item::builder(&descriptions[profile])
+                                        .control(profile_dropdown),
+                                )
+                                .add(
+                                    settings::item::builder(&descriptions[speed])
+                                        .flex_control(speed_control),
+                                )
+                                .add(settings::item_row(vec![
+                                    widget::horizontal_space().into(),
+                                    reset_button.into(),
+                                ])),
+                        )
+                    },
+                )
+                .apply(Element::from)
+                .map(crate::pages::Message::Input)
+        })
+}
+
+/// Uses `udev` to list the mice and touchpads connected to the system.
+fn pointer_devices() -> Vec<Device> {
+    let Ok(mut enumerator) = udev::Enumerator::new() else {
+        return Vec::new();
+    };
+
+    let _res = enumerator.match_subsystem("input");
+
+    let Ok(devices) = enumerator.scan_devices() else {
+        return Vec::new();
+    };
+
+    let is_set = |device: &udev::Device, property: &str| {
+        device
+            .property_value(property)
+            .map_or(false, |value| value == "1")
+    };
+
+    let mut devices = devices
+        // Only the parent `inputN` devices have a name; their event nodes do not.
+        .filter_map(|device| {
+            let touchpad = is_set(&device, "ID_INPUT_TOUCHPAD");
+
+            if !touchpad && !is_set(&device, "ID_INPUT_MOUSE") {
+                return None;
+            }
+
+            let name = device.attribute_value("name")?.to_str()?.to_owned();
+            Some(Device { name, touchpad })
+        })
+        .collect::<Vec<_>>();
+
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices.dedup();
+    devices
+}
+
 /// Uses `udev` to check if a touchpad device exists on the system.
 fn system_has_touchpad() -> bool {
     let Ok(mut enumerator) = udev::Enumerator::new() else {
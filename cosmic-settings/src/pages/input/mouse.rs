@@ -35,7 +35,15 @@ impl page::Page<crate::pages::Message> for Page {
         &self,
         sections: &mut SlotMap<section::Entity, Section<crate::pages::Message>>,
     ) -> Option<page::Content> {
-        Some(vec![sections.insert(mouse()), sections.insert(scrolling())])
+        Some(vec![
+            sections.insert(mouse()),
+            sections.insert(scrolling()),
+            sections.insert(super::devices::<Page>(false)),
+        ])
+    }
+
+    fn on_enter(&mut self) -> cosmic::Task<crate::pages::Message> {
+        cosmic::task::message(crate::pages::Message::Input(Message::RefreshDevices))
     }
 
     fn info(&self) -> page::Info {
@@ -46,9 +46,14 @@ impl page::Page<crate::pages::Message> for Page {
             sections.insert(click_behavior()),
             sections.insert(scrolling()),
             sections.insert(gestures()),
+            sections.insert(super::devices::<Page>(true)),
         ])
     }
 
+    fn on_enter(&mut self) -> cosmic::Task<crate::pages::Message> {
+        cosmic::task::message(crate::pages::Message::Input(Message::RefreshDevices))
+    }
+
     fn info(&self) -> page::Info {
         page::Info::new("touchpad", "input-touchpad-symbolic")
             .title(fl!("touchpad"))
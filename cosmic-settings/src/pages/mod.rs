@@ -1,7 +1,7 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic_settings_page::Entity;
+use cosmic_settings_page::{Entity, Section};
 
 #[cfg(feature = "page-accessibility")]
 pub mod accessibility;
@@ -36,6 +36,8 @@ pub enum Message {
     #[cfg(feature = "page-bluetooth")]
     Bluetooth(bluetooth::Message),
     #[cfg(feature = "page-input")]
+    ConflictShortcuts(input::keyboard::shortcuts::conflicts::Message),
+    #[cfg(feature = "page-input")]
     CustomShortcuts(input::keyboard::shortcuts::custom::Message),
     #[cfg(feature = "page-date")]
     DateAndTime(time::date::Message),
@@ -71,6 +73,7 @@ pub enum Message {
     NavShortcuts(input::keyboard::shortcuts::ShortcutMessage),
     #[cfg(feature = "page-networking")]
     Networking(networking::Message),
+    Notifications(desktop::notifications::Message),
     Page(Entity),
     #[cfg(feature = "wayland")]
     Panel(desktop::panel::Message),
@@ -107,3 +110,39 @@ impl From<Message> for crate::Message {
         crate::Message::PageMessage(message)
     }
 }
+
+/// Builds the searchable descriptions of a section from fluent message keys.
+pub trait SectionDescriptions {
+    /// Inserts the localized message of each key, such as `custom-shortcuts.add`.
+    #[must_use]
+    fn descriptions_from(self, keys: &[&str]) -> Self;
+}
+
+impl<Message> SectionDescriptions for Section<Message> {
+    fn descriptions_from(mut self, keys: &[&str]) -> Self {
+        for key in keys {
+            self.descriptions.insert(crate::localize::message(key));
+        }
+
+        self
+    }
+}
+
+/// Where the content of the page being shown is scrolled to, for views that only build what
+/// is in view. Kept as a resource of the page binder, and reset as pages are changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PageViewport {
+    /// How far down the content is scrolled.
+    pub offset: f32,
+    /// Height of the view of the content, unknown until it is first scrolled.
+    pub height: Option<f32>,
+    /// Height of all of the content.
+    pub content_height: f32,
+}
+
+impl PageViewport {
+    /// The viewport of the page being shown, as kept by the binder.
+    pub fn of<Message: 'static>(binder: &cosmic_settings_page::Binder<Message>) -> Self {
+        binder.resource::<Self>().copied().unwrap_or_default()
+    }
+}
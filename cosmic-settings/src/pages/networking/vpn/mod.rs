@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 mod nmcli;
+mod validate;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -32,6 +34,8 @@ pub enum Message {
     AddNetwork,
     /// Show a dialog requesting a name for the WireGuard device
     AddWireGuardDevice(String, String, String),
+    /// IP addresses assigned to a connected VPN
+    Addresses(ConnectionId, Vec<String>),
     /// Cancels an active dialog.
     CancelDialog,
     /// Connect to a VPN with the given username and password
@@ -119,9 +123,20 @@ pub enum ConnectionSettings {
     Wireguard { id: String },
 }
 
+impl ConnectionSettings {
+    fn id(&self) -> &str {
+        match self {
+            ConnectionSettings::Vpn(connection) => connection.id.as_str(),
+            ConnectionSettings::Wireguard { id } => id.as_str(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct VpnConnectionSettings {
     id: String,
+    /// The server to connect to, such as `vpn.example.com:1194`.
+    remote: Option<String>,
     username: Option<String>,
     connection_type: Option<ConnectionType>,
     password_flag: Option<PasswordFlag>,
@@ -190,6 +205,8 @@ pub struct Page {
     dialog: Option<VpnDialog>,
     view_more_popup: Option<ConnectionId>,
     known_connections: IndexMap<UUID, ConnectionSettings>,
+    /// IP addresses of the connected VPNs.
+    addresses: HashMap<ConnectionId, Vec<String>>,
     /// Withhold device update if the view more popup is shown.
     withheld_devices: Option<Vec<network_manager::devices::DeviceInfo>>,
     /// Withhold active connections update if the view more popup is shown.
@@ -387,6 +404,11 @@ impl Page {
 
             Message::KnownConnections(connections) => {
                 self.known_connections = connections;
+                return self.load_addresses();
+            }
+
+            Message::Addresses(uuid, addresses) => {
+                self.addresses.insert(uuid, addresses);
             }
 
             Message::UpdateDevices(devices) => {
@@ -395,6 +417,7 @@ impl Page {
 
             Message::UpdateState(state) => {
                 self.update_active_conns(state);
+                return self.load_addresses();
             }
 
             Message::NetworkManager(
@@ -684,6 +707,36 @@ impl Page {
         Task::none()
     }
 
+    /// Reads the addresses of the connected VPNs, and forgets those of the disconnected.
+    fn load_addresses(&mut self) -> Task<crate::app::Message> {
+        let Some(NmState {
+            ref active_conns, ..
+        }) = self.nm_state
+        else {
+            return Task::none();
+        };
+
+        let connected = self
+            .known_connections
+            .iter()
+            .filter(|(_, connection)| is_connected(active_conns, connection.id()))
+            .map(|(uuid, _)| uuid.clone())
+            .collect::<Vec<_>>();
+
+        self.addresses.retain(|uuid, _| connected.contains(uuid));
+
+        cosmic::Task::batch(connected.into_iter().map(|uuid| {
+            cosmic::task::future(async move {
+                let addresses = nmcli::addresses(&uuid).await.unwrap_or_else(|why| {
+                    tracing::error!(why, "failed to get addresses of VPN connection");
+                    Vec::new()
+                });
+
+                Message::Addresses(uuid, addresses)
+            })
+        }))
+    }
+
     /// Closes the view more popup and applies any withheld updates.
     fn close_popup_and_apply_updates(&mut self) {
         self.view_more_popup = None;
@@ -763,16 +816,8 @@ fn devices_view() -> Section<crate::pages::Message> {
                 let known_networks = page.known_connections.iter().fold(
                     vpn_connections,
                     |networks, (uuid, connection)| {
-                        let id = match connection {
-                            ConnectionSettings::Vpn(connection) => connection.id.as_str(),
-                            ConnectionSettings::Wireguard { id } => id.as_str(),
-                        };
-
-                        let is_connected = active_conns.iter().any(|conn| match conn {
-                            ActiveConnectionInfo::Vpn { name, .. } => name.as_str() == id,
-
-                            _ => false,
-                        });
+                        let id = connection.id();
+                        let is_connected = is_connected(active_conns, id);
 
                         let (connect_txt, connect_msg) = if is_connected {
                             (&section.descriptions[connected_txt], None)
@@ -783,7 +828,28 @@ fn devices_view() -> Section<crate::pages::Message> {
                             )
                         };
 
-                        let identifier = widget::text::body(id).wrapping(Wrapping::Glyph);
+                        // Show where a connected VPN connects to, and the addresses it was given.
+                        let details = is_connected
+                            .then(|| {
+                                let remote = match connection {
+                                    ConnectionSettings::Vpn(settings) => settings.remote.as_deref(),
+                                    ConnectionSettings::Wireguard { .. } => None,
+                                };
+
+                                page.addresses
+                                    .get(uuid)
+                                    .into_iter()
+                                    .flatten()
+                                    .map(String::as_str)
+                                    .chain(remote)
+                                    .collect::<Vec<_>>()
+                                    .join(" · ")
+                            })
+                            .filter(|details| !details.is_empty());
+
+                        let identifier = widget::column::with_capacity(2)
+                            .push(widget::text::body(id).wrapping(Wrapping::Glyph))
+                            .push_maybe(details.map(widget::text::caption));
 
                         let connect: Element<'_, Message> = if let Some(msg) = connect_msg {
                             widget::button::text(connect_txt).on_press(msg).into()
@@ -857,6 +923,14 @@ fn devices_view() -> Section<crate::pages::Message> {
         })
 }
 
+fn is_connected(active_conns: &[ActiveConnectionInfo], id: &str) -> bool {
+    active_conns.iter().any(|conn| match conn {
+        ActiveConnectionInfo::Vpn { name, .. } => name.as_str() == id,
+
+        _ => false,
+    })
+}
+
 fn popup_button(message: Message, text: &str) -> Element<'_, Message> {
     let theme = cosmic::theme::active();
     let theme = theme.cosmic();
@@ -918,6 +992,10 @@ fn add_network() -> Task<crate::app::Message> {
                             );
                         };
 
+                        if let Err(why) = read_config(&path, validate::wireguard).await {
+                            return Message::Error(ErrorKind::Config, why);
+                        }
+
                         let path = path.to_string_lossy().to_string();
 
                         let filename = device.rsplit_once("/").unwrap_or_default().1;
@@ -930,7 +1008,13 @@ fn add_network() -> Task<crate::app::Message> {
 
                         return Message::AddWireGuardDevice(device, filename.to_owned(), path);
                     } else {
-                        super::nm_add_vpn_file("openvpn", response.url().path()).await
+                        let path = response.url().path();
+
+                        if let Err(why) = read_config(path, validate::openvpn).await {
+                            return Message::Error(ErrorKind::Config, why);
+                        }
+
+                        super::nm_add_vpn_file("openvpn", path).await
                     };
 
                     match result {
@@ -949,6 +1033,18 @@ fn add_network() -> Task<crate::app::Message> {
         .apply(cosmic::task::future)
 }
 
+/// Reads a VPN config file and checks that it can be imported.
+async fn read_config(
+    path: impl AsRef<std::path::Path>,
+    validate: fn(&str) -> Result<(), validate::InvalidConfig>,
+) -> Result<(), String> {
+    let config = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|why| why.to_string())?;
+
+    validate(&config).map_err(validate::InvalidConfig::localized)
+}
+
 fn connection_settings(conn: zbus::Connection) -> Task<crate::app::Message> {
     let settings = async move {
         let settings = network_manager::dbus::settings::NetworkManagerSettings::new(&conn).await?;
@@ -991,7 +1087,7 @@ fn connection_settings(conn: zbus::Connection) -> Task<crate::app::Message> {
                 let id = connection.get("id")?.downcast_ref::<String>().ok()?;
                 let uuid = connection.get("uuid")?.downcast_ref::<String>().ok()?;
 
-                let (username, connection_type, password_flag) = vpn
+                let (username, remote, connection_type, password_flag) = vpn
                     .get("data")
                     .and_then(|data| data.downcast_ref::<zbus::zvariant::Dict>().ok())
                     .map(|dict| {
@@ -1003,6 +1099,12 @@ fn connection_settings(conn: zbus::Connection) -> Task<crate::app::Message> {
                             .flatten()
                             .filter(|value| !value.is_empty());
 
+                        let remote = dict
+                            .get::<String, String>(&String::from("remote"))
+                            .ok()
+                            .flatten()
+                            .filter(|value| !value.is_empty());
+
                         if let Some("password") = dict
                             .get::<String, String>(&String::from("connection-type"))
                             .ok()
@@ -1024,7 +1126,7 @@ fn connection_settings(conn: zbus::Connection) -> Task<crate::app::Message> {
                                 });
                         }
 
-                        (username, connection_type, password_flag)
+                        (username, remote, connection_type, password_flag)
                     })
                     .unwrap_or_default();
 
@@ -1032,6 +1134,7 @@ fn connection_settings(conn: zbus::Connection) -> Task<crate::app::Message> {
                     Arc::from(uuid),
                     ConnectionSettings::Vpn(VpnConnectionSettings {
                         id,
+                        remote,
                         connection_type,
                         password_flag,
                         username,
@@ -51,3 +51,50 @@ pub async fn connect(connection_name: &str) -> Result<(), String> {
         .await
         .apply(crate::utils::map_stderr_output)
 }
+
+/// Lists the IP addresses that an active connection was assigned.
+pub async fn addresses(uuid: &str) -> Result<Vec<String>, String> {
+    let output = tokio::process::Command::new("nmcli")
+        .args([
+            "--terse",
+            "--fields",
+            "IP4.ADDRESS,IP6.ADDRESS",
+            "connection",
+            "show",
+            uuid,
+        ])
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8(output.stderr).unwrap_or_default());
+    }
+
+    Ok(parse_addresses(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Reads lines such as `IP6.ADDRESS[1]:fd00\:\:2/64`, in which nmcli escapes `:` in values.
+fn parse_addresses(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(field, _)| field.starts_with("IP4.ADDRESS") || field.starts_with("IP6.ADDRESS"))
+        .map(|(_, address)| address.replace("\\:", ":"))
+        .filter(|address| !address.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_addresses;
+
+    #[test]
+    fn addresses() {
+        assert_eq!(
+            parse_addresses("IP4.ADDRESS[1]:10.8.0.2/24\nIP6.ADDRESS[1]:fd00\\:\\:2/64\n"),
+            vec![String::from("10.8.0.2/24"), String::from("fd00::2/64")]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Checks VPN config files before they are imported into NetworkManager.
+
+/// Why a VPN config file cannot be imported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvalidConfig {
+    /// An OpenVPN config without a server to connect to.
+    NoRemote,
+    /// A WireGuard config without an `[Interface]` section.
+    NoInterface,
+    /// A WireGuard interface without its private key.
+    NoPrivateKey,
+    /// A WireGuard config without a `[Peer]` section.
+    NoPeer,
+    /// A WireGuard peer without its public key.
+    NoPublicKey,
+}
+
+impl InvalidConfig {
+    pub fn localized(self) -> String {
+        match self {
+            Self::NoRemote => fl!("vpn-invalid-config", "no-remote"),
+            Self::NoInterface => fl!("vpn-invalid-config", "no-interface"),
+            Self::NoPrivateKey => fl!("vpn-invalid-config", "no-private-key"),
+            Self::NoPeer => fl!("vpn-invalid-config", "no-peer"),
+            Self::NoPublicKey => fl!("vpn-invalid-config", "no-public-key"),
+        }
+    }
+}
+
+/// Requires a `remote` directive, either at the top level or in a `<connection>` block.
+pub fn openvpn(config: &str) -> Result<(), InvalidConfig> {
+    let has_remote = config
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with(['#', ';']))
+        .any(|line| line.split_whitespace().next() == Some("remote"));
+
+    if has_remote {
+        Ok(())
+    } else {
+        Err(InvalidConfig::NoRemote)
+    }
+}
+
+/// Requires an `[Interface]` with a `PrivateKey`, and at least one `[Peer]`, each with a
+/// `PublicKey`.
+pub fn wireguard(config: &str) -> Result<(), InvalidConfig> {
+    enum Section {
+        None,
+        Interface,
+        Peer,
+    }
+
+    let mut section = Section::None;
+    let mut has_interface = false;
+    let mut has_private_key = false;
+    let mut peers = 0;
+    let mut public_keys = 0;
+
+    for line in config.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.eq_ignore_ascii_case("[Interface]") {
+            section = Section::Interface;
+            has_interface = true;
+            continue;
+        }
+
+        if line.eq_ignore_ascii_case("[Peer]") {
+            section = Section::Peer;
+            peers += 1;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        if value.trim().is_empty() {
+            continue;
+        }
+
+        match (&section, key.trim().to_ascii_lowercase().as_str()) {
+            (Section::Interface, "privatekey") => has_private_key = true,
+            (Section::Peer, "publickey") => public_keys += 1,
+            _ => (),
+        }
+    }
+
+    if !has_interface {
+        Err(InvalidConfig::NoInterface)
+    } else if !has_private_key {
+        Err(InvalidConfig::NoPrivateKey)
+    } else if peers == 0 {
+        Err(InvalidConfig::NoPeer)
+    } else if public_keys < peers {
+        Err(InvalidConfig::NoPublicKey)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidConfig, openvpn, wireguard};
+
+    #[test]
+    fn openvpn_remote() {
+        assert_eq!(openvpn("client\nremote vpn.example.com 1194\n"), Ok(()));
+        assert_eq!(
+            openvpn("client\n# remote vpn.example.com 1194\n"),
+            Err(InvalidConfig::NoRemote)
+        );
+    }
+
+    #[test]
+    fn wireguard_keys() {
+        const CONFIG: &str = "[Interface]
+PrivateKey = cGF5bG9hZA==
+Address = 10.0.0.2/32
+
+[Peer]
+PublicKey = c2VydmVy
+Endpoint = vpn.example.com:51820
+AllowedIPs = 0.0.0.0/0
+";
+
+        assert_eq!(wireguard(CONFIG), Ok(()));
+        assert_eq!(
+            wireguard("[Peer]\nPublicKey = c2VydmVy\n"),
+            Err(InvalidConfig::NoInterface)
+        );
+        assert_eq!(
+            wireguard(&CONFIG.replace("PrivateKey = cGF5bG9hZA==", "")),
+            Err(InvalidConfig::NoPrivateKey)
+        );
+        assert_eq!(
+            wireguard(&CONFIG.replace("PublicKey = c2VydmVy", "")),
+            Err(InvalidConfig::NoPublicKey)
+        );
+        assert_eq!(
+            wireguard("[Interface]\nPrivateKey = cGF5bG9hZA==\n"),
+            Err(InvalidConfig::NoPeer)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! A connection profile entered by hand, for networks that do not broadcast their SSID
+//! or that authenticate with WPA-Enterprise.
+
+use secure_string::SecureString;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Security {
+    Open,
+    Personal,
+    Enterprise,
+}
+
+impl Security {
+    pub const ALL: [Self; 3] = [Self::Open, Self::Personal, Self::Enterprise];
+
+    pub fn title(self) -> String {
+        match self {
+            Self::Open => fl!("hidden-network", "open"),
+            Self::Personal => fl!("hidden-network", "personal"),
+            Self::Enterprise => fl!("hidden-network", "enterprise"),
+        }
+    }
+}
+
+/// The EAP method of a WPA-Enterprise network.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EapMethod {
+    Peap,
+    Ttls,
+    Tls,
+}
+
+impl EapMethod {
+    pub const ALL: [Self; 3] = [Self::Peap, Self::Ttls, Self::Tls];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Peap => "PEAP",
+            Self::Ttls => "TTLS",
+            Self::Tls => "TLS",
+        }
+    }
+
+    fn nm_name(self) -> &'static str {
+        match self {
+            Self::Peap => "peap",
+            Self::Ttls => "ttls",
+            Self::Tls => "tls",
+        }
+    }
+}
+
+/// A text field of the hidden network form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    Ssid,
+    Identity,
+    CaCert,
+    ClientCert,
+    PrivateKey,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HiddenNetwork {
+    pub ssid: String,
+    pub security: Security,
+    pub method: EapMethod,
+    pub identity: String,
+    /// The network password, or the password of the private key with TLS.
+    pub password: SecureString,
+    pub password_hidden: bool,
+    /// Path of the certificate authority that signed the certificate of the server.
+    pub ca_cert: String,
+    /// Path of the client certificate, with TLS.
+    pub client_cert: String,
+    /// Path of the private key of the client certificate, with TLS.
+    pub private_key: String,
+}
+
+impl Default for HiddenNetwork {
+    fn default() -> Self {
+        Self {
+            ssid: String::new(),
+            security: Security::Personal,
+            method: EapMethod::Peap,
+            identity: String::new(),
+            password: SecureString::from(""),
+            password_hidden: true,
+            ca_cert: String::new(),
+            client_cert: String::new(),
+            private_key: String::new(),
+        }
+    }
+}
+
+impl HiddenNetwork {
+    pub fn set(&mut self, field: Field, value: String) {
+        *match field {
+            Field::Ssid => &mut self.ssid,
+            Field::Identity => &mut self.identity,
+            Field::CaCert => &mut self.ca_cert,
+            Field::ClientCert => &mut self.client_cert,
+            Field::PrivateKey => &mut self.private_key,
+        } = value;
+    }
+
+    /// Whether every field that the security and EAP method require has been filled in.
+    pub fn is_valid(&self) -> bool {
+        let ssid = self.ssid.trim();
+        if ssid.is_empty() || ssid.len() > 32 {
+            return false;
+        }
+
+        let password = self.password.unsecure();
+
+        match self.security {
+            Security::Open => true,
+            Security::Personal => (8..=63).contains(&password.len()),
+            Security::Enterprise => {
+                !self.identity.trim().is_empty()
+                    && match self.method {
+                        EapMethod::Peap | EapMethod::Ttls => !password.is_empty(),
+                        EapMethod::Tls => {
+                            !self.client_cert.trim().is_empty()
+                                && !self.private_key.trim().is_empty()
+                        }
+                    }
+            }
+        }
+    }
+
+    /// Arguments of `nmcli` that add a connection profile for this network.
+    pub fn nmcli_args(&self) -> Vec<String> {
+        let ssid = self.ssid.trim();
+        let password = self.password.unsecure();
+
+        let mut args = vec![
+            "connection",
+            "add",
+            "type",
+            "wifi",
+            "con-name",
+            ssid,
+            "ssid",
+            ssid,
+            "wifi.hidden",
+            "yes",
+        ];
+
+        match self.security {
+            Security::Open => (),
+
+            Security::Personal => {
+                args.extend(["wifi-sec.key-mgmt", "wpa-psk", "wifi-sec.psk", password]);
+            }
+
+            Security::Enterprise => {
+                args.extend([
+                    "wifi-sec.key-mgmt",
+                    "wpa-eap",
+                    "802-1x.eap",
+                    self.method.nm_name(),
+                    "802-1x.identity",
+                    self.identity.trim(),
+                ]);
+
+                match self.method {
+                    EapMethod::Peap | EapMethod::Ttls => {
+                        args.extend([
+                            "802-1x.phase2-auth",
+                            "mschapv2",
+                            "802-1x.password",
+                            password,
+                        ]);
+                    }
+
+                    EapMethod::Tls => {
+                        args.extend([
+                            "802-1x.client-cert",
+                            self.client_cert.trim(),
+                            "802-1x.private-key",
+                            self.private_key.trim(),
+                        ]);
+
+                        if !password.is_empty() {
+                            args.extend(["802-1x.private-key-password", password]);
+                        }
+                    }
+                }
+
+                if !self.ca_cert.trim().is_empty() {
+                    args.extend(["802-1x.ca-cert", self.ca_cert.trim()]);
+                }
+            }
+        }
+
+        args.into_iter().map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EapMethod, HiddenNetwork, Security};
+    use secure_string::SecureString;
+
+    #[test]
+    fn required_fields() {
+        let mut network = HiddenNetwork::default();
+        assert!(!network.is_valid());
+
+        network.ssid = String::from("Office");
+        network.password = SecureString::from("short");
+        assert!(!network.is_valid());
+
+        network.password = SecureString::from("long enough");
+        assert!(network.is_valid());
+
+        network.security = Security::Enterprise;
+        assert!(!network.is_valid());
+
+        network.identity = String::from("user");
+        assert!(network.is_valid());
+
+        network.method = EapMethod::Tls;
+        network.client_cert = String::from("/etc/certs/user.pem");
+        assert!(!network.is_valid());
+
+        network.private_key = String::from("/etc/certs/user.key");
+        assert!(network.is_valid());
+    }
+
+    #[test]
+    fn enterprise_profile() {
+        let network = HiddenNetwork {
+            ssid: String::from("eduroam"),
+            security: Security::Enterprise,
+            identity: String::from("user@example.edu"),
+            password: SecureString::from("secret"),
+            ca_cert: String::from("/etc/ssl/ca.pem"),
+            ..HiddenNetwork::default()
+        };
+
+        assert_eq!(
+            network.nmcli_args().join(" "),
+            "connection add type wifi con-name eduroam ssid eduroam wifi.hidden yes \
+             wifi-sec.key-mgmt wpa-eap 802-1x.eap peap 802-1x.identity user@example.edu \
+             802-1x.phase2-auth mschapv2 802-1x.password secret 802-1x.ca-cert /etc/ssl/ca.pem"
+        );
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::Arc,
 };
 
@@ -24,6 +24,9 @@ use cosmic_settings_subscriptions::network_manager::{
 use futures::StreamExt;
 use secure_string::SecureString;
 
+mod hidden;
+mod nmcli;
+
 #[derive(Clone, Debug)]
 pub enum Message {
     /// Add a network connection with nm-connection-editor
@@ -40,12 +43,28 @@ pub enum Message {
     Disconnect(network_manager::SSID),
     /// An error occurred.
     Error(String),
+    /// Open a dialog to connect to a network that does not broadcast its SSID
+    HiddenNetwork,
+    /// Add and activate the network of the hidden network dialog
+    HiddenNetworkConnect,
+    /// Choose the EAP method of the hidden network
+    HiddenNetworkMethod(hidden::EapMethod),
+    /// Update a text field of the hidden network dialog
+    HiddenNetworkInput(hidden::Field, String),
+    /// Result of connecting to the hidden network
+    HiddenNetworkResult(Result<(), String>),
+    /// Choose the security of the hidden network
+    HiddenNetworkSecurity(hidden::Security),
     /// Identity update from the dialog
     IdentityUpdate(String),
+    /// Hide networks that are not secured
+    HideOpen(bool),
     /// Create a dialog to ask for confirmation on forgetting a connection.
     ForgetRequest(network_manager::SSID),
     /// Forget a known access point.
     Forget(network_manager::SSID),
+    /// Signal, security, and band of the visible networks.
+    NetworkDetails(HashMap<String, nmcli::Network>),
     /// An update from the network manager daemon
     NetworkManager(network_manager::Event),
     /// Successfully connected to the system dbus.
@@ -56,6 +75,8 @@ pub enum Message {
     PasswordUpdate(SecureString),
     /// Selects a device to display connections from
     SelectDevice(Arc<network_manager::devices::DeviceInfo>),
+    /// A scan for networks finished.
+    ScanFinished(HashMap<String, nmcli::Network>),
     /// Opens settings page for the access point.
     Settings(network_manager::SSID),
     /// Identity submitted from the dialog
@@ -87,6 +108,7 @@ impl From<Message> for crate::pages::Message {
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum WiFiDialog {
     Forget(network_manager::SSID),
+    Hidden(hidden::HiddenNetwork),
     Password {
         ssid: network_manager::SSID,
         hw_address: HwAddress,
@@ -111,6 +133,11 @@ pub struct Page {
     withheld_devices: Option<Vec<network_manager::devices::DeviceInfo>>,
     /// Withhold state update if the view more popup is shown.
     withheld_state: Option<NetworkManagerState>,
+    /// Details of the visible networks, by SSID.
+    details: HashMap<String, nmcli::Network>,
+    /// Set while waiting for a scan for networks.
+    scanning: bool,
+    hide_open: bool,
 }
 
 #[derive(Debug)]
@@ -189,6 +216,107 @@ impl page::Page<crate::pages::Message> for Page {
                     .map(crate::pages::Message::WiFi)
             }
 
+            WiFiDialog::Hidden(network) => {
+                let security = hidden::Security::ALL.into_iter().fold(
+                    widget::row::with_capacity(3).spacing(16),
+                    |row, security| {
+                        row.push(widget::radio(
+                            widget::text::body(security.title()),
+                            security,
+                            Some(network.security),
+                            Message::HiddenNetworkSecurity,
+                        ))
+                    },
+                );
+
+                let mut control = column::column()
+                    .spacing(8)
+                    .push(hidden_input(
+                        fl!("hidden-network", "ssid"),
+                        &network.ssid,
+                        hidden::Field::Ssid,
+                    ))
+                    .push(security);
+
+                if network.security == hidden::Security::Enterprise {
+                    let methods = hidden::EapMethod::ALL.into_iter().fold(
+                        widget::row::with_capacity(3).spacing(16),
+                        |row, method| {
+                            row.push(widget::radio(
+                                widget::text::body(method.title()),
+                                method,
+                                Some(network.method),
+                                Message::HiddenNetworkMethod,
+                            ))
+                        },
+                    );
+
+                    control = control
+                        .push(widget::text::body(fl!("hidden-network", "method")))
+                        .push(methods)
+                        .push(hidden_input(
+                            fl!("identity"),
+                            &network.identity,
+                            hidden::Field::Identity,
+                        ))
+                        .push(hidden_input(
+                            fl!("hidden-network", "ca-cert"),
+                            &network.ca_cert,
+                            hidden::Field::CaCert,
+                        ));
+
+                    if network.method == hidden::EapMethod::Tls {
+                        control = control
+                            .push(hidden_input(
+                                fl!("hidden-network", "client-cert"),
+                                &network.client_cert,
+                                hidden::Field::ClientCert,
+                            ))
+                            .push(hidden_input(
+                                fl!("hidden-network", "private-key"),
+                                &network.private_key,
+                                hidden::Field::PrivateKey,
+                            ));
+                    }
+                }
+
+                if network.security != hidden::Security::Open {
+                    let placeholder = if network.security == hidden::Security::Enterprise
+                        && network.method == hidden::EapMethod::Tls
+                    {
+                        fl!("hidden-network", "private-key-password")
+                    } else {
+                        fl!("password")
+                    };
+
+                    control = control.push(
+                        widget::text_input::secure_input(
+                            placeholder,
+                            network.password.unsecure(),
+                            Some(Message::TogglePasswordVisibility),
+                            network.password_hidden,
+                        )
+                        .on_input(|input| Message::PasswordUpdate(SecureString::from(input))),
+                    );
+                }
+
+                let primary_action = widget::button::suggested(fl!("connect"))
+                    .on_press_maybe(network.is_valid().then_some(Message::HiddenNetworkConnect));
+
+                let secondary_action =
+                    widget::button::standard(fl!("cancel")).on_press(Message::CancelDialog);
+
+                widget::dialog()
+                    .title(fl!("hidden-network"))
+                    .icon(icon::from_name("preferences-wireless-symbolic").size(64))
+                    .body(fl!("hidden-network", "description"))
+                    .control(control)
+                    .primary_action(primary_action)
+                    .secondary_action(secondary_action)
+                    .apply(Element::from)
+                    .map(crate::pages::Message::WiFi)
+            }
+
             WiFiDialog::Forget(ssid) => {
                 let primary_action = widget::button::destructive(fl!("forget"))
                     .on_press(Message::Forget(ssid.clone()));
@@ -210,9 +338,17 @@ impl page::Page<crate::pages::Message> for Page {
 
     fn header_view(&self) -> Option<cosmic::Element<'_, crate::pages::Message>> {
         Some(
-            widget::button::standard(fl!("add-network"))
-                .trailing_icon(icon::from_name("window-pop-out-symbolic"))
-                .on_press(Message::AddNetwork)
+            widget::row::with_capacity(2)
+                .spacing(8)
+                .push(
+                    widget::button::standard(fl!("hidden-network", "add"))
+                        .on_press(Message::HiddenNetwork),
+                )
+                .push(
+                    widget::button::standard(fl!("add-network"))
+                        .trailing_icon(icon::from_name("window-pop-out-symbolic"))
+                        .on_press(Message::AddNetwork),
+                )
                 .apply(widget::container)
                 .width(Length::Fill)
                 .align_x(Alignment::End)
@@ -246,6 +382,8 @@ impl page::Page<crate::pages::Message> for Page {
         self.connecting.clear();
         self.withheld_state = None;
         self.withheld_devices = None;
+        self.details.clear();
+        self.scanning = false;
 
         if let Some(cancel) = self.nm_task.take() {
             _ = cancel.send(());
@@ -327,10 +465,24 @@ impl Page {
                     return cosmic::Task::batch(vec![
                         update_state(conn.clone()),
                         update_devices(conn.clone()),
+                        network_details(false),
                     ]);
                 }
             }
 
+            Message::NetworkDetails(details) => {
+                self.details = details;
+            }
+
+            Message::ScanFinished(details) => {
+                self.details = details;
+                self.scanning = false;
+            }
+
+            Message::HideOpen(hide) => {
+                self.hide_open = hide;
+            }
+
             Message::ConnectionSettings(settings) => {
                 self.ssid_to_uuid = settings;
             }
@@ -347,7 +499,8 @@ impl Page {
                     devices: Vec::new(),
                 });
 
-                return update_devices(conn);
+                self.scanning = true;
+                return cosmic::Task::batch(vec![update_devices(conn), network_details(true)]);
             }
 
             Message::AddNetwork => {
@@ -406,12 +559,60 @@ impl Page {
                 }
             }
 
-            Message::PasswordUpdate(pass) => {
-                if let Some(WiFiDialog::Password {
+            Message::PasswordUpdate(pass) => match self.dialog {
+                Some(WiFiDialog::Password {
                     ref mut password, ..
-                }) = self.dialog
-                {
-                    *password = pass;
+                }) => *password = pass,
+                Some(WiFiDialog::Hidden(ref mut network)) => network.password = pass,
+                _ => (),
+            },
+
+            Message::HiddenNetwork => {
+                self.dialog = Some(WiFiDialog::Hidden(hidden::HiddenNetwork::default()));
+            }
+
+            Message::HiddenNetworkInput(field, value) => {
+                if let Some(WiFiDialog::Hidden(ref mut network)) = self.dialog {
+                    network.set(field, value);
+                }
+            }
+
+            Message::HiddenNetworkSecurity(security) => {
+                if let Some(WiFiDialog::Hidden(ref mut network)) = self.dialog {
+                    network.security = security;
+                }
+            }
+
+            Message::HiddenNetworkMethod(method) => {
+                if let Some(WiFiDialog::Hidden(ref mut network)) = self.dialog {
+                    network.method = method;
+                }
+            }
+
+            Message::HiddenNetworkConnect => {
+                let Some(WiFiDialog::Hidden(network)) = self.dialog.take() else {
+                    return Task::none();
+                };
+
+                if !network.is_valid() {
+                    self.dialog = Some(WiFiDialog::Hidden(network));
+                    return Task::none();
+                }
+
+                let args = network.nmcli_args();
+                return cosmic::task::future(async move {
+                    Message::HiddenNetworkResult(nmcli::add_and_activate(args).await)
+                });
+            }
+
+            Message::HiddenNetworkResult(result) => {
+                if let Err(why) = result {
+                    tracing::error!(why, "failed to connect to hidden network");
+                    return cosmic::task::message(crate::app::Message::ShowToast(fl!(
+                        "hidden-network",
+                        "failed",
+                        why = why.trim()
+                    )));
                 }
             }
 
@@ -442,15 +643,17 @@ impl Page {
                 }
             }
 
-            Message::TogglePasswordVisibility => {
-                if let Some(WiFiDialog::Password {
+            Message::TogglePasswordVisibility => match self.dialog {
+                Some(WiFiDialog::Password {
                     ref mut password_hidden,
                     ..
-                }) = self.dialog
-                {
-                    *password_hidden = !*password_hidden;
-                }
-            }
+                })
+                | Some(WiFiDialog::Hidden(hidden::HiddenNetwork {
+                    ref mut password_hidden,
+                    ..
+                })) => *password_hidden = !*password_hidden,
+                _ => (),
+            },
 
             Message::ViewMore(ssid) => {
                 self.view_more_popup = ssid;
@@ -611,6 +814,8 @@ fn devices_view() -> Section<crate::pages::Message> {
         connecting_txt = fl!("connecting");
         disconnect_txt = fl!("disconnect");
         forget_txt = fl!("wifi", "forget");
+        hide_open_txt = fl!("wifi", "hide-open");
+        scanning_txt = fl!("wifi", "scanning");
         known_networks_txt = fl!("known-networks");
         no_networks_txt = fl!("no-networks");
         settings_txt = fl!("settings");
@@ -631,8 +836,11 @@ fn devices_view() -> Section<crate::pages::Message> {
             let wifi_enable = widget::settings::item::builder(&section.descriptions[wifi_txt])
                 .control(widget::toggler(state.wifi_enabled).on_toggle(Message::WiFiEnable));
 
-            let mut view = widget::column::with_capacity(4)
-                .push(widget::list_column().add(wifi_enable))
+            let hide_open = widget::settings::item::builder(&section.descriptions[hide_open_txt])
+                .toggler(page.hide_open, Message::HideOpen);
+
+            let mut view = widget::column::with_capacity(5)
+                .push(widget::list_column().add(wifi_enable).add(hide_open))
                 .push_maybe(state.airplane_mode.then(|| {
                     widget::row::with_capacity(2)
                         .push(icon::from_name("airplane-mode-symbolic"))
@@ -641,6 +849,15 @@ fn devices_view() -> Section<crate::pages::Message> {
                         .align_y(Alignment::Center)
                         .apply(widget::container)
                         .center_x(Length::Fill)
+                }))
+                .push_maybe(page.scanning.then(|| {
+                    widget::row::with_capacity(2)
+                        .push(icon::from_name("process-working-symbolic"))
+                        .push(widget::text::body(&section.descriptions[scanning_txt]))
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .apply(widget::container)
+                        .center_x(Length::Fill)
                 }));
 
             if !state.airplane_mode
@@ -656,8 +873,12 @@ fn devices_view() -> Section<crate::pages::Message> {
                 let mut has_known = false;
                 let mut has_visible = false;
 
+                // List the strongest networks first.
+                let mut networks = state.wireless_access_points.iter().collect::<Vec<_>>();
+                networks.sort_by(|a, b| b.strength.cmp(&a.strength));
+
                 // Create separate sections for known and visible networks.
-                let (known_networks, visible_networks) = state.wireless_access_points.iter().fold(
+                let (known_networks, visible_networks) = networks.into_iter().fold(
                     (
                         widget::settings::section()
                             .title(&section.descriptions[known_networks_txt]),
@@ -680,8 +901,33 @@ fn devices_view() -> Section<crate::pages::Message> {
                             }))
                             .any(|known| known == network.ssid.as_ref());
 
-                        // TODO: detect if access point is secured or not.
-                        let is_encrypted = true;
+                        let is_encrypted = !matches!(network.network_type, NetworkType::Open);
+
+                        if page.hide_open && !is_encrypted && !is_known && !is_connected {
+                            return (known_networks, visible_networks);
+                        }
+
+                        let details = page.details.get(network.ssid.as_ref());
+
+                        let security = details.map_or_else(
+                            || match network.network_type {
+                                NetworkType::Open => nmcli::Security::Open,
+                                NetworkType::PSK => nmcli::Security::Wpa,
+                                NetworkType::EAP => nmcli::Security::Enterprise,
+                            },
+                            |details| details.security,
+                        );
+
+                        let mut caption = security.title();
+                        if let Some(details) = details.filter(|details| !details.bands.is_empty()) {
+                            let bands = details
+                                .bands
+                                .iter()
+                                .map(|band| band.title())
+                                .collect::<Vec<_>>();
+                            caption.push_str(" · ");
+                            caption.push_str(&bands.join(", "));
+                        }
 
                         let (connect_txt, connect_msg) = if is_connected {
                             (&section.descriptions[connected_txt], None)
@@ -705,8 +951,14 @@ fn devices_view() -> Section<crate::pages::Message> {
                                     .then(|| widget::icon::from_name("connection-secure-symbolic")),
                             )
                             .push(
-                                widget::text::body(network.ssid.as_ref()).wrapping(Wrapping::Glyph),
+                                widget::column::with_capacity(2)
+                                    .push(
+                                        widget::text::body(network.ssid.as_ref())
+                                            .wrapping(Wrapping::Glyph),
+                                    )
+                                    .push(widget::text::caption(caption)),
                             )
+                            .align_y(Alignment::Center)
                             .spacing(spacing.space_xxs);
 
                         let connect: Element<'_, Message> = if let Some(msg) = connect_msg {
@@ -899,6 +1151,32 @@ pub fn update_devices(conn: zbus::Connection) -> Task<crate::app::Message> {
     })
 }
 
+/// Reads the signal, security, and band of the visible networks.
+fn network_details(rescan: bool) -> Task<crate::app::Message> {
+    cosmic::task::future(async move {
+        let details = nmcli::networks(rescan).await.unwrap_or_else(|why| {
+            tracing::error!(why, "failed to list Wi-Fi networks");
+            HashMap::new()
+        });
+
+        if rescan {
+            Message::ScanFinished(details)
+        } else {
+            Message::NetworkDetails(details)
+        }
+    })
+}
+
+fn hidden_input<'a>(
+    placeholder: String,
+    value: &'a str,
+    field: hidden::Field,
+) -> Element<'a, Message> {
+    widget::text_input::text_input(placeholder, value)
+        .on_input(move |input| Message::HiddenNetworkInput(field, input))
+        .into()
+}
+
 fn wifi_icon(strength: u8) -> &'static str {
     if strength < 25 {
         "network-wireless-signal-weak-symbolic"
@@ -0,0 +1,236 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Details of visible Wi-Fi networks that the NetworkManager subscription does not report.
+
+use cosmic::Apply;
+use std::collections::{BTreeSet, HashMap};
+use std::process::Stdio;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Band {
+    Ghz2_4,
+    Ghz5,
+    Ghz6,
+}
+
+impl Band {
+    /// Finds the band of a channel frequency in MHz.
+    pub fn from_frequency(mhz: u32) -> Option<Self> {
+        match mhz {
+            2400..=2500 => Some(Self::Ghz2_4),
+            4900..=5900 => Some(Self::Ghz5),
+            5925..=7125 => Some(Self::Ghz6),
+            _ => None,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Ghz2_4 => "2.4 GHz",
+            Self::Ghz5 => "5 GHz",
+            Self::Ghz6 => "6 GHz",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Security {
+    Open,
+    Wep,
+    Wpa,
+    Wpa2,
+    Wpa3,
+    Enterprise,
+}
+
+impl Security {
+    /// Reads the strongest security of the `SECURITY` field of nmcli, such as `WPA2 WPA3`.
+    pub fn parse(security: &str) -> Self {
+        let flags = security.split_whitespace().collect::<Vec<_>>();
+
+        if flags.contains(&"802.1X") {
+            Self::Enterprise
+        } else if flags.contains(&"WPA3") {
+            Self::Wpa3
+        } else if flags.contains(&"WPA2") {
+            Self::Wpa2
+        } else if flags.iter().any(|flag| flag.starts_with("WPA")) {
+            Self::Wpa
+        } else if flags.contains(&"WEP") {
+            Self::Wep
+        } else {
+            Self::Open
+        }
+    }
+
+    pub fn title(self) -> String {
+        match self {
+            Self::Open => fl!("wifi-security", "open"),
+            Self::Wep => fl!("wifi-security", "wep"),
+            Self::Wpa => fl!("wifi-security", "wpa"),
+            Self::Wpa2 => fl!("wifi-security", "wpa2"),
+            Self::Wpa3 => fl!("wifi-security", "wpa3"),
+            Self::Enterprise => fl!("wifi-security", "enterprise"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Network {
+    /// Signal strength of the strongest access point, in percent.
+    pub signal: u8,
+    pub security: Security,
+    /// Bands of every access point of the network.
+    pub bands: BTreeSet<Band>,
+}
+
+/// Lists the visible Wi-Fi networks by SSID, waiting for a new scan if `rescan` is set.
+pub async fn networks(rescan: bool) -> Result<HashMap<String, Network>, String> {
+    let output = tokio::process::Command::new("nmcli")
+        .args([
+            "--terse",
+            "--fields",
+            "SSID,FREQ,SIGNAL,SECURITY",
+            "device",
+            "wifi",
+            "list",
+            "--rescan",
+            if rescan { "yes" } else { "no" },
+        ])
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8(output.stderr).unwrap_or_default());
+    }
+
+    Ok(parse(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Adds a connection profile with the arguments of `nmcli connection add`, and activates it.
+pub async fn add_and_activate(args: Vec<String>) -> Result<(), String> {
+    let output = tokio::process::Command::new("nmcli")
+        .args(&args)
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8(output.stderr).unwrap_or_default());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let uuid = added_uuid(&stdout).ok_or_else(|| stdout.trim().to_owned())?;
+
+    tokio::process::Command::new("nmcli")
+        .args(["connection", "up", uuid])
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .apply(crate::utils::map_stderr_output)
+}
+
+/// Reads the UUID from a message such as `Connection 'x' (<uuid>) successfully added.`
+fn added_uuid(output: &str) -> Option<&str> {
+    let (_, uuid) = output.rsplit_once('(')?;
+    let (uuid, _) = uuid.split_once(')')?;
+    Some(uuid)
+}
+
+pub fn parse(output: &str) -> HashMap<String, Network> {
+    let mut networks = HashMap::<String, Network>::new();
+
+    for line in output.lines() {
+        let [ssid, frequency, signal, security] = &fields(line)[..] else {
+            continue;
+        };
+
+        // Hidden networks have no SSID to connect to.
+        if ssid.is_empty() {
+            continue;
+        }
+
+        let band = frequency
+            .split_whitespace()
+            .next()
+            .and_then(|mhz| mhz.parse().ok())
+            .and_then(Band::from_frequency);
+
+        let signal = signal.parse().unwrap_or_default();
+        let security = Security::parse(security);
+
+        let network = networks.entry(ssid.clone()).or_insert(Network {
+            signal,
+            security,
+            bands: BTreeSet::new(),
+        });
+
+        if signal > network.signal {
+            network.signal = signal;
+            network.security = security;
+        }
+
+        network.bands.extend(band);
+    }
+
+    networks
+}
+
+/// Splits a line of terse nmcli output, in which `:` and `\` are escaped by `\`.
+fn fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => fields.last_mut().unwrap().extend(chars.next()),
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(char),
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Band, Security, added_uuid, parse};
+
+    #[test]
+    fn wifi_list() {
+        const OUTPUT: &str = "Home:2437 MHz:54:WPA2
+Home:5180 MHz:72:WPA2 WPA3
+Cafe\\: Guest:2412 MHz:40:
+:5200 MHz:30:WPA2
+";
+
+        let networks = parse(OUTPUT);
+        assert_eq!(networks.len(), 2);
+
+        let home = &networks["Home"];
+        assert_eq!(home.signal, 72);
+        assert_eq!(home.security, Security::Wpa3);
+        assert_eq!(
+            home.bands.iter().copied().collect::<Vec<_>>(),
+            vec![Band::Ghz2_4, Band::Ghz5]
+        );
+
+        let cafe = &networks["Cafe: Guest"];
+        assert_eq!(cafe.security, Security::Open);
+        assert_eq!(Security::parse("WPA2 802.1X"), Security::Enterprise);
+    }
+
+    #[test]
+    fn connection_added() {
+        assert_eq!(
+            added_uuid(
+                "Connection 'Office (5 GHz)' (7a1c55f0-3b5e-4c3b-9d7e-6e7f2f5c1a2b) successfully added."
+            ),
+            Some("7a1c55f0-3b5e-4c3b-9d7e-6e7f2f5c1a2b")
+        );
+    }
+}
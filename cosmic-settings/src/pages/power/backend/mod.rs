@@ -53,7 +53,7 @@ pub async fn get_backend() -> Option<PowerBackendEnum> {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum PowerProfile {
     Battery,
     Balanced,
@@ -157,6 +157,34 @@ async fn get_s76power_daemon_proxy<'a>() -> Result<s76powerdaemon::PowerDaemonPr
     }
 }
 
+/// Reads the charge thresholds of the battery as a start and end percentage.
+///
+/// Only system76-power can limit charging, and only on hardware that supports it, so
+/// `None` means that charging cannot be limited.
+pub async fn get_charge_thresholds() -> Option<(u8, u8)> {
+    let daemon = get_s76power_daemon_proxy().await.ok()?;
+
+    match daemon.get_charge_thresholds().await {
+        Ok(thresholds) => Some(thresholds),
+        Err(why) => {
+            tracing::info!("Charge thresholds are not supported. {why}");
+            None
+        }
+    }
+}
+
+pub async fn set_charge_thresholds(thresholds: (u8, u8)) {
+    let Ok(daemon) = get_s76power_daemon_proxy().await else {
+        tracing::error!("Problem while setting charge thresholds.");
+        return;
+    };
+
+    match daemon.set_charge_thresholds(&thresholds).await {
+        Ok(()) => tracing::info!("Charge thresholds set to {thresholds:?}."),
+        Err(e) => tracing::error!("{e}"),
+    }
+}
+
 pub struct PPBackend {}
 
 impl SetPowerProfile for PPBackend {
@@ -296,6 +324,34 @@ async fn enumerate_devices<'a>() -> Result<Vec<upower_dbus::DeviceProxy<'a>>, zb
         .collect())
 }
 
+/// The capacity of a full charge of the system battery compared to its design capacity,
+/// as a percentage.
+pub async fn battery_health() -> Option<f64> {
+    let devices = enumerate_devices().await.ok()?;
+
+    for proxy in devices {
+        let (device_type, power_supply) = futures::join!(
+            proxy.type_().map(|r| r.unwrap_or(BatteryType::Unknown)),
+            proxy.power_supply().map(Result::unwrap_or_default),
+        );
+
+        if !matches!(device_type, BatteryType::Battery) || !power_supply {
+            continue;
+        }
+
+        let (full, design) = futures::join!(
+            proxy.energy_full().map(Result::unwrap_or_default),
+            proxy.energy_full_design().map(Result::unwrap_or_default),
+        );
+
+        if design > 0.0 {
+            return Some((full / design * 100.0).clamp(0.0, 100.0));
+        }
+    }
+
+    None
+}
+
 impl Battery {
     pub async fn from_device(proxy: DeviceProxy<'_>) -> Self {
         let mut remaining_duration: Duration = Duration::default();
@@ -0,0 +1,79 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Named power profiles that pair a preset of the power daemon with idle settings.
+
+use cosmic::cosmic_config::{self, ConfigGet, ConfigSet};
+use serde::{Deserialize, Serialize};
+
+use super::backend::PowerProfile;
+
+const NAME: &str = "com.system76.CosmicSettings";
+const CUSTOM_PROFILES: &str = "power_profiles";
+const ACTIVE_PROFILE: &str = "active_power_profile";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CustomProfile {
+    pub name: String,
+    /// The preset of the power daemon, which decides the CPU governor and turbo.
+    pub preset: PowerProfile,
+    /// Milliseconds of idle time before the screen turns off, or never if `None`.
+    pub screen_off_time: Option<u32>,
+}
+
+impl Default for CustomProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            preset: PowerProfile::Balanced,
+            screen_off_time: Some(15 * 60 * 1000),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Config {
+    config: Option<cosmic_config::Config>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        let config = cosmic_config::Config::new(NAME, 1)
+            .inspect_err(|why| tracing::error!(?why, "failed to get {NAME} config"))
+            .ok();
+
+        Self { config }
+    }
+
+    pub fn profiles(&self) -> Vec<CustomProfile> {
+        self.config
+            .as_ref()
+            .and_then(|config| config.get::<Vec<CustomProfile>>(CUSTOM_PROFILES).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn set_profiles(&self, profiles: &[CustomProfile]) {
+        if let Some(config) = self.config.as_ref() {
+            if let Err(why) = config.set(CUSTOM_PROFILES, profiles) {
+                tracing::error!(?why, "failed to store custom power profiles");
+            }
+        }
+    }
+
+    /// The name of the custom profile that was selected last, if a preset was not
+    /// selected after it.
+    pub fn active_profile(&self) -> Option<String> {
+        self.config
+            .as_ref()
+            .and_then(|config| config.get::<Option<String>>(ACTIVE_PROFILE).ok())
+            .flatten()
+    }
+
+    pub fn set_active_profile(&self, name: Option<&str>) {
+        if let Some(config) = self.config.as_ref() {
+            if let Err(why) = config.set(ACTIVE_PROFILE, name) {
+                tracing::error!(?why, "failed to store active power profile");
+            }
+        }
+    }
+}
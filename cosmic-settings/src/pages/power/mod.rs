@@ -1,7 +1,9 @@
 mod backend;
+mod custom;
 
 use self::backend::{GetCurrentPowerProfile, SetPowerProfile};
 use backend::{Battery, ConnectedDevice, PowerProfile};
+use custom::CustomProfile;
 
 use chrono::TimeDelta;
 use cosmic::Task;
@@ -16,6 +18,7 @@ use futures::StreamExt;
 use itertools::Itertools;
 use slab::Slab;
 use slotmap::SlotMap;
+use std::borrow::Cow;
 use std::iter;
 use std::time::Duration;
 
@@ -27,6 +30,9 @@ static SCREEN_OFF_TIMES: &[Duration] = &[
     Duration::from_secs(30 * 60),
 ];
 
+/// Charging resumes once the battery drains this many percent below the charge limit.
+const CHARGE_RESUME_GAP: u8 = 5;
+
 static SUSPEND_TIMES: &[Duration] = &[
     Duration::from_secs(15 * 60),
     Duration::from_secs(20 * 60),
@@ -52,22 +58,39 @@ fn format_time(duration: Duration) -> String {
 pub struct Page {
     entity: page::Entity,
     battery: Battery,
+    battery_health: Option<f64>,
+    /// Start and end of charging, if the hardware supports limiting it.
+    charge_thresholds: Option<(u8, u8)>,
     connected_devices: Vec<ConnectedDevice>,
     on_enter_handle: Option<cosmic::iced::task::Handle>,
     screen_off_labels: Vec<String>,
     suspend_labels: Vec<String>,
     idle_config: Config,
     idle_conf: CosmicIdleConfig,
+    profiles_config: custom::Config,
+    custom_profiles: Vec<CustomProfile>,
+    /// The custom profile in use, which overrides the selection of the preset.
+    active_custom_profile: Option<usize>,
+    /// A custom profile being created.
+    new_profile: Option<CustomProfile>,
+    preset_labels: Vec<String>,
 }
 
 impl Default for Page {
     fn default() -> Self {
         let idle_config = Config::new("com.system76.CosmicIdle", 1).unwrap();
         let idle_conf = CosmicIdleConfig::get_entry(&idle_config).unwrap_or_else(|(_, conf)| conf);
+        let profiles_config = custom::Config::new();
+        let custom_profiles = profiles_config.profiles();
+        let active_custom_profile = profiles_config
+            .active_profile()
+            .and_then(|name| custom_profiles.iter().position(|p| p.name == name));
 
         Self {
             entity: Default::default(),
             battery: Default::default(),
+            battery_health: None,
+            charge_thresholds: None,
             connected_devices: Vec::new(),
             on_enter_handle: None,
             screen_off_labels: SCREEN_OFF_TIMES
@@ -84,6 +107,14 @@ impl Default for Page {
                 .collect(),
             idle_config,
             idle_conf,
+            profiles_config,
+            custom_profiles,
+            active_custom_profile,
+            new_profile: None,
+            preset_labels: backend::get_power_profiles()
+                .iter()
+                .map(PowerProfile::title)
+                .collect(),
         }
     }
 }
@@ -105,6 +136,7 @@ impl page::Page<crate::pages::Message> for Page {
     ) -> Option<page::Content> {
         Some(vec![
             sections.insert(battery_info()),
+            sections.insert(battery_charging()),
             sections.insert(connected_devices()),
             sections.insert(profiles()),
             sections.insert(power_saving()),
@@ -117,6 +149,12 @@ impl page::Page<crate::pages::Message> for Page {
                 let battery = Battery::update_battery().await;
                 Message::UpdateBattery(battery)
             }),
+            cosmic::Task::future(async move {
+                Message::UpdateBatteryHealth(backend::battery_health().await)
+            }),
+            cosmic::Task::future(async move {
+                Message::UpdateChargeThresholds(backend::get_charge_thresholds().await)
+            }),
             cosmic::Task::future(async move {
                 let devices = ConnectedDevice::update_connected_devices().await;
                 Message::UpdateConnectedDevices(devices)
@@ -187,12 +225,24 @@ impl page::Page<crate::pages::Message> for Page {
 pub enum Message {
     PowerProfileChange(PowerProfile),
     UpdateBattery(Battery),
+    UpdateBatteryHealth(Option<f64>),
+    UpdateChargeThresholds(Option<(u8, u8)>),
+    ChargeLimitChange(u8),
+    ChargeLimitApply,
     UpdateConnectedDevices(Vec<ConnectedDevice>),
     DeviceDisconnect(String),
     DeviceConnect(ConnectedDevice),
     ScreenOffTimeChange(Option<Duration>),
     SuspendOnAcTimeChange(Option<Duration>),
     SuspendOnBatteryTimeChange(Option<Duration>),
+    CustomProfileSelect(usize),
+    CustomProfileRemove(usize),
+    CustomProfileAdd,
+    CustomProfileName(String),
+    CustomProfilePreset(usize),
+    CustomProfileScreenOff(Option<Duration>),
+    CustomProfileSave,
+    CustomProfileCancel,
     Surface(surface::Action),
 }
 
@@ -207,8 +257,110 @@ impl Page {
                 if let Some(b) = backend {
                     runtime.block_on(b.set_power_profile(p));
                 }
+
+                self.active_custom_profile = None;
+                self.profiles_config.set_active_profile(None);
+            }
+            Message::CustomProfileSelect(pos) => {
+                let Some(profile) = self.custom_profiles.get(pos) else {
+                    return Task::none();
+                };
+
+                if let Some(b) = backend {
+                    runtime.block_on(b.set_power_profile(profile.preset));
+                }
+
+                if let Err(err) = self
+                    .idle_conf
+                    .set_screen_off_time(&self.idle_config, profile.screen_off_time)
+                {
+                    tracing::error!("failed to set screen off time: {}", err)
+                }
+
+                self.active_custom_profile = Some(pos);
+                self.profiles_config
+                    .set_active_profile(Some(profile.name.as_str()));
+            }
+            Message::CustomProfileRemove(pos) => {
+                if pos >= self.custom_profiles.len() {
+                    return Task::none();
+                }
+
+                self.custom_profiles.remove(pos);
+                self.profiles_config.set_profiles(&self.custom_profiles);
+
+                match self.active_custom_profile {
+                    Some(active) if active == pos => {
+                        self.active_custom_profile = None;
+                        self.profiles_config.set_active_profile(None);
+                    }
+                    Some(active) if active > pos => {
+                        self.active_custom_profile = Some(active - 1);
+                    }
+                    _ => (),
+                }
+            }
+            Message::CustomProfileAdd => {
+                self.new_profile = Some(CustomProfile::default());
+            }
+            Message::CustomProfileName(name) => {
+                if let Some(profile) = self.new_profile.as_mut() {
+                    profile.name = name;
+                }
+            }
+            Message::CustomProfilePreset(pos) => {
+                if let Some((profile, preset)) = self
+                    .new_profile
+                    .as_mut()
+                    .zip(backend::get_power_profiles().get(pos))
+                {
+                    profile.preset = *preset;
+                }
+            }
+            Message::CustomProfileScreenOff(time) => {
+                if let Some(profile) = self.new_profile.as_mut() {
+                    profile.screen_off_time = time.map(|x| x.as_millis() as u32);
+                }
+            }
+            Message::CustomProfileSave => {
+                let Some(mut profile) = self.new_profile.take() else {
+                    return Task::none();
+                };
+
+                profile.name = profile.name.trim().to_owned();
+                if profile.name.is_empty() {
+                    self.new_profile = Some(profile);
+                    return Task::none();
+                }
+
+                // Names identify the active profile, so saving an existing name replaces it.
+                match self
+                    .custom_profiles
+                    .iter()
+                    .position(|p| p.name == profile.name)
+                {
+                    Some(pos) => self.custom_profiles[pos] = profile,
+                    None => self.custom_profiles.push(profile),
+                }
+
+                self.profiles_config.set_profiles(&self.custom_profiles);
+            }
+            Message::CustomProfileCancel => {
+                self.new_profile = None;
             }
             Message::UpdateBattery(battery) => self.battery = battery,
+            Message::UpdateBatteryHealth(health) => self.battery_health = health,
+            Message::UpdateChargeThresholds(thresholds) => self.charge_thresholds = thresholds,
+            Message::ChargeLimitChange(limit) => {
+                if let Some(thresholds) = self.charge_thresholds.as_mut() {
+                    *thresholds = (limit.saturating_sub(CHARGE_RESUME_GAP), limit);
+                }
+            }
+            Message::ChargeLimitApply => {
+                if let Some(thresholds) = self.charge_thresholds {
+                    tokio::task::spawn(backend::set_charge_thresholds(thresholds));
+                }
+            }
             Message::UpdateConnectedDevices(connected_devices) => {
                 self.connected_devices = connected_devices;
             }
@@ -277,6 +429,65 @@ fn battery_info() -> Section<crate::pages::Message> {
         })
 }
 
+fn battery_charging() -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
+
+    let health = descriptions.insert(fl!("battery-charging", "health"));
+    let health_desc = descriptions.insert(fl!("battery-charging", "health-desc"));
+    let limit = descriptions.insert(fl!("battery-charging", "limit"));
+    let limit_desc = descriptions.insert(fl!("battery-charging", "limit-desc"));
+
+    Section::default()
+        .title(fl!("battery-charging"))
+        .descriptions(descriptions)
+        .show_while::<Page>(|page| {
+            page.battery.is_present
+                && (page.battery_health.is_some() || page.charge_thresholds.is_some())
+        })
+        .view::<Page>(move |_binder, page, section| {
+            let descriptions = &section.descriptions;
+            let mut section = settings::section().title(&section.title);
+
+            if let Some(battery_health) = page.battery_health {
+                section = section.add(
+                    settings::item::builder(&descriptions[health])
+                        .description(&descriptions[health_desc])
+                        .control(text::body(format!("{battery_health:.0}%"))),
+                );
+            }
+
+            // Hardware that cannot limit charging has no thresholds to show.
+            if let Some((_, end)) = page.charge_thresholds {
+                section = section.add(
+                    settings::item::builder(&descriptions[limit])
+                        .description(&descriptions[limit_desc])
+                        .flex_control({
+                            let slider = widget::slider(50..=100, end, Message::ChargeLimitChange)
+                                .step(5u8)
+                                .on_release(Message::ChargeLimitApply)
+                                .width(Length::Fill)
+                                .apply(widget::container)
+                                .max_width(250);
+
+                            widget::row::with_capacity(2)
+                                .align_y(Alignment::Center)
+                                .spacing(8)
+                                .push(
+                                    text::body(format!("{end}%"))
+                                        .width(Length::Fixed(36.0))
+                                        .align_x(Alignment::Center),
+                                )
+                                .push(slider)
+                        }),
+                );
+            }
+
+            section
+                .apply(cosmic::Element::from)
+                .map(crate::pages::Message::Power)
+        })
+}
+
 fn connected_devices() -> Section<crate::pages::Message> {
     let descriptions = Slab::new();
 
@@ -368,7 +579,7 @@ fn profiles() -> Section<crate::pages::Message> {
     Section::default()
         .title(fl!("power-mode"))
         .descriptions(descriptions)
-        .view::<Page>(move |_binder, _page, section| {
+        .view::<Page>(move |_binder, page, section| {
             let mut section = settings::section().title(&section.title);
 
             let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -389,7 +600,10 @@ fn profiles() -> Section<crate::pages::Message> {
                                     .push(text::body(profile.title()))
                                     .push(text::caption(profile.description())),
                                 profile,
-                                Some(current_profile),
+                                // A custom profile in use takes the place of its preset.
+                                page.active_custom_profile
+                                    .is_none()
+                                    .then_some(current_profile),
                                 Message::PowerProfileChange,
                             )
                             .width(Length::Fill)
@@ -397,6 +611,41 @@ fn profiles() -> Section<crate::pages::Message> {
                         ])
                     })
                     .fold(section, settings::Section::add);
+
+                section = page
+                    .custom_profiles
+                    .iter()
+                    .enumerate()
+                    .map(|(pos, profile)| {
+                        settings::item_row(vec![
+                            radio(
+                                widget::column::with_capacity(2)
+                                    .push(text::body(&profile.name))
+                                    .push(text::caption(profile.preset.title())),
+                                pos,
+                                page.active_custom_profile,
+                                Message::CustomProfileSelect,
+                            )
+                            .width(Length::Fill)
+                            .into(),
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::CustomProfileRemove(pos))
+                                .into(),
+                        ])
+                    })
+                    .fold(section, settings::Section::add);
+
+                section = match page.new_profile.as_ref() {
+                    Some(profile) => custom_profile_editor(page, profile)
+                        .into_iter()
+                        .fold(section, settings::Section::add),
+                    None => section.add(settings::item_row(vec![
+                        widget::horizontal_space().into(),
+                        widget::button::standard(fl!("power-mode", "add-custom"))
+                            .on_press(Message::CustomProfileAdd)
+                            .into(),
+                    ])),
+                };
             } else {
                 let item = text::body(fl!("power-mode", "no-backend"));
                 section = section.add(item);
@@ -408,8 +657,60 @@ fn profiles() -> Section<crate::pages::Message> {
         })
 }
 
+fn custom_profile_editor<'a>(
+    page: &'a Page,
+    profile: &'a CustomProfile,
+) -> Vec<cosmic::Element<'a, Message>> {
+    let preset = backend::get_power_profiles()
+        .iter()
+        .position(|preset| *preset == profile.preset);
+
+    vec![
+        settings::item(
+            fl!("power-mode", "custom-name"),
+            widget::text_input("", profile.name.as_str())
+                .on_input(Message::CustomProfileName)
+                .on_submit(|_| Message::CustomProfileSave),
+        )
+        .into(),
+        settings::item(
+            fl!("power-mode", "custom-preset"),
+            widget::dropdown::popup_dropdown(
+                &page.preset_labels,
+                preset,
+                Message::CustomProfilePreset,
+                cosmic::iced::window::Id::RESERVED,
+                Message::Surface,
+                |a| crate::app::Message::PageMessage(crate::pages::Message::Power(a)),
+            ),
+        )
+        .into(),
+        power_saving_row(
+            fl!("power-saving", "turn-off-screen-after"),
+            &page.screen_off_labels,
+            profile
+                .screen_off_time
+                .map(|t| Duration::from_millis(t.into())),
+            SCREEN_OFF_TIMES,
+            Message::CustomProfileScreenOff,
+        ),
+        settings::item_row(vec![
+            widget::horizontal_space().into(),
+            widget::button::standard(fl!("cancel"))
+                .on_press(Message::CustomProfileCancel)
+                .into(),
+            widget::button::suggested(fl!("save"))
+                .on_press_maybe(
+                    (!profile.name.trim().is_empty()).then_some(Message::CustomProfileSave),
+                )
+                .into(),
+        ])
+        .into(),
+    ]
+}
+
 fn power_saving_row<'a>(
-    label: &'a str,
+    label: impl Into<Cow<'a, str>>,
     labels: &'a [String],
     selected_time: Option<Duration>,
     times: &'static [Duration],
@@ -0,0 +1,69 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Measures the level of an input device through `parec`.
+
+use std::process::Stdio;
+
+use futures::SinkExt;
+use tokio::io::AsyncReadExt;
+
+const RATE: usize = 8000;
+
+/// Bytes of 16-bit mono samples measured for each level, which is a twentieth of a second.
+const CHUNK: usize = RATE / 20 * 2;
+
+/// Sends the peak level of the input device named `source`, from 0 to 1, twenty times a
+/// second until the receiver is dropped or recording fails.
+pub async fn watch(source: String, mut tx: futures::channel::mpsc::Sender<f32>) {
+    let Ok(mut child) = tokio::process::Command::new("parec")
+        .arg(format!("--device={source}"))
+        .args([
+            "--format=s16le",
+            "--channels=1",
+            "--latency-msec=50",
+            "--property=application.name=cosmic-settings",
+        ])
+        .arg(format!("--rate={RATE}"))
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    else {
+        return;
+    };
+
+    let Some(mut stdout) = child.stdout.take() else {
+        return;
+    };
+
+    let mut chunk = [0u8; CHUNK];
+
+    while stdout.read_exact(&mut chunk).await.is_ok() {
+        if tx.send(peak(&chunk)).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// The peak of 16-bit little-endian samples, from 0 to 1.
+pub fn peak(samples: &[u8]) -> f32 {
+    samples
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]).unsigned_abs())
+        .max()
+        .map_or(0.0, |peak| f32::from(peak) / 32768.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::peak;
+
+    #[test]
+    fn sample_peak() {
+        assert_eq!(peak(&[]), 0.0);
+        assert_eq!(peak(&[0x00, 0x40, 0x00, 0xe0]), 0.5);
+        assert_eq!(peak(&[0x00, 0x80]), 1.0);
+    }
+}
@@ -16,6 +16,12 @@ use indexmap::IndexMap;
 use slab::Slab;
 use slotmap::SlotMap;
 
+mod level;
+mod speakers;
+mod streams;
+
+use streams::{Stream, StreamId};
+
 pub type NodeId = u32;
 pub type ProfileId = u32;
 
@@ -53,6 +59,22 @@ pub enum Message {
     SourceVolumeApply(NodeId),
     /// Toggle the mute status of the input output.
     SourceMuteToggle,
+    /// Get the level of the default input.
+    SourceLevel(f32),
+    /// Play a sample through each channel of the default output.
+    TestOutput,
+    /// Get the channels of the output under test.
+    TestOutputChannels(String, Vec<String>),
+    /// Play a sample through a channel of the output under test.
+    TestOutputChannel(String, usize),
+    /// Get the playback streams of applications.
+    Streams(Vec<Stream>),
+    /// Request to change the volume of an application's stream.
+    StreamVolumeChanged(StreamId, u32),
+    /// Change the volume of an application's stream.
+    StreamVolumeApply(StreamId),
+    /// Toggle the mute status of an application's stream.
+    StreamMuteToggle(StreamId),
     /// Surface Action
     Surface(surface::Action),
 }
@@ -80,6 +102,8 @@ pub struct Page {
     entity: page::Entity,
     pipewire_thread: Option<(tokio::sync::oneshot::Sender<()>, pipewire::Sender<()>)>,
     pulse_thread: Option<tokio::sync::oneshot::Sender<()>>,
+    streams_thread: Option<tokio::sync::oneshot::Sender<()>>,
+    level_thread: Option<(String, tokio::sync::oneshot::Sender<()>)>,
     devices: BTreeMap<DeviceId, Card>,
     card_names: IndexMap<DeviceId, String>,
     card_profiles: IndexMap<DeviceId, Vec<pulse::CardProfile>>,
@@ -101,6 +125,7 @@ pub struct Page {
     source_volume_text: String,
     source_mute: bool,
     source_volume_debounce: bool,
+    source_level: f32,
 
     sinks: Vec<String>,
     sink_ids: Vec<NodeId>,
@@ -120,6 +145,13 @@ pub struct Page {
 
     changing_sink_profile: bool,
     changing_source_profile: bool,
+
+    streams: IndexMap<StreamId, Stream>,
+    stream_volume_debounce: Option<StreamId>,
+
+    testing_output: bool,
+    test_channels: Vec<String>,
+    test_channel: Option<usize>,
 }
 
 impl page::Page<crate::pages::Message> for Page {
@@ -131,7 +163,11 @@ impl page::Page<crate::pages::Message> for Page {
         &self,
         sections: &mut SlotMap<section::Entity, Section<crate::pages::Message>>,
     ) -> Option<page::Content> {
-        Some(vec![sections.insert(output()), sections.insert(input())])
+        Some(vec![
+            sections.insert(output()),
+            sections.insert(input()),
+            sections.insert(applications()),
+        ])
     }
 
     fn info(&self) -> page::Info {
@@ -141,7 +177,7 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
-        let mut tasks = Vec::with_capacity(2);
+        let mut tasks = Vec::with_capacity(3);
         if self.pulse_thread.is_none() {
             let (tx, mut rx) = futures::channel::mpsc::channel(1);
             let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel::<()>();
@@ -194,6 +230,33 @@ impl page::Page<crate::pages::Message> for Page {
             self.pipewire_thread = Some((cancel_tx, terminate));
         }
 
+        if self.streams_thread.is_none() {
+            let (tx, mut rx) = futures::channel::mpsc::channel(1);
+            let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel::<()>();
+
+            // Watch the playback streams of applications until the application
+            // requests to stop, which also kills the `pactl subscribe` process.
+            tasks.push(Task::stream(async_fn_stream::fn_stream(
+                |emitter| async move {
+                    let forwarder = std::pin::pin!(async move {
+                        let watcher = std::pin::pin!(streams::watch(tx));
+                        let receiver = std::pin::pin!(async move {
+                            while let Some(streams) = rx.next().await {
+                                let event = crate::pages::Message::Sound(Message::Streams(streams));
+                                emitter.emit(event).await;
+                            }
+                        });
+
+                        futures::future::join(watcher, receiver).await;
+                    });
+
+                    futures::future::select(std::pin::pin!(cancel_rx), forwarder).await;
+                },
+            )));
+
+            self.streams_thread = Some(cancel_tx);
+        }
+
         cosmic::task::batch(tasks)
     }
 
@@ -207,6 +270,14 @@ impl page::Page<crate::pages::Message> for Page {
             _ = terminate.send(());
         }
 
+        if let Some(cancellation) = self.streams_thread.take() {
+            _ = cancellation.send(());
+        }
+
+        if let Some((_, cancellation)) = self.level_thread.take() {
+            _ = cancellation.send(());
+        }
+
         *self = Page::default();
 
         Task::none()
@@ -304,6 +375,65 @@ impl Page {
         }
     }
 
+    /// Measures the level of the default input, restarting when the default input changes.
+    fn watch_source_level(&mut self) -> Task<crate::app::Message> {
+        if self.default_source.is_empty()
+            || self
+                .level_thread
+                .as_ref()
+                .is_some_and(|(source, _)| *source == self.default_source)
+        {
+            return Task::none();
+        }
+
+        if let Some((_, cancellation)) = self.level_thread.take() {
+            _ = cancellation.send(());
+        }
+
+        self.source_level = 0.0;
+
+        let source = self.default_source.clone();
+        let (tx, mut rx) = futures::channel::mpsc::channel(1);
+        let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let task = Task::stream(async_fn_stream::fn_stream(|emitter| async move {
+            let forwarder = std::pin::pin!(async move {
+                let watcher = std::pin::pin!(level::watch(source, tx));
+                let receiver = std::pin::pin!(async move {
+                    while let Some(level) = rx.next().await {
+                        let event = crate::pages::Message::Sound(Message::SourceLevel(level));
+                        emitter.emit(event).await;
+                    }
+                });
+
+                futures::future::join(watcher, receiver).await;
+            });
+
+            futures::future::select(std::pin::pin!(cancel_rx), forwarder).await;
+        }));
+
+        self.level_thread = Some((self.default_source.clone(), cancel_tx));
+
+        task.map(crate::app::Message::PageMessage)
+    }
+
+    /// Plays the sample of the channel at `pos`, or ends the test after the last channel.
+    fn test_output_channel(&mut self, sink: String, pos: usize) -> Task<crate::app::Message> {
+        let Some(channel) = self.test_channels.get(pos).cloned() else {
+            self.testing_output = false;
+            self.test_channels.clear();
+            self.test_channel = None;
+            return Task::none();
+        };
+
+        self.test_channel = Some(pos);
+
+        cosmic::task::future(async move {
+            speakers::play(sink.clone(), channel).await;
+            crate::pages::Message::Sound(Message::TestOutputChannel(sink, pos + 1))
+        })
+    }
+
     pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
         match message {
             Message::SourceVolumeChanged(volume) => {
@@ -390,6 +520,7 @@ impl Page {
             Message::Pulse(pulse::Event::DefaultSource(source)) => {
                 if !self.changing_source_profile {
                     self.set_default_source(source);
+                    return self.watch_source_level();
                 }
             }
             Message::Pulse(pulse::Event::SinkMute(mute)) => {
@@ -523,7 +654,7 @@ impl Page {
                                 self.active_source = Some(pos);
                                 pactl_set_default_source(device.identifier.clone());
                                 self.set_default_source(device.identifier.clone());
-                                return Task::none();
+                                return self.watch_source_level();
                             }
                         }
                     }
@@ -619,6 +750,80 @@ impl Page {
                         self.set_default_source(device.identifier.clone());
                     }
                 }
+
+                return self.watch_source_level();
+            }
+            Message::SourceLevel(level) => {
+                self.source_level = level;
+            }
+            Message::TestOutput => {
+                if self.testing_output || self.default_sink.is_empty() {
+                    return Task::none();
+                }
+
+                self.testing_output = true;
+                let sink = self.default_sink.clone();
+                return cosmic::task::future(async move {
+                    let channels = speakers::channels(sink.clone()).await;
+                    crate::pages::Message::Sound(Message::TestOutputChannels(sink, channels))
+                });
+            }
+            Message::TestOutputChannels(sink, channels) => {
+                self.test_channels = channels;
+                return self.test_output_channel(sink, 0);
+            }
+            Message::TestOutputChannel(sink, pos) => {
+                return self.test_output_channel(sink, pos);
+            }
+            Message::Streams(streams) => {
+                // Keep the volume of a stream that is being changed, which the
+                // stream only reports after the change has been applied.
+                let changing = self
+                    .stream_volume_debounce
+                    .and_then(|index| Some((index, self.streams.get(&index)?.volume)));
+
+                self.streams = streams
+                    .into_iter()
+                    .map(|stream| (stream.index, stream))
+                    .collect();
+
+                if let Some((index, volume)) = changing {
+                    if let Some(stream) = self.streams.get_mut(&index) {
+                        stream.volume = volume;
+                    }
+                }
+            }
+            Message::StreamVolumeChanged(index, volume) => {
+                // The stream may have vanished while its slider was held.
+                let Some(stream) = self.streams.get_mut(&index) else {
+                    return Task::none();
+                };
+
+                stream.volume = volume;
+                if self.stream_volume_debounce == Some(index) {
+                    return Task::none();
+                }
+
+                self.stream_volume_debounce = Some(index);
+                return cosmic::task::future(async move {
+                    tokio::time::sleep(Duration::from_millis(64)).await;
+                    crate::pages::Message::Sound(Message::StreamVolumeApply(index))
+                });
+            }
+            Message::StreamVolumeApply(index) => {
+                if self.stream_volume_debounce == Some(index) {
+                    self.stream_volume_debounce = None;
+                }
+
+                if let Some(stream) = self.streams.get(&index) {
+                    streams::set_volume(index, stream.volume);
+                }
+            }
+            Message::StreamMuteToggle(index) => {
+                if let Some(stream) = self.streams.get_mut(&index) {
+                    stream.mute = !stream.mute;
+                    streams::set_mute(index, stream.mute);
+                }
             }
             Message::Surface(a) => {
                 return cosmic::task::message(crate::app::Message::Surface(a));
@@ -633,7 +838,7 @@ fn input() -> Section<crate::pages::Message> {
 
     let volume = descriptions.insert(fl!("sound-input", "volume"));
     let device = descriptions.insert(fl!("sound-input", "device"));
-    let _level = descriptions.insert(fl!("sound-input", "level"));
+    let level = descriptions.insert(fl!("sound-input", "level"));
     let profile = descriptions.insert(fl!("profile"));
 
     Section::default()
@@ -675,7 +880,11 @@ fn input() -> Section<crate::pages::Message> {
                     &*section.descriptions[volume],
                     volume_control,
                 ))
-                .add(settings::item(&*section.descriptions[device], devices));
+                .add(settings::item(&*section.descriptions[device], devices))
+                .add(settings::flex_item(
+                    &*section.descriptions[level],
+                    widget::progress_bar(0.0..=1.0, page.source_level),
+                ));
 
             if !page.source_profiles.is_empty() {
                 let dropdown = widget::dropdown::popup_dropdown(
@@ -704,6 +913,8 @@ fn output() -> Section<crate::pages::Message> {
     let balance = descriptions.insert(fl!("sound-output", "balance"));
     let left = descriptions.insert(fl!("sound-output", "left"));
     let right = descriptions.insert(fl!("sound-output", "right"));
+    let test = descriptions.insert(fl!("sound-output", "test"));
+    let test_button = descriptions.insert(fl!("sound-output", "test-button"));
     // let balance = descriptions.insert(fl!("sound-output", "balance"));
 
     Section::default()
@@ -748,6 +959,32 @@ fn output() -> Section<crate::pages::Message> {
                 ))
                 .add(settings::item(&*section.descriptions[device], devices));
 
+            let test_control = page
+                .test_channels
+                .iter()
+                .enumerate()
+                .fold(
+                    widget::row::with_capacity(page.test_channels.len() + 1)
+                        .align_y(Alignment::Center)
+                        .spacing(cosmic::theme::spacing().space_xs),
+                    |row, (pos, channel)| {
+                        // Highlight the channel that is playing.
+                        row.push(widget::text::body(channel_name(channel)).class(
+                            if page.test_channel == Some(pos) {
+                                cosmic::theme::Text::Accent
+                            } else {
+                                cosmic::theme::Text::Default
+                            },
+                        ))
+                    },
+                )
+                .push(
+                    widget::button::standard(&*section.descriptions[test_button])
+                        .on_press_maybe((!page.testing_output).then_some(Message::TestOutput)),
+                );
+
+            controls = controls.add(settings::item(&*section.descriptions[test], test_control));
+
             if !page.sink_profiles.is_empty() {
                 let dropdown = widget::dropdown::popup_dropdown(
                     &page.sink_profiles,
@@ -808,23 +1045,76 @@ fn output() -> Section<crate::pages::Message> {
 //         })
 // }
 
-// fn applications() -> Section<crate::pages::Message> {
-//     let mut descriptions = Slab::new();
+fn applications() -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
 
-//     let applications = descriptions.insert(fl!("sound-applications", "desc"));
+    let empty = descriptions.insert(fl!("sound-applications", "empty"));
 
-//     Section::default()
-//         .title(fl!("sound-applications"))
-//         .descriptions(descriptions)
-//         .view::<Page>(move |_binder, _page, section| {
-//             settings::section().title(&section.title)
-//                 .add(settings::item(
-//                     &*section.descriptions[applications],
-//                     text::body("TODO"),
-//                 ))
-//                 .into()
-//         })
-// }
+    Section::default()
+        .title(fl!("sound-applications"))
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let mut controls = settings::section().title(&section.title);
+
+            if page.streams.is_empty() {
+                controls = controls.add(settings::item_row(vec![
+                    widget::text::body(&*section.descriptions[empty]).into(),
+                ]));
+            }
+
+            for (&index, stream) in &page.streams {
+                let volume_control = widget::row::with_capacity(4)
+                    .align_y(Alignment::Center)
+                    .push(
+                        widget::button::icon(if stream.mute {
+                            widget::icon::from_name("audio-volume-muted-symbolic")
+                        } else {
+                            widget::icon::from_name("audio-volume-high-symbolic")
+                        })
+                        .on_press(Message::StreamMuteToggle(index)),
+                    )
+                    .push(
+                        widget::text::body(stream.volume.to_string())
+                            .width(Length::Fixed(22.0))
+                            .align_x(Alignment::Center),
+                    )
+                    .push(widget::horizontal_space().width(8))
+                    .push(
+                        widget::slider(0..=150, stream.volume, move |volume| {
+                            Message::StreamVolumeChanged(index, volume)
+                        })
+                        .breakpoints(&[100]),
+                    );
+
+                let item = settings::item::builder(stream.name.as_str());
+                let item = match stream.icon.as_deref() {
+                    Some(icon) => item.icon(widget::icon::from_name(icon).size(24).icon()),
+                    None => item,
+                };
+
+                controls = controls.add(item.flex_control(volume_control));
+            }
+
+            Element::from(controls).map(crate::pages::Message::Sound)
+        })
+}
+
+/// The localized name of a PulseAudio channel position, such as `front-left`.
+fn channel_name(channel: &str) -> String {
+    match channel {
+        "mono" => fl!("sound-channel", "mono"),
+        "front-left" => fl!("sound-channel", "front-left"),
+        "front-right" => fl!("sound-channel", "front-right"),
+        "front-center" => fl!("sound-channel", "front-center"),
+        "rear-left" => fl!("sound-channel", "rear-left"),
+        "rear-right" => fl!("sound-channel", "rear-right"),
+        "rear-center" => fl!("sound-channel", "rear-center"),
+        "side-left" => fl!("sound-channel", "side-left"),
+        "side-right" => fl!("sound-channel", "side-right"),
+        "lfe" => fl!("sound-channel", "lfe"),
+        _ => channel.to_owned(),
+    }
+}
 
 fn sort_pulse_devices(descriptions: &mut Vec<String>, node_ids: &mut Vec<NodeId>) {
     let mut tmp: Vec<(String, NodeId)> = std::mem::take(descriptions)
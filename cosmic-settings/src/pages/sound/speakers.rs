@@ -0,0 +1,101 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Plays a voice sample through each channel of an output device to verify speaker wiring.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+
+/// Lists the channels of the output device named `sink`, such as `front-left`.
+pub async fn channels(sink: String) -> Vec<String> {
+    let Ok(output) = tokio::process::Command::new("pactl")
+        .args(["--format=json", "list", "sinks"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    else {
+        return Vec::new();
+    };
+
+    channel_map(&String::from_utf8_lossy(&output.stdout), &sink)
+}
+
+/// Reads the channel map of `sink` from the output of `pactl --format=json list sinks`.
+pub fn channel_map(json: &str, sink: &str) -> Vec<String> {
+    let Ok(serde_json::Value::Array(sinks)) = serde_json::from_str(json) else {
+        return Vec::new();
+    };
+
+    sinks
+        .iter()
+        .find(|info| info.get("name").and_then(serde_json::Value::as_str) == Some(sink))
+        .and_then(|info| info.get("channel_map")?.as_str())
+        .map(|map| {
+            map.split(',')
+                .map(str::trim)
+                .filter(|channel| !channel.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Plays the sample of `channel` through that channel of `sink`, returning once it has played.
+pub async fn play(sink: String, channel: String) {
+    let Some(sample) = sample(&channel) else {
+        return;
+    };
+
+    _ = tokio::process::Command::new("paplay")
+        .arg(format!("--device={sink}"))
+        .arg(format!("--channel-map={channel}"))
+        .arg(sample)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+}
+
+/// Finds the sample of the freedesktop sound theme that names the channel, or the
+/// generic test signal for channels that have no sample of their own.
+fn sample(channel: &str) -> Option<PathBuf> {
+    let xdg_data_dirs = std::env::var("XDG_DATA_DIRS").ok();
+
+    let sound_dirs = xdg_data_dirs
+        .as_deref()
+        // Default from the XDG Base Directory Specification
+        .or(Some("/usr/local/share/:/usr/share/"))
+        .into_iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join("sounds/freedesktop/stereo"))
+        .collect::<Vec<_>>();
+
+    [
+        format!("audio-channel-{channel}.oga"),
+        String::from("audio-test-signal.oga"),
+    ]
+    .iter()
+    .flat_map(|file| sound_dirs.iter().map(move |dir| dir.join(file)))
+    .find(|path| path.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::channel_map;
+
+    #[test]
+    fn sink_channels() {
+        const EXAMPLE: &str = r#"[
+            {"name": "alsa_output.hdmi", "channel_map": "front-left,front-right,lfe"},
+            {"name": "alsa_output.analog", "channel_map": "mono"}
+        ]"#;
+
+        assert_eq!(
+            channel_map(EXAMPLE, "alsa_output.hdmi"),
+            vec!["front-left", "front-right", "lfe"]
+        );
+        assert_eq!(channel_map(EXAMPLE, "alsa_output.analog"), vec!["mono"]);
+        assert!(channel_map(EXAMPLE, "bluez_output").is_empty());
+    }
+}
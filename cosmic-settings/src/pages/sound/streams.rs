@@ -0,0 +1,186 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Tracks the playback streams of running applications through `pactl`.
+
+use std::process::Stdio;
+
+use futures::SinkExt;
+use tokio::io::AsyncBufReadExt;
+
+/// Index of a sink input, which PulseAudio never reuses while the server runs.
+pub type StreamId = u32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stream {
+    pub index: StreamId,
+    pub name: String,
+    pub icon: Option<String>,
+    /// Volume of the loudest channel, as a percentage.
+    pub volume: u32,
+    pub mute: bool,
+}
+
+/// Sends the list of playback streams every time a stream appears, changes or vanishes,
+/// until the receiver is dropped or `pactl subscribe` exits.
+pub async fn watch(mut tx: futures::channel::mpsc::Sender<Vec<Stream>>) {
+    let Ok(mut child) = tokio::process::Command::new("pactl")
+        .arg("subscribe")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    else {
+        return;
+    };
+
+    let Some(stdout) = child.stdout.take() else {
+        return;
+    };
+
+    if tx.send(list().await).await.is_err() {
+        return;
+    }
+
+    let mut lines = tokio::io::BufReader::new(stdout).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        // Events look like `Event 'change' on sink-input #42`.
+        if line.contains("sink-input") && tx.send(list().await).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Lists the current playback streams.
+pub async fn list() -> Vec<Stream> {
+    let Ok(output) = tokio::process::Command::new("pactl")
+        .args(["--format=json", "list", "sink-inputs"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    else {
+        return Vec::new();
+    };
+
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the output of `pactl --format=json list sink-inputs`.
+pub fn parse(json: &str) -> Vec<Stream> {
+    let Ok(serde_json::Value::Array(inputs)) = serde_json::from_str(json) else {
+        return Vec::new();
+    };
+
+    let mut streams = inputs
+        .iter()
+        .filter_map(|input| {
+            let index = u32::try_from(input.get("index")?.as_u64()?).ok()?;
+            let properties = input.get("properties");
+            let property = |key: &str| {
+                properties
+                    .and_then(|properties| properties.get(key))
+                    .and_then(serde_json::Value::as_str)
+                    .filter(|value| !value.is_empty())
+                    .map(String::from)
+            };
+
+            // Event sounds are short-lived and have their own volume in the alerts settings.
+            if property("media.role").as_deref() == Some("event") {
+                return None;
+            }
+
+            let volume = input
+                .get("volume")
+                .and_then(serde_json::Value::as_object)
+                .into_iter()
+                .flat_map(|channels| channels.values())
+                .filter_map(|channel| channel.get("value")?.as_u64())
+                .max()
+                .unwrap_or(0);
+
+            Some(Stream {
+                index,
+                name: property("application.name")
+                    .or_else(|| property("media.name"))
+                    .unwrap_or_else(|| format!("#{index}")),
+                icon: property("application.icon_name"),
+                volume: (volume as f64 * 100.0 / 65536.0).round() as u32,
+                mute: input
+                    .get("mute")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    streams.sort_by(|a, b| a.name.cmp(&b.name).then(a.index.cmp(&b.index)));
+    streams
+}
+
+pub fn set_volume(index: StreamId, volume: u32) {
+    tokio::task::spawn(async move {
+        let index = index.to_string();
+        let volume = format!("{volume}%");
+        _ = tokio::process::Command::new("pactl")
+            .args(["set-sink-input-volume", index.as_str(), volume.as_str()])
+            .status()
+            .await;
+    });
+}
+
+pub fn set_mute(index: StreamId, mute: bool) {
+    tokio::task::spawn(async move {
+        let index = index.to_string();
+        _ = tokio::process::Command::new("pactl")
+            .args([
+                "set-sink-input-mute",
+                index.as_str(),
+                if mute { "1" } else { "0" },
+            ])
+            .status()
+            .await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Stream, parse};
+
+    #[test]
+    fn sink_inputs() {
+        const EXAMPLE: &str = r#"[
+            {"index": 42, "mute": false,
+             "volume": {"front-left": {"value": 32768}, "front-right": {"value": 65536}},
+             "properties": {"application.name": "Firefox", "application.icon_name": "firefox"}},
+            {"index": 7, "mute": true,
+             "volume": {"mono": {"value": 98304}},
+             "properties": {"media.name": "Playback"}},
+            {"index": 8, "mute": false, "volume": {},
+             "properties": {"media.role": "event", "application.name": "Bell"}}
+        ]"#;
+
+        assert_eq!(
+            parse(EXAMPLE),
+            vec![
+                Stream {
+                    index: 42,
+                    name: String::from("Firefox"),
+                    icon: Some(String::from("firefox")),
+                    volume: 100,
+                    mute: false,
+                },
+                Stream {
+                    index: 7,
+                    name: String::from("Playback"),
+                    icon: None,
+                    volume: 150,
+                    mute: true,
+                },
+            ]
+        );
+        assert!(parse("not json").is_empty());
+    }
+}
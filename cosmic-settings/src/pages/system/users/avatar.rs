@@ -0,0 +1,100 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Profile images of accounts, chosen from the faces that the distribution provides or
+//! from an image file.
+//!
+//! Image files are cropped to a square and downscaled before they are given to the accounts
+//! daemon, which refuses large files.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use image::imageops::FilterType;
+
+/// Directory of the default profile images.
+const FACES_DIR: &str = "/usr/share/pixmaps/faces";
+
+/// Width and height that profile images are downscaled to.
+const SIZE: u32 = 256;
+
+/// The default profile images that may be chosen instead of an image file.
+pub fn faces() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(FACES_DIR) else {
+        return Vec::new();
+    };
+
+    let mut faces = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "png" | "jpg" | "jpeg" | "webp"))
+        })
+        .collect::<Vec<_>>();
+
+    faces.sort();
+    faces
+}
+
+/// Crops an image file to a square at its center, downscales it, and saves it where the
+/// accounts daemon can copy it from.
+pub fn prepare(source: &Path, uid: u64) -> Result<PathBuf, String> {
+    let image = image::open(source).map_err(|why| format!("failed to open image: {why}"))?;
+
+    let (x, y, side) = center_square(image.width(), image.height());
+    let mut image = image.crop_imm(x, y, side, side);
+
+    if side > SIZE {
+        image = image.resize_exact(SIZE, SIZE, FilterType::Lanczos3);
+    }
+
+    let dir = dirs::cache_dir()
+        .ok_or_else(|| String::from("no cache directory"))?
+        .join("cosmic-settings/avatars");
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|why| format!("failed to create {}: {why}", dir.display()))?;
+
+    // Each image is saved under a new name, so that it is not shown from the cache of the
+    // image it replaces.
+    let prefix = format!("{uid}-");
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.filter_map(Result::ok) {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    let time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = dir.join(format!("{prefix}{time}.png"));
+
+    image
+        .save(&path)
+        .map_err(|why| format!("failed to save {}: {why}", path.display()))?;
+
+    Ok(path)
+}
+
+/// The offset and side of the largest square at the center of an image.
+fn center_square(width: u32, height: u32) -> (u32, u32, u32) {
+    let side = width.min(height);
+    ((width - side) / 2, (height - side) / 2, side)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::center_square;
+
+    #[test]
+    fn square() {
+        assert_eq!(center_square(1920, 1080), (420, 0, 1080));
+        assert_eq!(center_square(600, 801), (0, 100, 600));
+        assert_eq!(center_square(64, 64), (0, 0, 64));
+    }
+}
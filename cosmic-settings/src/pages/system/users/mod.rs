@@ -1,9 +1,12 @@
 // Copyright 2024 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+mod avatar;
 mod getent;
+mod validate;
 
 use crate::pages;
+use chrono::{DateTime, Local};
 use cosmic::{
     Apply, Element,
     dialog::file_chooser,
@@ -12,7 +15,6 @@ use cosmic::{
 };
 use cosmic_settings_page::{self as page, Section, section};
 use pwhash::{bcrypt, md5_crypt, sha256_crypt, sha512_crypt};
-use regex::Regex;
 use slab::Slab;
 use slotmap::SlotMap;
 use std::{
@@ -20,15 +22,28 @@ use std::{
     fs::File,
     future::Future,
     io::{BufRead, BufReader},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use url::Url;
+use zbus::proxy;
+use zbus::zvariant::ObjectPath;
 use zbus_polkit::policykit1::CheckAuthorizationFlags;
 
 const DEFAULT_ICON_FILE: &str = "/usr/share/pixmaps/faces/pop-robot.png";
 const USERS_ADMIN_POLKIT_POLICY_ID: &str = "com.system76.CosmicSettings.Users.Admin";
 
+/// Properties of an account that the accounts daemon reads from the login records.
+#[proxy(
+    interface = "org.freedesktop.Accounts.User",
+    default_service = "org.freedesktop.Accounts"
+)]
+trait Logins {
+    /// Seconds since the epoch of the last login, or 0 if there is none on record.
+    #[zbus(property)]
+    fn login_time(&self) -> zbus::Result<i64>;
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct User {
     id: u64,
@@ -40,6 +55,7 @@ pub struct User {
     full_name_edit: bool,
     username_edit: bool,
     is_admin: bool,
+    last_login: Option<DateTime<Local>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -51,6 +67,9 @@ pub enum EditorField {
 #[derive(Clone, Debug)]
 pub enum Dialog {
     AddNewUser(User),
+    /// Choose the profile image of an account
+    Avatar(u64),
+    ConfirmDelete(User),
     UpdatePassword(User),
 }
 
@@ -69,6 +88,8 @@ pub struct Page {
     fullname_label: String,
     password_hidden: bool,
     password_confirm_hidden: bool,
+    /// Default profile images, listed when they are first shown
+    faces: Vec<PathBuf>,
 }
 
 impl Default for Page {
@@ -87,6 +108,7 @@ impl Default for Page {
             fullname_label: crate::fl!("full-name"),
             password_hidden: true,
             password_confirm_hidden: true,
+            faces: Vec::new(),
         }
     }
 }
@@ -103,6 +125,7 @@ pub enum Message {
     None,
     SelectProfileImage(u64),
     SelectedProfileImage(u64, Arc<Result<Url, file_chooser::Error>>),
+    SetProfileImage(u64, PathBuf),
     SelectUser(usize),
     SelectedUserDelete(u64),
     SelectedUserSetAdmin(u64, bool),
@@ -210,31 +233,38 @@ impl page::Page<crate::pages::Message> for Page {
 
                 // validation
                 let mut validation_msg = String::new();
-                let username_regex = Regex::new("^[a-z][a-z0-9-]{0,30}$").unwrap();
-                let username_valid = username_regex.is_match(&user.username);
-                let complete_maybe = if !username_valid && !user.username.is_empty() {
-                    validation_msg = fl!("invalid-username");
-                    None
-                } else if user.password != user.password_confirm
-                    && user.password != ""
-                    && user.password_confirm != ""
-                {
-                    validation_msg = fl!("password-mismatch");
-                    None
-                } else if user.full_name.is_empty()
-                    || user.username.is_empty()
-                    || user.password.is_empty()
-                    || user.password_confirm.is_empty()
-                {
-                    None
-                } else {
-                    Some(Message::NewUser(
-                        user.username.clone(),
-                        user.full_name.clone(),
-                        user.password.clone(),
-                        user.is_admin,
-                    ))
-                };
+                let username_error = validate::username_error(
+                    &user.username,
+                    self.users.iter().map(|user| user.username.as_str()),
+                );
+                let weakness = validate::password_weakness(&user.password, &user.username);
+                let complete_maybe =
+                    if let Some(why) = username_error.filter(|_| !user.username.is_empty()) {
+                        validation_msg = why.localized();
+                        None
+                    } else if let Some(why) = weakness.filter(|_| !user.password.is_empty()) {
+                        validation_msg = why.localized();
+                        None
+                    } else if user.password != user.password_confirm
+                        && user.password != ""
+                        && user.password_confirm != ""
+                    {
+                        validation_msg = fl!("password-mismatch");
+                        None
+                    } else if user.full_name.is_empty()
+                        || user.username.is_empty()
+                        || user.password.is_empty()
+                        || user.password_confirm.is_empty()
+                    {
+                        None
+                    } else {
+                        Some(Message::NewUser(
+                            user.username.clone(),
+                            user.full_name.clone(),
+                            user.password.clone(),
+                            user.is_admin,
+                        ))
+                    };
 
                 let add_user_button = widget::button::suggested(fl!("add-user"))
                     .on_press_maybe(complete_maybe)
@@ -307,17 +337,22 @@ impl page::Page<crate::pages::Message> for Page {
 
                 // validation
                 let mut validation_msg = String::new();
-                let complete_maybe = if user.password != user.password_confirm
-                    && user.password != ""
-                    && user.password_confirm != ""
-                {
-                    validation_msg = fl!("password-mismatch");
-                    None
-                } else if user.password.is_empty() || user.password_confirm.is_empty() {
-                    None
-                } else {
-                    Some(Message::SaveNewPassword(user.clone()))
-                };
+                let weakness = validate::password_weakness(&user.password, &user.username);
+                let complete_maybe =
+                    if let Some(why) = weakness.filter(|_| !user.password.is_empty()) {
+                        validation_msg = why.localized();
+                        None
+                    } else if user.password != user.password_confirm
+                        && user.password != ""
+                        && user.password_confirm != ""
+                    {
+                        validation_msg = fl!("password-mismatch");
+                        None
+                    } else if user.password.is_empty() || user.password_confirm.is_empty() {
+                        None
+                    } else {
+                        Some(Message::SaveNewPassword(user.clone()))
+                    };
 
                 let save_button = widget::button::suggested(fl!("save"))
                     .on_press_maybe(complete_maybe)
@@ -338,6 +373,64 @@ impl page::Page<crate::pages::Message> for Page {
                     .tertiary_action(widget::text::body(validation_msg))
                     .apply(Element::from)
             }
+
+            Dialog::Avatar(uid) => {
+                let uid = *uid;
+
+                let faces = self
+                    .faces
+                    .iter()
+                    .map(|path| {
+                        widget::button::icon(icon::from_path(path.clone()))
+                            .large()
+                            .padding(0)
+                            .class(cosmic::theme::Button::Standard)
+                            .on_press(Message::SetProfileImage(uid, path.clone()))
+                            .into()
+                    })
+                    .collect::<Vec<Element<_>>>();
+
+                let file_button = widget::button::standard(fl!("users", "profile-file"))
+                    .on_press(Message::SelectProfileImage(uid));
+
+                let cancel_button =
+                    widget::button::standard(fl!("cancel")).on_press(Message::Dialog(None));
+
+                widget::dialog()
+                    .title(fl!("users", "profile-add"))
+                    .control(
+                        widget::flex_row(faces)
+                            .column_spacing(8)
+                            .row_spacing(8)
+                            .apply(widget::scrollable)
+                            .height(Length::Shrink),
+                    )
+                    .primary_action(file_button)
+                    .secondary_action(cancel_button)
+                    .apply(Element::from)
+            }
+
+            Dialog::ConfirmDelete(user) => {
+                let name = if user.full_name.is_empty() {
+                    &user.username
+                } else {
+                    &user.full_name
+                };
+
+                let delete_button = widget::button::destructive(fl!("remove-user"))
+                    .on_press(Message::SelectedUserDelete(user.id));
+
+                let cancel_button =
+                    widget::button::standard(fl!("cancel")).on_press(Message::Dialog(None));
+
+                widget::dialog()
+                    .title(fl!("remove-user-dialog", name = name.as_str()))
+                    .icon(icon::from_name("dialog-warning").size(64))
+                    .body(fl!("remove-user-dialog", "description"))
+                    .primary_action(delete_button)
+                    .secondary_action(cancel_button)
+                    .apply(Element::from)
+            }
         };
 
         dialog_element.map(crate::pages::Message::User).into()
@@ -388,6 +481,7 @@ impl Page {
                     .icon_file()
                     .await
                     .ok()
+                    .filter(|path| Path::new(path).is_file())
                     .map(|path| icon::from_path(PathBuf::from(path))),
                 is_admin: match user_proxy.account_type().await {
                     Ok(1) => true,
@@ -402,6 +496,7 @@ impl Page {
                 password_confirm: String::new(),
                 full_name_edit: false,
                 username_edit: false,
+                last_login: last_login(&conn, user_proxy.inner().path().clone()).await,
             });
         }
 
@@ -431,9 +526,11 @@ impl Page {
             }
 
             Message::SelectProfileImage(uid) => {
+                self.dialog = None;
+
                 return cosmic::task::future(async move {
                     let dialog_result = file_chooser::open::Dialog::new()
-                        .title(fl!("wallpaper", "folder-dialog"))
+                        .title(fl!("users", "profile-add"))
                         .accept_label(fl!("dialog-add"))
                         .modal(false)
                         .open_file()
@@ -453,6 +550,32 @@ impl Page {
                     }
                 };
 
+                let Ok(path) = url.to_file_path() else {
+                    tracing::error!("selected image is not a file path");
+                    return cosmic::Task::none();
+                };
+
+                return cosmic::task::future(async move {
+                    let prepared =
+                        tokio::task::spawn_blocking(move || avatar::prepare(&path, uid)).await;
+
+                    match prepared {
+                        Ok(Ok(path)) => Message::SetProfileImage(uid, path),
+                        Ok(Err(why)) => {
+                            tracing::error!(%why, "failed to prepare profile image");
+                            Message::None
+                        }
+                        Err(why) => {
+                            tracing::error!(?why, "failed to prepare profile image");
+                            Message::None
+                        }
+                    }
+                });
+            }
+
+            Message::SetProfileImage(uid, path) => {
+                self.dialog = None;
+
                 return cosmic::task::future(async move {
                     let Ok(conn) = zbus::Connection::system().await else {
                         return Message::None;
@@ -462,15 +585,13 @@ impl Page {
                         return Message::None;
                     };
 
-                    let Ok(path) = url.to_file_path() else {
-                        tracing::error!("selected image is not a file path");
+                    let Some(path_str) = path.to_str() else {
+                        tracing::error!("profile image path is not UTF-8");
                         return Message::None;
                     };
 
-                    let result = request_permission_on_denial(&conn, || {
-                        user.set_icon_file(path.to_str().unwrap())
-                    })
-                    .await;
+                    let result =
+                        request_permission_on_denial(&conn, || user.set_icon_file(path_str)).await;
 
                     if let Err(why) = result {
                         tracing::error!(?why, "failed to set profile icon");
@@ -609,6 +730,12 @@ impl Page {
             }
 
             Message::SelectedUserDelete(uid) => {
+                self.dialog = None;
+
+                if self.is_last_admin(uid) {
+                    return cosmic::Task::none();
+                }
+
                 return cosmic::task::future(async move {
                     let Ok(conn) = zbus::Connection::system().await else {
                         return Message::None;
@@ -635,6 +762,10 @@ impl Page {
             }
 
             Message::Dialog(dialog) => {
+                if matches!(dialog, Some(Dialog::Avatar(_))) && self.faces.is_empty() {
+                    self.faces = avatar::faces();
+                }
+
                 self.password_hidden = true;
                 self.password_confirm_hidden = true;
                 self.dialog = dialog;
@@ -681,6 +812,10 @@ impl Page {
             }
 
             Message::SelectedUserSetAdmin(uid, is_admin) => {
+                if !is_admin && self.is_last_admin(uid) {
+                    return cosmic::Task::none();
+                }
+
                 return cosmic::task::future(async move {
                     let Ok(conn) = zbus::Connection::system().await else {
                         return Message::None;
@@ -707,6 +842,18 @@ impl Page {
 
         cosmic::Task::none()
     }
+
+    /// Whether the account is the only administrator, which must not be removed or made a
+    /// standard account, so that the users of the system can still be managed.
+    fn is_last_admin(&self, uid: u64) -> bool {
+        self.users
+            .iter()
+            .any(|user| user.id == uid && user.is_admin)
+            && !self
+                .users
+                .iter()
+                .any(|user| user.id != uid && user.is_admin)
+    }
 }
 
 impl page::AutoBind<crate::pages::Message> for Page {}
@@ -716,6 +863,7 @@ fn user_list() -> Section<crate::pages::Message> {
 
     let user_type_standard = descriptions.insert(fl!("users", "standard"));
     let user_type_admin = descriptions.insert(fl!("users", "admin"));
+    let last_admin_note = descriptions.insert(fl!("users", "last-admin"));
 
     Section::default()
         .descriptions(descriptions)
@@ -768,6 +916,13 @@ fn user_list() -> Section<crate::pages::Message> {
                         &descriptions[user_type_standard]
                     });
 
+                    let last_login = user.last_login.map(|time| {
+                        text::caption(fl!("last-login", time = time.format("%x %X").to_string()))
+                    });
+
+                    let uid = user.id;
+                    let last_admin = page.is_last_admin(uid);
+
                     let expanded_details = expanded.then(|| {
                         let mut details_list = widget::list_column()
                             .add(settings::item(&page.fullname_label, fullname))
@@ -780,18 +935,30 @@ fn user_list() -> Section<crate::pages::Message> {
                                     .into(),
                                 widget::horizontal_space().width(Length::Fill).into(),
                                 widget::toggler(user.is_admin)
-                                    .on_toggle(|enabled| {
-                                        Message::SelectedUserSetAdmin(user.id, enabled)
-                                    })
+                                    .on_toggle_maybe((!last_admin).then_some(move |enabled| {
+                                        Message::SelectedUserSetAdmin(uid, enabled)
+                                    }))
                                     .into(),
                             ]));
 
                         if page.users.len() > 1 {
-                            details_list = details_list.add(settings::item_row(vec![
-                                widget::horizontal_space().width(Length::Fill).into(),
+                            let remove_button =
                                 widget::button::destructive(crate::fl!("remove-user"))
-                                    .on_press(Message::SelectedUserDelete(user.id))
-                                    .into(),
+                                    .on_press_maybe((!last_admin).then(|| {
+                                        Message::Dialog(Some(Dialog::ConfirmDelete(user.clone())))
+                                    }));
+
+                            details_list = details_list.add(settings::item_row(vec![
+                                last_admin
+                                    .then(|| {
+                                        text::caption(&descriptions[last_admin_note])
+                                            .width(Length::Fill)
+                                            .into()
+                                    })
+                                    .unwrap_or_else(|| {
+                                        widget::horizontal_space().width(Length::Fill).into()
+                                    }),
+                                remove_button.into(),
                             ]));
                         }
 
@@ -807,15 +974,16 @@ fn user_list() -> Section<crate::pages::Message> {
                         .large()
                         .padding(0)
                         .class(cosmic::theme::Button::Standard)
-                        .on_press(Message::SelectProfileImage(user.id));
+                        .on_press(Message::Dialog(Some(Dialog::Avatar(user.id))));
 
                     let account_details_content = settings::item_row(vec![
                         widget::row::with_capacity(2)
                             .push(profile_icon)
                             .push(
-                                column::with_capacity(2)
+                                column::with_capacity(3)
                                     .push(fullname_text)
-                                    .push(account_type),
+                                    .push(account_type)
+                                    .push_maybe(last_login),
                             )
                             .align_y(Alignment::Center)
                             .spacing(space_xxs)
@@ -868,6 +1036,23 @@ fn user_list() -> Section<crate::pages::Message> {
         })
 }
 
+/// When the account last logged in, if the login records have it.
+async fn last_login(conn: &zbus::Connection, path: ObjectPath<'_>) -> Option<DateTime<Local>> {
+    let time = LoginsProxy::builder(conn)
+        .path(path)
+        .ok()?
+        .build()
+        .await
+        .ok()?
+        .login_time()
+        .await
+        .ok()?;
+
+    DateTime::from_timestamp(time, 0)
+        .filter(|_| time > 0)
+        .map(|time| time.with_timezone(&Local))
+}
+
 async fn check_authorization(conn: &zbus::Connection) -> anyhow::Result<()> {
     let proxy = zbus_polkit::policykit1::AuthorityProxy::new(conn).await?;
     let subject = zbus_polkit::policykit1::Subject::new_for_owner(std::process::id(), None, None)?;
@@ -0,0 +1,141 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Checks of the usernames and passwords entered for new accounts, before they are given to
+//! the accounts daemon.
+
+/// Longest username that `useradd` accepts.
+const MAX_USERNAME_LEN: usize = 32;
+
+/// Shortest password that is accepted.
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// Passwords at least this long are accepted without mixing kinds of characters, so that
+/// passphrases of plain words may be used.
+const PASSPHRASE_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UsernameError {
+    /// The username does not start with a lowercase letter, or has characters other than
+    /// lowercase letters, digits, `-`, and `_`.
+    Invalid,
+    TooLong,
+    /// Another account has the username.
+    Taken,
+}
+
+impl UsernameError {
+    pub fn localized(self) -> String {
+        match self {
+            Self::Invalid => fl!("invalid-username"),
+            Self::TooLong => fl!("invalid-username", "too-long", max = MAX_USERNAME_LEN),
+            Self::Taken => fl!("invalid-username", "taken"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PasswordWeakness {
+    TooShort,
+    /// The password has only one kind of character, such as only lowercase letters.
+    TooSimple,
+    ContainsUsername,
+}
+
+impl PasswordWeakness {
+    pub fn localized(self) -> String {
+        match self {
+            Self::TooShort => fl!("weak-password", "too-short", min = MIN_PASSWORD_LEN),
+            Self::TooSimple => fl!("weak-password", "too-simple"),
+            Self::ContainsUsername => fl!("weak-password", "contains-username"),
+        }
+    }
+}
+
+/// Why a username cannot be given to a new account, if it cannot.
+pub fn username_error<'a>(
+    username: &str,
+    mut existing: impl Iterator<Item = &'a str>,
+) -> Option<UsernameError> {
+    let mut chars = username.chars();
+
+    if !chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        || !chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+    {
+        Some(UsernameError::Invalid)
+    } else if username.len() > MAX_USERNAME_LEN {
+        Some(UsernameError::TooLong)
+    } else if existing.any(|name| name == username) {
+        Some(UsernameError::Taken)
+    } else {
+        None
+    }
+}
+
+/// Why a password is too weak to be set for an account, if it is.
+pub fn password_weakness(password: &str, username: &str) -> Option<PasswordWeakness> {
+    let len = password.chars().count();
+
+    let kinds = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_numeric()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .into_iter()
+    .filter(|&kind| kind)
+    .count();
+
+    if len < MIN_PASSWORD_LEN {
+        Some(PasswordWeakness::TooShort)
+    } else if !username.is_empty() && password.to_lowercase().contains(username) {
+        Some(PasswordWeakness::ContainsUsername)
+    } else if kinds < 2 && len < PASSPHRASE_LEN {
+        Some(PasswordWeakness::TooSimple)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PasswordWeakness, UsernameError, password_weakness, username_error};
+
+    #[test]
+    fn usernames() {
+        let existing = ["alice", "bob"];
+        let error = |name: &str| username_error(name, existing.into_iter());
+
+        assert_eq!(error("carol"), None);
+        assert_eq!(error("dev_ops-2"), None);
+
+        assert_eq!(error(""), Some(UsernameError::Invalid));
+        assert_eq!(error("2fast"), Some(UsernameError::Invalid));
+        assert_eq!(error("Carol"), Some(UsernameError::Invalid));
+        assert_eq!(error("carol smith"), Some(UsernameError::Invalid));
+        assert_eq!(error(&"a".repeat(33)), Some(UsernameError::TooLong));
+        assert_eq!(error("bob"), Some(UsernameError::Taken));
+    }
+
+    #[test]
+    fn passwords() {
+        assert_eq!(password_weakness("Tr0ub4dor", "carol"), None);
+        assert_eq!(
+            password_weakness("correct horse battery staple", "carol"),
+            None
+        );
+
+        assert_eq!(
+            password_weakness("Ab1!", "carol"),
+            Some(PasswordWeakness::TooShort)
+        );
+        assert_eq!(
+            password_weakness("password", "carol"),
+            Some(PasswordWeakness::TooSimple)
+        );
+        assert_eq!(
+            password_weakness("Carol1234", "carol"),
+            Some(PasswordWeakness::ContainsUsername)
+        );
+    }
+}
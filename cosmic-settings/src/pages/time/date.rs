@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use std::str::FromStr;
+use std::time::SystemTime;
 
 use chrono::{Datelike, Timelike};
 use cosmic::{
@@ -23,13 +24,22 @@ use slotmap::{Key, SlotMap};
 pub use timedate_zbus::TimeDateProxy;
 use tracing::error;
 
+use super::{geolocation, timesync};
+
 crate::cache_dynamic_lazy! {
     static WEEKDAYS: [String; 4] = [fl!("time-format", "friday"), fl!("time-format", "saturday"), fl!("time-format", "sunday"), fl!("time-format", "monday")];
 }
 
 #[derive(Debug, Clone)]
 pub struct Info {
+    pub can_ntp: bool,
     pub ntp_enabled: bool,
+    pub ntp_synchronized: bool,
+    /// The NTP servers that timesyncd is configured with.
+    pub ntp_servers: Vec<String>,
+    /// The NTP server that timesyncd synchronizes with.
+    pub ntp_server: Option<String>,
+    pub last_sync: Option<SystemTime>,
     pub timezone_id: Option<usize>,
     pub timezone_list: Vec<String>,
 }
@@ -40,8 +50,25 @@ pub struct Page {
     first_day_of_week: usize,
     military_time: bool,
     show_seconds: bool,
+    can_ntp: bool,
     ntp_enabled: bool,
+    ntp_synchronized: bool,
+    ntp_server: Option<String>,
+    /// When the clock was last synchronized, as it is shown.
+    last_sync: Option<String>,
+    /// The NTP servers as they are being edited.
+    ntp_servers: String,
+    ntp_servers_invalid: bool,
+    manual_date: String,
+    manual_time: String,
+    manual_invalid: bool,
     show_date_in_top_panel: bool,
+    settings_config: Option<cosmic_config::Config>,
+    /// Whether the time zone is set from the location of the device.
+    auto_timezone: bool,
+    /// The time zone found at the location of the device, or why it was not found.
+    detected_timezone: Option<Result<String, String>>,
+    detecting_timezone: bool,
     timezone_context: bool,
     local_time: Option<DateTime<Iso>>,
     timezone: Option<usize>,
@@ -95,15 +122,35 @@ impl Default for Page {
                 true
             });
 
+        let settings_config = cosmic_config::Config::new("com.system76.CosmicSettings", 1).ok();
+
+        let auto_timezone = settings_config
+            .as_ref()
+            .and_then(|config| config.get("auto_timezone").ok())
+            .unwrap_or(false);
+
         Self {
             entity: page::Entity::null(),
             cosmic_applet_config,
+            settings_config,
+            auto_timezone,
+            detected_timezone: None,
+            detecting_timezone: false,
             first_day_of_week,
             formatted_date: String::new(),
             local_time: None,
             military_time,
             show_seconds,
+            can_ntp: false,
             ntp_enabled: false,
+            ntp_synchronized: false,
+            ntp_server: None,
+            last_sync: None,
+            ntp_servers: String::new(),
+            ntp_servers_invalid: false,
+            manual_date: String::new(),
+            manual_time: String::new(),
+            manual_invalid: false,
             show_date_in_top_panel,
             timezone: None,
             timezone_context: false,
@@ -136,34 +183,14 @@ impl page::Page<crate::pages::Message> for Page {
     }
 
     fn on_enter(&mut self) -> Task<crate::pages::Message> {
-        cosmic::Task::future(async move {
-            let client = match zbus::Connection::system().await {
-                Ok(client) => client,
-                Err(why) => {
-                    return Message::Error(why.to_string());
-                }
-            };
-
-            let timedate_proxy = match TimeDateProxy::new(&client).await {
-                Ok(timedate_proxy) => timedate_proxy,
-                Err(why) => {
-                    return Message::Error(why.to_string());
-                }
-            };
-
-            let can_ntp = timedate_proxy.can_ntp().await.unwrap_or_default();
-            let ntp_enabled = can_ntp && timedate_proxy.ntp().await.unwrap_or_default();
-            let timezone_list = timedate_proxy.list_timezones().await.unwrap_or_default();
+        let mut tasks = vec![cosmic::Task::future(refresh())];
 
-            let timezone = timedate_proxy.timezone().await.unwrap_or_default();
+        // The device may have moved since the time zone was last detected.
+        if self.auto_timezone {
+            tasks.push(self.detect_timezone());
+        }
 
-            Message::Refresh(Info {
-                ntp_enabled,
-                timezone_id: timezone_list.iter().position(|tz| tz == &timezone),
-                timezone_list,
-            })
-        })
-        .map(crate::pages::Message::DateAndTime)
+        Task::batch(tasks).map(crate::pages::Message::DateAndTime)
     }
 
     fn context_drawer(&self) -> Option<ContextDrawer<crate::pages::Message>> {
@@ -268,8 +295,110 @@ impl Page {
                 }
             }
 
+            Message::AutoTimezone(enable) => {
+                self.auto_timezone = enable;
+
+                if let Some(config) = self.settings_config.as_ref() {
+                    if let Err(err) = config.set("auto_timezone", enable) {
+                        error!(?err, "Failed to set config 'auto_timezone'");
+                    }
+                }
+
+                if enable {
+                    return self
+                        .detect_timezone()
+                        .map(crate::pages::Message::DateAndTime)
+                        .map(crate::Message::PageMessage);
+                }
+
+                self.detected_timezone = None;
+            }
+
+            Message::TimezoneDetected(result) => {
+                self.detecting_timezone = false;
+
+                if let Err(why) = &result {
+                    tracing::error!(why, "failed to detect the time zone");
+                }
+
+                let detected = result
+                    .as_ref()
+                    .ok()
+                    .and_then(|timezone| self.timezone_list.iter().position(|tz| tz == timezone));
+
+                self.detected_timezone = Some(result);
+
+                if let Some(id) =
+                    detected.filter(|&id| self.auto_timezone && self.timezone != Some(id))
+                {
+                    return self.update(Message::Timezone(id));
+                }
+            }
+
+            Message::Ntp(enable) => {
+                self.set_ntp(enable);
+            }
+
+            Message::NtpServers(servers) => {
+                self.ntp_servers_invalid = false;
+                self.ntp_servers = servers;
+            }
+
+            Message::NtpServersSubmit => {
+                let Some(servers) = timesync::parse_servers(&self.ntp_servers) else {
+                    self.ntp_servers_invalid = true;
+                    return Task::none();
+                };
+
+                return cosmic::Task::future(async move {
+                    if let Err(why) = timesync::set_servers(servers).await {
+                        tracing::error!(?why, "failed to set NTP servers");
+                    }
+
+                    Message::NtpServersApplied
+                })
+                .map(crate::pages::Message::DateAndTime)
+                .map(crate::Message::PageMessage);
+            }
+
+            Message::NtpServersApplied => {
+                return cosmic::Task::future(refresh())
+                    .map(crate::pages::Message::DateAndTime)
+                    .map(crate::Message::PageMessage);
+            }
+
+            Message::ManualDate(date) => {
+                self.manual_invalid = false;
+                self.manual_date = date;
+            }
+
+            Message::ManualTime(time) => {
+                self.manual_invalid = false;
+                self.manual_time = time;
+            }
+
+            Message::SetTime => {
+                let Some(usec_utc) =
+                    timesync::parse_manual_time(&self.manual_date, &self.manual_time)
+                else {
+                    self.manual_invalid = true;
+                    return Task::none();
+                };
+
+                return cosmic::Task::future(set_time(usec_utc))
+                    .map(crate::pages::Message::DateAndTime)
+                    .map(crate::Message::PageMessage);
+            }
+
+            Message::TimeSet => {
+                self.ntp_enabled = false;
+                self.manual_date.clear();
+                self.manual_time.clear();
+                self.update_local_time();
+            }
+
             Message::Error(why) => {
-                tracing::error!(why, "failed to set timezone");
+                tracing::error!(why, "failed to update date and time");
                 self.timezone_context = false;
                 return cosmic::task::message(crate::pages::Message::CloseContextDrawer);
             }
@@ -282,7 +411,16 @@ impl Page {
             }
 
             Message::Refresh(info) => {
+                self.can_ntp = info.can_ntp;
                 self.ntp_enabled = info.ntp_enabled;
+                self.ntp_synchronized = info.ntp_synchronized;
+                self.ntp_server = info.ntp_server;
+                self.last_sync = info.last_sync.map(|time| {
+                    let time = iso_datetime(chrono::DateTime::<chrono::Local>::from(time));
+                    format_date(&time, self.military_time, false)
+                });
+                self.ntp_servers = info.ntp_servers.join(" ");
+                self.ntp_servers_invalid = false;
                 self.timezone_list = info.timezone_list;
                 self.timezone = info.timezone_id;
 
@@ -323,13 +461,18 @@ impl Page {
         });
     }
 
+    fn detect_timezone(&mut self) -> Task<Message> {
+        self.detecting_timezone = true;
+        cosmic::Task::future(async {
+            Message::TimezoneDetected(geolocation::detect_timezone().await)
+        })
+    }
+
     fn timezone_context_view(&self) -> Element<'_, crate::pages::Message> {
         let mut list = widget::list_column();
 
-        let search_input = &self.timezone_search.trim().to_lowercase();
-
         for (id, timezone) in self.timezone_list.iter().enumerate() {
-            if search_input.is_empty() || timezone.to_lowercase().contains(search_input) {
+            if timezone_matches(timezone, &self.timezone_search) {
                 list = list.add(self.timezone_context_item(id, timezone));
             }
         }
@@ -339,8 +482,14 @@ impl Page {
     }
 
     fn timezone_context_item<'a>(&self, id: usize, timezone: &'a str) -> Element<'a, Message> {
+        let (city, region) = timezone_names(timezone);
+
+        let names = widget::column::with_capacity(2)
+            .push(widget::text::body(city).wrapping(Wrapping::Word))
+            .push_maybe(region.map(widget::text::caption));
+
         widget::button::custom(widget::settings::item_row(vec![
-            widget::text::body(timezone).wrapping(Wrapping::Word).into(),
+            names.into(),
             widget::horizontal_space().into(),
         ]))
         .on_press(Message::Timezone(id))
@@ -361,36 +510,208 @@ impl Page {
 #[derive(Clone, Debug)]
 pub enum Message {
     Error(String),
+    ManualDate(String),
+    ManualTime(String),
     MilitaryTime(bool),
+    Ntp(bool),
+    NtpServers(String),
+    NtpServersApplied,
+    NtpServersSubmit,
     ShowSeconds(bool),
     None,
     FirstDayOfWeek(usize),
     Refresh(Info),
+    AutoTimezone(bool),
+    SetTime,
     ShowDate(bool),
     Timezone(usize),
     TimezoneContext,
+    TimezoneDetected(Result<String, String>),
     TimezoneSearch(String),
+    TimeSet,
     UpdateTime,
     Surface(surface::Action),
 }
 
 impl page::AutoBind<crate::pages::Message> for Page {}
 
+async fn refresh() -> Message {
+    let client = match zbus::Connection::system().await {
+        Ok(client) => client,
+        Err(why) => {
+            return Message::Error(why.to_string());
+        }
+    };
+
+    let timedate_proxy = match TimeDateProxy::new(&client).await {
+        Ok(timedate_proxy) => timedate_proxy,
+        Err(why) => {
+            return Message::Error(why.to_string());
+        }
+    };
+
+    let can_ntp = timedate_proxy.can_ntp().await.unwrap_or_default();
+    let ntp_enabled = can_ntp && timedate_proxy.ntp().await.unwrap_or_default();
+    let ntp_synchronized = timedate_proxy.ntpsynchronized().await.unwrap_or_default();
+    let timezone_list = timedate_proxy.list_timezones().await.unwrap_or_default();
+
+    let timezone = timedate_proxy.timezone().await.unwrap_or_default();
+
+    let (ntp_servers, ntp_server) = timesync::servers(&client).await;
+
+    Message::Refresh(Info {
+        can_ntp,
+        ntp_enabled,
+        ntp_synchronized,
+        ntp_servers,
+        ntp_server,
+        last_sync: timesync::last_sync(),
+        timezone_id: timezone_list.iter().position(|tz| tz == &timezone),
+        timezone_list,
+    })
+}
+
+async fn set_time(usec_utc: i64) -> Message {
+    let client = match zbus::Connection::system().await {
+        Ok(client) => client,
+        Err(why) => {
+            return Message::Error(why.to_string());
+        }
+    };
+
+    let timedate_proxy = match TimeDateProxy::new(&client).await {
+        Ok(timedate_proxy) => timedate_proxy,
+        Err(why) => {
+            return Message::Error(why.to_string());
+        }
+    };
+
+    // The time daemon refuses to set the time while it is synchronized automatically.
+    if timedate_proxy.ntp().await.unwrap_or_default() {
+        if let Err(why) = timedate_proxy.set_ntp(false, true).await {
+            return Message::Error(why.to_string());
+        }
+    }
+
+    match timedate_proxy.set_time(usec_utc, false, true).await {
+        Ok(()) => Message::TimeSet,
+        Err(why) => Message::Error(why.to_string()),
+    }
+}
+
 fn date() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
     let title = descriptions.insert(fl!("time-date"));
+    let auto = descriptions.insert(fl!("time-date", "auto"));
+    let not_synced = descriptions.insert(fl!("time-date", "not-synced"));
+    let ntp_servers = descriptions.insert(fl!("time-date", "ntp-servers"));
+    let ntp_servers_desc = descriptions.insert(fl!("time-date", "ntp-servers-desc"));
+    let ntp_servers_invalid = descriptions.insert(fl!("time-date", "ntp-servers-invalid"));
+    let manual = descriptions.insert(fl!("time-date", "manual"));
+    let manual_desc = descriptions.insert(fl!("time-date", "manual-desc"));
+    let manual_invalid = descriptions.insert(fl!("time-date", "manual-invalid"));
+    let manual_ntp_warning = descriptions.insert(fl!("time-date", "manual-ntp-warning"));
+    let set = descriptions.insert(fl!("time-date", "set"));
 
     Section::default()
         .title(fl!("time-date"))
         .descriptions(descriptions)
         .view::<Page>(move |_binder, page, section| {
-            settings::section()
-                .title(&section.title)
+            let descriptions = &section.descriptions;
+            let spacing = cosmic::theme::spacing();
+
+            let mut section = settings::section().title(&section.title).add(
+                settings::item::builder(&*descriptions[title])
+                    .description(fl!("time-date", "auto-ntp"))
+                    .control(widget::text::body(&page.formatted_date)),
+            );
+
+            if page.can_ntp {
+                let status = if !page.ntp_synchronized {
+                    descriptions[not_synced].clone()
+                } else {
+                    let server = page
+                        .ntp_server
+                        .as_ref()
+                        .map(|server| fl!("time-date", "synced", server = server.as_str()));
+                    let last_sync = page
+                        .last_sync
+                        .as_ref()
+                        .map(|time| fl!("time-date", "last-sync", time = time.as_str()));
+
+                    server
+                        .into_iter()
+                        .chain(last_sync)
+                        .collect::<Vec<_>>()
+                        .join(" · ")
+                };
+
+                let servers = widget::column::with_capacity(2)
+                    .spacing(spacing.space_xxxs)
+                    .push(
+                        widget::text_input("", &page.ntp_servers)
+                            .on_input(Message::NtpServers)
+                            .on_submit(|_| Message::NtpServersSubmit),
+                    )
+                    .push_maybe(page.ntp_servers_invalid.then(|| {
+                        widget::text::caption(&descriptions[ntp_servers_invalid])
+                            .class(cosmic::theme::Text::Accent)
+                    }));
+
+                section = section
+                    .add(
+                        settings::item::builder(&*descriptions[auto])
+                            .description(status)
+                            .toggler(page.ntp_enabled, Message::Ntp),
+                    )
+                    .add(
+                        settings::item::builder(&*descriptions[ntp_servers])
+                            .description(&*descriptions[ntp_servers_desc])
+                            .flex_control(servers),
+                    );
+            }
+
+            let manual_inputs = widget::row::with_capacity(3)
+                .spacing(spacing.space_xxs)
+                .align_y(cosmic::iced::Alignment::Center)
+                .push(
+                    widget::text_input("YYYY-MM-DD", &page.manual_date)
+                        .on_input(Message::ManualDate)
+                        .on_submit(|_| Message::SetTime),
+                )
+                .push(
+                    widget::text_input("HH:MM", &page.manual_time)
+                        .on_input(Message::ManualTime)
+                        .on_submit(|_| Message::SetTime),
+                )
+                .push(
+                    widget::button::standard(&*descriptions[set]).on_press_maybe(
+                        (!page.manual_date.is_empty() && !page.manual_time.is_empty())
+                            .then_some(Message::SetTime),
+                    ),
+                );
+
+            let manual_notice = if page.manual_invalid {
+                Some(&descriptions[manual_invalid])
+            } else if page.ntp_enabled {
+                Some(&descriptions[manual_ntp_warning])
+            } else {
+                None
+            };
+
+            let manual_control = widget::column::with_capacity(2)
+                .spacing(spacing.space_xxxs)
+                .push(manual_inputs)
+                .push_maybe(manual_notice.map(|notice| {
+                    widget::text::caption(notice.as_str()).class(cosmic::theme::Text::Accent)
+                }));
+
+            section
                 .add(
-                    settings::item::builder(&*section.descriptions[title])
-                        .description(fl!("time-date", "auto-ntp"))
-                        .control(widget::text::body(&page.formatted_date)),
+                    settings::item::builder(&*descriptions[manual])
+                        .description(&*descriptions[manual_desc])
+                        .flex_control(manual_control),
                 )
                 .apply(cosmic::Element::from)
                 .map(crate::pages::Message::DateAndTime)
@@ -464,11 +785,30 @@ fn timezone() -> Section<crate::pages::Message> {
     let mut descriptions = Slab::new();
 
     let time_zone = descriptions.insert(fl!("time-zone"));
+    let auto = descriptions.insert(fl!("time-zone", "auto"));
+    let auto_info = descriptions.insert(fl!("time-zone", "auto-info"));
+    let detecting = descriptions.insert(fl!("time-zone", "detecting"));
+    let detect_failed = descriptions.insert(fl!("time-zone", "detect-failed"));
 
     Section::default()
         .title(fl!("time-zone"))
         .descriptions(descriptions)
         .view::<Page>(move |_binder, page, section| {
+            let auto_status = if !page.auto_timezone {
+                section.descriptions[auto_info].clone()
+            } else if page.detecting_timezone {
+                section.descriptions[detecting].clone()
+            } else {
+                match &page.detected_timezone {
+                    Some(Ok(timezone)) => {
+                        fl!("time-zone", "detected", timezone = timezone.as_str())
+                    }
+                    Some(Err(_)) => section.descriptions[detect_failed].clone(),
+                    None => section.descriptions[auto_info].clone(),
+                }
+            };
+
+            // The time zone is only chosen by hand while it is not detected.
             let timezone_context_button = widget::row::with_capacity(2)
                 .spacing(12)
                 .push(
@@ -484,10 +824,16 @@ fn timezone() -> Section<crate::pages::Message> {
                 .class(cosmic::theme::Container::List)
                 .apply(widget::button::custom)
                 .class(cosmic::theme::Button::Transparent)
-                .on_press(Message::TimezoneContext);
+                .on_press_maybe((!page.auto_timezone).then_some(Message::TimezoneContext));
 
             settings::section()
                 .title(&section.title)
+                // Automatic time zone toggle
+                .add(
+                    settings::item::builder(&*section.descriptions[auto])
+                        .description(auto_status)
+                        .toggler(page.auto_timezone, Message::AutoTimezone),
+                )
                 // Time zone select
                 .add(
                     settings::item::builder(&*section.descriptions[time_zone])
@@ -498,6 +844,28 @@ fn timezone() -> Section<crate::pages::Message> {
         })
 }
 
+/// The city of a time zone such as `America/Argentina/Buenos_Aires`, and the regions that it
+/// is in, as they are read.
+fn timezone_names(timezone: &str) -> (String, Option<String>) {
+    match timezone.rsplit_once('/') {
+        Some((region, city)) => (
+            city.replace('_', " "),
+            Some(region.replace('_', " ").replace('/', " / ")),
+        ),
+        None => (timezone.replace('_', " "), None),
+    }
+}
+
+/// Whether a time zone matches every word of a search, by its city or region names.
+fn timezone_matches(timezone: &str, search: &str) -> bool {
+    let timezone = timezone.replace('_', " ").to_lowercase();
+
+    search
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| timezone.contains(word))
+}
+
 fn locale() -> Result<Locale, Box<dyn std::error::Error>> {
     let locale = std::env::var("LC_TIME").or_else(|_| std::env::var("LANG"))?;
     let locale = locale
@@ -538,8 +906,10 @@ fn format_date(date: &DateTime<Iso>, military: bool, show_seconds: bool) -> Stri
 }
 
 fn update_local_time() -> DateTime<Iso> {
-    let now = chrono::Local::now();
+    iso_datetime(chrono::Local::now())
+}
 
+fn iso_datetime(now: chrono::DateTime<chrono::Local>) -> DateTime<Iso> {
     DateTime::try_new_gregorian_datetime(
         now.year(),
         now.month() as u8,
@@ -551,3 +921,29 @@ fn update_local_time() -> DateTime<Iso> {
     .unwrap()
     .to_iso()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{timezone_matches, timezone_names};
+
+    #[test]
+    fn timezone_search() {
+        assert!(timezone_matches("America/New_York", "new york"));
+        assert!(timezone_matches("America/New_York", "York America"));
+        assert!(timezone_matches(
+            "America/Argentina/Buenos_Aires",
+            "argentina"
+        ));
+        assert!(timezone_matches("Europe/Berlin", ""));
+        assert!(!timezone_matches("Europe/Berlin", "paris"));
+
+        assert_eq!(
+            timezone_names("America/Argentina/Buenos_Aires"),
+            (
+                String::from("Buenos Aires"),
+                Some(String::from("America / Argentina"))
+            )
+        );
+        assert_eq!(timezone_names("UTC"), (String::from("UTC"), None));
+    }
+}
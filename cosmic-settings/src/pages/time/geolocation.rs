@@ -0,0 +1,238 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Finds the time zone of the device from its location, as reported by GeoClue.
+//!
+//! The location is matched to the nearest principal location of a time zone in the
+//! `zone1970.tab` table of the time zone database.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use zbus::proxy;
+use zbus::zvariant::OwnedObjectPath;
+
+/// The table of time zones and the coordinates of their principal locations.
+const ZONE_TABLE: &str = "/usr/share/zoneinfo/zone1970.tab";
+
+/// How long to wait for GeoClue to find the location.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// GeoClue accuracy of a city, which is as much as a time zone needs.
+const ACCURACY_CITY: u32 = 4;
+
+#[proxy(
+    interface = "org.freedesktop.GeoClue2.Manager",
+    default_service = "org.freedesktop.GeoClue2",
+    default_path = "/org/freedesktop/GeoClue2/Manager"
+)]
+trait Manager {
+    fn get_client(&self) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.GeoClue2.Client",
+    default_service = "org.freedesktop.GeoClue2"
+)]
+trait Client {
+    fn start(&self) -> zbus::Result<()>;
+
+    fn stop(&self) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn set_desktop_id(&self, id: &str) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn set_requested_accuracy_level(&self, level: u32) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn location_updated(&self, old: OwnedObjectPath, new: OwnedObjectPath) -> zbus::Result<()>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.GeoClue2.Location",
+    default_service = "org.freedesktop.GeoClue2"
+)]
+trait Location {
+    #[zbus(property)]
+    fn latitude(&self) -> zbus::Result<f64>;
+
+    #[zbus(property)]
+    fn longitude(&self) -> zbus::Result<f64>;
+}
+
+/// Finds the time zone that the device is in.
+pub async fn detect_timezone() -> Result<String, String> {
+    let (latitude, longitude) = locate().await?;
+
+    let table = tokio::fs::read_to_string(ZONE_TABLE)
+        .await
+        .map_err(|why| format!("failed to read {ZONE_TABLE}: {why}"))?;
+
+    nearest_timezone(&table, latitude, longitude)
+        .ok_or_else(|| format!("no time zones in {ZONE_TABLE}"))
+}
+
+/// The latitude and longitude of the device, in degrees.
+async fn locate() -> Result<(f64, f64), String> {
+    let conn = zbus::Connection::system()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    let client_path = ManagerProxy::new(&conn)
+        .await
+        .map_err(|why| why.to_string())?
+        .get_client()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    let client = ClientProxy::builder(&conn)
+        .path(client_path)
+        .map_err(|why| why.to_string())?
+        .build()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    client
+        .set_desktop_id("com.system76.CosmicSettings")
+        .await
+        .map_err(|why| why.to_string())?;
+    client
+        .set_requested_accuracy_level(ACCURACY_CITY)
+        .await
+        .map_err(|why| why.to_string())?;
+
+    let mut updates = client
+        .receive_location_updated()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    client.start().await.map_err(|why| why.to_string())?;
+
+    let update = tokio::time::timeout(TIMEOUT, updates.next()).await;
+    _ = client.stop().await;
+
+    let location_path = match update {
+        Ok(Some(update)) => update.args().map_err(|why| why.to_string())?.new,
+        Ok(None) => return Err(String::from("GeoClue stopped without a location")),
+        Err(_) => return Err(String::from("timed out waiting for a location")),
+    };
+
+    let location = LocationProxy::builder(&conn)
+        .path(location_path)
+        .map_err(|why| why.to_string())?
+        .build()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    let latitude = location.latitude().await.map_err(|why| why.to_string())?;
+    let longitude = location.longitude().await.map_err(|why| why.to_string())?;
+
+    Ok((latitude, longitude))
+}
+
+/// The time zone of a `zone1970.tab` table whose principal location is nearest to a point.
+pub fn nearest_timezone(table: &str, latitude: f64, longitude: f64) -> Option<String> {
+    table
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let coordinates = parse_coordinates(fields.nth(1)?)?;
+            let timezone = fields.next()?;
+            Some((distance((latitude, longitude), coordinates), timezone))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, timezone)| timezone.to_owned())
+}
+
+/// Reads ISO 6709 coordinates as they are written in the table, such as `+4043-07400` or
+/// `+404251-0740023`, as degrees of latitude and longitude.
+fn parse_coordinates(coordinates: &str) -> Option<(f64, f64)> {
+    let split = coordinates.get(1..)?.find(['+', '-'])? + 1;
+    let (latitude, longitude) = coordinates.split_at(split);
+
+    Some((parse_degrees(latitude, 2)?, parse_degrees(longitude, 3)?))
+}
+
+/// Reads a signed angle of degrees, minutes, and optionally seconds, where the degrees take
+/// the given number of digits.
+fn parse_degrees(angle: &str, degree_digits: usize) -> Option<f64> {
+    let (sign, digits) = angle.split_at(1);
+    let sign = if sign == "-" { -1.0 } else { 1.0 };
+
+    let degrees: f64 = digits.get(..degree_digits)?.parse().ok()?;
+    let minutes: f64 = digits.get(degree_digits..degree_digits + 2)?.parse().ok()?;
+    let seconds: f64 = digits
+        .get(degree_digits + 2..)
+        .filter(|seconds| !seconds.is_empty())
+        .map_or(Some(0.0), |seconds| seconds.parse().ok())?;
+
+    Some(sign * (degrees + minutes / 60.0 + seconds / 3600.0))
+}
+
+/// The angle between two points on a sphere, which orders points by how far apart they are.
+fn distance((lat_a, lon_a): (f64, f64), (lat_b, lon_b): (f64, f64)) -> f64 {
+    let (lat_a, lat_b) = (lat_a.to_radians(), lat_b.to_radians());
+    let delta_lat = lat_b - lat_a;
+    let delta_lon = (lon_b - lon_a).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat_a.cos() * lat_b.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    2.0 * a.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{nearest_timezone, parse_coordinates};
+
+    const TABLE: &str = "\
+#codes\tcoordinates\tTZ\tcomments
+US\t+404251-0740023\tAmerica/New_York\tEastern (most areas)
+US\t+415100-0873900\tAmerica/Chicago\tCentral (most areas)
+GB,GG,IM,JE\t+513030-0000731\tEurope/London
+DE,DK,NO,SE,SJ\t+5230+01322\tEurope/Berlin\tmost of Germany
+AU\t-3352+15113\tAustralia/Sydney\tNew South Wales (most areas)
+";
+
+    #[test]
+    fn coordinates() {
+        let (latitude, longitude) = parse_coordinates("+404251-0740023").unwrap();
+        assert!((latitude - 40.714_166).abs() < 1e-4);
+        assert!((longitude + 74.006_388).abs() < 1e-4);
+
+        assert_eq!(
+            parse_coordinates("+5230+01322"),
+            Some((52.5, 13.0 + 22.0 / 60.0))
+        );
+        assert_eq!(
+            parse_coordinates("-3352+15113").map(|(lat, _)| lat < 0.0),
+            Some(true)
+        );
+        assert_eq!(parse_coordinates("+52"), None);
+    }
+
+    #[test]
+    fn nearest() {
+        // Boston, Munich, Melbourne, and Manchester.
+        assert_eq!(
+            nearest_timezone(TABLE, 42.36, -71.06).as_deref(),
+            Some("America/New_York")
+        );
+        assert_eq!(
+            nearest_timezone(TABLE, 48.14, 11.58).as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            nearest_timezone(TABLE, -37.81, 144.96).as_deref(),
+            Some("Australia/Sydney")
+        );
+        assert_eq!(
+            nearest_timezone(TABLE, 53.48, -2.24).as_deref(),
+            Some("Europe/London")
+        );
+
+        assert_eq!(nearest_timezone("", 0.0, 0.0), None);
+    }
+}
@@ -5,8 +5,12 @@ use cosmic_settings_page as page;
 
 #[cfg(feature = "page-date")]
 pub mod date;
+#[cfg(feature = "page-date")]
+pub mod geolocation;
 #[cfg(feature = "page-region")]
 pub mod region;
+#[cfg(feature = "page-date")]
+pub mod timesync;
 
 #[derive(Default)]
 pub struct Page {
@@ -10,7 +10,7 @@ use cosmic::app::{ContextDrawer, context_drawer};
 use cosmic::iced::{Alignment, Border, Color, Length};
 use cosmic::iced_core::text::Wrapping;
 use cosmic::widget::{self, button, container};
-use cosmic::{Apply, Element, theme};
+use cosmic::{Apply, Element, surface, theme};
 use cosmic_config::{ConfigGet, ConfigSet};
 use cosmic_settings_page::Section;
 use cosmic_settings_page::{self as page, section};
@@ -25,6 +25,60 @@ use icu::decimal::options::FixedDecimalFormatterOptions;
 use locales_rs as locale;
 use slotmap::{DefaultKey, SlotMap};
 
+crate::cache_dynamic_lazy! {
+    static MEASUREMENT_SYSTEMS: [String; 2] = [fl!("measurement", "metric"), fl!("measurement", "imperial")];
+}
+
+/// Territories whose locales measure in imperial units.
+const IMPERIAL_TERRITORIES: [&str; 3] = ["US", "LR", "MM"];
+
+/// A locale that measures in metric units, for regions that do not.
+const METRIC_LOCALE: &str = "C.UTF-8";
+/// A locale that measures in imperial units, for regions that do not.
+const IMPERIAL_LOCALE: &str = "en_US.UTF-8";
+
+/// Parts of the locale that may follow another locale than the region.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Facet {
+    Time,
+    Numbers,
+    Currency,
+    Measurement,
+}
+
+impl Facet {
+    const ALL: [Self; 4] = [Self::Time, Self::Numbers, Self::Currency, Self::Measurement];
+
+    /// The locale variable that sets this part of the locale.
+    fn variable(self) -> &'static str {
+        match self {
+            Self::Time => "LC_TIME",
+            Self::Numbers => "LC_NUMERIC",
+            Self::Currency => "LC_MONETARY",
+            Self::Measurement => "LC_MEASUREMENT",
+        }
+    }
+
+    fn title(self) -> String {
+        match self {
+            Self::Time => fl!("formatting", "date-and-time"),
+            Self::Numbers => fl!("formatting", "numbers"),
+            Self::Currency => fl!("formatting", "currency"),
+            Self::Measurement => fl!("formatting", "measurement"),
+        }
+    }
+}
+
+/// How a locale writes amounts of money, as read from its `LC_MONETARY` definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurrencyFormat {
+    symbol: String,
+    /// Whether the symbol is written before the amount.
+    precedes: bool,
+    /// Whether a space separates the symbol from the amount.
+    separated: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum Message {
     AddLanguage(DefaultKey),
@@ -34,6 +88,11 @@ pub enum Message {
     ExpandLanguagePopover(Option<usize>),
     InstallAdditionalLanguages,
     SelectRegion(DefaultKey),
+    /// Sets a part of the locale to follow another locale, or the region if `None`.
+    SelectFacet(Facet, Option<DefaultKey>),
+    FacetContext(Facet),
+    Measurement(usize),
+    Surface(surface::Action),
     SourceContext(SourceContext),
     Refresh(Arc<eyre::Result<PageRefresh>>),
     RegionContext,
@@ -54,6 +113,7 @@ impl From<Message> for crate::pages::Message {
 
 enum ContextView {
     AddLanguage,
+    Facet(Facet),
     Region,
 }
 
@@ -99,6 +159,9 @@ pub struct PageRefresh {
     region: Option<SystemLocale>,
     available_languages: SlotMap<DefaultKey, SystemLocale>,
     system_locales: BTreeMap<String, SystemLocale>,
+    /// Every locale variable as it is set, including locales missing from the registry.
+    locale_variables: BTreeMap<String, String>,
+    currency: Option<CurrencyFormat>,
 }
 
 #[derive(Default)]
@@ -113,6 +176,9 @@ pub struct Page {
     registry: Option<locale::Registry>,
     expanded_source_popover: Option<usize>,
     add_language_search: String,
+    /// Locales that parts of the locale follow instead of the region, by their variable.
+    overrides: BTreeMap<&'static str, String>,
+    currency: Option<CurrencyFormat>,
 }
 
 impl page::Page<crate::pages::Message> for Page {
@@ -149,6 +215,8 @@ impl page::Page<crate::pages::Message> for Page {
         self.language = None;
         self.region = None;
         self.registry = None;
+        self.overrides = BTreeMap::new();
+        self.currency = None;
         self.system_locales = BTreeMap::new();
         cosmic::Task::none()
     }
@@ -192,6 +260,20 @@ impl page::Page<crate::pages::Message> for Page {
                 .title(fl!("region"))
                 .header(search)
             }
+            ContextView::Facet(facet) => {
+                let search = widget::search_input(fl!("type-to-search"), &self.add_language_search)
+                    .on_input(Message::AddLanguageSearch)
+                    .on_clear(Message::AddLanguageSearch(String::new()))
+                    .apply(Element::from)
+                    .map(crate::pages::Message::from);
+
+                context_drawer(
+                    self.facet_view(*facet).map(crate::pages::Message::from),
+                    crate::pages::Message::CloseContextDrawer,
+                )
+                .title(facet.title())
+                .header(search)
+            }
         })
     }
 }
@@ -222,21 +304,56 @@ impl Page {
             }
 
             Message::SelectRegion(id) => {
-                if let Some((region, language)) =
-                    self.available_languages.get(id).zip(self.language.as_ref())
-                {
+                if let Some(region) = self.available_languages.get(id) {
                     self.region = Some(region.clone());
 
-                    let lang = language.lang_code.clone();
-                    let region = region.lang_code.clone();
-
-                    return cosmic::task::future(async move {
-                        _ = set_locale(lang, region).await;
-                        Message::Refresh(Arc::new(page_reload().await))
+                    // Overrides that the new region already agrees with are no longer needed.
+                    let region_code = region.lang_code.clone();
+                    self.overrides.retain(|var, locale| {
+                        *locale != region_code
+                            && (*var != Facet::Measurement.variable()
+                                || is_imperial(locale) != is_imperial(&region_code))
                     });
+
+                    return self.apply_locale();
                 }
             }
 
+            Message::SelectFacet(facet, id) => {
+                let locale = id
+                    .and_then(|id| self.available_languages.get(id))
+                    .map(|locale| locale.lang_code.clone());
+
+                self.set_override(facet, locale);
+                return self.apply_locale();
+            }
+
+            Message::FacetContext(facet) => {
+                self.context = Some(ContextView::Facet(facet));
+                return cosmic::Task::done(crate::app::Message::OpenContextDrawer(self.entity));
+            }
+
+            Message::Measurement(system) => {
+                let imperial = system == 1;
+                let region_is_imperial = self
+                    .region
+                    .as_ref()
+                    .is_some_and(|region| is_imperial(&region.lang_code));
+
+                let locale = match (imperial, region_is_imperial) {
+                    (true, false) => Some(IMPERIAL_LOCALE.to_owned()),
+                    (false, true) => Some(METRIC_LOCALE.to_owned()),
+                    _ => None,
+                };
+
+                self.set_override(Facet::Measurement, locale);
+                return self.apply_locale();
+            }
+
+            Message::Surface(a) => {
+                return cosmic::task::message(crate::app::Message::Surface(a));
+            }
+
             Message::AddLanguageContext => {
                 self.context = Some(ContextView::AddLanguage);
                 return cosmic::Task::done(crate::app::Message::OpenContextDrawer(self.entity));
@@ -272,6 +389,18 @@ impl Page {
                     self.language = page_refresh.language;
                     self.region = page_refresh.region;
                     self.registry = Some(page_refresh.registry.0);
+                    self.currency = page_refresh.currency;
+
+                    // Parts of the locale that differ from the region were set apart from it.
+                    self.overrides = Facet::ALL
+                        .iter()
+                        .filter_map(|facet| {
+                            let locale = page_refresh.locale_variables.get(facet.variable())?;
+                            let region = self.region.as_ref()?;
+                            (*locale != region.lang_code)
+                                .then(|| (facet.variable(), locale.clone()))
+                        })
+                        .collect();
                 }
 
                 Err(why) => {
@@ -317,11 +446,13 @@ impl Page {
                             let language = language.clone();
                             self.language = Some(language.clone());
                             let region = self.region.clone();
+                            let overrides = self.overrides.clone();
 
                             tokio::spawn(async move {
                                 _ = set_locale(
                                     language.lang_code.clone(),
                                     region.unwrap_or(language).lang_code.clone(),
+                                    overrides,
                                 )
                                 .await;
                             });
@@ -334,6 +465,45 @@ impl Page {
         cosmic::Task::none()
     }
 
+    /// Sets a part of the locale to follow a locale, or the region if it is `None` or the
+    /// region's own locale.
+    fn set_override(&mut self, facet: Facet, locale: Option<String>) {
+        let region = self.region.as_ref().map(|region| region.lang_code.as_str());
+
+        match locale.filter(|locale| Some(locale.as_str()) != region) {
+            Some(locale) => _ = self.overrides.insert(facet.variable(), locale),
+            None => _ = self.overrides.remove(facet.variable()),
+        }
+    }
+
+    /// Writes the language, region, and overrides to the system locale.
+    fn apply_locale(&self) -> cosmic::Task<crate::app::Message> {
+        let Some((language, region)) = self.language.as_ref().zip(self.region.as_ref()) else {
+            return cosmic::Task::none();
+        };
+
+        let lang = language.lang_code.clone();
+        let region = region.lang_code.clone();
+        let overrides = self.overrides.clone();
+
+        cosmic::task::future(async move {
+            _ = set_locale(lang, region, overrides).await;
+            Message::Refresh(Arc::new(page_reload().await))
+        })
+    }
+
+    /// The name of the locale that a part of the locale follows, if not the region.
+    fn override_name(&self, facet: Facet) -> Option<&str> {
+        let locale = self.overrides.get(facet.variable())?;
+
+        Some(
+            self.available_languages
+                .values()
+                .find(|available| available.lang_code == *locale)
+                .map_or(locale.as_str(), |available| available.region_name.as_str()),
+        )
+    }
+
     fn add_language_view(&self) -> cosmic::Element<'_, crate::pages::Message> {
         let mut list = widget::list_column();
 
@@ -523,12 +693,80 @@ impl Page {
         formatter.format(&value).to_string()
     }
 
-    fn region_view(&self) -> cosmic::Element<'_, crate::pages::Message> {
-        let svg_accent = Rc::new(|theme: &cosmic::Theme| {
-            let color = theme.cosmic().accent_color().into();
-            cosmic::widget::svg::Style { color: Some(color) }
-        });
+    fn formatted_currency(&self) -> String {
+        let monetary_locale = self
+            .system_locales
+            .get("LC_MONETARY")
+            .or_else(|| self.system_locales.get("LANG"))
+            .map_or("en_US", |locale| &locale.lang_code)
+            .split('.')
+            .next()
+            .unwrap_or("en_US");
+
+        let Ok(locale) = icu::locid::Locale::from_str(monetary_locale) else {
+            return String::new();
+        };
+
+        let options = FixedDecimalFormatterOptions::default();
+        let formatter = FixedDecimalFormatter::try_new(&locale.into(), options).unwrap();
+        let mut value = FixedDecimal::from(123456);
+        value.multiply_pow10(-2);
+        let amount = formatter.format(&value).to_string();
+
+        match self.currency.as_ref() {
+            Some(currency) => format_currency(&amount, currency),
+            None => amount,
+        }
+    }
+
+    fn formatted_measurement(&self) -> &str {
+        &MEASUREMENT_SYSTEMS[usize::from(self.measures_imperial())]
+    }
+
+    /// Whether lengths, weights, and volumes are measured in imperial units.
+    fn measures_imperial(&self) -> bool {
+        match self.overrides.get(Facet::Measurement.variable()) {
+            Some(locale) => is_imperial(locale),
+            None => self
+                .region
+                .as_ref()
+                .is_some_and(|region| is_imperial(&region.lang_code)),
+        }
+    }
+
+    /// Locales that a part of the locale may follow, beneath the region that it follows
+    /// unless another is chosen.
+    fn facet_view(&self, facet: Facet) -> cosmic::Element<'_, crate::pages::Message> {
+        let selected = self.overrides.get(facet.variable());
+        let search_input = &self.add_language_search.trim().to_lowercase();
+
+        let same_as_region = locale_row(
+            fl!("same-as-region"),
+            selected.is_none(),
+            Message::SelectFacet(facet, None),
+        );
+
+        let list = self
+            .available_languages
+            .iter()
+            .filter(|(_, locale)| {
+                search_input.is_empty() || locale.display_name.to_lowercase().contains(search_input)
+            })
+            .map(|(id, locale)| {
+                locale_row(
+                    locale.region_name.clone(),
+                    selected == Some(&locale.lang_code),
+                    Message::SelectFacet(facet, Some(id)),
+                )
+            })
+            .fold(widget::list_column().add(same_as_region), |list, row| {
+                list.add(row)
+            });
+
+        list.apply(Element::from).map(crate::pages::Message::Region)
+    }
 
+    fn region_view(&self) -> cosmic::Element<'_, crate::pages::Message> {
         let mut list = widget::list_column();
 
         let search_input = &self.add_language_search.trim().to_lowercase();
@@ -541,37 +779,11 @@ impl Page {
                     .as_ref()
                     .map_or(false, |l| l.lang_code == locale.lang_code);
 
-                let button = widget::settings::item_row(vec![
-                    widget::text::body(&locale.region_name)
-                        .class(if is_selected {
-                            cosmic::theme::Text::Accent
-                        } else {
-                            cosmic::theme::Text::Default
-                        })
-                        .wrapping(Wrapping::Word)
-                        .width(Length::Fill)
-                        .into(),
-                    if is_selected {
-                        widget::icon::from_name("object-select-symbolic")
-                            .size(16)
-                            .icon()
-                            .class(cosmic::theme::Svg::Custom(svg_accent.clone()))
-                            .into()
-                    } else {
-                        widget::horizontal_space().width(16).into()
-                    },
-                ])
-                .apply(widget::container)
-                .class(cosmic::theme::Container::List)
-                .apply(widget::button::custom)
-                .class(cosmic::theme::Button::Transparent)
-                .on_press_maybe(if is_selected {
-                    None
-                } else {
-                    Some(Message::SelectRegion(id))
-                });
-
-                list = list.add(button)
+                list = list.add(locale_row(
+                    locale.region_name.clone(),
+                    is_selected,
+                    Message::SelectRegion(id),
+                ));
             }
         }
 
@@ -644,7 +856,7 @@ mod preferred_languages {
 }
 
 mod formatting {
-    use super::Message;
+    use super::{Facet, Message};
     use cosmic::{Apply, widget};
     use cosmic_settings_page::Section;
 
@@ -655,16 +867,19 @@ mod formatting {
             time_txt = fl!("formatting", "time");
             date_and_time_txt = fl!("formatting", "date-and-time");
             numbers_txt = fl!("formatting", "numbers");
-            // measurement_txt = fl!("formatting", "measurement");
+            currency_txt = fl!("formatting", "currency");
+            measurement_txt = fl!("formatting", "measurement");
             // paper_txt = fl!("formatting", "paper");
             region_txt = fl!("region");
+            same_as_region_txt = fl!("same-as-region");
         });
 
         let dates_label = [&descriptions[dates_txt], ":"].concat();
         let time_label = [&descriptions[time_txt], ":"].concat();
         let date_and_time_label = [&descriptions[date_and_time_txt], ":"].concat();
         let numbers_label = [&descriptions[numbers_txt], ":"].concat();
-        // let measurement_label = [&descriptions[measurement_txt], ":"].concat();
+        let currency_label = [&descriptions[currency_txt], ":"].concat();
+        let measurement_label = [&descriptions[measurement_txt], ":"].concat();
         // let paper_label = [&descriptions[paper_txt], ":"].concat();
 
         Section::default()
@@ -696,12 +911,19 @@ mod formatting {
                     .push(widget::text::body(page.formatted_numbers()).font(cosmic::font::bold()))
                     .spacing(4);
 
-                // TODO: Display measurement and paper demos
+                let currency = widget::row::with_capacity(2)
+                    .push(widget::text::body(currency_label.clone()))
+                    .push(widget::text::body(page.formatted_currency()).font(cosmic::font::bold()))
+                    .spacing(4);
 
-                // let measurement = widget::row::with_capacity(2)
-                //     .push(widget::text::body(measurement_label.clone()))
-                //     .push(widget::text::body("").font(cosmic::font::bold()))
-                //     .spacing(4);
+                let measurement = widget::row::with_capacity(2)
+                    .push(widget::text::body(measurement_label.clone()))
+                    .push(
+                        widget::text::body(page.formatted_measurement()).font(cosmic::font::bold()),
+                    )
+                    .spacing(4);
+
+                // TODO: Display paper demo
 
                 // let paper = widget::row::with_capacity(2)
                 //     .push(widget::text::body(paper_label.clone()))
@@ -713,7 +935,8 @@ mod formatting {
                     .push(time)
                     .push(dates_and_times)
                     .push(numbers)
-                    // .push(measurement)
+                    .push(currency)
+                    .push(measurement)
                     // .push(paper)
                     .spacing(4)
                     .padding(5.0)
@@ -731,10 +954,36 @@ mod formatting {
                     Message::RegionContext,
                 );
 
-                widget::settings::section()
+                let mut section = widget::settings::section()
                     .title(&desc[formatting_txt])
                     .add(formatted_demo)
-                    .add(select_region)
+                    .add(select_region);
+
+                for facet in [Facet::Time, Facet::Numbers, Facet::Currency] {
+                    let locale = page
+                        .override_name(facet)
+                        .unwrap_or(&desc[same_as_region_txt]);
+
+                    section = section.add(crate::widget::go_next_with_item(
+                        facet.title(),
+                        widget::text::body(locale),
+                        Message::FacetContext(facet),
+                    ));
+                }
+
+                let measurement = widget::settings::item::builder(&desc[measurement_txt]).control(
+                    widget::dropdown::popup_dropdown(
+                        &*super::MEASUREMENT_SYSTEMS,
+                        Some(usize::from(page.measures_imperial())),
+                        Message::Measurement,
+                        cosmic::iced::window::Id::RESERVED,
+                        Message::Surface,
+                        |a| crate::app::Message::PageMessage(crate::pages::Message::Region(a)),
+                    ),
+                );
+
+                section
+                    .add(measurement)
                     .apply(cosmic::Element::from)
                     .map(Into::into)
             })
@@ -756,7 +1005,7 @@ pub async fn page_reload() -> eyre::Result<PageRefresh> {
 
     let registry = locale::Registry::new().wrap_err("failed to get locale registry")?;
 
-    let system_locales: BTreeMap<String, SystemLocale> = locale1::locale1Proxy::new(&conn)
+    let locale_variables: BTreeMap<String, String> = locale1::locale1Proxy::new(&conn)
         .await
         .wrap_err("locale1 proxy connect error")?
         .locale()
@@ -764,15 +1013,16 @@ pub async fn page_reload() -> eyre::Result<PageRefresh> {
         .wrap_err("could not get locale from locale1")?
         .into_iter()
         .filter_map(|expression| {
-            let mut fields = expression.split('=');
-            let var = fields.next()?;
-            let lang_code = fields.next()?;
-            let locale = registry.locale(lang_code)?;
+            let (var, lang_code) = expression.split_once('=')?;
+            Some((var.to_owned(), lang_code.to_owned()))
+        })
+        .collect();
 
-            Some((
-                var.to_owned(),
-                localized_locale(&locale, lang_code.to_owned()),
-            ))
+    let system_locales: BTreeMap<String, SystemLocale> = locale_variables
+        .iter()
+        .filter_map(|(var, lang_code)| {
+            let locale = registry.locale(lang_code)?;
+            Some((var.clone(), localized_locale(&locale, lang_code.clone())))
         })
         .collect();
 
@@ -799,11 +1049,21 @@ pub async fn page_reload() -> eyre::Result<PageRefresh> {
         .or_else(|| system_locales.get("LANG"))
         .cloned();
 
+    // Address formats are only ever set to the region, unlike times, numbers, and money.
     let region = system_locales
-        .get("LC_TIME")
+        .get("LC_ADDRESS")
+        .or_else(|| system_locales.get("LC_TIME"))
         .or_else(|| system_locales.get("LANG"))
         .cloned();
 
+    let currency = match locale_variables
+        .get("LC_MONETARY")
+        .or_else(|| locale_variables.get("LANG"))
+    {
+        Some(monetary) => currency_format(monetary).await,
+        None => None,
+    };
+
     let mut available_languages_set = BTreeSet::new();
 
     let output = tokio::process::Command::new("localectl")
@@ -835,9 +1095,84 @@ pub async fn page_reload() -> eyre::Result<PageRefresh> {
         region,
         available_languages,
         system_locales,
+        locale_variables,
+        currency,
     })
 }
 
+/// Reads how a locale writes amounts of money.
+async fn currency_format(locale: &str) -> Option<CurrencyFormat> {
+    let output = tokio::process::Command::new("locale")
+        .args(["currency_symbol", "p_cs_precedes", "p_sep_by_space"])
+        .env("LC_ALL", locale)
+        .output()
+        .await
+        .ok()?;
+
+    let output = String::from_utf8(output.stdout).ok()?;
+    let mut lines = output.lines();
+
+    Some(CurrencyFormat {
+        symbol: lines.next()?.to_owned(),
+        precedes: lines.next()? == "1",
+        separated: lines.next()? != "0",
+    })
+}
+
+/// Writes an amount as money, with the symbol on the side the locale puts it.
+fn format_currency(amount: &str, currency: &CurrencyFormat) -> String {
+    let space = if currency.separated { " " } else { "" };
+
+    if currency.precedes {
+        [&currency.symbol, space, amount].concat()
+    } else {
+        [amount, space, &currency.symbol].concat()
+    }
+}
+
+/// Whether a locale, such as `en_US.UTF-8`, measures in imperial units.
+fn is_imperial(lang_code: &str) -> bool {
+    let name = lang_code.split(['.', '@']).next().unwrap_or_default();
+
+    name.split_once('_')
+        .is_some_and(|(_, territory)| IMPERIAL_TERRITORIES.contains(&territory))
+}
+
+/// A locale in a list to choose from, which is pressable unless it is already selected.
+fn locale_row(name: String, is_selected: bool, on_press: Message) -> Element<'static, Message> {
+    let svg_accent = Rc::new(|theme: &cosmic::Theme| {
+        let color = theme.cosmic().accent_color().into();
+        cosmic::widget::svg::Style { color: Some(color) }
+    });
+
+    widget::settings::item_row(vec![
+        widget::text::body(name)
+            .class(if is_selected {
+                cosmic::theme::Text::Accent
+            } else {
+                cosmic::theme::Text::Default
+            })
+            .wrapping(Wrapping::Word)
+            .width(Length::Fill)
+            .into(),
+        if is_selected {
+            widget::icon::from_name("object-select-symbolic")
+                .size(16)
+                .icon()
+                .class(cosmic::theme::Svg::Custom(svg_accent))
+                .into()
+        } else {
+            widget::horizontal_space().width(16).into()
+        },
+    ])
+    .apply(widget::container)
+    .class(cosmic::theme::Container::List)
+    .apply(widget::button::custom)
+    .class(cosmic::theme::Button::Transparent)
+    .on_press_maybe((!is_selected).then_some(on_press))
+    .into()
+}
+
 fn language_element(
     id: usize,
     description: String,
@@ -944,22 +1279,87 @@ fn popover_menu_row(
         .map(move |()| Message::SourceContext(message(id)))
 }
 
-pub async fn set_locale(lang: String, region: String) {
-    eprintln!("setting locale lang={lang}, region={region}");
+/// Sets the language, and every part of the locale to the region unless it is overridden.
+pub async fn set_locale(lang: String, region: String, overrides: BTreeMap<&'static str, String>) {
+    eprintln!("setting locale lang={lang}, region={region}, overrides={overrides:?}");
     _ = tokio::process::Command::new("localectl")
         .arg("set-locale")
-        .args(&[
-            ["LANG=", &lang].concat(),
-            ["LC_ADDRESS=", &region].concat(),
-            ["LC_IDENTIFICATION=", &region].concat(),
-            ["LC_MEASUREMENT=", &region].concat(),
-            ["LC_MONETARY=", &region].concat(),
-            ["LC_NAME=", &region].concat(),
-            ["LC_NUMERIC=", &region].concat(),
-            ["LC_PAPER=", &region].concat(),
-            ["LC_TELEPHONE=", &region].concat(),
-            ["LC_TIME=", &region].concat(),
-        ])
+        .args(locale_assignments(&lang, &region, &overrides))
         .status()
         .await;
 }
+
+/// The `VARIABLE=locale` assignments given to `localectl set-locale`.
+fn locale_assignments(
+    lang: &str,
+    region: &str,
+    overrides: &BTreeMap<&'static str, String>,
+) -> Vec<String> {
+    const REGION_VARIABLES: [&str; 9] = [
+        "LC_ADDRESS",
+        "LC_IDENTIFICATION",
+        "LC_MEASUREMENT",
+        "LC_MONETARY",
+        "LC_NAME",
+        "LC_NUMERIC",
+        "LC_PAPER",
+        "LC_TELEPHONE",
+        "LC_TIME",
+    ];
+
+    std::iter::once(["LANG=", lang].concat())
+        .chain(REGION_VARIABLES.iter().map(|var| {
+            let locale = overrides.get(var).map_or(region, String::as_str);
+            [var, "=", locale].concat()
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurrencyFormat, format_currency, is_imperial, locale_assignments};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn overridden_variables() {
+        let overrides = BTreeMap::from([
+            ("LC_MEASUREMENT", String::from("C.UTF-8")),
+            ("LC_TIME", String::from("en_GB.UTF-8")),
+        ]);
+
+        let assignments = locale_assignments("en_US.UTF-8", "en_US.UTF-8", &overrides);
+
+        assert_eq!(assignments[0], "LANG=en_US.UTF-8");
+        assert!(assignments.contains(&String::from("LC_MEASUREMENT=C.UTF-8")));
+        assert!(assignments.contains(&String::from("LC_TIME=en_GB.UTF-8")));
+        assert!(assignments.contains(&String::from("LC_MONETARY=en_US.UTF-8")));
+        assert_eq!(assignments.len(), 10);
+    }
+
+    #[test]
+    fn measurement_systems() {
+        assert!(is_imperial("en_US.UTF-8"));
+        assert!(is_imperial("es_US"));
+        assert!(!is_imperial("en_GB.UTF-8"));
+        assert!(!is_imperial("C.UTF-8"));
+        assert!(!is_imperial("sr_RS.UTF-8@latin"));
+    }
+
+    #[test]
+    fn currency() {
+        let dollar = CurrencyFormat {
+            symbol: String::from("$"),
+            precedes: true,
+            separated: false,
+        };
+
+        let euro = CurrencyFormat {
+            symbol: String::from("€"),
+            precedes: false,
+            separated: true,
+        };
+
+        assert_eq!(format_currency("1,234.56", &dollar), "$1,234.56");
+        assert_eq!(format_currency("1.234,56", &euro), "1.234,56 €");
+    }
+}
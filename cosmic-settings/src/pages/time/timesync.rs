@@ -0,0 +1,152 @@
+// Copyright 2024 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! The NTP servers that systemd-timesyncd synchronizes the clock with.
+//!
+//! Servers chosen here are written to a drop-in of the timesyncd config, which takes the
+//! place of the servers that the distribution configures until it is removed.
+
+use std::time::SystemTime;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use zbus::proxy;
+
+/// Drop-in of the timesyncd config holding the servers chosen here.
+const DROP_IN: &str = "/etc/systemd/timesyncd.conf.d/cosmic-settings.conf";
+
+/// Touched by timesyncd whenever it synchronizes the clock.
+const CLOCK_FILE: &str = "/var/lib/systemd/timesync/clock";
+
+#[proxy(
+    interface = "org.freedesktop.timesync1.Manager",
+    default_service = "org.freedesktop.timesync1",
+    default_path = "/org/freedesktop/timesync1"
+)]
+pub trait Timesync {
+    #[zbus(property, name = "SystemNTPServers")]
+    fn system_ntp_servers(&self) -> zbus::Result<Vec<String>>;
+
+    #[zbus(property)]
+    fn server_name(&self) -> zbus::Result<String>;
+}
+
+/// The servers configured for timesyncd, and the one it synchronizes with.
+pub async fn servers(conn: &zbus::Connection) -> (Vec<String>, Option<String>) {
+    let Ok(proxy) = TimesyncProxy::new(conn).await else {
+        return (Vec::new(), None);
+    };
+
+    let servers = proxy.system_ntp_servers().await.unwrap_or_default();
+    let current = proxy
+        .server_name()
+        .await
+        .ok()
+        .filter(|name| !name.is_empty());
+
+    (servers, current)
+}
+
+/// When timesyncd last synchronized the clock.
+pub fn last_sync() -> Option<SystemTime> {
+    std::fs::metadata(CLOCK_FILE).ok()?.modified().ok()
+}
+
+/// Reads a list of servers separated by spaces or commas, or `None` if any is not a valid
+/// host name or address.
+pub fn parse_servers(input: &str) -> Option<Vec<String>> {
+    input
+        .split([' ', ','])
+        .filter(|server| !server.is_empty())
+        .map(|server| is_valid_server(server).then(|| server.to_owned()))
+        .collect()
+}
+
+fn is_valid_server(server: &str) -> bool {
+    server.len() <= 253
+        && !server.starts_with(['-', '.'])
+        && server
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':'))
+}
+
+/// Contents of the drop-in that sets the servers timesyncd synchronizes with.
+fn drop_in(servers: &[String]) -> String {
+    format!("[Time]\nNTP={}\n", servers.join(" "))
+}
+
+/// Writes the servers to the timesyncd config, or removes the drop-in if there are none so
+/// that the distribution's servers are used again, and restarts timesyncd.
+pub async fn set_servers(servers: Vec<String>) -> std::io::Result<()> {
+    let script = if servers.is_empty() {
+        r#"rm -f "$1" && systemctl try-restart systemd-timesyncd"#
+    } else {
+        r#"mkdir -p "$(dirname "$1")" && printf %s "$2" > "$1" && systemctl try-restart systemd-timesyncd"#
+    };
+
+    let status = tokio::process::Command::new("pkexec")
+        .args(["sh", "-c", script, "sh", DROP_IN, &drop_in(&servers)])
+        .status()
+        .await?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "pkexec exited with {status}"
+        )))
+    }
+}
+
+/// Reads a date as `YYYY-MM-DD` and a time as `HH:MM` or `HH:MM:SS` in the local time zone,
+/// as microseconds since the epoch.
+pub fn parse_manual_time(date: &str, time: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time.trim(), "%H:%M"))
+        .ok()?;
+
+    let local = Local
+        .from_local_datetime(&NaiveDateTime::new(date, time))
+        .earliest()?;
+
+    Some(local.timestamp_micros())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{drop_in, parse_manual_time, parse_servers};
+
+    #[test]
+    fn servers() {
+        assert_eq!(
+            parse_servers("0.pool.ntp.org, time.cloudflare.com  2001:db8::1"),
+            Some(vec![
+                String::from("0.pool.ntp.org"),
+                String::from("time.cloudflare.com"),
+                String::from("2001:db8::1"),
+            ])
+        );
+
+        assert_eq!(parse_servers(""), Some(Vec::new()));
+        assert_eq!(parse_servers("pool.ntp.org; rm -rf /"), None);
+        assert_eq!(parse_servers("-oops"), None);
+
+        assert_eq!(
+            drop_in(&[String::from("a.example"), String::from("b.example")]),
+            "[Time]\nNTP=a.example b.example\n"
+        );
+    }
+
+    #[test]
+    fn manual_time() {
+        assert!(parse_manual_time("2024-02-29", "13:05").is_some());
+        assert_eq!(
+            parse_manual_time("2024-02-29", "13:05"),
+            parse_manual_time(" 2024-02-29 ", "13:05:00")
+        );
+
+        assert_eq!(parse_manual_time("2023-02-29", "13:05"), None);
+        assert_eq!(parse_manual_time("2024-02-28", "25:00"), None);
+        assert_eq!(parse_manual_time("28/02/2024", "12:00"), None);
+    }
+}
@@ -38,3 +38,24 @@ pub fn display_container_screen() -> cosmic::theme::Container<'static> {
         }
     })
 }
+
+/// A list item that the search matched.
+#[must_use]
+pub fn search_match() -> cosmic::theme::Container<'static> {
+    theme::Container::custom(|theme| {
+        let cosmic = theme.cosmic();
+        cosmic::widget::container::Style {
+            icon_color: None,
+            text_color: None,
+            background: Some(cosmic::iced::Background::Color(
+                cosmic.background.component.base.into(),
+            )),
+            border: Border {
+                color: cosmic.accent_color().into(),
+                radius: cosmic.corner_radii.radius_s.into(),
+                width: 2.0,
+            },
+            shadow: Default::default(),
+        }
+    })
+}
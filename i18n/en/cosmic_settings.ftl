@@ -4,6 +4,8 @@ dbus-connection-error = Failed to connect to DBus
 ok = OK
 unknown = Unknown
 
+show-advanced = Show advanced settings
+
 number = { $number }
 
 ## Network & Wireless
@@ -85,6 +87,13 @@ vpn-error = VPN Error
         [password-flags] password-flags
     } with nmcli
 
+vpn-invalid-config = Invalid VPN config
+    .no-remote = The OpenVPN config does not name a server to connect to.
+    .no-interface = The WireGuard config has no [Interface] section.
+    .no-private-key = The WireGuard interface has no private key.
+    .no-peer = The WireGuard config has no [Peer] section.
+    .no-public-key = A WireGuard peer has no public key.
+
 wired = Wired
     .adapter = Wired adapter { $id }
     .connections = Wired Connections
@@ -94,6 +103,30 @@ wired = Wired
 wifi = Wi-Fi
     .adapter = Wi-Fi adapter { $id }
     .forget = Forget this network
+    .hide-open = Hide open networks
+    .scanning = Scanning for networks…
+
+hidden-network = Connect to Hidden Network
+    .add = Connect to hidden network
+    .description = Enter the name and security of a network that does not broadcast its name.
+    .ssid = Network name (SSID)
+    .open = Open
+    .personal = WPA Personal
+    .enterprise = WPA Enterprise
+    .method = Authentication method
+    .ca-cert = CA certificate path (optional)
+    .client-cert = Client certificate path
+    .private-key = Private key path
+    .private-key-password = Private key password (optional)
+    .failed = Failed to connect to the network: { $why }
+
+wifi-security = Security
+    .open = Open
+    .wep = WEP
+    .wpa = WPA
+    .wpa2 = WPA2
+    .wpa3 = WPA3
+    .enterprise = Enterprise
 
 wireguard-dialog = Add WireGuard device
     .description = Choose a device name for the WireGuard config.
@@ -118,6 +151,7 @@ bluetooth = Bluetooth
     .connect = Connect
     .disconnect = Disconnect
     .forget = Forget
+    .trusted = Trusted
     .dbus-error = An error has occurred while interacting with DBus: { $why }
     .disabled = The bluetooth service is disabled
     .inactive = The bluetooth service is not active
@@ -127,6 +161,15 @@ bluetooth-paired = Previously Connected Devices
     .connect = Connect
     .battery = { $percentage }% battery
 
+bluetooth-audio = Audio
+    .profile = Audio profile
+    .codec = Codec
+    .high-fidelity = High fidelity (A2DP)
+    .headset = Headset (HSP/HFP)
+
+bluetooth-forget-dialog = Forget { $device }?
+    .description = The device will need to be paired again before it can connect.
+
 bluetooth-confirm-pin = Confirm Bluetooth PIN
     .description = Please confirm that the following PIN matches the one displayed on { $device }
 
@@ -142,12 +185,19 @@ accessibility = Accessibility
     .off = Off
     .unavailable = Unavailable
     .high-contrast = High contrast mode
+    .high-contrast-desc = Strengthens the contrast of colors and borders in every app
+    .reduce-motion = Reduce motion
+    .reduce-motion-desc = Asks apps to turn off their animations
     .invert-colors = Invert Colors
     .color-filters = Color filters
 
 hearing = Hearing
     .mono = Play stereo audio as mono
 
+on-screen-keyboard = On-screen keyboard
+    .enable = Use the on-screen keyboard
+    .enable-desc = Type by touching keys on the screen, instead of with a physical keyboard
+
 default = Default
 magnifier = Magnifier
     .controls = Or use these shortcuts: { $zoom_in ->
@@ -165,6 +215,7 @@ magnifier = Magnifier
     .increment = Zoom increment
     .signin = Start magnifier on sign in
     .applet = Toggle magnifier on/off in applet on the panel
+    .shortcuts = Change the zoom shortcuts
     .movement = Zoomed view moves
     .continuous = Continuously with pointer
     .onedge = When pointer reaches edge
@@ -186,11 +237,17 @@ wallpaper = Wallpaper
     .change = Change image every
     .desc = Wallpaper images, colors, and slideshow options.
     .fit = Wallpaper fit
+    .letterbox = Background color
+    .letterbox-desc = Fills the space around images that are fitted to the screen.
     .folder-dialog = Choose wallpaper folder
     .image-dialog = Choose wallpaper image
     .plural = Wallpapers
     .same = Same wallpaper on all displays
     .slide = Slideshow
+    .shuffle = Shuffle images
+    .slideshow-folder = Folder
+    .choose-folder = Choose folder
+    .empty-folder = This folder has no images to show
 
 add-color = Add color
 add-image = Add image
@@ -199,6 +256,7 @@ colors = Colors
 dialog-add = Add
 fill = Fill
 fit-to-screen = Fit to Screen
+stretch = Stretch
 open-new-folder = Open new folder
 recent-folders = Recent Folders
 
@@ -215,6 +273,7 @@ appearance = Appearance
     .desc = Accent colors and theming.
 
 accent-color = Accent color
+    .invalid-hex = Enter a color as #RRGGBB or #RGB
 app-background = Application or window background
 auto = Auto
 close = Close
@@ -226,13 +285,26 @@ export = Export
 hex = Hex
 import = Import
 light = Light
+match-wallpaper = Match wallpaper
+    .failed = No accent color could be found in the wallpaper
 mode-and-colors = Mode and Colors
 recent-colors = Recent colors
 reset-to-default = Reset to default
+reset-binding = Reset to { $binding }
+reset-section = Reset section
 rgb = RGB
 window-hint-accent = Active window hint color
 window-hint-accent-toggle = Use theme accent color as active window hint
 
+theme-import = Import "{ $name }"
+    .apply = Apply
+    .dark = Switches to Dark mode with this theme.
+    .light = Switches to Light mode with this theme.
+    .builder-version = This theme was exported from a different version of COSMIC, so some of its settings may differ.
+    .no-mode = This theme was exported without its mode, so it will replace the theme of the current mode.
+    .unsupported = This theme needs a newer version of COSMIC Settings (format version { $version }).
+    .invalid = The file is not a theme: { $why }
+
 auto-switch = Automatically switch between Light and Dark modes
     .sunrise = Switches to Light mode at sunrise
     .sunset = Switches to Dark mode at sunset
@@ -270,21 +342,31 @@ interface-density = Interface Density
     .spacious = Spacious
 
 window-management-appearance = Window Management
+    .corner-radius = Window corner radius
     .active-hint = Active window hint size
     .gaps = Gaps around tiled windows
 
+### Fonts
+
+fonts = Fonts
+interface-font = System font
+monospace-font = Monospace font
+font-preview = The quick brown fox jumps over the lazy dog
+reset-fonts = Reset fonts
+
 ### Experimental
 
 experimental-settings = Experimental Settings
 icons-and-toolkit = Icons and toolkit theming
-interface-font = System font
-monospace-font = Monospace font
 
 ## Desktop: Notifications
 
 notifications = Notifications
     .desc = Do Not Disturb, lockscreen notifications, and per-application settings.
 
+do-not-disturb = Do not disturb
+    .desc = Notifications are silenced and kept for later.
+
 ## Desktop: Panel
 
 panel = Panel
@@ -404,6 +486,7 @@ display = Displays
     .arrangement = Display Arrangement
     .arrangement-desc = Drag displays to rearrange them.
     .enable = Enable display
+    .identify = Identify displays
     .external = { $size } { $output } External Display
     .laptop = { $size } Laptop Display
     .options = Display Options
@@ -415,6 +498,7 @@ display = Displays
 mirroring = Mirroring
     .id = Mirroring { $id }
     .dont = Don't mirror
+    .mirror-all = Mirror displays
     .mirror = Mirror { $display }
     .project = Project to { $display ->
         [all] all displays
@@ -463,6 +547,20 @@ sound-output = Output
     .balance = Balance
     .left = Left
     .right = Right
+    .test = Test speakers
+    .test-button = Test
+
+sound-channel = Channel
+    .mono = Mono
+    .front-left = Front left
+    .front-right = Front right
+    .front-center = Front center
+    .rear-left = Rear left
+    .rear-right = Rear right
+    .rear-center = Rear center
+    .side-left = Side left
+    .side-right = Side right
+    .lfe = Subwoofer
 
 sound-input = Input
     .volume = Input volume
@@ -475,6 +573,7 @@ sound-alerts = Alerts
 
 sound-applications = Applications
     .desc = Application volumes and settings
+    .empty = No applications are playing audio
 
 profile = Profile
 
@@ -503,6 +602,12 @@ battery = Battery
        *[other] empty
    }
 
+battery-charging = Battery Charging
+    .health = Battery health
+    .health-desc = Capacity of a full charge compared to when the battery was new.
+    .limit = Charge limit
+    .limit-desc = Stop charging at this level to extend the lifespan of the battery.
+
 connected-devices = Connected Devices
   .unknown = Unknown device
 
@@ -514,6 +619,9 @@ power-mode = Power Mode
     .performance = High performance
     .performance-desc = Peak performance and power usage.
     .no-backend = Backend not found. Install system76-power or power-profiles-daemon.
+    .add-custom = Add custom profile
+    .custom-name = Profile name
+    .custom-preset = Based on
 
 power-saving = Power Saving Options
     .turn-off-screen-after = Turn off the screen after
@@ -530,6 +638,13 @@ disable-while-typing = Disable while typing
 input-devices = Input Devices
     .desc = Input Devices
 
+input-device = Devices
+    .profile = Acceleration profile
+    .adaptive = Adaptive
+    .flat = Flat
+    .speed = Speed
+    .reset = Reset device to defaults
+
 primary-button = Primary button
     .desc = Sets the order of physical buttons.
     .left = Left
@@ -559,15 +674,24 @@ keyboard-sources = Input Sources
     .view-layout = View keyboard layout
     .remove = Remove
     .add = Add input source
+    .default = Default
+    .switch = Switch input sources
+    .switch-none = No shortcut
 
 keyboard-special-char = Special Character Entry
     .alternate = Alternate characters key
     .compose = Compose key
     .caps = Caps Lock key
 
+keyboard-compose = Compose Key
+    .file = Compose sequences are loaded from { $path }
+    .default-file = Compose sequences for your language are used
+
 keyboard-typing-assist = Typing
     .repeat-rate = Repeat rate
     .repeat-delay = Repeat delay
+    .test = Test repeat settings
+    .test-placeholder = Hold a key down here
 
 keyboard-numlock-boot = Numlock
     .boot-state = State on boot
@@ -590,6 +714,7 @@ cancel = Cancel
 command = Command
 custom = Custom
 debug = Debug
+delete = Delete
 disabled = Disabled
 input-source-switch = Switch keyboard language input source
 migrate-workspace-prev = Migrate workspace to previous output
@@ -612,8 +737,61 @@ custom-shortcuts = Custom Shortcuts
     .add = Add shortcut
     .context = Add Custom Shortcut
     .none = No custom shortcuts
+    .test = Test
+    .test-success = Command launched
+    .test-failed = Failed to launch command: { $why }
+    .run-failed = Failed to run shortcut: { $why }
+    .count = { $count ->
+        [one] 1 custom shortcut
+        *[other] { $count } custom shortcuts
+    }
+    .select = Select
+    .glyphs = Show key combinations as symbols
+    .note = Note
+    .note-placeholder = Optional note, such as when the shortcut works
+    .save-note = Save note
+    .delete-selected = Delete selected
+    .import-gnome = Import from GNOME
+    .import-failed = Failed to import GNOME keybindings: { $why }
+    .pick-application = Choose application
+    .recent-commands = Recently used commands
+    .argument-list = Enter as a list of arguments, without shell parsing
+    .run-in-terminal = Run in terminal, to see the output of the command
+    .runs-in-terminal = Runs in the terminal
+    .keep-adding = Keep open to add more shortcuts
+    .program = Program
+    .argument = Argument
+    .add-argument = Add argument
+    .reset-all = Remove all
+    .export = Export custom shortcuts
+    .export-success = Custom shortcuts exported
+    .export-failed = Failed to export custom shortcuts: { $why }
+    .import = Import custom shortcuts
+    .import-custom-failed = Failed to import custom shortcuts: { $why }
+    .keys = Key combinations
+    .name-help = Shown in the list of shortcuts, to tell them apart
+    .name-length = { $count }/{ $max } characters
+    .command-help = Run when the keys are pressed, as typed in a terminal, such as "firefox --private-window"
+    .keys-help = Modifiers and a key joined with +, such as "Super+Shift+T". Press Enter to add another combination
+    .capture-output = Show the output of test runs
+    .added-binding-to-existing = Added the key combination to the existing "{ $name }" shortcut
+    .saved-as = Saved as { $binding }
+    .conflicts = These shortcuts share key combinations, so only one of each pair will run
+
+spawn-output = Output
+    .exit-code = Exited with code { $code }
+    .signal = Stopped by signal { $signal }
+    .running = Still running after 5 seconds
+    .stdout = Standard output
+    .stderr = Standard error
+    .empty = Nothing was written
+    .truncated = Only the first { $limit } KiB of each stream are shown
 
 modified = { $count } modified
+binding-count = { $count ->
+    [one] 1 key combination
+    *[other] { $count } key combinations
+}
 
 nav-shortcuts = Navigation
     .prev-output = Focus previous output
@@ -697,6 +875,34 @@ system-shortcut = System
     .window-switcher-previous = Switch between open windows reversed
     .workspace-overview = Open the workspace overview
 
+# Other words that find a shortcut in the settings search, separated by commas.
+shortcut-keywords =
+    .app-library = applications, apps, programs
+    .brightness-down = brightness down, dim, screen
+    .brightness-up = brightness up, screen
+    .home-folder = files, file manager
+    .input-source-switch = keyboard layout, language
+    .keyboard-brightness-down = backlight down
+    .keyboard-brightness-up = backlight up
+    .launcher = search, run
+    .log-out = sign out, log off
+    .lock-screen = lock
+    .mute = volume off, sound off, silence
+    .mute-mic = microphone off
+    .play-pause = media, music
+    .play-next = media, music, skip
+    .play-prev = media, music, back
+    .screenshot = screen capture, print screen
+    .terminal = console, shell, command line
+    .volume-lower = volume down, quieter, sound
+    .volume-raise = volume up, louder, sound
+    .web-browser = internet, web
+    .window-switcher = alt tab, switch windows
+    .window-switcher-previous = alt tab, switch windows
+    .workspace-overview = workspaces
+    .zoom-in = magnifier, magnify
+    .zoom-out = magnifier
+
 window-tiling = Window tiling
     .horizontal = Set horizontal orientation
     .vertical = Set vertical orientation
@@ -708,6 +914,97 @@ window-tiling = Window tiling
 
 replace-shortcut-dialog = Replace Shortcut?
     .desc = { $shortcut } is used by { $name }. If you replace it, { $name } will be disabled.
+    .progress = Replace Shortcut? ({ $current } of { $total })
+
+delete-shortcuts-dialog = Delete Shortcuts?
+    .desc = { $count ->
+        [one] 1 shortcut will be permanently deleted.
+        *[other] { $count } shortcuts will be permanently deleted.
+    }
+
+reset-custom-dialog = Remove All Custom Shortcuts?
+    .desc = { $count ->
+        [one] 1 custom shortcut will be permanently removed.
+        *[other] All { $count } custom shortcuts will be permanently removed.
+    } This cannot be undone. Built-in shortcuts are not affected.
+    .remove = Remove all
+    .export = Export first
+
+import-gnome-dialog = GNOME Shortcuts Imported
+    .imported = { $count ->
+        [one] 1 shortcut was imported.
+        *[other] { $count } shortcuts were imported.
+    }
+    .unchanged = { $count ->
+        [one] 1 shortcut was already present.
+        *[other] { $count } shortcuts were already present.
+    }
+    .conflicts = { $count ->
+        [one] 1 shortcut uses a key combination that is already taken.
+        *[other] { $count } shortcuts use key combinations that are already taken.
+    }
+    .failed = { $count ->
+        [one] 1 shortcut has a key combination that could not be read.
+        *[other] { $count } shortcuts have key combinations that could not be read.
+    }
+    .unsaved = { $count ->
+        [one] 1 shortcut could not be saved.
+        *[other] { $count } shortcuts could not be saved.
+    }
+    .none = No custom shortcuts were found in the export.
+
+shortcut-category = Shortcut category
+    .enable = Enable shortcuts in this category
+    .mixed = Some shortcuts in this category are disabled
+
+shortcut-filter = Show shortcuts
+    .all = All
+    .modified = Modified
+    .default = Default
+    .none = No shortcuts match this filter.
+
+reset-shortcuts = Reset all shortcuts
+reset-shortcuts-dialog = Reset All Shortcuts?
+    .desc = { $count ->
+        [one] 1 shortcut differs from its default and will be reset.
+        *[other] { $count } shortcuts differ from their defaults and will be reset.
+    }
+    .shortcut = Shortcut
+    .current = Current
+    .default = Default
+    .reset = Reset all
+    .removed = Custom shortcuts that take the key combination of a default are removed
+    .removed-binding = Removed
+
+reset-section-dialog = Reset Section?
+    .desc = { $count ->
+        [one] 1 shortcut in this section differs from its default and will be reset.
+        *[other] { $count } shortcuts in this section differ from their defaults and will be reset.
+    }
+    .reset = Reset section
+
+shortcut-conflicts = Conflicting shortcuts
+    .desc = Key combinations bound to more than one shortcut
+    .count = { $count ->
+        [one] 1 conflict
+        *[other] { $count } conflicts
+    }
+    .none = No key combination is bound to more than one shortcut.
+    .resolve = Resolve
+
+binding-error = Invalid key combination
+    .unparsable = Not a valid key combination
+    .empty = No key combination was given
+    .unknown-modifier = Unknown modifier: { $modifier }
+    .unknown-key = Unknown key: { $key }
+    .unset = A key must be pressed along with the modifiers
+    .modifiers-only = { $shortcut } has only modifiers, and would never fire. Add a key to press with them, such as { $shortcut }+T.
+
+reserved-shortcut = { $shortcut } is reserved by the system and cannot be used as a shortcut.
+
+shortcut-apply-status = Shortcut status
+    .applied = Applied
+    .failed-desc = Failed to apply shortcut: { $why }
 
 zoom-in = Zoom In
 zoom-out = Zoom Out
@@ -762,10 +1059,24 @@ time-date = Date & Time
     .desc = Time zone, automatic clock settings, and some time formatting.
     .auto = Set automatically
     .auto-ntp = Date & time will update automatically when the time zone is set.
+    .synced = Synchronized with { $server }
+    .last-sync = Last synchronized { $time }
+    .not-synced = Not synchronized
+    .ntp-servers = NTP servers
+    .ntp-servers-desc = Separated by spaces. Leave empty to use the default servers.
+    .ntp-servers-invalid = Not a valid host name or address
+    .manual = Set date and time manually
+    .manual-desc = Date as YYYY-MM-DD and time as HH:MM in the current time zone
+    .manual-invalid = Not a valid date and time
+    .manual-ntp-warning = Automatic time would override a manual change, so setting the time turns it off.
+    .set = Set
 
 time-zone = Time Zone
     .auto = Automatic time zone
     .auto-info = Requires location services and internet access
+    .detecting = Detecting location…
+    .detected = Detected { $timezone }
+    .detect-failed = Could not detect the time zone from the location
 
 time-format = Date & Time Format
     .twenty-four = 24-hour time
@@ -785,9 +1096,16 @@ formatting = Formatting
     .time = Time
     .date-and-time = Date & Time
     .numbers = Numbers
+    .currency = Currency
     .measurement = Measurement
     .paper = Paper
 
+measurement = Measurement
+    .metric = Metric
+    .imperial = Imperial
+
+same-as-region = Same as region
+
 preferred-languages = Preferred Languages
     .desc = The order of languages determines which language is used for the translation of the desktop. Changes take effect on next login.
 
@@ -813,6 +1131,9 @@ default-apps = Default Applications
     .calendar = Calendar
     .terminal = Terminal
     .other-associations = Other Associations
+    .search-associations = Search file types and URL schemes
+    .no-associations = No file types or URL schemes match the search.
+    .more-associations = { $count } more match the search.
     .text-editor = Text Editor
 
 ## Applications: Startup Applications
@@ -825,6 +1146,14 @@ startup-apps = Startup Applications
     .remove-dialog-title = Remove { $name }?
     .remove-dialog-description = Are you sure you want to remove this as a startup application?
     .search-for-application = Search for application
+    .system = System applications
+    .system-description = These applications are installed with the system. Turning one off only affects your user.
+    .command = Run a command
+    .command-name = Name
+    .command-placeholder = Command
+    .applications = Applications
+    .no-delay = No delay
+    .delay-seconds = After { $seconds } s
 
 ## Applications: Legacy Applications
 
@@ -889,6 +1218,8 @@ users = Users
     .admin = Admin
     .standard = Standard
     .profile-add = Choose profile image
+    .profile-file = Choose image file
+    .last-admin = The only administrator can not be removed.
 
 administrator = Administrator
     .desc = Administrators can change settings for all users, add and remove other users.
@@ -896,7 +1227,16 @@ administrator = Administrator
 add-user = Add user
 change-password = Change password
 remove-user = Remove user
+remove-user-dialog = Remove { $name }?
+    .description = The account will be removed, and its user will no longer be able to log in.
 full-name = Full name
+last-login = Last login: { $time }
 invalid-username = Invalid username.
+    .too-long = Usernames can be at most { $max } characters long.
+    .taken = Another account has this username.
 password-mismatch = Password and confirmation must match.
+weak-password = Password is too weak.
+    .too-short = Passwords must be at least { $min } characters long.
+    .too-simple = Mix letters with numbers or symbols, or use a longer password.
+    .contains-username = Passwords must not contain the username.
 save = Save
@@ -56,6 +56,18 @@ impl<Message: Clone + 'static> Binder<Message> {
         self.content.get(page).map(Vec::as_slice)
     }
 
+    /// The sections of a page in the order that they are shown, such as for checking in tests
+    /// that a page registers the titles and descriptions that searches run against.
+    pub fn page_sections(
+        &self,
+        page: crate::Entity,
+    ) -> impl Iterator<Item = (section::Entity, &Section<Message>)> + '_ {
+        self.content(page)
+            .unwrap_or_default()
+            .iter()
+            .map(move |&id| (id, &self.sections[id]))
+    }
+
     /// Get an immutable reference to data associated with a page.
     #[must_use]
     pub fn data<Data: 'static>(&self, id: crate::Entity) -> Option<&Data> {
@@ -107,6 +119,10 @@ impl<Message: Clone + 'static> Binder<Message> {
         P::sub_pages(crate::Insert { id, model: self })
     }
 
+    /// Registers a page without its sub-pages.
+    ///
+    /// The sections of its content are stored and indexed at once, rather than when the page
+    /// is first shown, so that searches find the settings of pages that were never visited.
     pub fn register_page<P: Page<Message>>(&mut self, mut page: P) -> crate::Entity {
         let id = self.info.insert(page.info());
 
@@ -155,6 +171,30 @@ impl<Message: Clone + 'static> Binder<Message> {
         page.context_drawer()
     }
 
+    /// The navigation breadcrumb of a page whose context drawer reports a title.
+    ///
+    /// Pages without a context title have no breadcrumb.
+    #[must_use]
+    pub fn breadcrumb(&self, id: crate::Entity) -> Option<Vec<String>> {
+        let page = self.page.get(id)?;
+        let mut breadcrumb = vec![page.context_title()?];
+
+        breadcrumb.push(
+            page.title()
+                .unwrap_or(self.info.get(id)?.title.as_str())
+                .to_owned(),
+        );
+
+        let mut parent = self.info.get(id)?.parent;
+        while let Some(info) = parent.and_then(|id| self.info.get(id)) {
+            breadcrumb.push(info.title.clone());
+            parent = info.parent;
+        }
+
+        breadcrumb.reverse();
+        Some(breadcrumb)
+    }
+
     /// Create a dialog for the given page.
     #[must_use]
     #[inline]
@@ -224,11 +264,52 @@ impl<Message: Clone + 'static> Binder<Message> {
     pub fn search<'a>(
         &'a self,
         rule: &'a Regex,
+    ) -> impl Iterator<Item = (crate::Entity, section::Entity)> + 'a {
+        self.search_by(move |section, page| {
+            section.search_matches(rule)
+                || page.is_some_and(|page| section.search_items_match(page, rule))
+        })
+    }
+
+    /// Finds content of panels that match the search as a whole word.
+    pub fn search_words<'a>(
+        &'a self,
+        rule: &'a Regex,
+    ) -> impl Iterator<Item = (crate::Entity, section::Entity)> + 'a {
+        self.search_by(move |section, page| {
+            section.search_matches_word(rule)
+                || page.is_some_and(|page| section.search_items_match_word(page, rule))
+        })
+    }
+
+    /// Gives the search to the sections that it found, and takes it from every other section,
+    /// so that their views may emphasize what matched. `None` takes it from every section.
+    ///
+    /// A search that found the sections by whole words only emphasizes whole words.
+    pub fn search_highlight(
+        &mut self,
+        rule: Option<&Regex>,
+        whole_words: bool,
+        found: &[(crate::Entity, section::Entity)],
+    ) {
+        for (id, section) in &mut self.sections {
+            section.search_query = rule
+                .filter(|_| found.iter().any(|&(_page, found)| found == id))
+                .cloned();
+            section.search_whole_words = whole_words && section.search_query.is_some();
+        }
+    }
+
+    fn search_by<'a>(
+        &'a self,
+        matches: impl Fn(&Section<Message>, Option<&dyn Page<Message>>) -> bool + Copy + 'a,
     ) -> impl Iterator<Item = (crate::Entity, section::Entity)> + 'a {
         self.content.iter().flat_map(move |(page, sections)| {
+            let model = self.page.get(page).map(AsRef::as_ref);
+
             sections
                 .iter()
-                .filter(|&id| self.sections[*id].search_matches(rule))
+                .filter(move |&id| matches(&self.sections[*id], model))
                 .map(move |&id| (page, id))
         })
     }
@@ -248,3 +329,102 @@ pub trait AutoBind<Message: Clone + 'static>: Page<Message> + Default + 'static
         page
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Binder;
+    use crate::section::{self, Section, build_search_rule};
+    use crate::{AutoBind, Content, Info, Page};
+    use cosmic::Task;
+    use slab::Slab;
+    use slotmap::SlotMap;
+
+    /// A page that records whether it was ever shown.
+    #[derive(Default)]
+    struct Unvisited {
+        entered: bool,
+        items: Vec<String>,
+    }
+
+    impl Page<()> for Unvisited {
+        fn info(&self) -> Info {
+            Info::new("unvisited", "preferences-system-symbolic")
+        }
+
+        fn content(&self, sections: &mut SlotMap<section::Entity, Section<()>>) -> Option<Content> {
+            let mut descriptions = Slab::new();
+            descriptions.insert(String::from("Hidden setting"));
+
+            Some(vec![
+                sections.insert(
+                    Section::default()
+                        .title("Section")
+                        .descriptions(descriptions)
+                        .search_items::<Self>(|page| page.items.clone()),
+                ),
+            ])
+        }
+
+        fn on_enter(&mut self) -> Task<()> {
+            self.entered = true;
+            Task::none()
+        }
+    }
+
+    impl AutoBind<()> for Unvisited {}
+
+    #[test]
+    fn pages_are_searched_before_they_are_visited() {
+        let mut binder = Binder::<()>::default();
+        let page = binder.register::<Unvisited>().id();
+
+        let rule = build_search_rule("hidden setting");
+        let found = binder.search(&rule).collect::<Vec<_>>();
+
+        assert!(!binder.page::<Unvisited>().unwrap().entered);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, page);
+    }
+
+    #[test]
+    fn items_of_a_page_are_searched() {
+        let mut binder = Binder::<()>::default();
+        let page = binder.register::<Unvisited>().id();
+
+        let rule = build_search_rule("terminal");
+        assert_eq!(binder.search(&rule).count(), 0);
+
+        binder
+            .page_mut::<Unvisited>()
+            .unwrap()
+            .items
+            .push(String::from("Open a terminal"));
+
+        let found = binder.search(&rule).collect::<Vec<_>>();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, page);
+        assert_eq!(binder.search_words(&build_search_rule("term")).count(), 0);
+    }
+
+    #[test]
+    fn whole_word_searches_emphasize_whole_words() {
+        let mut binder = Binder::<()>::default();
+        binder.register::<Unvisited>();
+
+        let rule = build_search_rule("setting");
+        let found = binder.search_words(&rule).collect::<Vec<_>>();
+        assert_eq!(found.len(), 1);
+
+        binder.search_highlight(Some(&rule), true, &found);
+        let section = &binder.sections[found[0].1];
+        assert!(section.search_emphasizes("Hidden setting"));
+        assert!(!section.search_emphasizes("Settings"));
+
+        binder.search_highlight(Some(&rule), false, &found);
+        let section = &binder.sections[found[0].1];
+        assert!(section.search_emphasizes("Settings"));
+
+        binder.search_highlight(None, false, &[]);
+        assert!(!binder.sections[found[0].1].search_emphasizes("Hidden setting"));
+    }
+}
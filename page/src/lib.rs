@@ -45,6 +45,13 @@ pub trait Page<Message: Clone + 'static>: Downcast {
         None
     }
 
+    /// The title of the page's context drawer, appended to the navigation breadcrumb.
+    #[must_use]
+    #[inline]
+    fn context_title(&self) -> Option<String> {
+        None
+    }
+
     /// Set a custom page header
     #[inline]
     fn header(&self) -> Option<Element<'_, Message>> {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use derive_setters::Setters;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use slab::Slab;
 
 use crate::{Binder, Page};
@@ -12,7 +12,12 @@ slotmap::new_key_type! {
     pub struct Entity;
 }
 
-pub type ShowWhileFn<Message> = Box<dyn for<'a> Fn(&'a dyn Page<Message>) -> bool>;
+pub type ShowWhileFn<Message> =
+    Box<dyn for<'a> Fn(&'a Binder<Message>, &'a dyn Page<Message>) -> bool>;
+
+pub type SearchItemsFn<Message> = Box<dyn for<'a> Fn(&'a dyn Page<Message>) -> Vec<String>>;
+
+pub type ResetFn<Message> = Box<dyn for<'a> Fn(&'a dyn Page<Message>) -> Option<Message>>;
 
 pub type ViewFn<Message> = Box<
     dyn for<'a> Fn(
@@ -22,6 +27,15 @@ pub type ViewFn<Message> = Box<
     ) -> cosmic::Element<'a, Message>,
 >;
 
+/// The part of a section that matched a search, as reported by [`Section::search_explain`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SearchHit {
+    /// The title matched with this text.
+    Title(String),
+    /// The description at this index of [`Section::descriptions`] matched with this text.
+    Description(usize, String),
+}
+
 /// A searchable sub-component of a page.
 ///
 /// Searches can group multiple sections together.
@@ -34,10 +48,29 @@ pub struct Section<Message> {
     pub descriptions: Slab<String>,
     #[setters(skip)]
     pub show_while: Option<ShowWhileFn<Message>>,
+    /// The message that restores the settings of this section to their defaults, which is
+    /// sent by a reset button beneath it. Set with [`Section::on_reset`].
+    #[setters(skip)]
+    pub reset_fn: Option<ResetFn<Message>>,
     #[setters(skip)]
     pub view_fn: ViewFn<Message>,
     #[setters(bool)]
     pub search_ignore: bool,
+    /// Text of the items that the section lists from the state of its page, which searches
+    /// match besides its descriptions. Set with [`Section::search_items`].
+    #[setters(skip)]
+    pub search_items: Option<SearchItemsFn<Message>>,
+    /// Shown on its page only when advanced settings are expanded, but always searchable.
+    #[setters(bool)]
+    pub advanced: bool,
+    /// The search that this section was found by, which its view may emphasize the
+    /// matching controls of. Set with [`Binder::search_highlight`].
+    #[setters(skip)]
+    pub search_query: Option<Regex>,
+    /// Whether [`Section::search_query`] found this section by whole words only, and only
+    /// emphasizes whole words of its view.
+    #[setters(skip)]
+    pub search_whole_words: bool,
 }
 
 impl<Message: 'static> Default for Section<Message> {
@@ -46,8 +79,13 @@ impl<Message: 'static> Default for Section<Message> {
             title: String::new(),
             descriptions: Slab::new(),
             show_while: None,
+            reset_fn: None,
             view_fn: Box::new(unimplemented),
             search_ignore: false,
+            search_items: None,
+            advanced: false,
+            search_query: None,
+            search_whole_words: false,
         }
     }
 }
@@ -56,16 +94,71 @@ impl<Message: Clone + 'static> Section<Message> {
     #[must_use]
     #[inline]
     pub fn search_matches(&self, rule: &Regex) -> bool {
+        self.search_matches_by(|text| rule.is_match(text))
+    }
+
+    /// Like [`Section::search_matches`], but only whole words match, so that `on`
+    /// matches "Turn on" but not "Notifications".
+    #[must_use]
+    #[inline]
+    pub fn search_matches_word(&self, rule: &Regex) -> bool {
+        self.search_matches_by(|text| is_word_match(rule, text))
+    }
+
+    /// Whether the search matches an item that the section lists from the state of its page.
+    #[must_use]
+    pub fn search_items_match(&self, page: &dyn Page<Message>, rule: &Regex) -> bool {
+        self.search_items_match_by(page, |text| rule.is_match(text))
+    }
+
+    /// Like [`Section::search_items_match`], but only whole words match.
+    #[must_use]
+    pub fn search_items_match_word(&self, page: &dyn Page<Message>, rule: &Regex) -> bool {
+        self.search_items_match_by(page, |text| is_word_match(rule, text))
+    }
+
+    /// Explains why [`Section::search_matches`] accepts this section with the same rule,
+    /// for tuning the strings that searches run against.
+    #[must_use]
+    pub fn search_explain(&self, rule: &Regex) -> Option<SearchHit> {
+        if self.search_ignore {
+            return None;
+        }
+
+        if let Some(m) = rule.find(self.title.as_str()) {
+            return Some(SearchHit::Title(m.as_str().to_owned()));
+        }
+
+        self.descriptions.iter().find_map(|(index, description)| {
+            let m = rule.find(description)?;
+            Some(SearchHit::Description(index, m.as_str().to_owned()))
+        })
+    }
+
+    /// Whether the search that this section was found by matches text of its view, such as
+    /// the name of an item that the section lists.
+    #[must_use]
+    pub fn search_emphasizes(&self, text: &str) -> bool {
+        self.search_query.as_ref().is_some_and(|rule| {
+            if self.search_whole_words {
+                is_word_match(rule, text)
+            } else {
+                rule.find(text).is_some_and(|m| !m.is_empty())
+            }
+        })
+    }
+
+    fn search_matches_by(&self, matches: impl Fn(&str) -> bool) -> bool {
         if self.search_ignore {
             return false;
         }
 
-        if rule.is_match(self.title.as_str()) {
+        if matches(self.title.as_str()) {
             return true;
         }
 
         for (_, description) in &self.descriptions {
-            if rule.is_match(description) {
+            if matches(description) {
                 return true;
             }
         }
@@ -73,12 +166,86 @@ impl<Message: Clone + 'static> Section<Message> {
         false
     }
 
+    fn search_items_match_by(
+        &self,
+        page: &dyn Page<Message>,
+        matches: impl Fn(&str) -> bool,
+    ) -> bool {
+        !self.search_ignore
+            && self
+                .search_items
+                .as_ref()
+                .is_some_and(|items| items(page).iter().any(|item| matches(item)))
+    }
+
+    /// Lets searches match the items that the section lists, such as shortcuts that the user
+    /// added, which change as the app is used and so cannot be given as descriptions.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the `Model` type does not match the page type.
     #[inline]
-    pub fn show_while<Model: Page<Message>>(
+    pub fn search_items<Model: Page<Message>>(
         mut self,
+        func: impl for<'a> Fn(&'a Model) -> Vec<String> + 'static,
+    ) -> Self {
+        self.search_items = Some(Box::new(move |model: &dyn Page<Message>| {
+            let model = model.downcast_ref::<Model>().unwrap_or_else(|| {
+                panic!(
+                    "page model type mismatch: expected {}",
+                    std::any::type_name::<Model>()
+                )
+            });
+
+            func(model)
+        }));
+        self
+    }
+
+    #[inline]
+    pub fn show_while<Model: Page<Message>>(
+        self,
         func: impl for<'a> Fn(&'a Model) -> bool + 'static,
     ) -> Self {
-        self.show_while = Some(Box::new(move |model: &dyn Page<Message>| {
+        self.show_while_binder(move |_binder, model: &Model| func(model))
+    }
+
+    /// Like [`Section::show_while`], but the condition may also depend on other pages.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the `Model` type does not match the page type.
+    #[inline]
+    pub fn show_while_binder<Model: Page<Message>>(
+        mut self,
+        func: impl for<'a> Fn(&'a Binder<Message>, &'a Model) -> bool + 'static,
+    ) -> Self {
+        self.show_while = Some(Box::new(move |binder, model: &dyn Page<Message>| {
+            let model = model.downcast_ref::<Model>().unwrap_or_else(|| {
+                panic!(
+                    "page model type mismatch: expected {}",
+                    std::any::type_name::<Model>()
+                )
+            });
+
+            func(binder, model)
+        }));
+        self
+    }
+
+    /// Shows a button beneath the section that restores its settings to their defaults, by
+    /// sending the message returned by `func`. The button is disabled while `func` returns
+    /// `None`, such as when nothing differs from the defaults.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the `Model` type does not match the page type.
+    #[inline]
+    pub fn on_reset<Model: Page<Message>>(
+        mut self,
+        func: impl for<'a> Fn(&'a Model) -> Option<Message> + 'static,
+    ) -> Self {
+        self.reset_fn = Some(Box::new(move |model: &dyn Page<Message>| {
             let model = model.downcast_ref::<Model>().unwrap_or_else(|| {
                 panic!(
                     "page model type mismatch: expected {}",
@@ -118,6 +285,48 @@ impl<Message: Clone + 'static> Section<Message> {
     }
 }
 
+/// Builds the rule that sections are searched with from the text that the user entered.
+///
+/// The text is matched literally, so that characters such as `(` or `[` are searched for
+/// rather than read as a pattern, unless it is entered as `/pattern/`. A pattern that does
+/// not compile is matched literally too, slashes included.
+#[must_use]
+pub fn build_search_rule(query: &str) -> Regex {
+    let pattern = query
+        .strip_prefix('/')
+        .and_then(|query| query.strip_suffix('/'))
+        .filter(|pattern| !pattern.is_empty());
+
+    if let Some(pattern) = pattern {
+        let rule = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .unicode(true)
+            .size_limit(16 * 1024)
+            .build();
+
+        if let Ok(rule) = rule {
+            return rule;
+        }
+    }
+
+    RegexBuilder::new(&regex::escape(query))
+        .case_insensitive(true)
+        .unicode(true)
+        .build()
+        .expect("escaped text is a valid pattern")
+}
+
+/// Checks if the rule matches text that is not part of a larger word.
+fn is_word_match(rule: &Regex, text: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    rule.find_iter(text).any(|m| {
+        !m.is_empty()
+            && !text[..m.start()].chars().next_back().is_some_and(is_word)
+            && !text[m.end()..].chars().next().is_some_and(is_word)
+    })
+}
+
 #[must_use]
 #[inline]
 pub fn unimplemented<'a, Message: 'static>(
@@ -127,3 +336,78 @@ pub fn unimplemented<'a, Message: 'static>(
 ) -> cosmic::Element<'a, Message> {
     cosmic::widget::settings::view_column(vec![cosmic::widget::settings::section().into()]).into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchHit, Section, build_search_rule};
+    use slab::Slab;
+
+    #[test]
+    fn special_characters_are_literal() {
+        let rule = build_search_rule("[");
+        assert!(rule.is_match("Layout [beta]"));
+        assert!(!rule.is_match("Layout"));
+
+        let rule = build_search_rule("(");
+        assert!(rule.is_match("Scale (200%)"));
+        assert!(!rule.is_match("Scale"));
+
+        let rule = build_search_rule(".*");
+        assert!(rule.is_match("Files .* Hidden"));
+        assert!(!rule.is_match("Files"));
+    }
+
+    #[test]
+    fn text_keeps_its_spaces() {
+        let rule = build_search_rule("night light");
+        assert!(rule.is_match("Night Light"));
+        assert!(!rule.is_match("Nightlight"));
+    }
+
+    #[test]
+    fn slashes_enter_a_pattern() {
+        let rule = build_search_rule("/dark|light/");
+        assert!(rule.is_match("Dark mode"));
+        assert!(rule.is_match("Night Light"));
+
+        let rule = build_search_rule("/.*/");
+        assert!(rule.is_match("Anything"));
+
+        // Spaces in a pattern are matched, as they are in text.
+        let rule = build_search_rule("/night light|dark/");
+        assert!(rule.is_match("Night Light"));
+        assert!(!rule.is_match("Nightlight"));
+
+        // An invalid pattern is searched for as it was typed.
+        let rule = build_search_rule("/(/");
+        assert!(rule.is_match("a/(/b"));
+        assert!(!rule.is_match("("));
+    }
+
+    #[test]
+    fn search_explain_names_the_match() {
+        let mut descriptions = Slab::new();
+        descriptions.insert(String::from("Dark mode"));
+        let night_light = descriptions.insert(String::from("Night light"));
+
+        let section = Section::<()>::default()
+            .title("Appearance")
+            .descriptions(descriptions);
+
+        assert_eq!(
+            section.search_explain(&build_search_rule("appear")),
+            Some(SearchHit::Title(String::from("Appear")))
+        );
+        assert_eq!(
+            section.search_explain(&build_search_rule("light")),
+            Some(SearchHit::Description(night_light, String::from("light")))
+        );
+        assert_eq!(
+            section.search_explain(&build_search_rule("wallpaper")),
+            None
+        );
+
+        let section = section.search_ignore();
+        assert_eq!(section.search_explain(&build_search_rule("appear")), None);
+    }
+}